@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context, Result};
+use clap::Args;
+use katana_primitives::block::BlockNumber;
+use katana_primitives::da::payload::state_updates_to_payload;
+use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_rpc_types::da::BlockDaPayload;
+
+use super::open_db_ro;
+
+#[derive(Debug, Args)]
+pub struct ExportDaArgs {
+    /// Path to the database directory.
+    #[arg(short, long)]
+    pub path: String,
+
+    /// The block to export the data availability payload of.
+    #[arg(long, value_name = "BLOCK")]
+    pub block: BlockNumber,
+
+    /// Export the payloads of all the blocks from `--block` up to this block (inclusive).
+    #[arg(long, value_name = "BLOCK")]
+    pub to: Option<BlockNumber>,
+
+    /// The file to write the payloads to, as a JSON array. Defaults to stdout.
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+impl ExportDaArgs {
+    pub fn execute(self) -> Result<()> {
+        let from = self.block;
+        let to = self.to.unwrap_or(from);
+        ensure!(from <= to, "Invalid block range: {from} is greater than {to}");
+
+        let provider = DbProvider::new(open_db_ro(&self.path)?);
+        let payloads = export_da_payloads(&provider, from, to)?;
+
+        match self.output {
+            Some(path) => {
+                let file = File::create(&path)
+                    .with_context(|| format!("Creating output file at path {}", path.display()))?;
+                write_payloads(BufWriter::new(file), &payloads)
+            }
+            None => write_payloads(io::stdout().lock(), &payloads),
+        }
+    }
+}
+
+/// Computes the data availability payloads of the blocks from `from` to `to` (inclusive).
+fn export_da_payloads(
+    provider: &DbProvider,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> Result<Vec<BlockDaPayload>> {
+    let latest = provider.latest_number()?;
+    ensure!(to <= latest, "Block {to} not found, the latest block is {latest}");
+
+    let mut payloads = Vec::with_capacity((to - from + 1) as usize);
+
+    for block_number in from..=to {
+        let block_hash = provider
+            .block_hash_by_num(block_number)?
+            .with_context(|| format!("Block {block_number} not found"))?;
+
+        let state_updates = provider
+            .state_update(block_number.into())?
+            .with_context(|| format!("State update of block {block_number} not found"))?;
+
+        let payload = state_updates_to_payload(state_updates).with_context(|| {
+            format!("Computing data availability payload of block {block_number}")
+        })?;
+
+        payloads.push(BlockDaPayload { block_hash, block_number, payload });
+    }
+
+    Ok(payloads)
+}
+
+fn write_payloads<W: Write>(mut writer: W, payloads: &[BlockDaPayload]) -> Result<()> {
+    serde_json::to_writer_pretty(&mut writer, payloads)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use indicatif::{ProgressBar, ProgressStyle};
+use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::BlockNumberProvider;
+use katana_provider::traits::event::EventIndexProvider;
+
+use crate::cli::db::open_db_rw;
+
+#[derive(Debug, Args)]
+pub struct IndexArgs {
+    /// Path to the database directory.
+    #[arg(short, long)]
+    pub path: String,
+
+    /// The number of blocks to index in a single database transaction.
+    #[arg(long, value_name = "COUNT", default_value_t = 1000)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    pub batch_size: u64,
+}
+
+impl IndexArgs {
+    pub fn execute(self) -> Result<()> {
+        let provider = DbProvider::new(open_db_rw(&self.path)?);
+
+        let latest = provider.latest_number().context("Failed to get latest block number")?;
+        let checkpoint = provider.event_index_checkpoint()?;
+
+        if checkpoint.is_some_and(|c| c >= latest) {
+            println!("Events are already indexed up to the latest block {latest}");
+            return Ok(());
+        }
+
+        let pb = ProgressBar::new(checkpoint.map_or(latest + 1, |c| latest - c));
+        let style = ProgressStyle::default_bar()
+            .template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} [{elapsed_precise}] {per_sec}")
+            .unwrap()
+            .progress_chars("##-");
+        pb.set_style(style);
+        pb.set_message("Indexing events");
+
+        // The checkpoint is moved in the same transaction as the blocks are indexed, so the
+        // indexing can be resumed from where it was left off if it's interrupted.
+        while let Some(blocks) = provider.index_events(self.batch_size)? {
+            pb.inc(blocks.end() - blocks.start() + 1);
+        }
+
+        pb.finish_with_message("Indexed events");
+        println!("Events are indexed up to the latest block {latest}");
+
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@ use comfy_table::presets::UTF8_FULL;
 use comfy_table::Table;
 use katana_db::mdbx::{DbEnv, DbEnvKind};
 
+mod export_da;
+mod index;
 mod prune;
 mod stats;
 mod version;
@@ -27,11 +29,19 @@ enum Commands {
 
     /// Prune historical trie data.
     Prune(prune::PruneArgs),
+
+    /// Export the data availability payloads of blocks.
+    ExportDa(export_da::ExportDaArgs),
+
+    /// Build the event index of the database.
+    Index(index::IndexArgs),
 }
 
 impl DbArgs {
     pub fn execute(self) -> Result<()> {
         match self.commands {
+            Commands::ExportDa(args) => args.execute(),
+            Commands::Index(args) => args.execute(),
             Commands::Prune(args) => args.execute(),
             Commands::Stats(args) => args.execute(),
             Commands::Version(args) => args.execute(),
@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use katana_cli::options::{LoggingOptions, MetricsOptions, ServerOptions};
+use katana_node::config::db::DbConfig;
+use katana_node::config::metrics::MetricsConfig;
+use katana_node::config::rpc::RpcConfig;
+use katana_node::full::{
+    Config, NetworkConfig, Node, PipelineConfig, VerificationMode, DEFAULT_DOWNLOAD_BATCH_SIZE,
+    DEFAULT_PIPELINE_CHUNK_SIZE, DEFAULT_TIP_POLL_INTERVAL,
+};
+use katana_primitives::chain::ChainId;
+use tracing::info;
+use url::Url;
+
+/// The known networks the full node can sync from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Network {
+    Mainnet,
+    Sepolia,
+}
+
+#[derive(Debug, Args)]
+pub struct FullNodeArgs {
+    /// Directory path of the database.
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    pub db_dir: PathBuf,
+
+    /// The Starknet network to sync from.
+    #[arg(long)]
+    #[arg(value_name = "NETWORK")]
+    #[arg(conflicts_with = "gateway_url")]
+    pub network: Option<Network>,
+
+    /// The base URL of the feeder gateway to sync from.
+    ///
+    /// This can be any feeder gateway, including a Katana sequencer with its feeder gateway
+    /// endpoints enabled. Must be used together with `--chain-id`.
+    #[arg(long = "gateway.url", value_name = "URL")]
+    #[arg(requires = "chain_id")]
+    pub gateway_url: Option<Url>,
+
+    /// The chain ID of the network served by the feeder gateway.
+    ///
+    /// If a raw hex string (`0x` prefix) is provided, then it'd be used as the actual chain ID.
+    /// Otherwise, it's represented as the raw ASCII values. It must be a valid Cairo short string.
+    #[arg(long)]
+    #[arg(value_parser = ChainId::parse)]
+    #[arg(requires = "gateway_url")]
+    pub chain_id: Option<ChainId>,
+
+    /// The API key used to bypass the rate limiting of the feeder gateway.
+    #[arg(long = "gateway.api-key", value_name = "API_KEY")]
+    pub gateway_api_key: Option<String>,
+
+    /// The number of blocks the pipeline processes at a time.
+    #[arg(long = "pipeline.chunk-size", value_name = "BLOCKS")]
+    #[arg(default_value_t = DEFAULT_PIPELINE_CHUNK_SIZE)]
+    pub pipeline_chunk_size: u64,
+
+    /// The number of concurrent requests made to the feeder gateway when downloading data.
+    #[arg(long = "pipeline.download-batch-size", value_name = "SIZE")]
+    #[arg(default_value_t = DEFAULT_DOWNLOAD_BATCH_SIZE)]
+    pub download_batch_size: usize,
+
+    /// The interval at which the feeder gateway is polled for the latest block.
+    #[arg(long = "pipeline.tip-poll-interval", value_name = "SECONDS")]
+    #[arg(default_value_t = DEFAULT_TIP_POLL_INTERVAL.as_secs())]
+    pub tip_poll_interval: u64,
+
+    /// How the downloaded blocks are verified.
+    ///
+    /// `state-root` recomputes the state root of every block from its state diff, while
+    /// `execution` additionally re-executes the blocks and compares the resulting receipts and
+    /// state diffs with the downloaded ones. The node stops syncing at the first block that
+    /// diverges.
+    #[arg(long = "pipeline.verify", value_name = "MODE")]
+    #[arg(default_value_t = VerificationMode::None)]
+    pub verification: VerificationMode,
+
+    #[command(flatten)]
+    pub logging: LoggingOptions,
+
+    #[command(flatten)]
+    pub metrics: MetricsOptions,
+
+    #[command(flatten)]
+    pub server: ServerOptions,
+}
+
+impl FullNodeArgs {
+    pub async fn execute(self) -> Result<()> {
+        katana_log::init(self.logging.log_format, false, None).await?;
+
+        let config = self.config();
+        let node = Node::build(config).context("failed to build full node")?;
+        let handle = node.launch().await.context("failed to launch full node")?;
+
+        // Wait until an OS signal (ie SIGINT, SIGTERM) is received or the node is shutdown.
+        tokio::select! {
+            _ = dojo_utils::signal::wait_signals() => {
+                // Gracefully shutdown the node before exiting
+                handle.stop().await?;
+            },
+
+            _ = handle.stopped() => { }
+        }
+
+        info!("Shutting down.");
+
+        Ok(())
+    }
+
+    fn config(&self) -> Config {
+        let db = DbConfig { dir: Some(self.db_dir.clone()) };
+
+        let metrics = if self.metrics.metrics {
+            Some(MetricsConfig { addr: self.metrics.metrics_addr, port: self.metrics.metrics_port })
+        } else {
+            None
+        };
+
+        Config {
+            db,
+            metrics,
+            rpc: self.rpc_config(),
+            network: self.network_config(),
+            pipeline: self.pipeline_config(),
+        }
+    }
+
+    fn network_config(&self) -> NetworkConfig {
+        let mut config = match (&self.gateway_url, self.chain_id, self.network) {
+            (Some(url), Some(chain_id), _) => {
+                NetworkConfig { chain_id, gateway_url: url.clone(), gateway_api_key: None }
+            }
+            (_, _, Some(Network::Mainnet)) => NetworkConfig::mainnet(),
+            _ => NetworkConfig::sepolia(),
+        };
+
+        config.gateway_api_key = self.gateway_api_key.clone();
+        config
+    }
+
+    fn rpc_config(&self) -> RpcConfig {
+        RpcConfig {
+            addr: self.server.http_addr,
+            port: self.server.http_port,
+            apis: self.server.http_modules.clone().unwrap_or_default(),
+            cors_origins: self.server.http_cors_origins.clone(),
+            feeder_gateway: self.server.feeder_gateway,
+            max_connections: self.server.max_connections,
+            max_request_body_size: self.server.max_request_body_size,
+            max_response_body_size: self.server.max_response_body_size,
+            timeout: self.server.timeout.map(Duration::from_secs),
+            max_event_page_size: Some(self.server.max_event_page_size),
+            max_proof_keys: Some(self.server.max_proof_keys),
+            max_call_gas: Some(self.server.max_call_gas),
+            ..Default::default()
+        }
+    }
+
+    fn pipeline_config(&self) -> PipelineConfig {
+        PipelineConfig {
+            chunk_size: self.pipeline_chunk_size,
+            download_batch_size: self.download_batch_size,
+            tip_poll_interval: Duration::from_secs(self.tip_poll_interval),
+            verification: self.verification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use katana_node::config::rpc::{RpcModuleKind, DEFAULT_RPC_PORT};
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: FullNodeArgs,
+    }
+
+    fn parse(args: &[&str]) -> FullNodeArgs {
+        let mut argv = vec!["katana", "--db-dir", "db"];
+        argv.extend_from_slice(args);
+        Cli::parse_from(argv).args
+    }
+
+    #[test]
+    fn default_config() {
+        let config = parse(&[]).config();
+        assert_eq!(config.network, NetworkConfig::sepolia());
+        assert_eq!(config.pipeline, PipelineConfig::default());
+        assert_eq!(config.pipeline.tip_poll_interval, DEFAULT_TIP_POLL_INTERVAL);
+        assert!(config.metrics.is_none());
+        assert_eq!(config.rpc.port, DEFAULT_RPC_PORT);
+        assert!(config.rpc.apis.contains(&RpcModuleKind::Starknet));
+    }
+
+    #[test]
+    fn custom_gateway() {
+        let args = parse(&[
+            "--gateway.url",
+            "http://localhost:5050/",
+            "--chain-id",
+            "KATANA",
+            "--gateway.api-key",
+            "secret",
+            "--pipeline.chunk-size",
+            "10",
+            "--pipeline.tip-poll-interval",
+            "2",
+            "--pipeline.verify",
+            "state-root",
+        ]);
+
+        let config = args.config();
+        assert_eq!(config.network.chain_id, ChainId::parse("KATANA").unwrap());
+        assert_eq!(config.network.gateway_url, Url::parse("http://localhost:5050/").unwrap());
+        assert_eq!(config.network.gateway_api_key.as_deref(), Some("secret"));
+        assert_eq!(config.pipeline.chunk_size, 10);
+        assert_eq!(config.pipeline.tip_poll_interval, Duration::from_secs(2));
+        assert_eq!(config.pipeline.verification, VerificationMode::StateRoot);
+    }
+
+    #[test]
+    fn named_network() {
+        let config = parse(&["--network", "mainnet"]).config();
+        assert_eq!(config.network, NetworkConfig::mainnet());
+
+        // a custom gateway requires the chain id and can't be combined with a named network
+        let result =
+            Cli::try_parse_from(["katana", "--db-dir", "db", "--gateway.url", "http://a/"]);
+        assert!(result.is_err());
+
+        let result = Cli::try_parse_from([
+            "katana",
+            "--db-dir",
+            "db",
+            "--network",
+            "mainnet",
+            "--gateway.url",
+            "http://a/",
+            "--chain-id",
+            "KATANA",
+        ]);
+        assert!(result.is_err());
+    }
+}
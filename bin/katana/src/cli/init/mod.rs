@@ -9,6 +9,7 @@ use katana_chain_spec::rollup::{ChainConfigDir, FeeContract};
 use katana_chain_spec::{rollup, SettlementLayer};
 use katana_primitives::block::BlockNumber;
 use katana_primitives::chain::ChainId;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::genesis::allocation::DevAllocationsGenerator;
 use katana_primitives::genesis::constant::DEFAULT_PREFUNDED_ACCOUNT_BALANCE;
 use katana_primitives::genesis::Genesis;
@@ -131,7 +132,8 @@ impl InitArgs {
 
         // At the moment, the fee token is limited to a predefined token.
         let fee_contract = FeeContract::default();
-        let chain_spec = rollup::ChainSpec { id, genesis, settlement, fee_contract };
+        let l1_da_mode = L1DataAvailabilityMode::Calldata;
+        let chain_spec = rollup::ChainSpec { id, genesis, settlement, fee_contract, l1_da_mode };
 
         if let Some(path) = self.output_path {
             let dir = ChainConfigDir::create(path)?;
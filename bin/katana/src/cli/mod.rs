@@ -8,6 +8,7 @@ use tokio::runtime::Runtime;
 
 mod config;
 pub mod db;
+mod full;
 mod init;
 mod version;
 
@@ -31,6 +32,7 @@ impl Cli {
                 Commands::Config(args) => args.execute(),
                 Commands::Completions(args) => args.execute(),
                 Commands::Init(args) => execute_async(args.execute())?,
+                Commands::Full(args) => execute_async(args.execute())?,
             };
         }
 
@@ -49,6 +51,9 @@ enum Commands {
     #[command(about = "Database utilities")]
     Db(db::DbArgs),
 
+    #[command(about = "Run a full node that syncs the chain from a feeder gateway")]
+    Full(Box<full::FullNodeArgs>),
+
     #[command(about = "Generate shell completion file for specified shell")]
     Completions(CompletionsArgs),
 }
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+
+use clap::Parser;
+use fixtures::{empty_db, TempDb};
+use katana::cli::Cli;
+use katana_primitives::block::{Block, FinalityStatus};
+use katana_primitives::da::encoding::decode_state_updates;
+use katana_primitives::da::payload::state_updates_to_payload;
+use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
+use katana_primitives::{address, Felt};
+use katana_provider::traits::block::BlockWriter;
+use katana_rpc_types::da::BlockDaPayload;
+use rstest::*;
+
+mod fixtures;
+
+/// Returns the state diff of the block with the given number.
+fn state_updates(num: u64) -> StateUpdates {
+    let mut state_updates = StateUpdates::default();
+    let storage = BTreeMap::from([(Felt::from(num), Felt::from(num * 10))]);
+    state_updates.storage_updates.insert(address!("0x1337"), storage);
+    state_updates.nonce_updates.insert(address!("0x1337"), Felt::from(num + 1));
+    state_updates
+}
+
+fn block_hash(num: u64) -> Felt {
+    Felt::from(num + 100)
+}
+
+fn populate_db(db: &TempDb) {
+    let provider = db.provider_rw();
+
+    for num in 0..=2u64 {
+        let mut block = Block::default();
+        block.header.number = num;
+
+        let status = FinalityStatus::AcceptedOnL2;
+        let block = block.seal_with_hash_and_status(block_hash(num), status);
+        let states =
+            StateUpdatesWithClasses { state_updates: state_updates(num), ..Default::default() };
+
+        provider
+            .insert_block_with_states_and_receipts(block, states, Vec::new(), Vec::new())
+            .unwrap();
+    }
+}
+
+#[rstest]
+fn export_block_range(empty_db: TempDb) {
+    populate_db(&empty_db);
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("payloads.json");
+
+    let path = empty_db.path_str();
+    let output_path = output.to_str().unwrap();
+    Cli::parse_from([
+        "katana",
+        "db",
+        "export-da",
+        "--path",
+        path,
+        "--block",
+        "1",
+        "--to",
+        "2",
+        "--output",
+        output_path,
+    ])
+    .run()
+    .unwrap();
+
+    let payloads: Vec<BlockDaPayload> =
+        serde_json::from_reader(File::open(&output).unwrap()).unwrap();
+    assert_eq!(payloads.len(), 2);
+
+    for (payload, num) in payloads.into_iter().zip(1..=2u64) {
+        assert_eq!(payload.block_number, num);
+        assert_eq!(payload.block_hash, block_hash(num));
+        assert_eq!(payload.payload, state_updates_to_payload(state_updates(num)).unwrap());
+
+        let encoded = payload.payload.encoded.iter().map(Felt::to_biguint).collect::<Vec<_>>();
+        assert_eq!(decode_state_updates(&encoded).unwrap(), state_updates(num));
+    }
+}
+
+#[rstest]
+fn export_invalid_block_range(empty_db: TempDb) {
+    populate_db(&empty_db);
+    let path = empty_db.path_str();
+
+    let result =
+        Cli::parse_from(["katana", "db", "export-da", "--path", path, "--block", "2", "--to", "1"])
+            .run();
+    assert!(result.is_err());
+
+    // the block doesn't exist
+    let result =
+        Cli::parse_from(["katana", "db", "export-da", "--path", path, "--block", "3"]).run();
+    assert!(result.is_err());
+}
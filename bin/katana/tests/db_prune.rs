@@ -60,16 +60,9 @@ fn prune_latest_removes_all_history(db: TempDb) {
 
     let provider = db.provider_ro();
 
-    // Verify historical states (0 -> 14) are no longer accessible (ie zero)
+    // Verify historical tries (0 -> 14) are no longer accessible
     for num in 0..=14u64 {
-        // Right now, non existent tries default to zero.
-        //
-        // Ref:
-        // * crates/storage/db/src/trie/mod.rs#43-46
-        // * bonsai_trie::trie::trees::MerkleTrees::root_hash()
-        let (classes_root, contracts_root) = historical_roots(&provider, num).unwrap();
-        assert_eq!(classes_root, Felt::ZERO);
-        assert_eq!(contracts_root, Felt::ZERO);
+        assert!(historical_roots(&provider, num).is_err(), "trie of block {num} must be pruned");
     }
 
     let (final_classes_root, final_contracts_root) = latest_roots(&provider).unwrap();
@@ -110,16 +103,9 @@ fn prune_keep_last_n_blocks(db: TempDb) {
     let provider = db.provider_ro();
     let (final_classes_root, final_contracts_root) = latest_roots(&provider).unwrap();
 
-    // pruned blocks (ie blocks before the cuttoff point) should be zero
+    // pruned blocks (ie blocks before the cuttoff point) should no longer be accessible
     for num in 0..=(latest_block - keep_last_n) {
-        // Right now, non existent tries default to zero.
-        //
-        // Ref:
-        // * crates/storage/db/src/trie/mod.rs#43-46
-        // * bonsai_trie::trie::trees::MerkleTrees::root_hash()
-        let (classes_root, contracts_root) = historical_roots(&provider, num).unwrap();
-        assert_eq!(classes_root, Felt::ZERO);
-        assert_eq!(contracts_root, Felt::ZERO);
+        assert!(historical_roots(&provider, num).is_err(), "trie of block {num} must be pruned");
     }
 
     // blocks after the cuttoff point should be the same as before pruning
@@ -38,6 +38,9 @@ pub struct ChainSpec {
     pub fee_contracts: FeeContracts,
 
     pub settlement: Option<SettlementLayer>,
+
+    /// The mode in which the state diffs of the chain's blocks are published.
+    pub l1_da_mode: L1DataAvailabilityMode,
 }
 
 //////////////////////////////////////////////////////////////
@@ -45,7 +48,10 @@ pub struct ChainSpec {
 //////////////////////////////////////////////////////////////
 
 impl ChainSpec {
-    pub fn block(&self) -> Block {
+    /// Returns the genesis block of the chain.
+    ///
+    /// The L2 gas prices aren't part of the chain spec, as they are configured on the node.
+    pub fn block(&self, l2_gas_prices: GasPrice) -> Block {
         let header = Header {
             state_diff_length: 0,
             protocol_version: CURRENT_STARKNET_VERSION,
@@ -59,9 +65,9 @@ impl ChainSpec {
             transactions_commitment: Felt::ZERO,
             state_root: self.genesis.state_root,
             parent_hash: self.genesis.parent_hash,
-            l1_da_mode: L1DataAvailabilityMode::Calldata,
+            l1_da_mode: self.l1_da_mode,
             l1_gas_prices: self.genesis.gas_prices.clone(),
-            l2_gas_prices: GasPrice::MIN,
+            l2_gas_prices,
             l1_data_gas_prices: self.genesis.gas_prices.clone(),
             sequencer_address: self.genesis.sequencer_address,
         };
@@ -157,6 +163,7 @@ lazy_static! {
             genesis,
             fee_contracts,
             settlement: None,
+            l1_da_mode: L1DataAvailabilityMode::Calldata,
         }
     };
 }
@@ -348,8 +355,11 @@ mod tests {
                 strk: DEFAULT_STRK_FEE_TOKEN_ADDRESS,
             },
             settlement: None,
+            l1_da_mode: L1DataAvailabilityMode::Calldata,
         };
 
+        let l2_gas_prices = unsafe { GasPrice::new_unchecked(3333, 4444) };
+
         // setup expected storage values
         let expected_block = Block {
             header: Header {
@@ -363,7 +373,7 @@ mod tests {
                 state_root: chain_spec.genesis.state_root,
                 parent_hash: chain_spec.genesis.parent_hash,
                 sequencer_address: chain_spec.genesis.sequencer_address,
-                l2_gas_prices: GasPrice::MIN,
+                l2_gas_prices: l2_gas_prices.clone(),
                 l1_gas_prices: chain_spec.genesis.gas_prices.clone(),
                 l1_data_gas_prices: chain_spec.genesis.gas_prices.clone(),
                 l1_da_mode: L1DataAvailabilityMode::Calldata,
@@ -374,7 +384,7 @@ mod tests {
             body: Vec::new(),
         };
 
-        let actual_block = chain_spec.block();
+        let actual_block = chain_spec.block(l2_gas_prices);
         let actual_state_updates = chain_spec.state_updates();
 
         similar_asserts::assert_eq!(actual_block, expected_block);
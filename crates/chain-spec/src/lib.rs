@@ -1,5 +1,6 @@
 use katana_primitives::block::BlockNumber;
 use katana_primitives::chain::ChainId;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::genesis::Genesis;
 use katana_primitives::{eth, ContractAddress};
 use serde::{Deserialize, Serialize};
@@ -44,6 +45,14 @@ impl ChainSpec {
             Self::Rollup(spec) => Some(&spec.settlement),
         }
     }
+
+    /// Returns the mode in which the state diffs of the chain's blocks are published.
+    pub fn l1_da_mode(&self) -> L1DataAvailabilityMode {
+        match self {
+            Self::Dev(spec) => spec.l1_da_mode,
+            Self::Rollup(spec) => spec.l1_da_mode,
+        }
+    }
 }
 
 impl From<dev::ChainSpec> for ChainSpec {
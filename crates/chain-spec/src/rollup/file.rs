@@ -3,6 +3,7 @@ use std::io::{self, BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 
 use katana_primitives::chain::ChainId;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::genesis::json::GenesisJson;
 use katana_primitives::genesis::Genesis;
 use serde::{Deserialize, Serialize};
@@ -87,6 +88,7 @@ pub fn read(dir: &ChainConfigDir) -> Result<ChainSpec, Error> {
         id: chain_spec.id,
         settlement: chain_spec.settlement,
         fee_contract: chain_spec.fee_contract,
+        l1_da_mode: chain_spec.l1_da_mode,
     })
 }
 
@@ -96,6 +98,7 @@ pub fn write(dir: &ChainConfigDir, chain_spec: &ChainSpec) -> Result<(), Error>
             id: chain_spec.id,
             settlement: chain_spec.settlement.clone(),
             fee_contract: chain_spec.fee_contract.clone(),
+            l1_da_mode: chain_spec.l1_da_mode,
         };
 
         let content = toml::to_string_pretty(&cfg)?;
@@ -146,6 +149,8 @@ struct ChainSpecFile {
     id: ChainId,
     fee_contract: FeeContract,
     settlement: SettlementLayer,
+    #[serde(default)]
+    l1_da_mode: L1DataAvailabilityMode,
 }
 
 /// The local directory name where the chain configuration files are stored.
@@ -287,6 +292,7 @@ mod tests {
     use std::sync::OnceLock;
 
     use katana_primitives::chain::ChainId;
+    use katana_primitives::da::L1DataAvailabilityMode;
     use katana_primitives::genesis::Genesis;
     use katana_primitives::ContractAddress;
     use tempfile::TempDir;
@@ -341,6 +347,7 @@ mod tests {
                 core_contract: ContractAddress::default(),
                 rpc_url: Url::parse("http://localhost:5050").expect("valid url"),
             },
+            l1_da_mode: L1DataAvailabilityMode::Blob,
         }
     }
 
@@ -355,6 +362,7 @@ mod tests {
         assert_eq!(chain_spec.id, read_spec.id);
         assert_eq!(chain_spec.fee_contract, read_spec.fee_contract);
         assert_eq!(chain_spec.settlement, read_spec.settlement);
+        assert_eq!(chain_spec.l1_da_mode, read_spec.l1_da_mode);
     }
 
     #[test]
@@ -2,6 +2,7 @@ use katana_primitives::block::{ExecutableBlock, GasPrice, PartialHeader};
 use katana_primitives::chain::ChainId;
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::da::L1DataAvailabilityMode;
+use katana_primitives::genesis::constant::{DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE};
 use katana_primitives::genesis::Genesis;
 use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use serde::{Deserialize, Serialize};
@@ -29,6 +30,10 @@ pub struct ChainSpec {
 
     /// The chain's settlement layer configurations.
     pub settlement: SettlementLayer,
+
+    /// The mode in which the state diffs of the chain's blocks are published to the settlement
+    /// layer.
+    pub l1_da_mode: L1DataAvailabilityMode,
 }
 
 //////////////////////////////////////////////////////////////
@@ -42,8 +47,8 @@ impl ChainSpec {
             number: self.genesis.number,
             timestamp: self.genesis.timestamp,
             parent_hash: self.genesis.parent_hash,
-            l1_da_mode: L1DataAvailabilityMode::Calldata,
-            l2_gas_prices: GasPrice::MIN,
+            l1_da_mode: self.l1_da_mode,
+            l2_gas_prices: GasPrice::new(DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE),
             l1_gas_prices: self.genesis.gas_prices.clone(),
             l1_data_gas_prices: self.genesis.gas_prices.clone(),
             sequencer_address: self.genesis.sequencer_address,
@@ -345,6 +345,7 @@ mod tests {
     use katana_primitives::chain::ChainId;
     use katana_primitives::class::ClassHash;
     use katana_primitives::contract::Nonce;
+    use katana_primitives::da::L1DataAvailabilityMode;
     use katana_primitives::env::CfgEnv;
     use katana_primitives::genesis::allocation::{
         DevAllocationsGenerator, GenesisAccount, GenesisAccountAlloc, GenesisAllocation,
@@ -387,7 +388,8 @@ mod tests {
             rpc_url: Url::parse("http://localhost:5050").unwrap(),
         };
 
-        ChainSpec { id, genesis, settlement, fee_contract }
+        let l1_da_mode = L1DataAvailabilityMode::Calldata;
+        ChainSpec { id, genesis, settlement, fee_contract, l1_da_mode }
     }
 
     fn executor(chain_spec: &ChainSpec) -> BlockifierFactory {
@@ -13,16 +13,16 @@ use katana_chain_spec::ChainSpec;
 use katana_core::constants::DEFAULT_SEQUENCER_ADDRESS;
 use katana_messaging::MessagingConfig;
 use katana_node::config::db::DbConfig;
-use katana_node::config::dev::{DevConfig, FixedL1GasPriceConfig};
+use katana_node::config::dev::{DevConfig, FixedL1GasPriceConfig, L2GasPriceConfig};
 use katana_node::config::execution::ExecutionConfig;
-use katana_node::config::fork::ForkingConfig;
+use katana_node::config::fork::{BackendConfig, ForkingConfig};
 use katana_node::config::metrics::MetricsConfig;
 #[cfg(feature = "cartridge")]
 use katana_node::config::paymaster::PaymasterConfig;
 use katana_node::config::rpc::RpcConfig;
 #[cfg(feature = "server")]
 use katana_node::config::rpc::{RpcModuleKind, RpcModulesList};
-use katana_node::config::sequencing::SequencingConfig;
+use katana_node::config::sequencing::{SequencingConfig, TxOrderingKind};
 use katana_node::config::Config;
 use katana_node::Node;
 use katana_primitives::genesis::allocation::DevAllocationsGenerator;
@@ -63,6 +63,14 @@ pub struct NodeArgs {
     #[arg(value_name = "TOTAL")]
     pub block_cairo_steps_limit: Option<u64>,
 
+    /// The ordering of the transactions in the pool.
+    ///
+    /// `fifo` orders the transactions by their arrival time, while `fee-market` prioritizes
+    /// transactions by their tip and resource bounds.
+    #[arg(long = "sequencing.tx-ordering")]
+    #[arg(value_name = "ORDERING")]
+    pub tx_ordering: Option<TxOrderingKind>,
+
     /// Directory path of the database to initialize from.
     ///
     /// The path must either be an empty directory or a directory which already contains a
@@ -202,6 +210,7 @@ impl NodeArgs {
             block_time: self.block_time,
             no_mining: self.no_mining,
             block_cairo_steps_limit: self.block_cairo_steps_limit,
+            tx_ordering: self.tx_ordering.unwrap_or_default(),
         }
     }
 
@@ -254,6 +263,7 @@ impl NodeArgs {
                 max_response_body_size: None,
                 timeout: self.server.timeout.map(Duration::from_secs),
                 cors_origins,
+                feeder_gateway: self.server.feeder_gateway,
                 #[cfg(feature = "explorer")]
                 explorer: self.explorer.explorer,
                 max_event_page_size: Some(self.server.max_event_page_size),
@@ -283,6 +293,10 @@ impl NodeArgs {
                 chain_spec.id = id;
             }
 
+            if let Some(mode) = self.starknet.environment.l1_da_mode {
+                chain_spec.l1_da_mode = mode;
+            }
+
             if let Some(genesis) = &self.starknet.genesis {
                 chain_spec.genesis = genesis.clone();
             } else {
@@ -331,8 +345,26 @@ impl NodeArgs {
             prices.data_gas_price.strk = strk_data_gas_price;
         }
 
+        let mut l2_gas_prices = None;
+
+        if let Some(eth_gas_price) = self.gpo.l2_eth_gas_price {
+            let prices = l2_gas_prices.get_or_insert(L2GasPriceConfig::default());
+            prices.gas_price.eth = eth_gas_price;
+        }
+
+        if let Some(strk_gas_price) = self.gpo.l2_strk_gas_price {
+            let prices = l2_gas_prices.get_or_insert(L2GasPriceConfig::default());
+            prices.gas_price.strk = strk_gas_price;
+        }
+
+        if let Some(target_gas) = self.gpo.l2_target_gas {
+            let prices = l2_gas_prices.get_or_insert(L2GasPriceConfig::default());
+            prices.target_gas = Some(target_gas);
+        }
+
         DevConfig {
             fixed_gas_prices,
+            l2_gas_prices,
             fee: !self.development.no_fee,
             account_validation: !self.development.no_account_validation,
         }
@@ -350,7 +382,14 @@ impl NodeArgs {
 
     fn forking_config(&self) -> Result<Option<ForkingConfig>> {
         if let Some(ref url) = self.forking.fork_provider {
-            let cfg = ForkingConfig { url: url.clone(), block: self.forking.fork_block };
+            let backend = BackendConfig {
+                batch_size: self.forking.fork_batch_size,
+                prefetch_contracts: self.forking.fork_prefetch.clone(),
+                prefetch_blocks: self.forking.fork_prefetch_blocks,
+                remote_tries: !self.forking.fork_no_remote_tries,
+            };
+
+            let cfg = ForkingConfig { url: url.clone(), block: self.forking.fork_block, backend };
             return Ok(Some(cfg));
         }
 
@@ -403,6 +442,10 @@ impl NodeArgs {
             self.block_time = config.block_time;
         }
 
+        if self.tx_ordering.is_none() {
+            self.tx_ordering = config.tx_ordering;
+        }
+
         if self.db_dir.is_none() {
             self.db_dir = config.db_dir;
         }
@@ -462,14 +505,16 @@ mod test {
 
     use assert_matches::assert_matches;
     use katana_core::constants::{
-        DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
-        DEFAULT_STRK_L1_GAS_PRICE,
+        DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE,
+        DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE,
     };
     use katana_node::config::execution::{
         DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS,
     };
     use katana_node::config::rpc::RpcModuleKind;
+    use katana_primitives::block::BlockHashOrNumber;
     use katana_primitives::chain::ChainId;
+    use katana_primitives::da::L1DataAvailabilityMode;
     use katana_primitives::{address, felt, ContractAddress, Felt};
 
     use super::*;
@@ -516,6 +561,16 @@ mod test {
         assert_eq!(config.chain.genesis().sequencer_address, *DEFAULT_SEQUENCER_ADDRESS);
     }
 
+    #[test]
+    fn tx_ordering() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.sequencing.tx_ordering, TxOrderingKind::Fifo);
+
+        let args = ["katana", "--sequencing.tx-ordering", "fee-market"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_eq!(config.sequencing.tx_ordering, TxOrderingKind::FeeMarket);
+    }
+
     #[test]
     fn custom_fixed_gas_prices() {
         let config = NodeArgs::parse_from(["katana"]).config().unwrap();
@@ -598,6 +653,47 @@ mod test {
         })
     }
 
+    #[test]
+    fn l1_da_mode() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.chain.l1_da_mode(), L1DataAvailabilityMode::Calldata);
+
+        let config = NodeArgs::parse_from(["katana", "--l1-da-mode", "blob"]).config().unwrap();
+        assert_eq!(config.chain.l1_da_mode(), L1DataAvailabilityMode::Blob);
+
+        assert!(NodeArgs::try_parse_from(["katana", "--l1-da-mode", "foo"]).is_err());
+    }
+
+    #[test]
+    fn custom_l2_gas_prices() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert!(config.dev.l2_gas_prices.is_none());
+
+        let config =
+            NodeArgs::parse_from(["katana", "--gpo.l2-strk-gas-price", "20"]).config().unwrap();
+        assert_matches!(config.dev.l2_gas_prices, Some(prices) => {
+            assert_eq!(prices.gas_price.eth, DEFAULT_ETH_L2_GAS_PRICE);
+            assert_eq!(prices.gas_price.strk.get(), 20);
+            assert_eq!(prices.target_gas, None);
+        });
+
+        let config = NodeArgs::parse_from([
+            "katana",
+            "--gpo.l2-eth-gas-price",
+            "10",
+            "--gpo.l2-target-gas",
+            "1000000",
+        ])
+        .config()
+        .unwrap();
+
+        assert_matches!(config.dev.l2_gas_prices, Some(prices) => {
+            assert_eq!(prices.gas_price.eth.get(), 10);
+            assert_eq!(prices.gas_price.strk, DEFAULT_STRK_L2_GAS_PRICE);
+            assert_eq!(prices.target_gas, Some(1_000_000));
+        });
+    }
+
     #[test]
     fn genesis_with_fixed_gas_prices() {
         let config = NodeArgs::parse_from([
@@ -733,6 +829,55 @@ chain_id.Named = "Mainnet"
             .contains("The `dev` module can only be enabled in dev mode (ie `--dev` flag)"));
     }
 
+    #[test]
+    fn forking_with_db_dir() {
+        let args = NodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050/",
+            "--fork.block",
+            "10",
+            "--db-dir",
+            "/path/to/db",
+        ]);
+        let config = args.config().unwrap();
+
+        let forking = config.forking.unwrap();
+        assert_eq!(forking.url, Url::parse("http://localhost:5050/").unwrap());
+        assert_eq!(forking.block, Some(BlockHashOrNumber::Num(10)));
+        assert_eq!(config.db.dir, Some(PathBuf::from("/path/to/db")));
+    }
+
+    #[test]
+    fn forking_backend_options() {
+        let args = NodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050/",
+            "--fork.batch-size",
+            "50",
+            "--fork.prefetch",
+            "0x1,0x2",
+            "--fork.prefetch-blocks",
+            "20",
+            "--fork.no-remote-tries",
+        ]);
+        let config = args.config().unwrap();
+
+        let backend = config.forking.unwrap().backend;
+        assert_eq!(backend.batch_size, 50);
+        assert_eq!(backend.prefetch_contracts, vec![address!("0x1"), address!("0x2")]);
+        assert_eq!(backend.prefetch_blocks, 20);
+        assert!(!backend.remote_tries);
+
+        // the tries of the forked network are used by default
+        let args = NodeArgs::parse_from(["katana", "--fork.provider", "http://localhost:5050/"]);
+        let config = args.config().unwrap();
+        let backend = config.forking.unwrap().backend;
+        assert!(backend.remote_tries);
+        assert_eq!(backend, BackendConfig::default());
+    }
+
     #[test]
     fn test_dev_api_enabled() {
         let args = NodeArgs::parse_from(["katana", "--dev"]);
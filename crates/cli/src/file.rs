@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use katana_messaging::MessagingConfig;
+use katana_node::config::sequencing::TxOrderingKind;
 use serde::{Deserialize, Serialize};
 
 use crate::options::*;
@@ -13,6 +14,7 @@ pub struct NodeArgsConfig {
     pub no_mining: Option<bool>,
     pub block_time: Option<u64>,
     pub block_cairo_steps_limit: Option<u64>,
+    pub tx_ordering: Option<TxOrderingKind>,
     pub db_dir: Option<PathBuf>,
     pub messaging: Option<MessagingConfig>,
     pub logging: Option<LoggingOptions>,
@@ -47,6 +49,7 @@ impl TryFrom<NodeArgs> for NodeArgsConfig {
             no_mining: if args.no_mining { Some(true) } else { None },
             block_time: args.block_time,
             block_cairo_steps_limit: args.block_cairo_steps_limit,
+            tx_ordering: args.tx_ordering,
             db_dir: args.db_dir,
             messaging: args.messaging,
             ..Default::default()
@@ -14,6 +14,7 @@ use std::num::NonZeroU128;
 use clap::Args;
 use katana_log::{gcloud, otlp, LogFormat, TracerConfig};
 use katana_node::config::execution::{DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS};
+use katana_node::config::fork::{DEFAULT_BATCH_SIZE, DEFAULT_PREFETCH_BLOCKS};
 #[cfg(feature = "server")]
 use katana_node::config::metrics::{DEFAULT_METRICS_ADDR, DEFAULT_METRICS_PORT};
 #[cfg(feature = "server")]
@@ -24,6 +25,8 @@ use katana_node::config::rpc::{
 };
 use katana_primitives::block::BlockHashOrNumber;
 use katana_primitives::chain::ChainId;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::genesis::Genesis;
 #[cfg(feature = "server")]
 use katana_rpc::cors::HeaderValue;
@@ -32,7 +35,7 @@ use url::Url;
 
 #[cfg(feature = "server")]
 use crate::utils::{deserialize_cors_origins, serialize_cors_origins};
-use crate::utils::{parse_block_hash_or_number, parse_genesis};
+use crate::utils::{parse_block_hash_or_number, parse_genesis, parse_l1_da_mode};
 
 const DEFAULT_DEV_SEED: &str = "0";
 const DEFAULT_DEV_ACCOUNTS: u16 = 10;
@@ -107,6 +110,14 @@ pub struct ServerOptions {
     #[serde(default)]
     pub http_modules: Option<RpcModulesList>,
 
+    /// Serve the Starknet feeder gateway API from the node's storage.
+    ///
+    /// The endpoints will be accessible at the `/feeder_gateway` path relative to the RPC server
+    /// URL (eg, `localhost:5050/feeder_gateway/get_block`).
+    #[arg(long = "http.feeder-gateway")]
+    #[serde(default)]
+    pub feeder_gateway: bool,
+
     /// Maximum number of concurrent connections allowed.
     #[arg(long = "rpc.max-connections", value_name = "MAX")]
     pub max_connections: Option<u32>,
@@ -150,6 +161,7 @@ impl Default for ServerOptions {
             http_port: DEFAULT_RPC_PORT,
             http_cors_origins: Vec::new(),
             http_modules: None,
+            feeder_gateway: false,
             max_event_page_size: DEFAULT_RPC_MAX_EVENT_PAGE_SIZE,
             max_proof_keys: DEFAULT_RPC_MAX_PROOF_KEYS,
             max_connections: None,
@@ -177,6 +189,9 @@ impl ServerOptions {
             if self.http_modules.is_none() {
                 self.http_modules = other.http_modules.clone();
             }
+            if !self.feeder_gateway {
+                self.feeder_gateway = other.feeder_gateway;
+            }
             if self.max_connections.is_none() {
                 self.max_connections = other.max_connections;
             }
@@ -252,6 +267,16 @@ pub struct EnvironmentOptions {
     #[serde(default = "default_invoke_max_steps")]
     pub invoke_max_steps: u32,
 
+    /// The mode in which the state diffs of the produced blocks are published to L1. Either
+    /// `calldata` or `blob`.
+    ///
+    /// In `blob` mode, the data gas is priced as blob gas and the blobs of every block, along
+    /// with their KZG commitments, can be retrieved through the `katana` RPC module.
+    #[arg(long = "l1-da-mode", value_name = "MODE", conflicts_with = "chain")]
+    #[arg(value_parser = parse_l1_da_mode)]
+    #[serde(default)]
+    pub l1_da_mode: Option<L1DataAvailabilityMode>,
+
     /// Enable cairo-native compilation for improved performance.
     #[cfg(feature = "native")]
     #[arg(long = "enable-native-compilation")]
@@ -265,6 +290,7 @@ impl Default for EnvironmentOptions {
             validate_max_steps: DEFAULT_VALIDATION_MAX_STEPS,
             invoke_max_steps: DEFAULT_INVOCATION_MAX_STEPS,
             chain_id: None,
+            l1_da_mode: None,
             #[cfg(feature = "native")]
             compile_native: false,
         }
@@ -282,6 +308,10 @@ impl EnvironmentOptions {
                 self.invoke_max_steps = other.invoke_max_steps;
             }
 
+            if self.l1_da_mode.is_none() {
+                self.l1_da_mode = other.l1_da_mode;
+            }
+
             #[cfg(feature = "native")]
             if !self.compile_native {
                 self.compile_native = other.compile_native;
@@ -365,13 +395,19 @@ impl DevOptions {
     }
 }
 
-#[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Args, Clone, Serialize, Deserialize, PartialEq)]
 #[command(next_help_heading = "Forking options")]
 pub struct ForkingOptions {
     /// The RPC URL of the network to fork from.
     ///
     /// This will operate Katana in forked mode. Continuing from the tip of the forked network, or
     /// at a specific block if `fork.block` is provided.
+    ///
+    /// When used together with `--db-dir`, the forked chain is persisted in the database along with
+    /// the remote state fetched from the forked network, and is resumed on restart.
+    ///
+    /// When used together with `--chain`, the chain id, fee token and settlement configurations of
+    /// the rollup chain are kept, and its genesis is assumed to exist on the forked network.
     #[arg(long = "fork.provider", value_name = "URL", conflicts_with = "genesis")]
     pub fork_provider: Option<Url>,
 
@@ -380,6 +416,59 @@ pub struct ForkingOptions {
     #[arg(long = "fork.block", value_name = "BLOCK", requires = "fork_provider")]
     #[arg(value_parser = parse_block_hash_or_number)]
     pub fork_block: Option<BlockHashOrNumber>,
+
+    /// The maximum number of requests to send to the forked network in a single JSON-RPC batch.
+    ///
+    /// Only the requests that are pending at the same time are batched together. Most state reads
+    /// (eg during transaction execution) are synchronous and wait for their response before the
+    /// next one is sent, so they rarely end up in the same batch.
+    #[arg(requires = "fork_provider")]
+    #[arg(long = "fork.batch-size", value_name = "SIZE")]
+    #[arg(default_value_t = DEFAULT_BATCH_SIZE)]
+    #[serde(default = "default_fork_batch_size")]
+    pub fork_batch_size: usize,
+
+    /// Comma separated list of contracts whose storage will be prefetched from the forked network.
+    ///
+    /// The storage is prefetched from the state updates of the last `fork.prefetch-blocks` blocks
+    /// up to the forked block. Storage that wasn't updated within those blocks is still fetched on
+    /// demand.
+    #[arg(requires = "fork_provider")]
+    #[arg(long = "fork.prefetch", value_name = "ADDRESSES")]
+    #[arg(value_delimiter = ',')]
+    #[serde(default)]
+    pub fork_prefetch: Vec<ContractAddress>,
+
+    /// The number of blocks to prefetch the contracts storage from.
+    #[arg(requires = "fork_prefetch")]
+    #[arg(long = "fork.prefetch-blocks", value_name = "BLOCKS")]
+    #[arg(default_value_t = DEFAULT_PREFETCH_BLOCKS)]
+    #[serde(default = "default_fork_prefetch_blocks")]
+    pub fork_prefetch_blocks: u64,
+
+    /// Don't compute the state roots and storage proofs of the forked chain on top of the tries of
+    /// the forked network.
+    ///
+    /// By default, the nodes of the forked network's tries are fetched through its
+    /// `starknet_getStorageProof` method. Use this flag if the forked network doesn't support it,
+    /// in which case the state roots only account for the state updates of the local blocks.
+    #[arg(requires = "fork_provider")]
+    #[arg(long = "fork.no-remote-tries")]
+    #[serde(default)]
+    pub fork_no_remote_tries: bool,
+}
+
+impl Default for ForkingOptions {
+    fn default() -> Self {
+        ForkingOptions {
+            fork_provider: None,
+            fork_block: None,
+            fork_batch_size: DEFAULT_BATCH_SIZE,
+            fork_prefetch: Vec::new(),
+            fork_prefetch_blocks: DEFAULT_PREFETCH_BLOCKS,
+            fork_no_remote_tries: false,
+        }
+    }
 }
 
 #[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -416,6 +505,32 @@ pub struct GasPriceOracleOptions {
     #[serde(serialize_with = "serialize_option_as_hex")]
     #[serde(deserialize_with = "deserialize_nonzero_gas_price")]
     pub l1_strk_data_gas_price: Option<NonZeroU128>,
+
+    /// The L2 ETH gas price. (denominated in wei)
+    ///
+    /// If `--gpo.l2-target-gas` is set, this is the initial and minimum price.
+    #[arg(long = "gpo.l2-eth-gas-price", value_name = "WEI")]
+    #[serde(serialize_with = "serialize_option_as_hex")]
+    #[serde(deserialize_with = "deserialize_nonzero_gas_price")]
+    #[serde(default)]
+    pub l2_eth_gas_price: Option<NonZeroU128>,
+
+    /// The L2 STRK gas price. (denominated in fri)
+    ///
+    /// If `--gpo.l2-target-gas` is set, this is the initial and minimum price.
+    #[arg(long = "gpo.l2-strk-gas-price", value_name = "FRI")]
+    #[serde(serialize_with = "serialize_option_as_hex")]
+    #[serde(deserialize_with = "deserialize_nonzero_gas_price")]
+    #[serde(default)]
+    pub l2_strk_gas_price: Option<NonZeroU128>,
+
+    /// The amount of L2 gas a block is expected to use.
+    ///
+    /// When set, the L2 gas prices are adjusted after every block based on whether the block used
+    /// more or less L2 gas than this target, similar to Ethereum's EIP-1559 base fee.
+    #[arg(long = "gpo.l2-target-gas", value_name = "GAS")]
+    #[serde(default)]
+    pub l2_target_gas: Option<u64>,
 }
 
 #[cfg(feature = "cartridge")]
@@ -508,6 +623,14 @@ fn default_invoke_max_steps() -> u32 {
     DEFAULT_INVOCATION_MAX_STEPS
 }
 
+fn default_fork_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_fork_prefetch_blocks() -> u64 {
+    DEFAULT_PREFETCH_BLOCKS
+}
+
 #[cfg(feature = "server")]
 fn default_http_addr() -> IpAddr {
     DEFAULT_RPC_ADDR
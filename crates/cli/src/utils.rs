@@ -9,6 +9,7 @@ use katana_primitives::block::{BlockHash, BlockHashOrNumber, BlockNumber};
 use katana_primitives::chain::ChainId;
 use katana_primitives::class::ClassHash;
 use katana_primitives::contract::ContractAddress;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::genesis::allocation::GenesisAccountAlloc;
 use katana_primitives::genesis::constant::{
     DEFAULT_LEGACY_ERC20_CLASS_HASH, DEFAULT_LEGACY_UDC_CLASS_HASH, DEFAULT_UDC_ADDRESS,
@@ -51,6 +52,15 @@ pub fn parse_block_hash_or_number(value: &str) -> Result<BlockHashOrNumber> {
     }
 }
 
+/// Used as clap value parser for [L1DataAvailabilityMode].
+pub fn parse_l1_da_mode(value: &str) -> Result<L1DataAvailabilityMode> {
+    match value.to_lowercase().as_str() {
+        "blob" => Ok(L1DataAvailabilityMode::Blob),
+        "calldata" => Ok(L1DataAvailabilityMode::Calldata),
+        _ => Err(anyhow!("invalid data availability mode, expected `blob` or `calldata`")),
+    }
+}
+
 pub fn print_intro(args: &NodeArgs, chain: &ChainSpec) {
     let mut accounts = chain.genesis().accounts().peekable();
     let account_class_hash = accounts.peek().map(|e| e.1.class_hash());
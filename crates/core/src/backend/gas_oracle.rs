@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::num::NonZeroU128;
 use std::sync::Arc;
 
 use alloy_provider::{Provider, ProviderBuilder};
@@ -12,16 +13,67 @@ use tokio::time::Duration;
 use tracing::error;
 use url::Url;
 
+use crate::constants::{DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE};
+
 const BUFFER_SIZE: usize = 60;
 const INTERVAL: Duration = Duration::from_secs(60);
 const ONE_GWEI: u128 = 1_000_000_000;
 
+/// Provides the gas prices for new blocks.
+///
+/// The L1 gas and data gas prices are determined by the [`L1GasOracle`], unless they have been
+/// overridden at runtime, while the price of L2 gas (ie Sierra gas) is determined by the
+/// [`L2GasOracle`].
 #[derive(Debug)]
-pub enum GasOracle {
+pub struct GasOracle {
+    l1: L1GasOracle,
+    l2: L2GasOracle,
+    l1_overrides: Mutex<L1GasPriceOverrides>,
+}
+
+/// L1 gas prices set at runtime, which take precedence over the ones from the [`L1GasOracle`].
+#[derive(Debug, Default)]
+struct L1GasPriceOverrides {
+    gas_prices: Option<GasPrice>,
+    data_gas_prices: Option<GasPrice>,
+}
+
+#[derive(Debug)]
+pub enum L1GasOracle {
     Fixed(FixedGasOracle),
     Sampled(EthereumSampledGasOracle),
 }
 
+#[derive(Debug)]
+pub enum L2GasOracle {
+    /// Uses the same L2 gas prices for every block.
+    Fixed(GasPrice),
+    /// Adjusts the L2 gas prices after every block based on its L2 gas usage.
+    Eip1559(Eip1559GasOracle),
+}
+
+/// An L2 gas oracle that adjusts the gas prices in the same manner as the EIP-1559 base fee.
+///
+/// The prices go up if the previous block used more L2 gas than the target, and go down if it
+/// used less. The change is proportional to the distance from the target and is at most
+/// `1 / max_change_denominator` of the previous prices per block.
+#[derive(Debug)]
+pub struct Eip1559GasOracle {
+    params: Eip1559Params,
+    prices: Mutex<GasPrice>,
+}
+
+/// The parameters of the [`Eip1559GasOracle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Params {
+    /// The amount of L2 gas a block is expected to use.
+    pub target_gas: u128,
+    /// Bounds the amount by which the prices can change from one block to the next.
+    pub max_change_denominator: u128,
+    /// The prices will never go below these values.
+    pub min_prices: GasPrice,
+}
+
 #[derive(Debug)]
 pub struct FixedGasOracle {
     gas_prices: GasPrice,
@@ -49,45 +101,103 @@ pub struct GasOracleWorker {
 }
 
 impl GasOracle {
+    /// Creates a new gas oracle with fixed L1 gas prices.
+    ///
+    /// The L2 gas prices are fixed to the default values, use [`GasOracle::with_l2_oracle`] to
+    /// change how they are determined.
     pub fn fixed(gas_prices: GasPrice, data_gas_prices: GasPrice) -> Self {
-        GasOracle::Fixed(FixedGasOracle { gas_prices, data_gas_prices })
+        let l1 = L1GasOracle::Fixed(FixedGasOracle { gas_prices, data_gas_prices });
+        Self { l1, l2: L2GasOracle::default(), l1_overrides: Default::default() }
     }
 
     /// Creates a new gas oracle that samples the gas prices from an Ethereum chain.
     pub fn sampled_ethereum(eth_provider: Url) -> Self {
         let prices: Arc<Mutex<SampledPrices>> = Arc::new(Mutex::new(SampledPrices::default()));
-        GasOracle::Sampled(EthereumSampledGasOracle { prices, provider: eth_provider })
+        let l1 = L1GasOracle::Sampled(EthereumSampledGasOracle { prices, provider: eth_provider });
+        Self { l1, l2: L2GasOracle::default(), l1_overrides: Default::default() }
+    }
+
+    /// Replaces the oracle used to determine the L2 gas prices.
+    pub fn with_l2_oracle(mut self, l2: L2GasOracle) -> Self {
+        self.l2 = l2;
+        self
     }
 
     /// This is just placeholder for now, as Starknet doesn't provide a way to get the L2 gas
     /// prices, we just return a fixed gas price values of 0. This is equivalent to calling
-    /// [`GasOracle::fixed`] with 0 values for both gas and data prices.
+    /// [`GasOracle::fixed`] with 0 values for all gas prices.
     ///
     /// The result of this is the same as running the node with fee disabled.
     pub fn sampled_starknet() -> Self {
-        Self::fixed(GasPrice::MIN, GasPrice::MIN)
+        Self::fixed(GasPrice::MIN, GasPrice::MIN).with_l2_oracle(L2GasOracle::Fixed(GasPrice::MIN))
+    }
+
+    /// Overrides the L1 gas prices, regardless of how they are otherwise determined.
+    pub fn set_gas_prices(&self, prices: GasPrice) {
+        self.l1_overrides.lock().gas_prices = Some(prices);
+    }
+
+    /// Overrides the L1 data gas prices, regardless of how they are otherwise determined.
+    pub fn set_data_gas_prices(&self, prices: GasPrice) {
+        self.l1_overrides.lock().data_gas_prices = Some(prices);
     }
 
     /// Returns the current gas prices.
     pub fn current_gas_prices(&self) -> GasPrice {
-        match self {
-            GasOracle::Fixed(fixed) => fixed.current_gas_prices(),
-            GasOracle::Sampled(sampled) => sampled.prices.lock().gas_prices.clone(),
+        if let Some(prices) = &self.l1_overrides.lock().gas_prices {
+            return prices.clone();
+        }
+
+        match &self.l1 {
+            L1GasOracle::Fixed(fixed) => fixed.current_gas_prices(),
+            L1GasOracle::Sampled(sampled) => sampled.prices.lock().gas_prices.clone(),
         }
     }
 
     /// Returns the current data gas prices.
     pub fn current_data_gas_prices(&self) -> GasPrice {
-        match self {
-            GasOracle::Fixed(fixed) => fixed.current_data_gas_prices(),
-            GasOracle::Sampled(sampled) => sampled.prices.lock().data_gas_prices.clone(),
+        if let Some(prices) = &self.l1_overrides.lock().data_gas_prices {
+            return prices.clone();
+        }
+
+        match &self.l1 {
+            L1GasOracle::Fixed(fixed) => fixed.current_data_gas_prices(),
+            L1GasOracle::Sampled(sampled) => sampled.prices.lock().data_gas_prices.clone(),
+        }
+    }
+
+    /// Returns the current L2 gas prices.
+    pub fn current_l2_gas_prices(&self) -> GasPrice {
+        match &self.l2 {
+            L2GasOracle::Fixed(prices) => prices.clone(),
+            L2GasOracle::Eip1559(oracle) => oracle.current_gas_prices(),
+        }
+    }
+
+    /// Updates the L2 gas prices based on the amount of L2 gas used by the latest block.
+    ///
+    /// This is a no-op if the L2 gas prices are fixed.
+    pub fn on_block_mined(&self, l2_gas_used: u128) {
+        if let L2GasOracle::Eip1559(oracle) = &self.l2 {
+            oracle.update(l2_gas_used);
+        }
+    }
+
+    /// Resumes the L2 gas prices from a block that was mined with `prices` and used `l2_gas_used`
+    /// L2 gas, as if the block had just been mined.
+    ///
+    /// This is a no-op if the L2 gas prices are fixed.
+    pub fn sync_l2_gas_prices(&self, prices: GasPrice, l2_gas_used: u128) {
+        if let L2GasOracle::Eip1559(oracle) = &self.l2 {
+            oracle.reset(prices);
+            oracle.update(l2_gas_used);
         }
     }
 
     pub fn run_worker(&self, task_spawner: TaskSpawner) {
-        match self {
-            Self::Fixed(..) => {}
-            Self::Sampled(oracle) => {
+        match &self.l1 {
+            L1GasOracle::Fixed(..) => {}
+            L1GasOracle::Sampled(oracle) => {
                 let prices = oracle.prices.clone();
                 let l1_provider = oracle.provider.clone();
 
@@ -105,6 +215,68 @@ impl GasOracle {
     }
 }
 
+impl Default for L2GasOracle {
+    fn default() -> Self {
+        Self::Fixed(GasPrice::new(DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE))
+    }
+}
+
+impl Eip1559GasOracle {
+    /// Creates a new oracle starting from the given prices.
+    pub fn new(initial_prices: GasPrice, params: Eip1559Params) -> Self {
+        assert!(params.max_change_denominator > 0, "max change denominator must be non-zero");
+        Self { params, prices: Mutex::new(initial_prices) }
+    }
+
+    pub fn current_gas_prices(&self) -> GasPrice {
+        self.prices.lock().clone()
+    }
+
+    fn reset(&self, prices: GasPrice) {
+        *self.prices.lock() = prices;
+    }
+
+    fn update(&self, gas_used: u128) {
+        let mut prices = self.prices.lock();
+        let eth = self.next_price(prices.eth, self.params.min_prices.eth, gas_used);
+        let strk = self.next_price(prices.strk, self.params.min_prices.strk, gas_used);
+        *prices = GasPrice::new(eth, strk);
+    }
+
+    /// Computes the price for the next block from the price of the previous block and the amount
+    /// of gas it used.
+    fn next_price(&self, price: NonZeroU128, min: NonZeroU128, gas_used: u128) -> NonZeroU128 {
+        let Eip1559Params { target_gas, max_change_denominator, .. } = self.params;
+
+        // without a target, the price can't be adjusted
+        if target_gas == 0 || gas_used == target_gas {
+            return price.max(min);
+        }
+
+        let price = price.get();
+        let next = if gas_used > target_gas {
+            // usage beyond twice the target doesn't increase the price any further
+            let excess = (gas_used - target_gas).min(target_gas);
+            // the delta is at least 1 so that the price keeps increasing even when it's very low
+            let delta = mul_div(price, excess, target_gas) / max_change_denominator;
+            price.saturating_add(delta.max(1))
+        } else {
+            let delta = mul_div(price, target_gas - gas_used, target_gas) / max_change_denominator;
+            price.saturating_sub(delta)
+        };
+
+        NonZeroU128::new(next).unwrap_or(NonZeroU128::MIN).max(min)
+    }
+}
+
+/// Computes `a * b / c`, saturating at [`u128::MAX`] if the intermediate product overflows.
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    match a.checked_mul(b) {
+        Some(product) => product / c,
+        None => (a / c).saturating_mul(b),
+    }
+}
+
 impl EthereumSampledGasOracle {
     pub fn current_data_gas_prices(&self) -> GasPrice {
         self.prices.lock().data_gas_prices.clone()
@@ -296,14 +468,112 @@ mod tests {
         assert_eq!(buffer.average(), expected_avg);
     }
 
+    #[test]
+    fn overridden_l1_gas_prices() {
+        let gas_prices = unsafe { GasPrice::new_unchecked(10, 20) };
+        let data_gas_prices = unsafe { GasPrice::new_unchecked(30, 40) };
+
+        let oracle = GasOracle::fixed(GasPrice::MIN, GasPrice::MIN);
+        oracle.set_gas_prices(gas_prices.clone());
+        assert_eq!(oracle.current_gas_prices(), gas_prices);
+        assert_eq!(oracle.current_data_gas_prices(), GasPrice::MIN);
+
+        oracle.set_data_gas_prices(data_gas_prices.clone());
+        assert_eq!(oracle.current_data_gas_prices(), data_gas_prices);
+
+        // the overrides also take precedence over the sampled prices
+        let oracle = GasOracle::sampled_ethereum("http://localhost:8545".parse().unwrap());
+        oracle.set_gas_prices(gas_prices.clone());
+        assert_eq!(oracle.current_gas_prices(), gas_prices);
+    }
+
+    fn eip1559_oracle(initial: u128, min: u128) -> GasOracle {
+        let params = Eip1559Params {
+            target_gas: 1000,
+            max_change_denominator: 8,
+            min_prices: unsafe { GasPrice::new_unchecked(min, min) },
+        };
+        let initial = unsafe { GasPrice::new_unchecked(initial, initial) };
+        let l2 = L2GasOracle::Eip1559(Eip1559GasOracle::new(initial, params));
+        GasOracle::fixed(GasPrice::MIN, GasPrice::MIN).with_l2_oracle(l2)
+    }
+
+    #[test]
+    fn fixed_l2_gas_prices() {
+        let oracle = GasOracle::fixed(GasPrice::MIN, GasPrice::MIN);
+        let default_prices = GasPrice::new(DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE);
+        assert_eq!(oracle.current_l2_gas_prices(), default_prices);
+
+        // the usage of the mined blocks doesn't affect fixed prices
+        oracle.on_block_mined(u128::MAX);
+        assert_eq!(oracle.current_l2_gas_prices(), default_prices);
+    }
+
+    #[test]
+    fn eip1559_l2_gas_prices() {
+        let oracle = eip1559_oracle(800, 100);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 800);
+
+        // exactly at the target
+        oracle.on_block_mined(1000);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 800);
+
+        // a full block (ie twice the target) increases the price by 1/8
+        oracle.on_block_mined(2000);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 900);
+        assert_eq!(oracle.current_l2_gas_prices().strk.get(), 900);
+
+        // the change is proportional to the distance from the target
+        oracle.on_block_mined(1500);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 956);
+
+        // but is capped no matter how far the usage is above the target
+        oracle.on_block_mined(100_000);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 1075);
+
+        // an empty block decreases the price by 1/8
+        oracle.on_block_mined(0);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 941);
+
+        // the price never goes below the minimum
+        for _ in 0..100 {
+            oracle.on_block_mined(0);
+        }
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 100);
+    }
+
+    #[test]
+    fn eip1559_l2_gas_prices_increase_from_minimum() {
+        let oracle = eip1559_oracle(1, 1);
+
+        // the price must still increase when the computed delta rounds down to zero
+        oracle.on_block_mined(1001);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 2);
+    }
+
+    #[test]
+    fn sync_l2_gas_prices() {
+        let oracle = eip1559_oracle(800, 100);
+
+        // the prices carry on from the synced block as if it had just been mined
+        oracle.sync_l2_gas_prices(unsafe { GasPrice::new_unchecked(1600, 1600) }, 2000);
+        assert_eq!(oracle.current_l2_gas_prices().eth.get(), 1800);
+
+        // fixed prices are left untouched
+        let oracle = GasOracle::fixed(GasPrice::MIN, GasPrice::MIN);
+        oracle.sync_l2_gas_prices(GasPrice::MIN, 0);
+        let default_prices = GasPrice::new(DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE);
+        assert_eq!(oracle.current_l2_gas_prices(), default_prices);
+    }
+
     #[tokio::test]
     #[ignore = "Requires external assumption"]
     async fn test_gas_oracle() {
         let url = Url::parse("https://eth.merkle.io/").expect("Invalid URL");
         let oracle = GasOracle::sampled_ethereum(url.clone());
 
-        let shared_prices = match &oracle {
-            GasOracle::Sampled(sampled) => sampled.prices.clone(),
+        let shared_prices = match &oracle.l1 {
+            L1GasOracle::Sampled(sampled) => sampled.prices.clone(),
             _ => panic!("Expected sampled oracle"),
         };
 
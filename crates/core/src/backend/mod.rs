@@ -6,11 +6,10 @@ use gas_oracle::GasOracle;
 use katana_chain_spec::ChainSpec;
 use katana_executor::{ExecutionOutput, ExecutionResult, ExecutorFactory};
 use katana_primitives::block::{
-    BlockHash, BlockNumber, FinalityStatus, Header, PartialHeader, SealedBlock,
+    BlockHash, BlockNumber, FinalityStatus, GasPrice, Header, PartialHeader, SealedBlock,
     SealedBlockWithStatus,
 };
 use katana_primitives::class::{ClassHash, CompiledClassHash};
-use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::BlockEnv;
 use katana_primitives::execution::TypedTransactionExecutionInfo;
 use katana_primitives::receipt::{Event, Receipt, ReceiptWithTxHash};
@@ -18,9 +17,15 @@ use katana_primitives::state::{compute_state_diff_hash, StateUpdates, StateUpdat
 use katana_primitives::transaction::{TxHash, TxWithHash};
 use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::{address, ContractAddress, Felt};
+use katana_provider::error::ProviderError;
 use katana_provider::providers::EmptyStateProvider;
-use katana_provider::traits::block::{BlockHashProvider, BlockWriter};
+use katana_provider::traits::block::{
+    BlockHashProvider, BlockNumberProvider, BlockWriter, HeaderProvider,
+};
+use katana_provider::traits::fork::ForkInfoProvider;
+use katana_provider::traits::transaction::ReceiptProvider;
 use katana_provider::traits::trie::TrieWriter;
+use katana_provider::ProviderResult;
 use katana_trie::bonsai::databases::HashMapDb;
 use katana_trie::{
     compute_contract_state_hash, compute_merkle_root, ClassesTrie, CommitId, ContractLeaf,
@@ -77,7 +82,15 @@ impl<EF: ExecutorFactory> Backend<EF> {
     pub fn init_genesis(&self) -> anyhow::Result<()> {
         match self.chain_spec.as_ref() {
             ChainSpec::Dev(cs) => self.init_dev_genesis(cs),
-            ChainSpec::Rollup(cs) => self.init_rollup_genesis(cs),
+            ChainSpec::Rollup(cs) => {
+                // the genesis of a forked rollup chain has already been executed on the forked
+                // network
+                if self.blockchain.provider().fork_info()?.is_some() {
+                    self.init_forked_rollup_genesis(cs)
+                } else {
+                    self.init_rollup_genesis(cs)
+                }
+            }
         }
     }
 
@@ -90,13 +103,20 @@ impl<EF: ExecutorFactory> Backend<EF> {
         let mut traces = Vec::with_capacity(execution_output.transactions.len());
         let mut receipts = Vec::with_capacity(execution_output.transactions.len());
         let mut transactions = Vec::with_capacity(execution_output.transactions.len());
+        let mut rejected_txs = Vec::new();
 
         // only include successful transactions in the block
         for (tx, res) in execution_output.transactions {
-            if let ExecutionResult::Success { receipt, trace } = res {
-                traces.push(TypedTransactionExecutionInfo::new(receipt.r#type(), trace));
-                receipts.push(ReceiptWithTxHash::new(tx.hash, receipt));
-                transactions.push(tx);
+            match res {
+                ExecutionResult::Success { receipt, trace, state_diff } => {
+                    let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace);
+                    traces.push(trace.with_state_diff(state_diff));
+                    receipts.push(ReceiptWithTxHash::new(tx.hash, receipt));
+                    transactions.push(tx);
+                }
+                ExecutionResult::Failed { error } => {
+                    rejected_txs.push((tx.hash, error.to_string()));
+                }
             }
         }
 
@@ -108,7 +128,7 @@ impl<EF: ExecutorFactory> Backend<EF> {
             number: block_env.number,
             timestamp: block_env.timestamp,
             protocol_version: CURRENT_STARKNET_VERSION,
-            l1_da_mode: L1DataAvailabilityMode::Calldata,
+            l1_da_mode: block_env.l1_da_mode,
             sequencer_address: block_env.sequencer_address,
             l2_gas_prices: block_env.l2_gas_prices.clone(),
             l1_gas_prices: block_env.l1_gas_prices.clone(),
@@ -133,8 +153,16 @@ impl<EF: ExecutorFactory> Backend<EF> {
         let receipts = receipts.into_iter().map(|r| r.receipt).collect::<Vec<_>>();
         self.store_block(block, execution_output.states, receipts, traces)?;
 
+        // the L2 gas prices of the next block depend on how much L2 gas this block used
+        self.gas_oracle.on_block_mined(execution_output.stats.l2_gas_used);
+
         info!(target: LOG_TARGET, %block_number, %tx_count, "Block mined.");
-        Ok(MinedBlockOutcome { block_number, txs: tx_hashes, stats: execution_output.stats })
+        Ok(MinedBlockOutcome {
+            block_number,
+            txs: tx_hashes,
+            rejected_txs,
+            stats: execution_output.stats,
+        })
     }
 
     fn store_block(
@@ -154,26 +182,37 @@ impl<EF: ExecutorFactory> Backend<EF> {
         let mut context_gen = self.block_context_generator.write();
         let current_timestamp_secs = get_current_timestamp().as_secs() as i64;
 
-        let timestamp = if context_gen.next_block_start_time == 0 {
-            (current_timestamp_secs + context_gen.block_timestamp_offset) as u64
-        } else {
-            let timestamp = context_gen.next_block_start_time;
+        let timestamp = context_gen.next_block_timestamp(current_timestamp_secs);
+        if context_gen.next_block_start_time != 0 {
             context_gen.block_timestamp_offset = timestamp as i64 - current_timestamp_secs;
             context_gen.next_block_start_time = 0;
-            timestamp
-        };
+        }
 
         block_env.number += 1;
         block_env.timestamp = timestamp;
+        block_env.l1_da_mode = self.chain_spec.l1_da_mode();
 
         // update the gas prices
         self.update_block_gas_prices(block_env);
     }
 
+    /// Returns the timestamp that the next block will have if its block environment is created
+    /// now.
+    pub fn next_block_timestamp(&self) -> u64 {
+        let current_timestamp_secs = get_current_timestamp().as_secs() as i64;
+        self.block_context_generator.read().next_block_timestamp(current_timestamp_secs)
+    }
+
+    /// Sets the timestamp of the next block whose block environment is created.
+    pub fn set_next_block_timestamp(&self, timestamp: u64) {
+        self.block_context_generator.write().next_block_start_time = timestamp;
+    }
+
     /// Updates the gas prices in the block environment.
     pub fn update_block_gas_prices(&self, block_env: &mut BlockEnv) {
         block_env.l1_gas_prices = self.gas_oracle.current_gas_prices();
         block_env.l1_data_gas_prices = self.gas_oracle.current_data_gas_prices();
+        block_env.l2_gas_prices = self.gas_oracle.current_l2_gas_prices();
     }
 
     pub fn mine_empty_block(
@@ -188,12 +227,13 @@ impl<EF: ExecutorFactory> Backend<EF> {
         chain_spec: &katana_chain_spec::dev::ChainSpec,
     ) -> anyhow::Result<()> {
         let provider = self.blockchain.provider();
+        let l2_gas_prices = self.genesis_l2_gas_prices(chain_spec.genesis.number)?;
 
         // check whether the genesis block has been initialized
         let local_hash = provider.block_hash_by_num(chain_spec.genesis.number)?;
 
         if let Some(local_hash) = local_hash {
-            let genesis_hash = chain_spec.block().header.compute_hash();
+            let genesis_hash = chain_spec.block(l2_gas_prices).header.compute_hash();
             // check genesis should be the same
             if local_hash != genesis_hash {
                 return Err(anyhow!(
@@ -205,7 +245,7 @@ impl<EF: ExecutorFactory> Backend<EF> {
         } else {
             // Initialize the dev genesis block
 
-            let block = chain_spec.block().seal();
+            let block = chain_spec.block(l2_gas_prices).seal();
             let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL1 };
             let states = chain_spec.state_updates();
 
@@ -256,8 +296,9 @@ impl<EF: ExecutorFactory> Backend<EF> {
 
         // only include successful transactions in the block
         for (tx, res) in output.transactions {
-            if let ExecutionResult::Success { receipt, trace } = res {
-                traces.push(TypedTransactionExecutionInfo::new(receipt.r#type(), trace));
+            if let ExecutionResult::Success { receipt, trace, state_diff } = res {
+                let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace);
+                traces.push(trace.with_state_diff(state_diff));
                 receipts.push(ReceiptWithTxHash::new(tx.hash, receipt));
                 transactions.push(tx);
             }
@@ -306,6 +347,84 @@ impl<EF: ExecutorFactory> Backend<EF> {
 
         Ok(())
     }
+    /// Initializes the genesis of a rollup chain forked from another network.
+    ///
+    /// Unlike [`Self::init_rollup_genesis`], the genesis transactions are not executed as the
+    /// chain's genesis state is fetched from the forked network. The genesis block is an empty
+    /// block that mirrors the forked block.
+    fn init_forked_rollup_genesis(
+        &self,
+        chain_spec: &katana_chain_spec::rollup::ChainSpec,
+    ) -> anyhow::Result<()> {
+        let provider = self.blockchain.provider();
+
+        let genesis = &chain_spec.genesis;
+        let header = Header {
+            state_diff_length: 0,
+            protocol_version: CURRENT_STARKNET_VERSION,
+            number: genesis.number,
+            timestamp: genesis.timestamp,
+            events_count: 0,
+            transaction_count: 0,
+            events_commitment: Felt::ZERO,
+            receipts_commitment: Felt::ZERO,
+            state_diff_commitment: Felt::ZERO,
+            transactions_commitment: Felt::ZERO,
+            state_root: genesis.state_root,
+            parent_hash: genesis.parent_hash,
+            l1_da_mode: chain_spec.l1_da_mode,
+            l1_gas_prices: genesis.gas_prices.clone(),
+            l2_gas_prices: self.genesis_l2_gas_prices(genesis.number)?,
+            l1_data_gas_prices: genesis.gas_prices.clone(),
+            sequencer_address: genesis.sequencer_address,
+        };
+
+        if let Some(local_hash) = provider.block_hash_by_num(header.number)? {
+            let genesis_hash = header.compute_hash();
+            if local_hash != genesis_hash {
+                return Err(anyhow!(
+                    "Genesis block hash mismatch: expected {genesis_hash:#x}, got {local_hash:#x}",
+                ));
+            }
+
+            info!("Genesis has already been initialized");
+        } else {
+            let block = SealedBlock { hash: header.compute_hash(), header, body: Vec::new() };
+            let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL1 };
+            self.store_block(block, Default::default(), Vec::new(), Vec::new())?;
+
+            info!("Genesis initialized");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the L2 gas prices of the genesis block at `number`.
+    ///
+    /// They are the configured ones, as determined by the gas oracle before any block is mined.
+    /// Those of an already initialized genesis block are kept though, so that changing the
+    /// configuration doesn't change the genesis block hash.
+    fn genesis_l2_gas_prices(&self, number: BlockNumber) -> ProviderResult<GasPrice> {
+        match self.blockchain.provider().header(number.into())? {
+            Some(header) => Ok(header.l2_gas_prices),
+            None => Ok(self.gas_oracle.current_l2_gas_prices()),
+        }
+    }
+
+    /// Resumes the L2 gas prices of the gas oracle from the latest stored block, eg after the
+    /// node is restarted or the chain is reverted.
+    pub fn sync_l2_gas_prices(&self) -> ProviderResult<()> {
+        let provider = self.blockchain.provider();
+
+        let block = provider.latest_number()?;
+        let header =
+            provider.header(block.into())?.ok_or(ProviderError::MissingBlockHeader(block))?;
+        let receipts = provider.receipts_by_block(block.into())?.unwrap_or_default();
+        let l2_gas_used = receipts.iter().map(|r| r.resources_used().gas.l2_gas as u128).sum();
+
+        self.gas_oracle.sync_l2_gas_prices(header.l2_gas_prices, l2_gas_used);
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,17 +1,23 @@
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context, Result};
+use katana_chain_spec::ChainSpec;
 use katana_db::mdbx::DbEnv;
+use katana_db::models::fork::ForkInfo;
+use katana_fork::BackendConfig;
 use katana_primitives::block::{
     BlockHashOrNumber, BlockIdOrTag, BlockNumber, FinalityStatus, GasPrice, SealedBlockWithStatus,
 };
-use katana_primitives::da::L1DataAvailabilityMode;
+use katana_primitives::chain::ChainId;
 use katana_primitives::hash::{self, StarkHash};
 use katana_provider::providers::db::DbProvider;
 use katana_provider::providers::fork::ForkedProvider;
-use katana_provider::traits::block::{BlockProvider, BlockWriter};
+use katana_provider::traits::account::DevAccountProvider;
+use katana_provider::traits::block::{BlockNumberProvider, BlockProvider, BlockWriter};
 use katana_provider::traits::contract::ContractClassWriter;
 use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::event::EventIndexProvider;
+use katana_provider::traits::fork::ForkInfoProvider;
 use katana_provider::traits::stage::StageCheckpointProvider;
 use katana_provider::traits::state::{StateFactoryProvider, StateWriter};
 use katana_provider::traits::state_update::StateUpdateProvider;
@@ -27,7 +33,7 @@ use starknet::core::utils::parse_cairo_short_string;
 use starknet::macros::short_string;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
-use tracing::info;
+use tracing::{info, warn};
 use url::Url;
 
 pub trait Database:
@@ -45,6 +51,9 @@ pub trait Database:
     + BlockEnvProvider
     + TrieWriter
     + StageCheckpointProvider
+    + ForkInfoProvider
+    + EventIndexProvider
+    + DevAccountProvider
     + 'static
     + Send
     + Sync
@@ -67,6 +76,9 @@ impl<T> Database for T where
         + BlockEnvProvider
         + TrieWriter
         + StageCheckpointProvider
+        + ForkInfoProvider
+        + EventIndexProvider
+        + DevAccountProvider
         + 'static
         + Send
         + Sync
@@ -94,9 +106,18 @@ impl Blockchain {
         db: DbEnv,
         fork_url: Url,
         fork_block: Option<BlockHashOrNumber>,
-        chain: &mut katana_chain_spec::dev::ChainSpec,
+        backend_config: BackendConfig,
+        chain: &mut ChainSpec,
     ) -> Result<(Self, BlockNumber)> {
-        let provider = JsonRpcClient::new(HttpTransport::new(fork_url));
+        // the chain that the database was previously forked from, if any
+        let db_provider = DbProvider::new(db.clone());
+        let fork_info = db_provider.fork_info()?;
+
+        if fork_info.is_none() && db_provider.latest_number().is_ok() {
+            bail!("database already contains a chain that was not forked from another network")
+        }
+
+        let provider = JsonRpcClient::new(HttpTransport::new(fork_url.clone()));
         let chain_id = provider.chain_id().await.context("failed to fetch forked network id")?;
 
         // if the id is not in ASCII encoding, we display the chain id as is in hex.
@@ -105,13 +126,15 @@ impl Blockchain {
             Err(_) => format!("{chain_id:#x}"),
         };
 
-        // If the fork block number is not specified, we use the latest accepted block on the forked
-        // network.
-        let block_id = if let Some(id) = fork_block {
-            id
-        } else {
-            let num = provider.block_number().await?;
-            BlockHashOrNumber::Num(num)
+        // If the fork block number is not specified, we resume from the block that the database was
+        // forked from, or use the latest accepted block on the forked network.
+        let block_id = match (fork_block, &fork_info) {
+            (Some(id), _) => id,
+            (None, Some(info)) => BlockHashOrNumber::Num(info.block_number),
+            (None, None) => {
+                let num = provider.block_number().await?;
+                BlockHashOrNumber::Num(num)
+            }
         };
 
         info!(chain = %parsed_id, block = %block_id, "Forking chain.");
@@ -127,47 +150,66 @@ impl Blockchain {
 
         let block_num = forked_block.block_number;
 
-        chain.id = chain_id.into();
+        if let Some(info) = fork_info {
+            if info.block_hash != forked_block.block_hash {
+                bail!(
+                    "database was forked from block {} ({:#x}), cannot fork it from block {} \
+                     ({:#x})",
+                    info.block_number,
+                    info.block_hash,
+                    block_num,
+                    forked_block.block_hash
+                );
+            }
+
+            if info.url != fork_url.as_str() {
+                let (prev, new) = (&info.url, &fork_url);
+                warn!(%prev, %new, "Resuming forked chain from a different provider.");
+            }
+
+            info!(block = %block_num, "Resuming forked chain from database.");
+        } else {
+            let info = ForkInfo {
+                url: fork_url.to_string(),
+                block_number: block_num,
+                block_hash: forked_block.block_hash,
+            };
+            db_provider.set_fork_info(info)?;
+        }
+
+        let genesis = match chain {
+            ChainSpec::Dev(cs) => {
+                cs.id = chain_id.into();
+                &mut cs.genesis
+            }
+
+            // a rollup chain keeps its own chain id, fee token and settlement configurations as
+            // the forked network is expected to be a deployment of the same chain.
+            ChainSpec::Rollup(cs) => {
+                let forked = ChainId::from(chain_id);
+                if cs.id != forked {
+                    warn!(chain = %cs.id, %forked, "Forked network has a different chain id.");
+                }
+                &mut cs.genesis
+            }
+        };
 
         // adjust the genesis to match the forked block
-        chain.genesis.timestamp = forked_block.timestamp;
-        chain.genesis.number = forked_block.block_number;
-        chain.genesis.state_root = forked_block.new_root;
-        chain.genesis.parent_hash = forked_block.parent_hash;
-        chain.genesis.sequencer_address = forked_block.sequencer_address.into();
+        genesis.timestamp = forked_block.timestamp;
+        genesis.number = forked_block.block_number;
+        genesis.state_root = forked_block.new_root;
+        genesis.parent_hash = forked_block.parent_hash;
+        genesis.sequencer_address = forked_block.sequencer_address.into();
 
         // TODO: remove gas price from genesis
         let eth_l1_gas_price =
             forked_block.l1_gas_price.price_in_wei.to_u128().expect("should fit in u128");
         let strk_l1_gas_price =
             forked_block.l1_gas_price.price_in_fri.to_u128().expect("should fit in u128");
-        chain.genesis.gas_prices =
+        genesis.gas_prices =
             unsafe { GasPrice::new_unchecked(eth_l1_gas_price, strk_l1_gas_price) };
 
-        // TODO: convert this to block number instead of BlockHashOrNumber so that it is easier to
-        // check if the requested block is within the supported range or not.
-        let database = ForkedProvider::new(db, block_id, Arc::new(provider));
-
-        // update the genesis block with the forked block's data
-        // we dont update the `l1_gas_price` bcs its already done when we set the `gas_prices` in
-        // genesis. this flow is kinda flawed, we should probably refactor it out of the
-        // genesis.
-        let mut block = chain.block();
-
-        let eth_l1_data_gas_price =
-            forked_block.l1_data_gas_price.price_in_wei.to_u128().expect("should fit in u128");
-        let strk_l1_data_gas_price =
-            forked_block.l1_data_gas_price.price_in_fri.to_u128().expect("should fit in u128");
-
-        block.header.l1_data_gas_prices =
-            unsafe { GasPrice::new_unchecked(eth_l1_data_gas_price, strk_l1_data_gas_price) };
-
-        block.header.l1_da_mode = match forked_block.l1_da_mode {
-            starknet::core::types::L1DataAvailabilityMode::Blob => L1DataAvailabilityMode::Blob,
-            starknet::core::types::L1DataAvailabilityMode::Calldata => {
-                L1DataAvailabilityMode::Calldata
-            }
-        };
+        let database = ForkedProvider::new(db, block_num, Arc::new(provider), backend_config);
 
         Ok((Self::new(database), block_num))
     }
@@ -176,13 +218,14 @@ impl Blockchain {
     pub fn new_dev(
         provider: impl Database,
         chain_spec: &katana_chain_spec::dev::ChainSpec,
+        l2_gas_prices: GasPrice,
     ) -> Result<Self> {
         // check whether the genesis block has been initialized
         let genesis_hash = provider.block_hash_by_num(chain_spec.genesis.number)?;
 
         match genesis_hash {
             Some(db_hash) => {
-                let genesis_hash = chain_spec.block().header.compute_hash();
+                let genesis_hash = chain_spec.block(l2_gas_prices).header.compute_hash();
                 // check genesis should be the same
                 if db_hash == genesis_hash {
                     Ok(Self::new(provider))
@@ -195,7 +238,7 @@ impl Blockchain {
 
             // Initialize the dev genesis block
             None => {
-                let block = chain_spec.block().seal();
+                let block = chain_spec.block(l2_gas_prices).seal();
                 let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL1 };
                 let states = chain_spec.state_updates();
 
@@ -8,6 +8,14 @@ use starknet::macros::felt;
 pub const DEFAULT_ETH_L1_GAS_PRICE: NonZeroU128 = NonZeroU128::new(20 * u128::pow(10, 9)).unwrap(); // Given in units of Wei.
 pub const DEFAULT_STRK_L1_GAS_PRICE: NonZeroU128 = NonZeroU128::new(20 * u128::pow(10, 9)).unwrap(); // Given in units of STRK.
 
+// Default L2 gas prices, also used for the genesis block
+pub use katana_primitives::genesis::constant::{
+    DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE,
+};
+
+// The L2 gas prices can change by at most 1/8 (12.5%) from one block to the next.
+pub const DEFAULT_L2_GAS_PRICE_MAX_CHANGE_DENOMINATOR: u128 = 8;
+
 // Default data gas prices
 pub const DEFAULT_ETH_L1_DATA_GAS_PRICE: NonZeroU128 = NonZeroU128::new(u128::pow(10, 6)).unwrap(); // Given in units of Wei.
 pub const DEFAULT_STRK_L1_DATA_GAS_PRICE: NonZeroU128 = NonZeroU128::new(u128::pow(10, 6)).unwrap(); // Given in units of STRK.
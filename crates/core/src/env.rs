@@ -3,3 +3,15 @@ pub struct BlockContextGenerator {
     pub block_timestamp_offset: i64,
     pub next_block_start_time: u64,
 }
+
+impl BlockContextGenerator {
+    /// Returns the timestamp that the next block will have if its block context is generated at
+    /// `current_timestamp_secs`.
+    pub fn next_block_timestamp(&self, current_timestamp_secs: i64) -> u64 {
+        if self.next_block_start_time == 0 {
+            (current_timestamp_secs + self.block_timestamp_offset) as u64
+        } else {
+            self.next_block_start_time
+        }
+    }
+}
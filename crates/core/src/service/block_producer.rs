@@ -16,25 +16,28 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
+use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::stream::{Stream, StreamExt};
+use futures::task::AtomicWaker;
 use futures::FutureExt;
 use katana_executor::{BlockExecutor, ExecutionResult, ExecutionStats, ExecutorFactory};
 use katana_pool::validation::stateful::TxValidator;
-use katana_primitives::block::{BlockHashOrNumber, ExecutableBlock, PartialHeader};
-use katana_primitives::da::L1DataAvailabilityMode;
+use katana_pool::{TransactionPool, TxPool};
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber, ExecutableBlock, PartialHeader};
 use katana_primitives::execution::TransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxHash, TxWithHash};
 use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_provider::error::ProviderError;
-use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider};
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider, BlockWriter};
 use katana_provider::traits::env::BlockEnvProvider;
 use katana_provider::traits::state::StateFactoryProvider;
-use katana_tasks::{BlockingTaskPool, BlockingTaskResult};
+use katana_tasks::{BlockingTaskPool, BlockingTaskPoolInitError, BlockingTaskResult};
 use parking_lot::lock_api::RawMutex;
 use parking_lot::{Mutex, RwLock};
 use tokio::time::{interval_at, Instant, Interval};
-use tracing::{error, info, trace};
+use tracing::{error, info, trace, warn};
 
 use crate::backend::Backend;
 
@@ -54,6 +57,27 @@ pub enum BlockProductionError {
 
     #[error("transaction execution error: {0}")]
     TransactionExecutionError(#[from] katana_executor::ExecutorError),
+
+    #[error("a block is currently being mined")]
+    MiningInProgress,
+
+    #[error("block env of block {0} not found")]
+    MissingBlockEnv(BlockNumber),
+
+    #[error(transparent)]
+    BlockingTaskPool(#[from] BlockingTaskPoolInitError),
+}
+
+/// The mode in which a [BlockProducer] produces blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningMode {
+    /// A new block is mined as soon as there are transactions to execute.
+    Instant,
+    /// A new block is mined after the given number of milliseconds since its first transaction
+    /// was executed.
+    Interval(u64),
+    /// Blocks are only mined on demand, eg through the `dev_generateBlock` RPC method.
+    OnDemand,
 }
 
 impl BlockProductionError {
@@ -70,9 +94,22 @@ impl BlockProductionError {
 pub struct MinedBlockOutcome {
     pub block_number: u64,
     pub txs: Vec<TxHash>,
+    /// Transactions that failed to execute and were thus excluded from the block, along with the
+    /// reason of their failure.
+    pub rejected_txs: Vec<(TxHash, String)>,
     pub stats: ExecutionStats,
 }
 
+/// A change to the chain made by a [BlockProducer], as sent to its listeners.
+#[derive(Debug, Clone)]
+pub enum BlockProducerEvent {
+    /// A new block was mined.
+    Mined(MinedBlockOutcome),
+    /// The chain was reverted back to the given block, removing all the blocks after it. The
+    /// blocks mined afterwards reuse the numbers of the removed ones.
+    Reverted(BlockNumber),
+}
+
 #[derive(Debug, Clone)]
 pub struct TxWithOutcome {
     pub tx: TxWithHash,
@@ -97,30 +134,112 @@ type BlockProductionWithTxnsFuture =
 pub struct BlockProducer<EF: ExecutorFactory> {
     /// The inner mode of mining.
     pub producer: Arc<RwLock<BlockProducerMode<EF>>>,
+    /// Listeners for newly mined blocks and chain reverts.
+    listeners: Arc<RwLock<Vec<Sender<BlockProducerEvent>>>>,
+    /// Waker of the task polling the producer, woken up when the mining mode is changed.
+    waker: Arc<AtomicWaker>,
+    /// The pool of the transactions mined by this producer, if any.
+    pool: Option<TxPool>,
 }
 
 impl<EF: ExecutorFactory> BlockProducer<EF> {
     /// Creates a block producer that mines a new block every `interval` milliseconds.
     pub fn interval(backend: Arc<Backend<EF>>, interval: u64) -> Self {
         let producer = IntervalBlockProducer::new(backend, Some(interval));
-        let producer = Arc::new(RwLock::new(BlockProducerMode::Interval(producer)));
-        Self { producer }
+        Self::new(BlockProducerMode::Interval(producer))
     }
 
     /// Creates a new block producer that will only be possible to mine by calling the
     /// `katana_generateBlock` RPC method.
     pub fn on_demand(backend: Arc<Backend<EF>>) -> Self {
         let producer = IntervalBlockProducer::new(backend, None);
-        let producer = Arc::new(RwLock::new(BlockProducerMode::Interval(producer)));
-        Self { producer }
+        Self::new(BlockProducerMode::Interval(producer))
     }
 
     /// Creates a block producer that mines a new block as soon as there are ready transactions in
     /// the transactions pool.
     pub fn instant(backend: Arc<Backend<EF>>) -> Self {
         let producer = InstantBlockProducer::new(backend);
-        let producer = Arc::new(RwLock::new(BlockProducerMode::Instant(producer)));
-        Self { producer }
+        Self::new(BlockProducerMode::Instant(producer))
+    }
+
+    fn new(mode: BlockProducerMode<EF>) -> Self {
+        Self {
+            producer: Arc::new(RwLock::new(mode)),
+            listeners: Default::default(),
+            waker: Default::default(),
+            pool: None,
+        }
+    }
+
+    /// Sets the pool of the transactions mined by this producer.
+    ///
+    /// The pool is kept in sync with every block mined by the producer, however it was mined: the
+    /// mined transactions are removed from the pool, the ones that failed to execute are marked
+    /// as rejected, and the queued transactions whose nonce gap has been filled are promoted.
+    pub fn with_pool(mut self, pool: TxPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Registers a new listener that will be notified of every block mined by this producer,
+    /// including blocks that are mined on demand through [`BlockProducer::force_mine`], and of
+    /// every revert made through [`BlockProducer::revert_to`].
+    ///
+    /// Events are delivered in the order they happen. The listener is dropped once its receiving
+    /// half is closed.
+    pub fn add_listener(&self) -> Receiver<BlockProducerEvent> {
+        const BLOCK_LISTENER_BUFFER_SIZE: usize = 256;
+        let (tx, rx) = channel(BLOCK_LISTENER_BUFFER_SIZE);
+        self.listeners.write().push(tx);
+        rx
+    }
+
+    /// Updates the pool with a newly mined block and notifies the listeners about it. Every block
+    /// mined by the producer must go through this.
+    fn on_block_mined(&self, outcome: MinedBlockOutcome) {
+        if let Some(pool) = &self.pool {
+            // remove mined transactions from the pool
+            pool.remove_transactions(&outcome.txs);
+
+            // keep track of the transactions that failed to execute
+            for (hash, reason) in &outcome.rejected_txs {
+                pool.add_rejected_transaction(*hash, reason.clone());
+            }
+
+            // the validator state has been updated with the mined block, which may have filled
+            // the nonce gaps of some queued transactions
+            pool.promote_queued_transactions();
+        }
+
+        self.notify_listeners(BlockProducerEvent::Mined(outcome));
+    }
+
+    /// Notifies all listeners about the event.
+    fn notify_listeners(&self, event: BlockProducerEvent) {
+        let mut listeners = self.listeners.write();
+        // this is basically a retain but with mut reference
+        for n in (0..listeners.len()).rev() {
+            let mut listener = listeners.swap_remove(n);
+            let retain = match listener.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(e) => {
+                    if e.is_full() {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Unable to send block notification because channel is full."
+                        );
+                        true
+                    } else {
+                        false
+                    }
+                }
+            };
+
+            if retain {
+                listeners.push(listener)
+            }
+        }
     }
 
     pub(super) fn queue(&self, transactions: Vec<ExecutableTxWithHash>) {
@@ -152,25 +271,223 @@ impl<EF: ExecutorFactory> BlockProducer<EF> {
     // Handler for the `katana_generateBlock` RPC method.
     pub fn force_mine(&self) {
         trace!(target: LOG_TARGET, "Scheduling force block mining.");
+        let outcome = {
+            let mut mode = self.producer.write();
+            match &mut *mode {
+                BlockProducerMode::Instant(producer) => producer.force_mine(),
+                BlockProducerMode::Interval(producer) => producer.force_mine(),
+            }
+        };
+
+        if let Some(outcome) = outcome {
+            self.on_block_mined(outcome);
+        }
+    }
+
+    /// Mines `blocks` new blocks at once. The queued transactions, or the pending block in
+    /// _interval_ mode, are included in the first block and the remaining blocks are empty.
+    ///
+    /// If `interval` is given, the timestamp of every block after the first one is `interval`
+    /// seconds after the timestamp of its parent block.
+    pub fn mine(&self, blocks: u64, interval: Option<u64>) -> Result<(), BlockProductionError> {
         let mut mode = self.producer.write();
-        match &mut *mode {
-            BlockProducerMode::Instant(producer) => producer.force_mine(),
-            BlockProducerMode::Interval(producer) => producer.force_mine(),
+
+        for i in 0..blocks {
+            let outcome = match &mut *mode {
+                BlockProducerMode::Instant(producer) => {
+                    // the block env is created when the block is mined, so its timestamp is
+                    // derived from the block mined right before it.
+                    if let Some(interval) = interval.filter(|_| i > 0) {
+                        let provider = producer.backend.blockchain.provider();
+                        let latest_num = provider.latest_number()?;
+                        let latest_env = provider
+                            .block_env_at(latest_num.into())?
+                            .ok_or(BlockProductionError::MissingBlockEnv(latest_num))?;
+                        producer.backend.set_next_block_timestamp(latest_env.timestamp + interval);
+                    }
+
+                    producer.mine_block()?
+                }
+
+                BlockProducerMode::Interval(producer) => {
+                    // the block env of the next block is created as soon as the pending block is
+                    // mined, so its timestamp is derived from the pending block.
+                    if let Some(interval) = interval.filter(|_| i + 1 < blocks) {
+                        let timestamp = producer.executor.read().block_env().timestamp;
+                        producer.backend.set_next_block_timestamp(timestamp + interval);
+                    }
+
+                    producer.mine_pending_block()?
+                }
+            };
+
+            info!(target: LOG_TARGET, block_number = %outcome.block_number, "Mined block.");
+            self.on_block_mined(outcome);
         }
+
+        Ok(())
     }
 
-    pub(super) fn poll_next(&self, cx: &mut Context<'_>) -> Poll<Option<BlockProductionResult>> {
+    /// Returns the mode in which the block producer is currently producing blocks.
+    pub fn mining_mode(&self) -> MiningMode {
+        match &*self.producer.read() {
+            BlockProducerMode::Instant(_) => MiningMode::Instant,
+            BlockProducerMode::Interval(producer) => match producer.block_time {
+                Some(block_time) => MiningMode::Interval(block_time),
+                None => MiningMode::OnDemand,
+            },
+        }
+    }
+
+    /// Changes the mode in which the block producer produces blocks.
+    ///
+    /// When switching from _interval_ to _instant_ mode, the pending block is mined first so that
+    /// none of its changes are lost. Queued transactions are carried over to the new mode.
+    ///
+    /// Fails if a block is currently being mined or if transactions are being executed.
+    pub fn set_mining_mode(&self, mining_mode: MiningMode) -> Result<(), BlockProductionError> {
+        let outcome = {
+            let mut mode = self.producer.write();
+            let mut outcome = None;
+
+            let new_mode = match (&mut *mode, mining_mode) {
+                (BlockProducerMode::Instant(_), MiningMode::Instant) => None,
+
+                (BlockProducerMode::Instant(producer), target) => {
+                    if producer.block_mining.is_some() {
+                        return Err(BlockProductionError::MiningInProgress);
+                    }
+
+                    let block_time = match target {
+                        MiningMode::Interval(block_time) => Some(block_time),
+                        _ => None,
+                    };
+
+                    let mut new = IntervalBlockProducer::with_validator(
+                        producer.backend.clone(),
+                        block_time,
+                        producer.validator.clone(),
+                        producer.permit.clone(),
+                    )?;
+                    new.queued = std::mem::take(&mut producer.queued);
+
+                    Some(BlockProducerMode::Interval(new))
+                }
+
+                (BlockProducerMode::Interval(producer), MiningMode::Instant) => {
+                    if producer.ongoing_mining.is_some() || producer.ongoing_execution.is_some() {
+                        return Err(BlockProductionError::MiningInProgress);
+                    }
+
+                    let mut new = InstantBlockProducer::with_validator(
+                        producer.backend.clone(),
+                        producer.validator.clone(),
+                        producer.permit.clone(),
+                    )?;
+
+                    outcome = Some(producer.mine_pending_block()?);
+                    new.queued = std::mem::take(&mut producer.queued);
+
+                    Some(BlockProducerMode::Instant(new))
+                }
+
+                (BlockProducerMode::Interval(producer), MiningMode::Interval(block_time)) => {
+                    producer.set_block_time(Some(block_time));
+                    None
+                }
+
+                (BlockProducerMode::Interval(producer), MiningMode::OnDemand) => {
+                    producer.set_block_time(None);
+                    None
+                }
+            };
+
+            if let Some(new_mode) = new_mode {
+                *mode = new_mode;
+            }
+
+            outcome
+        };
+
+        if let Some(outcome) = outcome {
+            info!(target: LOG_TARGET, block_number = %outcome.block_number, "Mined block.");
+            self.on_block_mined(outcome);
+        }
+
+        info!(target: LOG_TARGET, mode = ?mining_mode, "Changed mining mode.");
+
+        // the new mode may have work to do (eg queued transactions or a new timer) that the task
+        // driving the producer must be woken up for.
+        self.waker.wake();
+        Ok(())
+    }
+
+    /// Writes the given state updates to the chain state, bypassing transaction execution.
+    ///
+    /// In _interval_ mode, the updates are applied to the pending block's state and will be
+    /// included in its state diff once it is mined. In _instant_ mode, a new block with the updates
+    /// as its only state diff is mined right away.
+    pub fn apply_state_updates(&self, updates: StateUpdates) -> Result<(), BlockProductionError> {
+        let outcome = {
+            let mut mode = self.producer.write();
+            match &mut *mode {
+                BlockProducerMode::Instant(producer) => {
+                    Some(producer.apply_state_updates(updates)?)
+                }
+                BlockProducerMode::Interval(producer) => {
+                    producer.apply_state_updates(updates)?;
+                    None
+                }
+            }
+        };
+
+        if let Some(outcome) = outcome {
+            self.on_block_mined(outcome);
+        }
+
+        Ok(())
+    }
+
+    /// Reverts the chain back to the given block, removing all the blocks after it, and resets the
+    /// producer's pending state on top of it.
+    pub fn revert_to(&self, block: BlockNumber) -> Result<(), BlockProductionError> {
         let mut mode = self.producer.write();
         match &mut *mode {
-            BlockProducerMode::Instant(producer) => producer.poll_next_unpin(cx),
-            BlockProducerMode::Interval(producer) => producer.poll_next_unpin(cx),
+            BlockProducerMode::Instant(producer) => producer.revert_to(block)?,
+            BlockProducerMode::Interval(producer) => producer.revert_to(block)?,
         }
+
+        self.notify_listeners(BlockProducerEvent::Reverted(block));
+        Ok(())
+    }
+
+    pub(super) fn poll_next(&self, cx: &mut Context<'_>) -> Poll<Option<BlockProductionResult>> {
+        self.waker.register(cx.waker());
+
+        let poll = {
+            let mut mode = self.producer.write();
+            match &mut *mode {
+                BlockProducerMode::Instant(producer) => producer.poll_next_unpin(cx),
+                BlockProducerMode::Interval(producer) => producer.poll_next_unpin(cx),
+            }
+        };
+
+        if let Poll::Ready(Some(Ok(outcome))) = &poll {
+            self.on_block_mined(outcome.clone());
+        }
+
+        poll
     }
 }
 
 impl<EF: ExecutorFactory> Clone for BlockProducer<EF> {
     fn clone(&self) -> Self {
-        BlockProducer { producer: self.producer.clone() }
+        BlockProducer {
+            producer: self.producer.clone(),
+            listeners: self.listeners.clone(),
+            waker: self.waker.clone(),
+            pool: self.pool.clone(),
+        }
     }
 }
 
@@ -275,6 +592,43 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
         }
     }
 
+    /// Creates a new [IntervalBlockProducer] that takes over the transaction validator of another
+    /// block producer, along with its permit. The validator is updated with the state of the new
+    /// pending block.
+    fn with_validator(
+        backend: Arc<Backend<EF>>,
+        block_time: Option<u64>,
+        validator: TxValidator,
+        permit: Arc<Mutex<()>>,
+    ) -> Result<Self, BlockProductionError> {
+        let provider = backend.blockchain.provider();
+
+        let latest_num = provider.latest_number()?;
+        let mut block_env = provider
+            .block_env_at(latest_num.into())?
+            .ok_or(BlockProductionError::MissingBlockEnv(latest_num))?;
+        backend.update_block_env(&mut block_env);
+
+        let state = provider.latest()?;
+        let executor = backend.executor_factory.with_state_and_block_env(state, block_env.clone());
+        validator.update(executor.state(), block_env);
+        let blocking_task_spawner = BlockingTaskPool::new()?;
+
+        Ok(Self {
+            is_block_full: false,
+            validator,
+            permit,
+            backend,
+            block_time,
+            timer: None,
+            ongoing_mining: None,
+            ongoing_execution: None,
+            queued: VecDeque::default(),
+            executor: PendingExecutor::new(executor),
+            blocking_task_spawner,
+        })
+    }
+
     /// Creates a new [IntervalBlockProducer] with no `interval`. This mode will not produce blocks
     /// for every fixed interval, although it will still execute all queued transactions and
     /// keep hold of the pending state.
@@ -287,30 +641,112 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
     }
 
     /// Force mine a new block. It will only able to mine if there is no ongoing mining process.
-    pub fn force_mine(&mut self) {
-        match Self::do_mine(self.permit.clone(), self.executor.clone(), self.backend.clone()) {
+    ///
+    /// Returns the outcome of the mined block, or `None` if mining failed.
+    pub fn force_mine(&mut self) -> Option<MinedBlockOutcome> {
+        match self.mine_pending_block() {
             Ok(outcome) => {
                 info!(target: LOG_TARGET, block_number = %outcome.block_number, "Force mined block.");
-                self.executor =
-                    self.create_new_executor_for_next_block().expect("fail to create executor");
+                Some(outcome)
+            }
+            Err(e) => {
+                error!(target: LOG_TARGET, error = %e, "On force mine.");
+                None
+            }
+        }
+    }
 
-                // update pool validator state here ---------
+    /// Mines the pending block and opens a new one on top of it.
+    ///
+    /// Fails if the pending block is already being mined.
+    fn mine_pending_block(&mut self) -> Result<MinedBlockOutcome, BlockProductionError> {
+        if self.ongoing_mining.is_some() {
+            return Err(BlockProductionError::MiningInProgress);
+        }
 
-                let provider = self.backend.blockchain.provider();
-                let state = self.executor.0.read().state();
-                let num = provider.latest_number().unwrap();
-                let block_env = provider.block_env_at(num.into()).unwrap().unwrap();
+        let result =
+            Self::do_mine(self.permit.clone(), self.executor.clone(), self.backend.clone())
+                .and_then(|outcome| self.open_next_block().map(|_| outcome));
 
-                self.validator.update(state, block_env);
+        // the permit is locked while mining, release it even if mining failed so that the pool
+        // doesn't stay blocked
+        unsafe { self.permit.raw().unlock() };
 
-                // -------------------------------------------
+        result
+    }
 
-                unsafe { self.permit.raw().unlock() };
-            }
-            Err(e) => {
-                error!(target: LOG_TARGET, error = %e, "On force mine.");
-            }
+    /// Opens a new pending block on top of the latest block, and updates the pool validator with
+    /// its state.
+    fn open_next_block(&mut self) -> Result<(), BlockProductionError> {
+        self.executor = self.create_new_executor_for_next_block()?;
+
+        // update pool validator state here ---------
+
+        let provider = self.backend.blockchain.provider();
+        let state = self.executor.0.read().state();
+        let num = provider.latest_number()?;
+        let block_env =
+            provider.block_env_at(num.into())?.ok_or(BlockProductionError::MissingBlockEnv(num))?;
+
+        self.validator.update(state, block_env);
+
+        // -------------------------------------------
+
+        Ok(())
+    }
+
+    /// Changes the block time. If a block is already open, its timer is restarted with the new
+    /// block time, or dropped if there is none.
+    fn set_block_time(&mut self, block_time: Option<u64>) {
+        self.block_time = block_time;
+        if self.timer.is_some() || !self.executor.read().transactions().is_empty() {
+            self.timer = block_time.map(block_timer);
+        }
+    }
+
+    /// Applies the state updates to the pending block's state.
+    ///
+    /// Fails if the pending block is currently being mined, as the updates would otherwise be lost.
+    pub fn apply_state_updates(
+        &mut self,
+        updates: StateUpdates,
+    ) -> Result<(), BlockProductionError> {
+        if self.ongoing_mining.is_some() {
+            return Err(BlockProductionError::MiningInProgress);
+        }
+
+        self.executor.write().apply_state_updates(updates)?;
+        Ok(())
+    }
+
+    /// Reverts the chain back to `block` and opens a new pending block on top of it. Any
+    /// transactions queued for execution are discarded.
+    ///
+    /// Fails if a block is currently being mined or if transactions are being executed.
+    pub fn revert_to(&mut self, block: BlockNumber) -> Result<(), BlockProductionError> {
+        if self.ongoing_mining.is_some() || self.ongoing_execution.is_some() {
+            return Err(BlockProductionError::MiningInProgress);
         }
+
+        let _permit = self.permit.lock();
+
+        let provider = self.backend.blockchain.provider();
+        provider.unwind_to(block)?;
+        self.backend.sync_l2_gas_prices()?;
+
+        self.executor = self.create_new_executor_for_next_block()?;
+        self.queued.clear();
+        self.timer = None;
+        self.is_block_full = false;
+
+        let state = self.executor.0.read().state();
+        let block_env = provider
+            .block_env_at(block.into())?
+            .ok_or(BlockProductionError::MissingBlockEnv(block))?;
+        self.validator.reset(state, block_env);
+
+        info!(target: LOG_TARGET, block_number = %block, "Reverted chain.");
+        Ok(())
     }
 
     fn do_mine(
@@ -370,7 +806,9 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
         let latest_num = provider.latest_number()?;
         let updated_state = provider.latest()?;
 
-        let mut block_env = provider.block_env_at(latest_num.into())?.unwrap();
+        let mut block_env = provider
+            .block_env_at(latest_num.into())?
+            .ok_or(BlockProductionError::MissingBlockEnv(latest_num))?;
         backend.update_block_env(&mut block_env);
 
         let executor = backend.executor_factory.with_state_and_block_env(updated_state, block_env);
@@ -441,12 +879,7 @@ impl<EF: ExecutorFactory> Stream for IntervalBlockProducer<EF> {
 
                 if pin.timer.is_none() {
                     // Start the interval timer if it's not already started
-                    pin.timer = pin.block_time.map(|time| {
-                        let duration = Duration::from_millis(time);
-                        let mut interval = interval_at(Instant::now() + duration, duration);
-                        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
-                        interval
-                    });
+                    pin.timer = pin.block_time.map(block_timer);
                 }
             }
 
@@ -492,24 +925,11 @@ impl<EF: ExecutorFactory> Stream for IntervalBlockProducer<EF> {
             if let Poll::Ready(res) = mining.poll_unpin(cx) {
                 match res {
                     Ok(outcome) => {
-                        match pin.create_new_executor_for_next_block() {
-                            Ok(executor) => {
-                                // update pool validator state here ---------
-
-                                let provider = pin.backend.blockchain.provider();
-                                let state = executor.0.read().state();
-                                let num = provider.latest_number()?;
-                                let block_env = provider.block_env_at(num.into()).unwrap().unwrap();
-
-                                pin.validator.update(state, block_env);
-
-                                // -------------------------------------------
+                        let result = pin.open_next_block();
+                        unsafe { pin.permit.raw().unlock() };
 
-                                pin.executor = executor;
-                                unsafe { pin.permit.raw().unlock() };
-                            }
-
-                            Err(e) => return Poll::Ready(Some(Err(e))),
+                        if let Err(e) = result {
+                            return Poll::Ready(Some(Err(e)));
                         }
 
                         pin.is_block_full = false;
@@ -532,6 +952,14 @@ impl<EF: ExecutorFactory> Stream for IntervalBlockProducer<EF> {
     }
 }
 
+/// Creates the timer of a block that is closed after `block_time` milliseconds.
+fn block_timer(block_time: u64) -> Interval {
+    let duration = Duration::from_millis(block_time);
+    let mut interval = interval_at(Instant::now() + duration, duration);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval
+}
+
 #[allow(missing_debug_implementations)]
 pub struct InstantBlockProducer<EF: ExecutorFactory> {
     /// Holds the backend if no block is being mined
@@ -580,18 +1008,104 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
         }
     }
 
-    pub fn force_mine(&mut self) {
+    /// Creates a new [InstantBlockProducer] that takes over the transaction validator of another
+    /// block producer, along with its permit.
+    fn with_validator(
+        backend: Arc<Backend<EF>>,
+        validator: TxValidator,
+        permit: Arc<Mutex<()>>,
+    ) -> Result<Self, BlockProductionError> {
+        Ok(Self {
+            permit,
+            backend,
+            validator,
+            block_mining: None,
+            queued: VecDeque::default(),
+            blocking_task_pool: BlockingTaskPool::new()?,
+        })
+    }
+
+    /// Force mine a new block with all the queued transactions.
+    ///
+    /// Returns the outcome of the mined block, or `None` if there is an ongoing mining process or
+    /// if mining failed.
+    pub fn force_mine(&mut self) -> Option<MinedBlockOutcome> {
         if self.block_mining.is_none() {
-            let txs = std::mem::take(&mut self.queued);
-            let _ = Self::do_mine(
-                self.validator.clone(),
-                self.permit.clone(),
-                self.backend.clone(),
-                txs,
-            );
+            match self.mine_block() {
+                Ok(outcome) => Some(outcome),
+                Err(error) => {
+                    error!(target: LOG_TARGET, %error, "On force mine.");
+                    None
+                }
+            }
         } else {
-            trace!(target: LOG_TARGET, "Unable to force mine while a mining process is running.")
+            trace!(target: LOG_TARGET, "Unable to force mine while a mining process is running.");
+            None
+        }
+    }
+
+    /// Mines a new block with all the queued transactions.
+    ///
+    /// Fails if a block is currently being mined.
+    fn mine_block(&mut self) -> Result<MinedBlockOutcome, BlockProductionError> {
+        if self.block_mining.is_some() {
+            return Err(BlockProductionError::MiningInProgress);
+        }
+
+        let txs = std::mem::take(&mut self.queued);
+        let (outcome, _) = Self::do_mine(
+            self.validator.clone(),
+            self.permit.clone(),
+            self.backend.clone(),
+            txs,
+            StateUpdates::default(),
+        )?;
+
+        Ok(outcome)
+    }
+
+    /// Mines a new block with no transactions whose state diff only consists of the given state
+    /// updates.
+    pub fn apply_state_updates(
+        &mut self,
+        updates: StateUpdates,
+    ) -> Result<MinedBlockOutcome, BlockProductionError> {
+        let (outcome, _) = Self::do_mine(
+            self.validator.clone(),
+            self.permit.clone(),
+            self.backend.clone(),
+            VecDeque::new(),
+            updates,
+        )?;
+
+        let block_number = outcome.block_number;
+        info!(target: LOG_TARGET, %block_number, "Mined block with state updates.");
+        Ok(outcome)
+    }
+
+    /// Reverts the chain back to `block`. Any transactions queued for mining are discarded.
+    ///
+    /// Fails if a block is currently being mined.
+    pub fn revert_to(&mut self, block: BlockNumber) -> Result<(), BlockProductionError> {
+        if self.block_mining.is_some() {
+            return Err(BlockProductionError::MiningInProgress);
         }
+
+        let _permit = self.permit.lock();
+
+        let provider = self.backend.blockchain.provider();
+        provider.unwind_to(block)?;
+        self.backend.sync_l2_gas_prices()?;
+        self.queued.clear();
+
+        let state = provider.latest()?;
+        let block_env = provider
+            .block_env_at(block.into())?
+            .ok_or(BlockProductionError::MissingBlockEnv(block))?;
+        self.validator.reset(state, block_env);
+
+        info!(target: LOG_TARGET, block_number = %block, "Reverted chain.");
+        Ok(())
     }
 
     fn do_mine(
@@ -599,6 +1113,7 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
         permit: Arc<Mutex<()>>,
         backend: Arc<Backend<EF>>,
         transactions: VecDeque<Vec<ExecutableTxWithHash>>,
+        state_updates: StateUpdates,
     ) -> Result<(MinedBlockOutcome, Vec<TxWithOutcome>), BlockProductionError> {
         let _permit = permit.lock();
 
@@ -611,13 +1126,16 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
         // TODO: don't use the previous block env, we should create on based on the current state of
         // the l1 (to determine the proper gas prices)
         let latest_num = provider.latest_number()?;
-        let mut block_env = provider.block_env_at(BlockHashOrNumber::Num(latest_num))?.unwrap();
+        let mut block_env = provider
+            .block_env_at(BlockHashOrNumber::Num(latest_num))?
+            .ok_or(BlockProductionError::MissingBlockEnv(latest_num))?;
         backend.update_block_env(&mut block_env);
 
         let parent_hash = provider.latest_hash()?;
         let latest_state = provider.latest()?;
 
         let mut executor = backend.executor_factory.with_state(latest_state);
+        executor.apply_state_updates(state_updates)?;
 
         let block = ExecutableBlock {
             body: transactions,
@@ -627,7 +1145,7 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
                 timestamp: block_env.timestamp,
                 protocol_version: CURRENT_STARKNET_VERSION,
                 sequencer_address: block_env.sequencer_address,
-                l1_da_mode: L1DataAvailabilityMode::Calldata,
+                l1_da_mode: block_env.l1_da_mode,
                 l2_gas_prices: block_env.l2_gas_prices.clone(),
                 l1_gas_prices: block_env.l1_gas_prices.clone(),
                 l1_data_gas_prices: block_env.l1_data_gas_prices.clone(),
@@ -656,7 +1174,9 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
         let provider = backend.blockchain.provider();
         let state = provider.latest()?;
         let latest_num = provider.latest_number()?;
-        let block_env = provider.block_env_at(latest_num.into())?.expect("latest");
+        let block_env = provider
+            .block_env_at(latest_num.into())?
+            .ok_or(BlockProductionError::MissingBlockEnv(latest_num))?;
         validator.update(state, block_env);
 
         // -------------------------------------------
@@ -683,7 +1203,9 @@ impl<EF: ExecutorFactory> Stream for InstantBlockProducer<EF> {
                 let permit = pin.permit.clone();
 
                 pin.blocking_task_pool
-                    .spawn(|| Self::do_mine(validator, permit, backend, transactions))
+                    .spawn(|| {
+                        Self::do_mine(validator, permit, backend, transactions, Default::default())
+                    })
             }));
         }
 
@@ -1,4 +1,5 @@
 use arbitrary::{Arbitrary, Unstructured};
+use assert_matches::assert_matches;
 use futures::pin_mut;
 use katana_chain_spec::ChainSpec;
 use katana_executor::implementation::noop::NoopExecutorFactory;
@@ -73,6 +74,78 @@ async fn interval_mine_after_timer() {
     assert_eq!(backend.blockchain.provider().latest_number().unwrap(), 1);
 }
 
+#[tokio::test]
+async fn block_listeners_notified_on_force_mine() {
+    let backend = test_backend();
+    let producer = BlockProducer::on_demand(backend.clone());
+
+    let mut listener = producer.add_listener();
+    producer.force_mine();
+    producer.force_mine();
+
+    let mut next_event = || listener.try_next().unwrap().expect("should receive event");
+    assert_matches!(next_event(), BlockProducerEvent::Mined(outcome) if outcome.block_number == 1);
+    assert_matches!(next_event(), BlockProducerEvent::Mined(outcome) if outcome.block_number == 2);
+
+    // the revert is delivered before the blocks mined after it
+    producer.revert_to(1).unwrap();
+    producer.force_mine();
+
+    assert_matches!(next_event(), BlockProducerEvent::Reverted(1));
+    assert_matches!(next_event(), BlockProducerEvent::Mined(outcome) if outcome.block_number == 2);
+
+    // no more blocks have been mined
+    assert!(listener.try_next().is_err());
+}
+
+#[tokio::test]
+async fn mine_blocks_with_interval() {
+    let backend = test_backend();
+    let provider = backend.blockchain.provider();
+
+    let timestamp =
+        |num: BlockNumber| provider.block_env_at(num.into()).unwrap().unwrap().timestamp;
+
+    let mine_and_check = |producer: BlockProducer<NoopExecutorFactory>| {
+        let start = provider.latest_number().unwrap();
+        producer.mine(3, Some(100)).unwrap();
+        assert_eq!(provider.latest_number().unwrap(), start + 3);
+
+        assert_eq!(timestamp(start + 2), timestamp(start + 1) + 100);
+        assert_eq!(timestamp(start + 3), timestamp(start + 2) + 100);
+
+        // mining no blocks is a no-op
+        producer.mine(0, None).unwrap();
+        assert_eq!(provider.latest_number().unwrap(), start + 3);
+    };
+
+    // the producers must be created one after the other as they're built on the latest block
+    mine_and_check(BlockProducer::instant(backend.clone()));
+    mine_and_check(BlockProducer::on_demand(backend.clone()));
+}
+
+#[tokio::test]
+async fn switch_mining_mode() {
+    let backend = test_backend();
+    let provider = backend.blockchain.provider();
+
+    let producer = BlockProducer::instant(backend.clone());
+    assert_eq!(producer.mining_mode(), MiningMode::Instant);
+
+    producer.set_mining_mode(MiningMode::OnDemand).unwrap();
+    assert_eq!(producer.mining_mode(), MiningMode::OnDemand);
+    assert!(producer.is_interval_mining());
+
+    producer.set_mining_mode(MiningMode::Interval(1000)).unwrap();
+    assert_eq!(producer.mining_mode(), MiningMode::Interval(1000));
+
+    // the pending block is mined when switching back to instant mode
+    let latest_num = provider.latest_number().unwrap();
+    producer.set_mining_mode(MiningMode::Instant).unwrap();
+    assert_eq!(producer.mining_mode(), MiningMode::Instant);
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 1);
+}
+
 // Helper functions to create test transactions
 fn dummy_transaction() -> ExecutableTxWithHash {
     fn tx() -> ExecutableTx {
@@ -7,7 +7,6 @@ use futures::stream::StreamExt;
 use katana_executor::ExecutorFactory;
 use katana_pool::ordering::PoolOrd;
 use katana_pool::pending::PendingTransactions;
-use katana_pool::{TransactionPool, TxPool};
 use katana_primitives::transaction::ExecutableTxWithHash;
 use tracing::{error, info};
 
@@ -35,8 +34,6 @@ where
     pub(crate) block_producer: BlockProducer<EF>,
     /// the miner responsible to select transactions from the `pool´
     pub(crate) miner: TransactionMiner<O>,
-    /// the pool that holds all transactions
-    pub(crate) pool: TxPool,
     /// Metrics for recording the service operations
     metrics: BlockProducerMetrics,
 }
@@ -46,12 +43,8 @@ where
     EF: ExecutorFactory,
     O: PoolOrd<Transaction = ExecutableTxWithHash>,
 {
-    pub fn new(
-        pool: TxPool,
-        miner: TransactionMiner<O>,
-        block_producer: BlockProducer<EF>,
-    ) -> Self {
-        Self { block_producer, miner, pool, metrics: BlockProducerMetrics::default() }
+    pub fn new(miner: TransactionMiner<O>, block_producer: BlockProducer<EF>) -> Self {
+        Self { block_producer, miner, metrics: BlockProducerMetrics::default() }
     }
 }
 
@@ -77,9 +70,6 @@ where
                         let steps_used = outcome.stats.cairo_steps_used;
                         this.metrics.l1_gas_processed_total.increment(gas_used as u64);
                         this.metrics.cairo_steps_processed_total.increment(steps_used as u64);
-
-                        // remove mined transactions from the pool
-                        this.pool.remove_transactions(&outcome.txs);
                     }
 
                     Err(error) => {
@@ -4,16 +4,21 @@ use katana_chain_spec::{dev, ChainSpec, SettlementLayer};
 use katana_core::backend::gas_oracle::GasOracle;
 use katana_core::backend::storage::{Blockchain, Database};
 use katana_core::backend::Backend;
+use katana_db::models::fork::ForkInfo;
 use katana_executor::implementation::blockifier::cache::ClassCache;
 use katana_executor::implementation::blockifier::BlockifierFactory;
 use katana_executor::BlockLimits;
+use katana_primitives::block::BlockHashOrNumber;
 use katana_primitives::chain::ChainId;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::CfgEnv;
 use katana_primitives::felt;
 use katana_primitives::genesis::allocation::DevAllocationsGenerator;
 use katana_primitives::genesis::constant::DEFAULT_PREFUNDED_ACCOUNT_BALANCE;
 use katana_primitives::genesis::Genesis;
 use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::BlockProvider;
+use katana_provider::traits::fork::ForkInfoProvider;
 use rstest::rstest;
 use url::Url;
 
@@ -68,7 +73,8 @@ fn rollup_chain_spec() -> rollup::ChainSpec {
         rpc_url: Url::parse("http://localhost:5050").unwrap(),
     };
 
-    rollup::ChainSpec { id, genesis, settlement, fee_contract }
+    let l1_da_mode = L1DataAvailabilityMode::Calldata;
+    rollup::ChainSpec { id, genesis, settlement, fee_contract, l1_da_mode }
 }
 
 #[rstest]
@@ -111,3 +117,29 @@ fn reinitialize_with_different_rollup_chain_spec() {
     let err = backend2.init_genesis().unwrap_err().to_string();
     assert!(err.as_str().contains("Genesis block hash mismatch"));
 }
+
+#[test]
+fn initialize_forked_rollup_genesis() {
+    let db = DbProvider::new_ephemeral();
+
+    let mut chain = rollup_chain_spec();
+    chain.genesis.number = 10;
+    chain.genesis.parent_hash = felt!("0x1337");
+    chain.genesis.state_root = felt!("0x420");
+
+    let info = ForkInfo { block_number: 10, ..Default::default() };
+    db.set_fork_info(info).unwrap();
+
+    let chain = ChainSpec::Rollup(chain);
+    let backend = backend_with_db(&chain, db.clone());
+    backend.init_genesis().expect("failed to initialize genesis");
+
+    // the genesis transactions are not executed as the genesis state exists on the forked network
+    let block = db.block(BlockHashOrNumber::Num(10)).unwrap().unwrap();
+    assert!(block.body.is_empty());
+    assert_eq!(block.header.parent_hash, felt!("0x1337"));
+    assert_eq!(block.header.state_root, felt!("0x420"));
+
+    let backend = backend_with_db(&chain, db);
+    backend.init_genesis().unwrap();
+}
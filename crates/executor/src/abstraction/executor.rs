@@ -1,5 +1,6 @@
 use katana_primitives::block::ExecutableBlock;
 use katana_primitives::env::{BlockEnv, CfgEnv};
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxWithHash};
 use katana_provider::traits::state::StateProvider;
 
@@ -40,6 +41,13 @@ pub trait BlockExecutor<'a>: Send + Sync + core::fmt::Debug {
         transactions: Vec<ExecutableTxWithHash>,
     ) -> ExecutorResult<(usize, Option<ExecutorError>)>;
 
+    /// Writes the given state updates directly to the executor's state, without executing any
+    /// transaction. The updates will be included in the state diff of the executed block.
+    ///
+    /// Only nonce, storage and contract class hash updates are applied. Class declarations are
+    /// ignored.
+    fn apply_state_updates(&mut self, updates: StateUpdates) -> ExecutorResult<()>;
+
     /// Takes the output state of the executor.
     fn take_execution_output(&mut self) -> ExecutorResult<ExecutionOutput>;
 
@@ -1,11 +1,15 @@
 mod executor;
 
+use std::collections::HashSet;
+use std::sync::Arc;
+
 pub use executor::*;
 use katana_primitives::execution::TransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::TxWithHash;
 use katana_primitives::{ContractAddress, Felt};
+use parking_lot::RwLock;
 
 pub use crate::error::*;
 
@@ -36,11 +40,18 @@ pub struct ExecutionFlags {
     fee: bool,
     /// Determine whether to perform transaction's sender nonce check.
     nonce_check: bool,
+    /// Accounts whose transactions are executed without performing the account validation logic.
+    impersonated_accounts: ImpersonatedAccounts,
 }
 
 impl Default for ExecutionFlags {
     fn default() -> Self {
-        Self { account_validation: true, fee: true, nonce_check: true }
+        Self {
+            account_validation: true,
+            fee: true,
+            nonce_check: true,
+            impersonated_accounts: ImpersonatedAccounts::default(),
+        }
     }
 }
 
@@ -67,11 +78,24 @@ impl ExecutionFlags {
         self
     }
 
+    /// Set the accounts to impersonate.
+    pub fn with_impersonated_accounts(mut self, accounts: ImpersonatedAccounts) -> Self {
+        self.impersonated_accounts = accounts;
+        self
+    }
+
     /// Returns whether the account validation is enabled.
     pub fn account_validation(&self) -> bool {
         self.account_validation
     }
 
+    /// Returns whether the account validation is enabled for transactions sent by `sender`.
+    ///
+    /// This is always disabled for impersonated accounts.
+    pub fn account_validation_for(&self, sender: ContractAddress) -> bool {
+        self.account_validation && !self.impersonated_accounts.contains(sender)
+    }
+
     /// Returns whether the fee related operations are enabled.
     pub fn fee(&self) -> bool {
         self.fee
@@ -81,6 +105,38 @@ impl ExecutionFlags {
     pub fn nonce_check(&self) -> bool {
         self.nonce_check
     }
+
+    /// Returns the impersonated accounts.
+    pub fn impersonated_accounts(&self) -> &ImpersonatedAccounts {
+        &self.impersonated_accounts
+    }
+}
+
+/// A set of accounts that are being impersonated.
+///
+/// Transactions sent by an impersonated account are executed without running the account's
+/// validation logic (ie `__validate__`), allowing them to be sent without the account's signature.
+///
+/// The set is shared between all of its clones, so accounts added or removed through one of them
+/// are visible to the others.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts(Arc<RwLock<HashSet<ContractAddress>>>);
+
+impl ImpersonatedAccounts {
+    /// Starts impersonating `address`. Returns `false` if the account is already impersonated.
+    pub fn add(&self, address: ContractAddress) -> bool {
+        self.0.write().insert(address)
+    }
+
+    /// Stops impersonating `address`. Returns `false` if the account isn't impersonated.
+    pub fn remove(&self, address: ContractAddress) -> bool {
+        self.0.write().remove(&address)
+    }
+
+    /// Returns whether `address` is impersonated.
+    pub fn contains(&self, address: ContractAddress) -> bool {
+        self.0.read().contains(&address)
+    }
 }
 
 /// Stats about the transactions execution.
@@ -88,6 +144,8 @@ impl ExecutionFlags {
 pub struct ExecutionStats {
     /// The total gas used.
     pub l1_gas_used: u128,
+    /// The total L2 gas used.
+    pub l2_gas_used: u128,
     /// The total cairo steps used.
     pub cairo_steps_used: u128,
 }
@@ -116,14 +174,23 @@ pub struct EntryPointCall {
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum ExecutionResult {
-    Success { receipt: Receipt, trace: TransactionExecutionInfo },
+    Success {
+        receipt: Receipt,
+        trace: TransactionExecutionInfo,
+        /// The state changes made by the transaction.
+        state_diff: StateUpdates,
+    },
     Failed { error: ExecutionError },
 }
 
 impl ExecutionResult {
     /// Creates a new successful execution result.
-    pub fn new_success(receipt: Receipt, trace: TransactionExecutionInfo) -> Self {
-        ExecutionResult::Success { receipt, trace }
+    pub fn new_success(
+        receipt: Receipt,
+        trace: TransactionExecutionInfo,
+        state_diff: StateUpdates,
+    ) -> Self {
+        ExecutionResult::Success { receipt, trace, state_diff }
     }
 
     /// Creates a new failed execution result with the given error.
@@ -156,6 +223,15 @@ impl ExecutionResult {
             _ => None,
         }
     }
+
+    /// Returns the state changes made by the transaction if it was successful. Otherwise, returns
+    /// `None`.
+    pub fn state_diff(&self) -> Option<&StateUpdates> {
+        match self {
+            ExecutionResult::Success { state_diff, .. } => Some(state_diff),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -15,14 +15,26 @@ use blockifier::state::cached_state::CachedState;
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::objects::{DeprecatedTransactionInfo, TransactionInfo};
 use cairo_vm::vm::runners::cairo_runner::RunResources;
+use katana_primitives::state::StateUpdates;
 use katana_primitives::Felt;
 use starknet_api::core::EntryPointSelector;
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::Calldata;
 
-use super::utils::to_blk_address;
+use super::utils::{state_updates_from_state_maps, to_blk_address};
 use crate::{EntryPointCall, ExecutionError};
 
+/// The full execution info of a function call, as returned by [`trace_call`].
+#[derive(Debug)]
+pub struct CallTrace {
+    /// The execution info of the call, including all of its inner calls.
+    pub call_info: CallInfo,
+    /// The error of the call if its execution failed.
+    pub revert_error: Option<String>,
+    /// The state changes made by the call. They're never committed.
+    pub state_updates: StateUpdates,
+}
+
 /// Perform a function call on a contract and retrieve the return values.
 pub fn execute_call<S: StateReader>(
     request: EntryPointCall,
@@ -34,11 +46,49 @@ pub fn execute_call<S: StateReader>(
     Ok(res.execution.retdata.0)
 }
 
+/// Perform a function call on a contract and retrieve its full execution info.
+///
+/// Unlike [`execute_call`], a call that fails during its execution isn't returned as an error, so
+/// that the execution of the failing call can still be inspected.
+pub fn trace_call<S: StateReader>(
+    request: EntryPointCall,
+    state: &mut CachedState<S>,
+    block_context: Arc<BlockContext>,
+    max_gas: u64,
+) -> Result<CallTrace, ExecutionError> {
+    let selector = request.entry_point_selector;
+    let call_info = execute_entry_point(request, state, block_context, max_gas)?;
+
+    let revert_error = match check_call_failure(&call_info, selector) {
+        Ok(()) => None,
+        Err(error @ EntryPointExecutionError::PreExecutionError(_)) => return Err(error.into()),
+        Err(error) => Some(ExecutionError::from(error).to_string()),
+    };
+
+    let state_maps = state.to_state_diff()?.state_maps;
+    // classes can't be declared from a function call
+    let state_updates = state_updates_from_state_maps(state_maps, |_| false);
+
+    Ok(CallTrace { call_info, revert_error, state_updates })
+}
+
 fn execute_call_inner<S: StateReader>(
     request: EntryPointCall,
     state: &mut CachedState<S>,
     block_context: Arc<BlockContext>,
     max_gas: u64,
+) -> EntryPointExecutionResult<CallInfo> {
+    let selector = request.entry_point_selector;
+    let call_info = execute_entry_point(request, state, block_context, max_gas)?;
+    check_call_failure(&call_info, selector)?;
+    Ok(call_info)
+}
+
+fn execute_entry_point<S: StateReader>(
+    request: EntryPointCall,
+    state: &mut CachedState<S>,
+    block_context: Arc<BlockContext>,
+    max_gas: u64,
 ) -> EntryPointExecutionResult<CallInfo> {
     let call = CallEntryPoint {
         initial_gas: max_gas,
@@ -77,28 +127,27 @@ fn execute_call_inner<S: StateReader>(
     // If `initial_gas` can't fit in a usize, use the maximum.
     ctx.vm_run_resources = RunResources::new(max_gas.try_into().unwrap_or(usize::MAX));
     let mut remaining_gas = call.initial_gas;
-    let call_info = call.execute(state, &mut ctx, &mut remaining_gas)?;
+    call.execute(state, &mut ctx, &mut remaining_gas)
+}
 
+fn check_call_failure(call_info: &CallInfo, selector: Felt) -> EntryPointExecutionResult<()> {
     // In Starknet 0.13.4 calls return error as return data (Ok variant) instead of returning as an
     // Err. Refer to: https://github.com/dojoengine/sequencer/blob/5d737b9c90a14bdf4483d759d1a1d4ce64aa9fd2/crates/blockifier/src/execution/execution_utils.rs#L74
     if call_info.execution.failed {
         match call_info.execution.retdata.0.as_slice() {
             [error_code] if *error_code == Felt::from_str(ENTRYPOINT_NOT_FOUND_ERROR).unwrap() => {
-                let error = PreExecutionError::EntryPointNotFound(EntryPointSelector(
-                    request.entry_point_selector,
-                ));
-
+                let error = PreExecutionError::EntryPointNotFound(EntryPointSelector(selector));
                 return Err(EntryPointExecutionError::PreExecutionError(error));
             }
             _ => {
                 let error_trace =
-                    extract_trailing_cairo1_revert_trace(&call_info, Cairo1RevertHeader::Execution);
+                    extract_trailing_cairo1_revert_trace(call_info, Cairo1RevertHeader::Execution);
                 return Err(EntryPointExecutionError::ExecutionFailed { error_trace });
             }
         }
     }
 
-    Ok(call_info)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -116,9 +165,9 @@ mod tests {
     use katana_provider::traits::state::{StateFactoryProvider, StateWriter};
     use starknet::macros::selector;
 
-    use super::execute_call_inner;
+    use super::{execute_call_inner, trace_call};
     use crate::implementation::blockifier::state::StateProviderDb;
-    use crate::EntryPointCall;
+    use crate::{EntryPointCall, ExecutionError};
 
     #[test]
     fn max_steps() {
@@ -233,4 +282,56 @@ mod tests {
             assert!(error_trace.to_string().contains("fail"));
         }
     }
+
+    #[test]
+    fn trace_call_with_panic() {
+        let json = include_str!("../../../tests/fixtures/call_test.json");
+        let class = ContractClass::from_str(json).unwrap();
+        let class_hash = class.class_hash().unwrap();
+        let casm_hash = class.clone().compile().unwrap().class_hash().unwrap();
+
+        let provider = test_utils::test_provider();
+        provider.set_class(class_hash, class).unwrap();
+        provider.set_compiled_class_hash_of_class_hash(class_hash, casm_hash).unwrap();
+        let address = address!("0x1337");
+        provider.set_class_hash_of_contract(address, class_hash).unwrap();
+
+        let state = provider.latest().unwrap();
+        let state = StateProviderDb::new(state);
+
+        let mut state = cached_state::CachedState::new(state);
+        let ctx = Arc::new(BlockContext::create_for_testing());
+
+        let req = EntryPointCall {
+            calldata: vec![felt!("460")],
+            contract_address: address,
+            entry_point_selector: selector!("bounded_call"),
+        };
+
+        let trace = trace_call(req, &mut state, ctx.clone(), 1_000_000).unwrap();
+        assert!(trace.revert_error.is_none());
+        assert!(!trace.call_info.execution.failed);
+        assert_eq!(trace.call_info.call.class_hash.map(|h| h.0), Some(class_hash));
+
+        // the trace of a failed call is still returned, along with its error
+        let req = EntryPointCall {
+            calldata: Vec::new(),
+            contract_address: address,
+            entry_point_selector: selector!("call_with_panic"),
+        };
+
+        let trace = trace_call(req, &mut state, ctx.clone(), u64::MAX).unwrap();
+        assert!(trace.call_info.execution.failed);
+        assert!(trace.revert_error.unwrap().contains("fail"));
+
+        // a call to a missing entry point is still an error
+        let req = EntryPointCall {
+            calldata: Vec::new(),
+            contract_address: address,
+            entry_point_selector: selector!("missing"),
+        };
+
+        let result = trace_call(req, &mut state, ctx, u64::MAX);
+        assert!(matches!(result, Err(ExecutionError::EntryPointNotFound(_))));
+    }
 }
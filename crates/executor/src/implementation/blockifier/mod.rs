@@ -11,14 +11,19 @@ pub mod state;
 pub mod utils;
 
 use blockifier::context::BlockContext;
+use blockifier::state::cached_state::StateMaps;
 use cache::ClassCache;
 use katana_primitives::block::{ExecutableBlock, GasPrice as KatanaGasPrices, PartialHeader};
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::{BlockEnv, CfgEnv};
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, TxWithHash};
 use katana_provider::traits::state::StateProvider;
 use starknet_api::block::{
     BlockInfo, BlockNumber, BlockTimestamp, GasPriceVector, GasPrices, NonzeroGasPrice,
 };
+use starknet_api::core::{ClassHash, Nonce};
+use starknet_api::state::StorageKey;
 use tracing::info;
 
 use self::state::CachedState;
@@ -157,6 +162,10 @@ impl<'a> StarknetVMProcessor<'a> {
         let strk_l1_data_gas_price =
             NonzeroGasPrice::new(header.l1_data_gas_prices.strk.get().into())
                 .unwrap_or(NonzeroGasPrice::MIN);
+        let eth_l2_gas_price = NonzeroGasPrice::new(header.l2_gas_prices.eth.get().into())
+            .unwrap_or(NonzeroGasPrice::MIN);
+        let strk_l2_gas_price = NonzeroGasPrice::new(header.l2_gas_prices.strk.get().into())
+            .unwrap_or(NonzeroGasPrice::MIN);
 
         // TODO: @kariy, not sure here if we should add some functions to alter it
         // instead of cloning. Or did I miss a function?
@@ -171,17 +180,15 @@ impl<'a> StarknetVMProcessor<'a> {
                 eth_gas_prices: GasPriceVector {
                     l1_gas_price: eth_l1_gas_price,
                     l1_data_gas_price: eth_l1_data_gas_price,
-                    // TODO: update to use the correct value
-                    l2_gas_price: eth_l1_gas_price,
+                    l2_gas_price: eth_l2_gas_price,
                 },
                 strk_gas_prices: GasPriceVector {
                     l1_gas_price: strk_l1_gas_price,
                     l1_data_gas_price: strk_l1_data_gas_price,
-                    // TODO: update to use the correct value
-                    l2_gas_price: strk_l1_gas_price,
+                    l2_gas_price: strk_l2_gas_price,
                 },
             },
-            use_kzg_da: false,
+            use_kzg_da: header.l1_da_mode == L1DataAvailabilityMode::Blob,
         };
 
         self.block_context = Arc::new(BlockContext::new(
@@ -231,8 +238,9 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
             match result {
                 Ok(exec_result) => {
                     match &exec_result {
-                        ExecutionResult::Success { receipt, trace } => {
+                        ExecutionResult::Success { receipt, trace, .. } => {
                             self.stats.l1_gas_used += receipt.resources_used().gas.l1_gas as u128;
+                            self.stats.l2_gas_used += receipt.resources_used().gas.l2_gas as u128;
                             self.stats.cairo_steps_used +=
                                 receipt.resources_used().computation_resources.n_steps as u128;
 
@@ -264,6 +272,31 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
         Ok((total_executed, None))
     }
 
+    fn apply_state_updates(&mut self, updates: StateUpdates) -> ExecutorResult<()> {
+        let mut writes = StateMaps::default();
+
+        for (address, nonce) in updates.nonce_updates {
+            writes.nonces.insert(utils::to_blk_address(address), Nonce(nonce));
+        }
+
+        let class_updates = updates.deployed_contracts.into_iter().chain(updates.replaced_classes);
+        for (address, class_hash) in class_updates {
+            writes.class_hashes.insert(utils::to_blk_address(address), ClassHash(class_hash));
+        }
+
+        for (address, entries) in updates.storage_updates {
+            let address = utils::to_blk_address(address);
+            for (key, value) in entries {
+                let key = key.try_into().map_err(|e| ExecutorError::Other(Box::new(e)))?;
+                writes.storage.insert((address, StorageKey(key)), value);
+            }
+        }
+
+        self.state.inner.lock().cached_state.update_cache(&writes, Default::default());
+
+        Ok(())
+    }
+
     fn take_execution_output(&mut self) -> ExecutorResult<ExecutionOutput> {
         let states = utils::state_update_from_cached_state(&self.state);
         let transactions = std::mem::take(&mut self.transactions);
@@ -314,6 +347,11 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
             number: self.block_context.block_info().block_number.0,
             timestamp: self.block_context.block_info().block_timestamp.0,
             sequencer_address: utils::to_address(self.block_context.block_info().sequencer_address),
+            l1_da_mode: if self.block_context.block_info().use_kzg_da {
+                L1DataAvailabilityMode::Blob
+            } else {
+                L1DataAvailabilityMode::Calldata
+            },
         }
     }
 }
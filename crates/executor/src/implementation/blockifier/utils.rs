@@ -9,7 +9,7 @@ use blockifier::execution::contract_class::{
     CompiledClassV0, CompiledClassV1, RunnableCompiledClass,
 };
 use blockifier::fee::fee_utils::get_fee_by_gas_vector;
-use blockifier::state::cached_state::{self, TransactionalState};
+use blockifier::state::cached_state::{self, StateMaps, TransactionalState};
 use blockifier::state::state_api::{StateReader, UpdatableState};
 use blockifier::transaction::account_transaction::{
     AccountTransaction, ExecutionFlags as BlockifierExecutionFlags,
@@ -19,6 +19,7 @@ use blockifier::transaction::transaction_execution::Transaction;
 use blockifier::transaction::transactions::ExecutableTransaction;
 use cairo_vm::types::errors::program_errors::ProgramError;
 use katana_primitives::chain::NamedChainId;
+use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::{BlockEnv, CfgEnv};
 use katana_primitives::fee::{FeeInfo, PriceUnit, ResourceBoundsMapping};
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
@@ -121,14 +122,22 @@ pub fn transact<S: StateReader>(
         Ok((execution_info, fee))
     }
 
+    // A transaction can declare at most one class.
+    let declares_legacy_class = match tx.as_ref() {
+        ExecutableTx::Declare(tx) => tx.class.is_legacy(),
+        _ => false,
+    };
+
     let transaction = to_executor_tx(tx.clone(), simulation_flags.clone());
     let mut tx_state = TransactionalState::create_transactional(state);
     let result = transact_inner(&mut tx_state, block_context, transaction);
 
     match result {
         Ok((info, fee)) => {
+            let state_maps = tx_state.to_state_diff().unwrap().state_maps;
+
             if let Some(bouncer) = bouncer {
-                let tx_state_changes_keys = tx_state.to_state_diff().unwrap().state_maps.keys();
+                let tx_state_changes_keys = state_maps.keys();
                 let versioned_constants = block_context.versioned_constants();
 
                 bouncer.try_update(
@@ -143,7 +152,8 @@ pub fn transact<S: StateReader>(
             tx_state.commit();
 
             let receipt = build_receipt(tx.tx_ref(), fee, &info);
-            Ok(ExecutionResult::new_success(receipt, info))
+            let state_diff = state_updates_from_state_maps(state_maps, |_| declares_legacy_class);
+            Ok(ExecutionResult::new_success(receipt, info, state_diff))
         }
 
         Err(e) => {
@@ -169,6 +179,13 @@ pub fn to_executor_tx(mut tx: ExecutableTxWithHash, mut flags: ExecutionFlags) -
         flags = flags.with_fee(!skip_fee_on_zero_gas(&tx));
     }
 
+    // Impersonated accounts can send transactions without a valid signature, so we skip their
+    // account validation.
+    if flags.account_validation() {
+        let validate = flags.account_validation_for(sender_address(&tx.transaction));
+        flags = flags.with_account_validation(validate);
+    }
+
     // In blockifier, if all the resource bounds are specified (ie., the
     // ValidResourceBounds::AllResources enum in blockifier types), then blockifier will use the
     // max l2 gas as the initial gas for this transaction's execution. So when we do fee estimates,
@@ -404,6 +421,25 @@ pub fn to_executor_tx(mut tx: ExecutableTxWithHash, mut flags: ExecutionFlags) -
     }
 }
 
+/// Returns the address of the account that sent the transaction.
+fn sender_address(tx: &ExecutableTx) -> katana_primitives::ContractAddress {
+    match tx {
+        ExecutableTx::Invoke(tx) => match tx {
+            InvokeTx::V0(tx) => tx.contract_address,
+            InvokeTx::V1(tx) => tx.sender_address,
+            InvokeTx::V3(tx) => tx.sender_address,
+        },
+        ExecutableTx::Declare(tx) => match &tx.transaction {
+            DeclareTx::V0(tx) => tx.sender_address,
+            DeclareTx::V1(tx) => tx.sender_address,
+            DeclareTx::V2(tx) => tx.sender_address,
+            DeclareTx::V3(tx) => tx.sender_address,
+        },
+        ExecutableTx::DeployAccount(tx) => tx.contract_address(),
+        ExecutableTx::L1Handler(tx) => tx.contract_address,
+    }
+}
+
 fn set_max_initial_sierra_gas(tx: &mut ExecutableTxWithHash) {
     match &mut tx.transaction {
         ExecutableTx::Invoke(InvokeTx::V3(tx)) => {
@@ -443,19 +479,21 @@ pub fn block_context_from_envs(block_env: &BlockEnv, cfg_env: &CfgEnv) -> BlockC
     let strk_l1_data_gas_price =
         NonzeroGasPrice::new(block_env.l1_data_gas_prices.strk.get().into())
             .unwrap_or(NonzeroGasPrice::MIN);
+    let eth_l2_gas_price = NonzeroGasPrice::new(block_env.l2_gas_prices.eth.get().into())
+        .unwrap_or(NonzeroGasPrice::MIN);
+    let strk_l2_gas_price = NonzeroGasPrice::new(block_env.l2_gas_prices.strk.get().into())
+        .unwrap_or(NonzeroGasPrice::MIN);
 
     let gas_prices = GasPrices {
         eth_gas_prices: GasPriceVector {
             l1_gas_price: eth_l1_gas_price,
             l1_data_gas_price: eth_l1_data_gas_price,
-            // TODO: update to use the correct value
-            l2_gas_price: eth_l1_gas_price,
+            l2_gas_price: eth_l2_gas_price,
         },
         strk_gas_prices: GasPriceVector {
             l1_gas_price: strk_l1_gas_price,
             l1_data_gas_price: strk_l1_data_gas_price,
-            // TODO: update to use the correct value
-            l2_gas_price: strk_l1_gas_price,
+            l2_gas_price: strk_l2_gas_price,
         },
     };
 
@@ -464,7 +502,7 @@ pub fn block_context_from_envs(block_env: &BlockEnv, cfg_env: &CfgEnv) -> BlockC
         block_timestamp: BlockTimestamp(block_env.timestamp),
         sequencer_address: to_blk_address(block_env.sequencer_address),
         gas_prices,
-        use_kzg_da: false,
+        use_kzg_da: block_env.l1_da_mode == L1DataAvailabilityMode::Blob,
     };
 
     let chain_info = ChainInfo { fee_token_addresses, chain_id: to_blk_chain_id(cfg_env.chain_id) };
@@ -496,27 +534,43 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
         katana_primitives::class::ContractClass,
     > = BTreeMap::new();
 
+    for class_hash in state_diff.state_maps.compiled_class_hashes.keys() {
+        let hash = class_hash.0;
+        let class = state.class(hash).unwrap().expect("must exist if declared");
+        declared_contract_classes.insert(hash, class);
+    }
+
+    let state_updates = state_updates_from_state_maps(state_diff.state_maps, |hash| {
+        declared_contract_classes.get(&hash).is_some_and(|class| class.is_legacy())
+    });
+
+    StateUpdatesWithClasses { classes: declared_contract_classes, state_updates }
+}
+
+/// Converts the state changes tracked by blockifier into [`StateUpdates`].
+///
+/// `is_legacy_class` tells whether a declared class is a legacy class.
+pub(super) fn state_updates_from_state_maps(
+    state_maps: StateMaps,
+    is_legacy_class: impl Fn(katana_primitives::class::ClassHash) -> bool,
+) -> StateUpdates {
     let mut declared_classes = BTreeMap::new();
     let mut deprecated_declared_classes = BTreeSet::new();
 
     // TODO: Legacy class shouldn't have a compiled class hash. This is a hack we added
     // in our fork of `blockifier. Check if it's possible to remove it now.
-    for (class_hash, compiled_hash) in state_diff.state_maps.compiled_class_hashes {
+    for (class_hash, compiled_hash) in state_maps.compiled_class_hashes {
         let hash = class_hash.0;
-        let class = state.class(hash).unwrap().expect("must exist if declared");
 
-        if class.is_legacy() {
+        if is_legacy_class(hash) {
             deprecated_declared_classes.insert(hash);
         } else {
             declared_classes.insert(hash, compiled_hash.0);
         }
-
-        declared_contract_classes.insert(hash, class);
     }
 
     let nonce_updates =
-        state_diff
-            .state_maps
+        state_maps
             .nonces
             .into_iter()
             .map(|(key, value)| (to_address(key), value.0))
@@ -525,7 +579,7 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
                 katana_primitives::contract::Nonce,
             >>();
 
-    let storage_updates = state_diff.state_maps.storage.into_iter().fold(
+    let storage_updates = state_maps.storage.into_iter().fold(
         BTreeMap::new(),
         |mut storage, ((addr, key), value)| {
             let entry: &mut BTreeMap<
@@ -538,8 +592,7 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
     );
 
     let deployed_contracts =
-        state_diff
-            .state_maps
+        state_maps
             .class_hashes
             .into_iter()
             .map(|(key, value)| (to_address(key), value.0))
@@ -548,16 +601,13 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
                 katana_primitives::class::ClassHash,
             >>();
 
-    StateUpdatesWithClasses {
-        classes: declared_contract_classes,
-        state_updates: StateUpdates {
-            nonce_updates,
-            storage_updates,
-            declared_classes,
-            deployed_contracts,
-            deprecated_declared_classes,
-            replaced_classes: BTreeMap::default(),
-        },
+    StateUpdates {
+        nonce_updates,
+        storage_updates,
+        declared_classes,
+        deployed_contracts,
+        deprecated_declared_classes,
+        replaced_classes: BTreeMap::default(),
     }
 }
 
@@ -2,6 +2,7 @@ use katana_primitives::block::ExecutableBlock;
 use katana_primitives::class::{ClassHash, CompiledClassHash, ContractClass};
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::env::{BlockEnv, CfgEnv};
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxWithHash};
 use katana_provider::traits::contract::ContractClassProvider;
 use katana_provider::traits::state::{StateProofProvider, StateProvider, StateRootProvider};
@@ -76,6 +77,11 @@ impl<'a> BlockExecutor<'a> for NoopExecutor {
         Ok((transactions.len(), None))
     }
 
+    fn apply_state_updates(&mut self, updates: StateUpdates) -> ExecutorResult<()> {
+        let _ = updates;
+        Ok(())
+    }
+
     fn take_execution_output(&mut self) -> ExecutorResult<ExecutionOutput> {
         Ok(ExecutionOutput::default())
     }
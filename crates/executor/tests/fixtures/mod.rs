@@ -112,7 +112,7 @@ pub fn valid_blocks() -> [ExecutableBlock; 3] {
                 timestamp: 100,
                 sequencer_address,
                 parent_hash: 123u64.into(),
-                l2_gas_prices: GasPrice::MIN,
+                l2_gas_prices: gas_prices.clone(),
                 l1_gas_prices: gas_prices.clone(),
                 l1_data_gas_prices: gas_prices.clone(),
                 l1_da_mode: L1DataAvailabilityMode::Calldata,
@@ -162,7 +162,7 @@ pub fn valid_blocks() -> [ExecutableBlock; 3] {
                 timestamp: 200,
                 sequencer_address,
                 parent_hash: 1234u64.into(),
-                l2_gas_prices: GasPrice::MIN,
+                l2_gas_prices: gas_prices.clone(),
                 l1_gas_prices: gas_prices.clone(),
                 l1_data_gas_prices: gas_prices.clone(),
                 l1_da_mode: L1DataAvailabilityMode::Calldata,
@@ -196,7 +196,7 @@ pub fn valid_blocks() -> [ExecutableBlock; 3] {
                 timestamp: 300,
                 sequencer_address,
                 parent_hash: 12345u64.into(),
-                l2_gas_prices: GasPrice::MIN,
+                l2_gas_prices: gas_prices.clone(),
                 l1_gas_prices: gas_prices.clone(),
                 l1_data_gas_prices: gas_prices.clone(),
                 l1_da_mode: L1DataAvailabilityMode::Calldata,
@@ -47,18 +47,24 @@ pub struct SequencerGateway {
 }
 
 impl SequencerGateway {
+    /// The base URL of the Starknet mainnet feeder gateway.
+    pub const MAINNET_URL: &'static str = "https://alpha-mainnet.starknet.io/";
+
+    /// The base URL of the Starknet sepolia feeder gateway.
+    pub const SEPOLIA_URL: &'static str = "https://alpha-sepolia.starknet.io/";
+
     /// Creates a new gateway client to Starknet mainnet.
     ///
     /// https://docs.starknet.io/tools/important-addresses/#sequencer_base_url
     pub fn sn_mainnet() -> Self {
-        Self::new(Url::parse("https://alpha-mainnet.starknet.io/").unwrap())
+        Self::new(Url::parse(Self::MAINNET_URL).unwrap())
     }
 
     /// Creates a new gateway client to Starknet sepolia.
     ///
     /// https://docs.starknet.io/tools/important-addresses/#sequencer_base_url
     pub fn sn_sepolia() -> Self {
-        Self::new(Url::parse("https://alpha-sepolia.starknet.io/").unwrap())
+        Self::new(Url::parse(Self::SEPOLIA_URL).unwrap())
     }
 
     /// Creates a new gateway client at the given base URL.
@@ -176,7 +182,7 @@ impl RequestBuilder<'_> {
     }
 }
 
-#[derive(Debug, thiserror::Error, Deserialize)]
+#[derive(Debug, thiserror::Error, Serialize, Deserialize)]
 #[error("{message} ({code:?})")]
 pub struct SequencerError {
     pub code: ErrorCode,
@@ -1,19 +1,21 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use katana_primitives::block::{BlockHash, BlockNumber};
+use katana_primitives::block::{
+    BlockHash, BlockNumber, FinalityStatus, GasPrice, SealedBlockWithStatus,
+};
 pub use katana_primitives::class::CasmContractClass;
 use katana_primitives::class::{
     ClassHash, CompiledClassHash, LegacyContractClass, SierraContractClass,
 };
 use katana_primitives::contract::{Nonce, StorageKey, StorageValue};
 use katana_primitives::da::L1DataAvailabilityMode;
+use katana_primitives::receipt::Receipt;
 use katana_primitives::version::ProtocolVersion;
 use katana_primitives::{ContractAddress, Felt};
 use katana_rpc_types::class::ConversionError;
 pub use katana_rpc_types::class::RpcSierraContractClass;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use starknet::core::types::ResourcePrice;
-use starknet::providers::sequencer::models::BlockStatus;
 
 mod receipt;
 mod serde_utils;
@@ -23,7 +25,7 @@ pub use receipt::*;
 pub use transaction::*;
 
 /// The contract class type returns by `/get_class_by_hash` endpoint.
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContractClass {
     Class(RpcSierraContractClass),
@@ -31,7 +33,7 @@ pub enum ContractClass {
 }
 
 /// The state update type returns by `/get_state_update` endpoint.
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StateUpdate {
     pub block_hash: Option<Felt>,
     pub new_root: Option<Felt>,
@@ -39,7 +41,7 @@ pub struct StateUpdate {
     pub state_diff: StateDiff,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StateDiff {
     pub storage_diffs: BTreeMap<ContractAddress, Vec<StorageDiff>>,
     pub deployed_contracts: Vec<DeployedContract>,
@@ -49,36 +51,46 @@ pub struct StateDiff {
     pub replaced_classes: Vec<DeployedContract>,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StorageDiff {
     pub key: StorageKey,
     pub value: StorageValue,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeployedContract {
     pub address: ContractAddress,
     pub class_hash: Felt,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeclaredContract {
     pub class_hash: ClassHash,
     pub compiled_class_hash: CompiledClassHash,
 }
 
 /// The state update type returns by `/get_state_update` endpoint, with `includeBlock=true`.
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StateUpdateWithBlock {
     pub state_update: StateUpdate,
     pub block: Block,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BlockStatus {
+    Pending,
+    Aborted,
+    Reverted,
+    AcceptedOnL2,
+    AcceptedOnL1,
+}
+
 // The reason why we're not using the GasPrices from the `katana_primitives` crate is because
 // the serde impl is different. So for now, lets just use starknet-rs types. The type isn't
 // that complex anyway so the conversion is simple. But if we can use the primitive types, we
 // should.
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Block {
     #[serde(default)]
     pub block_hash: Option<BlockHash>,
@@ -161,6 +173,117 @@ impl From<StateDiff> for katana_primitives::state::StateUpdates {
     }
 }
 
+// -- Conversion from Katana primitive types.
+
+impl TryFrom<katana_primitives::class::ContractClass> for ContractClass {
+    type Error = ConversionError;
+
+    fn try_from(value: katana_primitives::class::ContractClass) -> Result<Self, Self::Error> {
+        match value {
+            katana_primitives::class::ContractClass::Legacy(class) => Ok(Self::Legacy(class)),
+            katana_primitives::class::ContractClass::Class(class) => {
+                Ok(Self::Class(RpcSierraContractClass::try_from(class)?))
+            }
+        }
+    }
+}
+
+impl From<katana_primitives::state::StateUpdates> for StateDiff {
+    fn from(value: katana_primitives::state::StateUpdates) -> Self {
+        let storage_diffs = value
+            .storage_updates
+            .into_iter()
+            .map(|(addr, entries)| {
+                let diffs = entries.into_iter().map(|(key, value)| StorageDiff { key, value });
+                (addr, diffs.collect())
+            })
+            .collect();
+
+        let deployed_contracts = value
+            .deployed_contracts
+            .into_iter()
+            .map(|(address, class_hash)| DeployedContract { address, class_hash })
+            .collect();
+
+        let declared_classes = value
+            .declared_classes
+            .into_iter()
+            .map(|(class_hash, compiled_class_hash)| DeclaredContract {
+                class_hash,
+                compiled_class_hash,
+            })
+            .collect();
+
+        let replaced_classes = value
+            .replaced_classes
+            .into_iter()
+            .map(|(address, class_hash)| DeployedContract { address, class_hash })
+            .collect();
+
+        Self {
+            storage_diffs,
+            declared_classes,
+            replaced_classes,
+            deployed_contracts,
+            nonces: value.nonce_updates,
+            old_declared_contracts: value.deprecated_declared_classes.into_iter().collect(),
+        }
+    }
+}
+
+impl From<FinalityStatus> for BlockStatus {
+    fn from(status: FinalityStatus) -> Self {
+        match status {
+            FinalityStatus::AcceptedOnL2 => Self::AcceptedOnL2,
+            FinalityStatus::AcceptedOnL1 => Self::AcceptedOnL1,
+        }
+    }
+}
+
+impl Block {
+    /// Creates a new block from a sealed block and the receipts of its transactions.
+    pub fn new(block: SealedBlockWithStatus, receipts: Vec<Receipt>) -> Self {
+        fn to_resource_price(prices: GasPrice) -> ResourcePrice {
+            ResourcePrice {
+                price_in_wei: Felt::from(prices.eth.get()),
+                price_in_fri: Felt::from(prices.strk.get()),
+            }
+        }
+
+        let SealedBlockWithStatus { block, status } = block;
+        let header = block.header;
+
+        let transaction_receipts = block
+            .body
+            .iter()
+            .zip(receipts)
+            .enumerate()
+            .map(|(idx, (tx, receipt))| ConfirmedReceipt::new(tx, idx as u64, receipt))
+            .collect();
+
+        let transactions = block.body.into_iter().map(ConfirmedTransaction::from).collect();
+
+        Self {
+            transactions,
+            transaction_receipts,
+            status: status.into(),
+            block_hash: Some(block.hash),
+            timestamp: header.timestamp,
+            l1_da_mode: header.l1_da_mode,
+            block_number: Some(header.number),
+            state_root: Some(header.state_root),
+            parent_block_hash: header.parent_hash,
+            starknet_version: Some(header.protocol_version),
+            event_commitment: Some(header.events_commitment),
+            sequencer_address: Some(header.sequencer_address),
+            transaction_commitment: Some(header.transactions_commitment),
+            l1_gas_price: to_resource_price(header.l1_gas_prices),
+            l2_gas_price: to_resource_price(header.l2_gas_prices),
+            l1_data_gas_price: to_resource_price(header.l1_data_gas_prices),
+        }
+    }
+}
+
 fn default_l2_gas_price() -> ResourcePrice {
     ResourcePrice { price_in_fri: Felt::from(1), price_in_wei: Felt::from(1) }
 }
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
 use katana_primitives::execution::{BuiltinCounters, BuiltinName, VmResources};
-use katana_primitives::receipt::{DataAvailabilityResources, Event, MessageToL1};
+use katana_primitives::receipt::{DataAvailabilityResources, Event, MessageToL1, Receipt};
+use katana_primitives::transaction::{Tx, TxWithHash};
 use katana_primitives::{eth, Felt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConfirmedReceipt {
     pub transaction_hash: Felt,
     pub transaction_index: u64,
@@ -18,7 +19,7 @@ pub struct ConfirmedReceipt {
     pub actual_fee: Felt,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ExecutionStatus {
     Succeeded,
@@ -31,7 +32,7 @@ pub struct ExecutionResources {
     pub data_availability: Option<DataAvailabilityResources>,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct L1ToL2Message {
     /// The address of the Ethereum (L1) contract that sent the message.
     pub from_address: eth::Address,
@@ -41,6 +42,38 @@ pub struct L1ToL2Message {
     pub nonce: Option<Felt>,
 }
 
+impl Serialize for ExecutionResources {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct BuiltinCounterHelper<'a>(&'a HashMap<BuiltinName, usize>);
+
+        impl Serialize for BuiltinCounterHelper<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let entries = self.0.iter().map(|(name, count)| (name.to_str_with_suffix(), count));
+                serializer.collect_map(entries)
+            }
+        }
+
+        #[derive(Serialize)]
+        pub struct Helper<'a> {
+            pub n_steps: usize,
+            pub n_memory_holes: usize,
+            pub builtin_instance_counter: BuiltinCounterHelper<'a>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub data_availability: Option<&'a DataAvailabilityResources>,
+        }
+
+        Helper {
+            n_steps: self.vm_resources.n_steps,
+            n_memory_holes: self.vm_resources.n_memory_holes,
+            builtin_instance_counter: BuiltinCounterHelper(
+                &self.vm_resources.builtin_instance_counter,
+            ),
+            data_availability: self.data_availability.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for ExecutionResources {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         struct BuiltinCounterHelper(BuiltinCounters);
@@ -164,3 +197,59 @@ impl<'de> Deserialize<'de> for ExecutionResources {
         Ok(Self { data_availability: helper.data_availability, vm_resources })
     }
 }
+
+// -- Conversion from Katana primitive types.
+
+impl ConfirmedReceipt {
+    /// Creates the receipt of `tx`, which is the `index`-th transaction of its block.
+    pub fn new(tx: &TxWithHash, index: u64, receipt: Receipt) -> Self {
+        let execution_status = if receipt.is_reverted() {
+            ExecutionStatus::Reverted
+        } else {
+            ExecutionStatus::Succeeded
+        };
+
+        let l1_to_l2_consumed_message = match &tx.transaction {
+            Tx::L1Handler(tx) => Some(L1ToL2Message::from(tx)),
+            _ => None,
+        };
+
+        let actual_fee = Felt::from(receipt.fee().overall_fee);
+        let resources = receipt.resources_used();
+        let execution_resources = ExecutionResources {
+            vm_resources: resources.computation_resources.clone(),
+            data_availability: Some(resources.da_resources.clone()),
+        };
+
+        Self {
+            actual_fee,
+            transaction_hash: tx.hash,
+            transaction_index: index,
+            l1_to_l2_consumed_message,
+            execution_status: Some(execution_status),
+            execution_resources: Some(execution_resources),
+            revert_error: receipt.revert_reason().map(ToString::to_string),
+            l2_to_l1_messages: receipt.messages_sent().to_vec(),
+            events: receipt.events().to_vec(),
+        }
+    }
+}
+
+impl From<&katana_primitives::transaction::L1HandlerTx> for L1ToL2Message {
+    fn from(tx: &katana_primitives::transaction::L1HandlerTx) -> Self {
+        // The first element of the calldata is always the L1 sender address, and the rest is the
+        // message payload.
+        let (from_address, payload) = match tx.calldata.split_first() {
+            Some((from_address, payload)) => (*from_address, payload.to_vec()),
+            None => (Felt::ZERO, Vec::new()),
+        };
+
+        Self {
+            payload,
+            from_address: eth::Address::from_slice(&from_address.to_bytes_be()[12..]),
+            nonce: Some(tx.nonce),
+            to_address: tx.contract_address.into(),
+            selector: tx.entry_point_selector,
+        }
+    }
+}
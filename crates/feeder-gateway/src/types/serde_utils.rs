@@ -1,5 +1,7 @@
+use std::fmt::LowerHex;
+
 use serde::de::Visitor;
-use serde::Deserialize;
+use serde::{Deserialize, Serializer};
 
 pub fn deserialize_u64<'de, D>(deserializer: D) -> Result<u64, D::Error>
 where
@@ -104,3 +106,24 @@ where
         }
     }
 }
+
+/// Serializes an integer as a 0x-prefixed hex string, the format expected by
+/// [`deserialize_u64`] and [`deserialize_u128`].
+pub fn serialize_as_hex<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: LowerHex,
+{
+    serializer.serialize_str(&format!("{value:#x}"))
+}
+
+pub fn serialize_optional_as_hex<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: LowerHex,
+{
+    match value {
+        Some(value) => serialize_as_hex(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
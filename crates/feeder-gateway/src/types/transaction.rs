@@ -7,13 +7,14 @@ use katana_primitives::transaction::{
     L1HandlerTx, Tx, TxHash, TxType, TxWithHash,
 };
 use katana_primitives::{ContractAddress, Felt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::serde_utils::{
     deserialize_optional_u128, deserialize_optional_u64, deserialize_u128, deserialize_u64,
+    serialize_as_hex, serialize_optional_as_hex,
 };
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConfirmedTransaction {
     #[serde(rename = "transaction_hash")]
     pub hash: TxHash,
@@ -21,7 +22,7 @@ pub struct ConfirmedTransaction {
     pub tx: TypedTransaction,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TypedTransaction {
     Deploy(DeployTx),
@@ -41,6 +42,18 @@ pub enum DataAvailabilityMode {
     L2,
 }
 
+impl Serialize for DataAvailabilityMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            DataAvailabilityMode::L1 => serializer.serialize_u8(0),
+            DataAvailabilityMode::L2 => serializer.serialize_u8(1),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for DataAvailabilityMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -59,25 +72,26 @@ impl<'de> Deserialize<'de> for DataAvailabilityMode {
 
 // Same reason as `DataAvailabilityMode` above, this struct is also defined because the serde
 // implementation of its primitive counterpart is different.
-#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResourceBounds {
-    #[serde(deserialize_with = "deserialize_u64")]
+    #[serde(serialize_with = "serialize_as_hex", deserialize_with = "deserialize_u64")]
     pub max_amount: u64,
-    #[serde(deserialize_with = "deserialize_u128")]
+    #[serde(serialize_with = "serialize_as_hex", deserialize_with = "deserialize_u128")]
     pub max_price_per_unit: u128,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub struct ResourceBoundsMapping {
     pub l1_gas: ResourceBounds,
     pub l2_gas: ResourceBounds,
     /// Marked as optional because prior to 0.13.4, L1 data gas is not a required field in the
     /// resource bounds.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub l1_data_gas: Option<ResourceBounds>,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RawL1HandlerTx {
     /// The L1 to L2 message nonce.
     pub nonce: Option<Nonce>,
@@ -91,64 +105,85 @@ pub struct RawL1HandlerTx {
     pub entry_point_selector: Felt,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RawInvokeTx {
     // Alias for v0 transaction
     #[serde(alias = "contract_address")]
     pub sender_address: ContractAddress,
     // v0 doesn't include nonce
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<Nonce>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entry_point_selector: Option<Felt>,
     pub calldata: Vec<Felt>,
     pub signature: Vec<Felt>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u128")]
     pub max_fee: Option<u128>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resource_bounds: Option<ResourceBoundsMapping>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u64")]
     pub tip: Option<u64>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub paymaster_data: Option<Vec<Felt>>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_deployment_data: Option<Vec<Felt>>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce_data_availability_mode: Option<DataAvailabilityMode>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_data_availability_mode: Option<DataAvailabilityMode>,
     pub version: Felt,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RawDeclareTx {
     pub sender_address: ContractAddress,
     pub nonce: Felt,
     pub signature: Vec<Felt>,
     pub class_hash: ClassHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub compiled_class_hash: Option<CompiledClassHash>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u128")]
     pub max_fee: Option<u128>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resource_bounds: Option<ResourceBoundsMapping>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u64")]
     pub tip: Option<u64>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub paymaster_data: Option<Vec<Felt>>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account_deployment_data: Option<Vec<Felt>>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce_data_availability_mode: Option<DataAvailabilityMode>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_data_availability_mode: Option<DataAvailabilityMode>,
     pub version: Felt,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RawDeployAccountTx {
     pub nonce: Nonce,
     pub signature: Vec<Felt>,
@@ -157,18 +192,26 @@ pub struct RawDeployAccountTx {
     pub contract_address_salt: Felt,
     pub constructor_calldata: Vec<Felt>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u128")]
     pub max_fee: Option<u128>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resource_bounds: Option<ResourceBoundsMapping>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_optional_as_hex")]
     #[serde(deserialize_with = "deserialize_optional_u64")]
     pub tip: Option<u64>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub paymaster_data: Option<Vec<Felt>>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce_data_availability_mode: Option<DataAvailabilityMode>,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_data_availability_mode: Option<DataAvailabilityMode>,
     pub version: Felt,
 }
@@ -458,3 +501,222 @@ impl From<ResourceBoundsMapping> for katana_primitives::fee::ResourceBoundsMappi
         }
     }
 }
+
+// -- Conversion from Katana primitive types.
+
+impl From<TxWithHash> for ConfirmedTransaction {
+    fn from(tx: TxWithHash) -> Self {
+        Self { hash: tx.hash, tx: tx.transaction.into() }
+    }
+}
+
+impl From<Tx> for TypedTransaction {
+    fn from(tx: Tx) -> Self {
+        match tx {
+            Tx::Deploy(tx) => Self::Deploy(tx),
+            Tx::Declare(tx) => Self::Declare(tx.into()),
+            Tx::L1Handler(tx) => Self::L1Handler(tx.into()),
+            Tx::Invoke(tx) => Self::InvokeFunction(tx.into()),
+            Tx::DeployAccount(tx) => Self::DeployAccount(tx.into()),
+        }
+    }
+}
+
+impl From<InvokeTx> for RawInvokeTx {
+    fn from(tx: InvokeTx) -> Self {
+        match tx {
+            InvokeTx::V0(tx) => Self {
+                sender_address: tx.contract_address,
+                nonce: None,
+                entry_point_selector: Some(tx.entry_point_selector),
+                calldata: tx.calldata,
+                signature: tx.signature,
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                account_deployment_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::ZERO,
+            },
+            InvokeTx::V1(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: Some(tx.nonce),
+                entry_point_selector: None,
+                calldata: tx.calldata,
+                signature: tx.signature,
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                account_deployment_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::ONE,
+            },
+            InvokeTx::V3(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: Some(tx.nonce),
+                entry_point_selector: None,
+                calldata: tx.calldata,
+                signature: tx.signature,
+                max_fee: None,
+                resource_bounds: Some(tx.resource_bounds.into()),
+                tip: Some(tx.tip),
+                paymaster_data: Some(tx.paymaster_data),
+                account_deployment_data: Some(tx.account_deployment_data),
+                nonce_data_availability_mode: Some(tx.nonce_data_availability_mode.into()),
+                fee_data_availability_mode: Some(tx.fee_data_availability_mode.into()),
+                version: Felt::THREE,
+            },
+        }
+    }
+}
+
+impl From<DeclareTx> for RawDeclareTx {
+    fn from(tx: DeclareTx) -> Self {
+        match tx {
+            DeclareTx::V0(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: Felt::ZERO,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                compiled_class_hash: None,
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                account_deployment_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::ZERO,
+            },
+            DeclareTx::V1(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                compiled_class_hash: None,
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                account_deployment_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::ONE,
+            },
+            DeclareTx::V2(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                compiled_class_hash: Some(tx.compiled_class_hash),
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                account_deployment_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::TWO,
+            },
+            DeclareTx::V3(tx) => Self {
+                sender_address: tx.sender_address,
+                nonce: tx.nonce,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                compiled_class_hash: Some(tx.compiled_class_hash),
+                max_fee: None,
+                resource_bounds: Some(tx.resource_bounds.into()),
+                tip: Some(tx.tip),
+                paymaster_data: Some(tx.paymaster_data),
+                account_deployment_data: Some(tx.account_deployment_data),
+                nonce_data_availability_mode: Some(tx.nonce_data_availability_mode.into()),
+                fee_data_availability_mode: Some(tx.fee_data_availability_mode.into()),
+                version: Felt::THREE,
+            },
+        }
+    }
+}
+
+impl From<DeployAccountTx> for RawDeployAccountTx {
+    fn from(tx: DeployAccountTx) -> Self {
+        match tx {
+            DeployAccountTx::V1(tx) => Self {
+                nonce: tx.nonce,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                contract_address: Some(tx.contract_address),
+                contract_address_salt: tx.contract_address_salt,
+                constructor_calldata: tx.constructor_calldata,
+                max_fee: Some(tx.max_fee),
+                resource_bounds: None,
+                tip: None,
+                paymaster_data: None,
+                nonce_data_availability_mode: None,
+                fee_data_availability_mode: None,
+                version: Felt::ONE,
+            },
+            DeployAccountTx::V3(tx) => Self {
+                nonce: tx.nonce,
+                signature: tx.signature,
+                class_hash: tx.class_hash,
+                contract_address: Some(tx.contract_address),
+                contract_address_salt: tx.contract_address_salt,
+                constructor_calldata: tx.constructor_calldata,
+                max_fee: None,
+                resource_bounds: Some(tx.resource_bounds.into()),
+                tip: Some(tx.tip),
+                paymaster_data: Some(tx.paymaster_data),
+                nonce_data_availability_mode: Some(tx.nonce_data_availability_mode.into()),
+                fee_data_availability_mode: Some(tx.fee_data_availability_mode.into()),
+                version: Felt::THREE,
+            },
+        }
+    }
+}
+
+impl From<L1HandlerTx> for RawL1HandlerTx {
+    fn from(tx: L1HandlerTx) -> Self {
+        Self {
+            nonce: Some(tx.nonce),
+            version: tx.version,
+            calldata: tx.calldata,
+            contract_address: tx.contract_address,
+            entry_point_selector: tx.entry_point_selector,
+        }
+    }
+}
+
+impl From<katana_primitives::da::DataAvailabilityMode> for DataAvailabilityMode {
+    fn from(mode: katana_primitives::da::DataAvailabilityMode) -> Self {
+        match mode {
+            katana_primitives::da::DataAvailabilityMode::L1 => Self::L1,
+            katana_primitives::da::DataAvailabilityMode::L2 => Self::L2,
+        }
+    }
+}
+
+impl From<katana_primitives::fee::ResourceBounds> for ResourceBounds {
+    fn from(bounds: katana_primitives::fee::ResourceBounds) -> Self {
+        Self { max_amount: bounds.max_amount, max_price_per_unit: bounds.max_price_per_unit }
+    }
+}
+
+impl From<katana_primitives::fee::ResourceBoundsMapping> for ResourceBoundsMapping {
+    fn from(bounds: katana_primitives::fee::ResourceBoundsMapping) -> Self {
+        match bounds {
+            katana_primitives::fee::ResourceBoundsMapping::All(bounds) => Self {
+                l1_gas: bounds.l1_gas.into(),
+                l2_gas: bounds.l2_gas.into(),
+                l1_data_gas: Some(bounds.l1_data_gas.into()),
+            },
+            // The L2 gas bounds of legacy transactions is always zero.
+            katana_primitives::fee::ResourceBoundsMapping::L1Gas(bounds) => {
+                Self { l1_gas: bounds.into(), l2_gas: ResourceBounds::default(), l1_data_gas: None }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use katana_feeder_gateway::types::{Block, StateUpdate, StateUpdateWithBlock};
+use rstest::rstest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod fixtures;
+
+use fixtures::test_data;
+
+/// Serializes the value and deserializes it back, ensuring the serialized form is the same as the
+/// one served by the feeder gateway.
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_value(value).unwrap();
+    serde_json::from_value(json).unwrap()
+}
+
+#[rstest]
+#[case::pre_v0_7_0(test_data("pre_0.7.0/block/mainnet_genesis.json"))]
+#[case::v0_7_0(test_data("0.7.0/block/mainnet_2240.json"))]
+#[case::v0_11_1(test_data("0.11.1/block/mainnet_65000.json"))]
+#[case::v0_13_0(test_data("0.13.0/block/mainnet_550000.json"))]
+#[case::v0_13_2(test_data("0.13.2/block/sepolia_integration_35748.json"))]
+#[case::v0_13_4(test_data("0.13.4/block/sepolia_integration_63881.json"))]
+fn block(#[case] block: Block) {
+    similar_asserts::assert_eq!(round_trip(&block), block);
+}
+
+#[rstest]
+#[case::pre_v0_7_0(test_data("pre_0.7.0/state_update/mainnet_genesis.json"))]
+#[case::v0_12_2(test_data("0.12.2/state_update/mainnet_350000.json"))]
+#[case::v0_13_4(test_data("0.13.4/state_update/sepolia_integration_63881.json"))]
+fn state_update(#[case] state_update: StateUpdate) {
+    similar_asserts::assert_eq!(round_trip(&state_update), state_update);
+}
+
+#[rstest]
+#[case::v0_13_0(test_data("0.13.0/state_update_with_block/mainnet_550000.json"))]
+#[case::v0_13_1(test_data("0.13.1/state_update_with_block/sepolia_integration_9703.json"))]
+fn state_update_with_block(#[case] state_update: StateUpdateWithBlock) {
+    similar_asserts::assert_eq!(round_trip(&state_update), state_update);
+}
@@ -1,6 +1,6 @@
 use katana_core::constants::{
-    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
-    DEFAULT_STRK_L1_GAS_PRICE,
+    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_ETH_L2_GAS_PRICE,
+    DEFAULT_STRK_L1_DATA_GAS_PRICE, DEFAULT_STRK_L1_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE,
 };
 use katana_primitives::block::GasPrice;
 
@@ -31,6 +31,11 @@ pub struct DevConfig {
     ///
     /// These are the prices that will be used for calculating the gas fee for transactions.
     pub fixed_gas_prices: Option<FixedL1GasPriceConfig>,
+
+    /// How the L2 gas prices are determined.
+    ///
+    /// If not specified, the L2 gas prices are fixed to their default values.
+    pub l2_gas_prices: Option<L2GasPriceConfig>,
 }
 
 /// Fixed gas prices for development.
@@ -50,8 +55,31 @@ impl std::default::Default for FixedL1GasPriceConfig {
     }
 }
 
+/// L2 gas prices configuration for development.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L2GasPriceConfig {
+    /// The L2 gas prices.
+    ///
+    /// If `target_gas` is set, these are the prices of the first block and the lowest the prices
+    /// can go.
+    pub gas_price: GasPrice,
+
+    /// The amount of L2 gas a block is expected to use.
+    ///
+    /// If set, the L2 gas prices are adjusted after every block depending on whether the block
+    /// used more or less L2 gas than this target, the same way the base fee is on Ethereum.
+    pub target_gas: Option<u64>,
+}
+
+impl std::default::Default for L2GasPriceConfig {
+    fn default() -> Self {
+        let gas_price = GasPrice::new(DEFAULT_ETH_L2_GAS_PRICE, DEFAULT_STRK_L2_GAS_PRICE);
+        Self { gas_price, target_gas: None }
+    }
+}
+
 impl std::default::Default for DevConfig {
     fn default() -> Self {
-        Self { fee: true, account_validation: true, fixed_gas_prices: None }
+        Self { fee: true, account_validation: true, fixed_gas_prices: None, l2_gas_prices: None }
     }
 }
@@ -1,3 +1,4 @@
+pub use katana_fork::{BackendConfig, DEFAULT_BATCH_SIZE, DEFAULT_PREFETCH_BLOCKS};
 use katana_primitives::block::BlockHashOrNumber;
 use starknet::providers::Url;
 
@@ -8,4 +9,6 @@ pub struct ForkingConfig {
     pub url: Url,
     /// The block number to fork from. If `None`, the latest block will be used.
     pub block: Option<BlockHashOrNumber>,
+    /// Configurations for fetching data from the forked network.
+    pub backend: BackendConfig,
 }
@@ -32,6 +32,8 @@ pub const DEFAULT_RPC_MAX_CALL_GAS: u64 = 1_000_000_000;
 pub enum RpcModuleKind {
     Starknet,
     Dev,
+    Katana,
+    Debug,
     #[cfg(feature = "cartridge")]
     Cartridge,
 }
@@ -43,6 +45,8 @@ pub struct RpcConfig {
     pub port: u16,
     #[cfg(feature = "explorer")]
     pub explorer: bool,
+    /// Whether to serve the feeder gateway endpoints from the node's storage.
+    pub feeder_gateway: bool,
     pub apis: RpcModulesList,
     pub cors_origins: Vec<HeaderValue>,
     pub max_connections: Option<u32>,
@@ -67,6 +71,7 @@ impl Default for RpcConfig {
         Self {
             #[cfg(feature = "explorer")]
             explorer: false,
+            feeder_gateway: false,
             cors_origins: Vec::new(),
             addr: DEFAULT_RPC_ADDR,
             port: DEFAULT_RPC_PORT,
@@ -102,6 +107,8 @@ impl RpcModulesList {
         Self(HashSet::from([
             RpcModuleKind::Starknet,
             RpcModuleKind::Dev,
+            RpcModuleKind::Katana,
+            RpcModuleKind::Debug,
             #[cfg(feature = "cartridge")]
             RpcModuleKind::Cartridge,
         ]))
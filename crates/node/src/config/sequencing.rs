@@ -1,4 +1,29 @@
 use katana_executor::BlockLimits;
+use serde::{Deserialize, Serialize};
+
+/// The ordering of the transactions in the pool, which determines the order in which they are
+/// included in a block.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    strum_macros::EnumString,
+    strum_macros::Display,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive, serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum TxOrderingKind {
+    /// First-come-first-serve.
+    #[default]
+    Fifo,
+    /// Prioritize transactions by their tip and resource bounds.
+    FeeMarket,
+}
 
 /// Configurations related to block production.
 #[derive(Debug, Clone, Default)]
@@ -20,6 +45,9 @@ pub struct SequencingConfig {
     ///
     /// See <https://docs.starknet.io/chain-info/#current_limits>.
     pub block_cairo_steps_limit: Option<u64>,
+
+    /// The ordering of the transactions in the pool.
+    pub tx_ordering: TxOrderingKind,
 }
 
 impl SequencingConfig {
@@ -1,41 +1,166 @@
 //! Experimental full node implementation.
 
 mod exit;
+mod syncing;
 mod tip_watcher;
 
 use std::future::IntoFuture;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use exit::NodeStoppedFuture;
+use http::header::CONTENT_TYPE;
+use http::Method;
+use jsonrpsee::RpcModule;
+use katana_chain_spec::{dev, ChainSpec};
+use katana_core::backend::gas_oracle::GasOracle;
+use katana_core::backend::storage::Blockchain;
+use katana_core::backend::Backend;
+use katana_core::constants::{
+    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
+    DEFAULT_STRK_L1_GAS_PRICE,
+};
 use katana_db::mdbx::DbEnv;
+use katana_executor::implementation::blockifier::cache::ClassCache;
+use katana_executor::implementation::blockifier::BlockifierFactory;
+use katana_executor::{BlockLimits, ExecutionFlags, ExecutorFactory};
 use katana_feeder_gateway::client::SequencerGateway;
 use katana_metrics::exporters::prometheus::PrometheusRecorder;
 use katana_metrics::{Report, Server as MetricsServer};
 use katana_pipeline::{Pipeline, PipelineHandle};
-use katana_pool::ordering::FiFo;
-use katana_pool::pool::Pool;
-use katana_pool::validation::NoopValidator;
-use katana_primitives::transaction::ExecutableTxWithHash;
+use katana_pool::ordering::{FiFo, TxOrdering};
+use katana_pool::validation::stateful::TxValidator;
+use katana_pool::TxPool;
+use katana_primitives::block::GasPrice;
+use katana_primitives::chain::ChainId;
+use katana_primitives::env::{BlockEnv, CfgEnv, FeeTokenAddressses};
 use katana_provider::providers::db::DbProvider;
-use katana_stage::{Blocks, Classes};
+use katana_provider::traits::state::StateFactoryProvider;
+use katana_rpc::cors::Cors;
+use katana_rpc::feeder_gateway::FeederGatewayLayer;
+use katana_rpc::katana::KatanaApi;
+use katana_rpc::starknet::{StarknetApi, StarknetApiConfig};
+use katana_rpc::{RpcServer, RpcServerHandle};
+use katana_rpc_api::debug::DebugApiServer;
+use katana_rpc_api::katana::KatanaApiServer;
+use katana_rpc_api::starknet::{StarknetApiServer, StarknetTraceApiServer};
+use katana_stage::{Blocks, Classes, Verification};
 use katana_tasks::TaskManager;
+use parking_lot::Mutex;
+use syncing::PipelineSyncProgress;
 use tip_watcher::ChainTipWatcher;
 use tracing::info;
+use url::Url;
 
 use crate::config::db::DbConfig;
+use crate::config::execution::{
+    DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS, MAX_RECURSION_DEPTH,
+};
 use crate::config::metrics::MetricsConfig;
+use crate::config::rpc::{RpcConfig, RpcModuleKind};
 
-type TxPool =
-    Pool<ExecutableTxWithHash, NoopValidator<ExecutableTxWithHash>, FiFo<ExecutableTxWithHash>>;
+/// The default number of blocks the pipeline processes at a time.
+pub const DEFAULT_PIPELINE_CHUNK_SIZE: u64 = 64;
 
-#[derive(Debug)]
+/// The default number of concurrent requests the stages make to the feeder gateway.
+pub const DEFAULT_DOWNLOAD_BATCH_SIZE: usize = 3;
+
+/// The default interval at which the feeder gateway is polled for the chain tip.
+pub const DEFAULT_TIP_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
 pub struct Config {
     pub db: DbConfig,
     pub metrics: Option<MetricsConfig>,
+    pub network: NetworkConfig,
+    pub pipeline: PipelineConfig,
+    pub rpc: RpcConfig,
+}
+
+/// The network the node syncs from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkConfig {
+    /// The chain id of the network.
+    pub chain_id: ChainId,
+    /// The base URL of the feeder gateway to sync from.
+    ///
+    /// This can be the Starknet feeder gateway or a Katana sequencer with its feeder gateway
+    /// endpoints enabled.
+    pub gateway_url: Url,
+    /// The API key used to bypass the rate limiting of the feeder gateway.
     pub gateway_api_key: Option<String>,
 }
 
+impl NetworkConfig {
+    /// Starknet mainnet.
+    pub fn mainnet() -> Self {
+        let gateway_url = Url::parse(SequencerGateway::MAINNET_URL).expect("valid url");
+        Self { chain_id: ChainId::MAINNET, gateway_url, gateway_api_key: None }
+    }
+
+    /// Starknet sepolia.
+    pub fn sepolia() -> Self {
+        let gateway_url = Url::parse(SequencerGateway::SEPOLIA_URL).expect("valid url");
+        Self { chain_id: ChainId::SEPOLIA, gateway_url, gateway_api_key: None }
+    }
+
+    /// Creates a feeder gateway client for this network.
+    pub fn gateway(&self) -> SequencerGateway {
+        let client = SequencerGateway::new(self.gateway_url.clone());
+        match &self.gateway_api_key {
+            Some(key) => client.with_api_key(key.clone()),
+            None => client,
+        }
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self::sepolia()
+    }
+}
+
+/// Configuration of the syncing pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineConfig {
+    /// The number of blocks the pipeline processes at a time.
+    pub chunk_size: u64,
+    /// The number of concurrent requests the stages make to the feeder gateway.
+    pub download_batch_size: usize,
+    /// The interval at which the feeder gateway is polled for the chain tip.
+    pub tip_poll_interval: Duration,
+    /// How the synced blocks are verified.
+    pub verification: VerificationMode,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_PIPELINE_CHUNK_SIZE,
+            download_batch_size: DEFAULT_DOWNLOAD_BATCH_SIZE,
+            tip_poll_interval: DEFAULT_TIP_POLL_INTERVAL,
+            verification: VerificationMode::default(),
+        }
+    }
+}
+
+/// How the blocks downloaded from the feeder gateway are verified.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, strum_macros::EnumString, strum_macros::Display,
+)]
+#[strum(ascii_case_insensitive, serialize_all = "kebab-case")]
+pub enum VerificationMode {
+    /// The downloaded blocks are trusted as is.
+    #[default]
+    None,
+    /// Recompute the state root of every block from its state diff.
+    StateRoot,
+    /// Re-execute every block, on top of recomputing its state root. The execution traces are
+    /// stored, so that they can be served by the trace and debug APIs.
+    Execution,
+}
+
 #[derive(Debug)]
 pub struct Node {
     pub db: DbEnv,
@@ -43,6 +168,9 @@ pub struct Node {
     pub config: Arc<Config>,
     pub task_manager: TaskManager,
     pub pipeline: Pipeline<DbProvider>,
+    pub rpc_server: RpcServer,
+    pub backend: Arc<Backend<BlockifierFactory>>,
+    tip_watcher: ChainTipWatcher,
 }
 
 impl Node {
@@ -66,28 +194,172 @@ impl Node {
 
         let provider = DbProvider::new(db.clone());
 
-        // --- build transaction pool
+        // --- build backend
+        //
+        // The backend is only used for serving the synced chain through the RPC server, the node
+        // itself never produces any blocks.
+
+        let chain_spec =
+            dev::ChainSpec { id: config.network.chain_id, ..dev::DEV_UNALLOCATED.clone() };
+
+        let cfg_env = CfgEnv {
+            chain_id: chain_spec.id,
+            fee_token_addresses: FeeTokenAddressses {
+                eth: chain_spec.fee_contracts.eth,
+                strk: chain_spec.fee_contracts.strk,
+            },
+            invoke_tx_max_n_steps: DEFAULT_INVOCATION_MAX_STEPS,
+            validate_max_n_steps: DEFAULT_VALIDATION_MAX_STEPS,
+            max_recursion_depth: MAX_RECURSION_DEPTH,
+        };
+
+        let class_cache = ClassCache::builder().build_global()?;
+        let flags = ExecutionFlags::new();
+        let executor_factory =
+            BlockifierFactory::new(cfg_env, flags, BlockLimits::default(), class_cache);
 
-        let pool = TxPool::new(NoopValidator::new(), FiFo::new());
+        let gas_oracle = GasOracle::fixed(
+            GasPrice { eth: DEFAULT_ETH_L1_GAS_PRICE, strk: DEFAULT_STRK_L1_GAS_PRICE },
+            GasPrice { eth: DEFAULT_ETH_L1_DATA_GAS_PRICE, strk: DEFAULT_STRK_L1_DATA_GAS_PRICE },
+        );
+
+        let blockchain = Blockchain::new_with_db(db.clone());
+        let chain_spec = Arc::new(ChainSpec::Dev(chain_spec));
+        let backend = Arc::new(Backend::new(chain_spec, blockchain, gas_oracle, executor_factory));
+
+        // --- build transaction pool
+        //
+        // The full node doesn't accept transactions, so the pool will always be empty.
+
+        let validator = TxValidator::new(
+            backend.blockchain.provider().latest()?,
+            backend.executor_factory.execution_flags().clone(),
+            backend.executor_factory.cfg().clone(),
+            BlockEnv::default(),
+            Arc::new(Mutex::new(())),
+        );
+        let pool = TxPool::new(validator, TxOrdering::FiFo(FiFo::new()));
 
         // --- build pipeline
 
-        let fgw = if let Some(ref key) = config.gateway_api_key {
-            SequencerGateway::sn_sepolia().with_api_key(key.clone())
-        } else {
-            SequencerGateway::sn_sepolia()
+        let fgw = config.network.gateway();
+        let batch_size = config.pipeline.download_batch_size;
+
+        info!(
+            target: "node",
+            chain = %config.network.chain_id,
+            gateway = %config.network.gateway_url,
+            "Syncing from feeder gateway."
+        );
+
+        let (mut pipeline, _) = Pipeline::new(provider.clone(), config.pipeline.chunk_size);
+        pipeline.add_stage(Blocks::new(provider.clone(), fgw.clone(), batch_size));
+        pipeline.add_stage(Classes::new(provider.clone(), fgw.clone(), batch_size));
+
+        match config.pipeline.verification {
+            VerificationMode::None => {}
+            VerificationMode::StateRoot => {
+                pipeline.add_stage(Verification::<_, BlockifierFactory>::new(provider.clone()));
+            }
+            VerificationMode::Execution => {
+                let factory = backend.executor_factory.clone();
+                pipeline.add_stage(Verification::new(provider.clone()).with_execution(factory));
+            }
+        }
+
+        let interval = config.pipeline.tip_poll_interval;
+        let tip_watcher = ChainTipWatcher::new(fgw, pipeline.handle(), interval);
+
+        // --- build rpc server
+
+        anyhow::ensure!(
+            !config.rpc.apis.contains(&RpcModuleKind::Dev),
+            "The `dev` module is not supported by the full node"
+        );
+
+        let mut rpc_modules = RpcModule::new(());
+
+        let cors = Cors::new()
+            .allow_origins(config.rpc.cors_origins.clone())
+            // Allow `POST` when accessing the resource
+            .allow_methods([Method::POST, Method::GET])
+            .allow_headers([CONTENT_TYPE]);
+
+        let starknet_enabled = config.rpc.apis.contains(&RpcModuleKind::Starknet);
+        let debug_enabled = config.rpc.apis.contains(&RpcModuleKind::Debug);
+
+        if starknet_enabled || debug_enabled {
+            let cfg = StarknetApiConfig {
+                max_event_page_size: config.rpc.max_event_page_size,
+                max_proof_keys: config.rpc.max_proof_keys,
+                max_call_gas: config.rpc.max_call_gas,
+                max_concurrent_estimate_fee_requests: config
+                    .rpc
+                    .max_concurrent_estimate_fee_requests,
+                #[cfg(feature = "cartridge")]
+                paymaster: None,
+            };
+
+            let stages = pipeline.stage_ids();
+            let progress = PipelineSyncProgress::new(provider, stages, tip_watcher.subscribe())?;
+            let api =
+                StarknetApi::new_syncing(backend.clone(), pool.clone(), Arc::new(progress), cfg);
+
+            // Only the read-only APIs are exposed as the node doesn't accept transactions.
+            if starknet_enabled {
+                rpc_modules.merge(StarknetApiServer::into_rpc(api.clone()))?;
+                rpc_modules.merge(StarknetTraceApiServer::into_rpc(api.clone()))?;
+            }
+
+            if debug_enabled {
+                rpc_modules.merge(DebugApiServer::into_rpc(api))?;
+            }
+        }
+
+        if config.rpc.apis.contains(&RpcModuleKind::Katana) {
+            let api = KatanaApi::new(backend.blockchain.provider().clone());
+            rpc_modules.merge(KatanaApiServer::into_rpc(api))?;
+        }
+
+        let mut rpc_server =
+            RpcServer::new().metrics(true).health_check(true).cors(cors).module(rpc_modules)?;
+
+        if config.rpc.feeder_gateway {
+            let provider = backend.blockchain.provider().clone();
+            rpc_server = rpc_server.feeder_gateway(FeederGatewayLayer::new(provider));
+        }
+
+        if let Some(timeout) = config.rpc.timeout {
+            rpc_server = rpc_server.timeout(timeout);
         };
 
-        let (mut pipeline, _) = Pipeline::new(provider.clone(), 64);
-        pipeline.add_stage(Blocks::new(provider.clone(), fgw.clone(), 3));
-        pipeline.add_stage(Classes::new(provider, fgw.clone(), 3));
+        if let Some(max_connections) = config.rpc.max_connections {
+            rpc_server = rpc_server.max_connections(max_connections);
+        }
+
+        if let Some(max_request_body_size) = config.rpc.max_request_body_size {
+            rpc_server = rpc_server.max_request_body_size(max_request_body_size);
+        }
+
+        if let Some(max_response_body_size) = config.rpc.max_response_body_size {
+            rpc_server = rpc_server.max_response_body_size(max_response_body_size);
+        }
 
-        let node = Node { pool, config: Arc::new(config), task_manager, pipeline, db };
+        let node = Node {
+            db,
+            pool,
+            backend,
+            pipeline,
+            rpc_server,
+            tip_watcher,
+            task_manager,
+            config: Arc::new(config),
+        };
 
         Ok(node)
     }
 
-    pub fn launch(self) -> Result<LaunchedNode> {
+    pub async fn launch(self) -> Result<LaunchedNode> {
         if let Some(ref cfg) = self.config.metrics {
             let reports: Vec<Box<dyn Report>> = vec![Box::new(self.db.clone()) as Box<dyn Report>];
             let exporter = PrometheusRecorder::current().expect("qed; should exist at this point");
@@ -99,21 +371,14 @@ impl Node {
             info!(%addr, "Metrics server started.");
         }
 
-        let fgw = if let Some(key) = self.config.gateway_api_key.as_ref() {
-            SequencerGateway::sn_sepolia().with_api_key(key.clone())
-        } else {
-            SequencerGateway::sn_sepolia()
-        };
-
         let pipeline_handle = self.pipeline.handle();
-        let tip_watcher = ChainTipWatcher::new(fgw, pipeline_handle.clone());
 
         self.task_manager
             .task_spawner()
             .build_task()
             .critical()
             .name("Chain tip watcher")
-            .spawn(tip_watcher.into_future());
+            .spawn(self.tip_watcher.into_future());
 
         self.task_manager
             .task_spawner()
@@ -122,10 +387,14 @@ impl Node {
             .name("Pipeline")
             .spawn(self.pipeline.into_future());
 
+        let rpc = self.rpc_server.start(self.config.rpc.socket_addr()).await?;
+
         Ok(LaunchedNode {
             db: self.db,
+            rpc,
             pipeline_handle,
             pool: self.pool,
+            backend: self.backend,
             config: self.config,
             task_manager: self.task_manager,
         })
@@ -139,10 +408,14 @@ pub struct LaunchedNode {
     pub task_manager: TaskManager,
     pub config: Arc<Config>,
     pub pipeline_handle: PipelineHandle,
+    pub backend: Arc<Backend<BlockifierFactory>>,
+    /// Handle to the rpc server.
+    pub rpc: RpcServerHandle,
 }
 
 impl LaunchedNode {
     pub async fn stop(&self) -> Result<()> {
+        self.rpc.stop()?;
         self.task_manager.shutdown().await;
         Ok(())
     }
@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+
+use katana_primitives::block::BlockNumber;
+use katana_provider::traits::block::BlockHashProvider;
+use katana_provider::traits::stage::StageCheckpointProvider;
+use katana_provider::ProviderResult;
+use katana_rpc::starknet::SyncProgress;
+use katana_rpc_types::SyncingStatus;
+use starknet::core::types::SyncStatus;
+use tokio::sync::watch;
+
+use super::tip_watcher::ChainTip;
+
+/// Reports the syncing progress of the full node.
+///
+/// The node is considered synced up to the lowest checkpoint among all the pipeline stages, as
+/// only then is a block fully processed, and it is syncing for as long as that block is behind
+/// the chain tip.
+#[derive(Debug)]
+pub struct PipelineSyncProgress<P> {
+    provider: P,
+    /// The ids of the pipeline stages.
+    stages: Vec<&'static str>,
+    /// The block the node was synced up to when it was started.
+    starting_block: BlockNumber,
+    /// The latest chain tip reported by the chain tip watcher.
+    tip: watch::Receiver<Option<ChainTip>>,
+}
+
+impl<P: StageCheckpointProvider> PipelineSyncProgress<P> {
+    pub fn new(
+        provider: P,
+        stages: Vec<&'static str>,
+        tip: watch::Receiver<Option<ChainTip>>,
+    ) -> ProviderResult<Self> {
+        let starting_block = synced_block(&provider, &stages)?;
+        Ok(Self { provider, stages, starting_block, tip })
+    }
+}
+
+impl<P> SyncProgress for PipelineSyncProgress<P>
+where
+    P: StageCheckpointProvider + BlockHashProvider + Debug + Send + Sync + 'static,
+{
+    fn status(&self) -> ProviderResult<SyncingStatus> {
+        // the tip is not known until the feeder gateway has been queried at least once
+        let Some(tip) = *self.tip.borrow() else { return Ok(SyncingStatus::NotSyncing) };
+
+        let current_block = synced_block(&self.provider, &self.stages)?;
+        if current_block >= tip.number {
+            return Ok(SyncingStatus::NotSyncing);
+        }
+
+        let starting_block_hash =
+            self.provider.block_hash_by_num(self.starting_block)?.unwrap_or_default();
+        let current_block_hash =
+            self.provider.block_hash_by_num(current_block)?.unwrap_or_default();
+
+        Ok(SyncingStatus::Syncing(SyncStatus {
+            starting_block_num: self.starting_block,
+            starting_block_hash,
+            current_block_num: current_block,
+            current_block_hash,
+            highest_block_num: tip.number,
+            highest_block_hash: tip.hash,
+        }))
+    }
+}
+
+/// Returns the block up to which all the stages have been executed.
+fn synced_block<P: StageCheckpointProvider>(
+    provider: &P,
+    stages: &[&'static str],
+) -> ProviderResult<BlockNumber> {
+    let mut block: Option<BlockNumber> = None;
+    for id in stages {
+        let checkpoint = provider.checkpoint(id)?.unwrap_or_default();
+        block = Some(block.map_or(checkpoint, |block| block.min(checkpoint)));
+    }
+    Ok(block.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::Felt;
+    use katana_provider::test_utils::test_provider;
+    use katana_provider::traits::block::BlockHashProvider;
+    use katana_provider::traits::stage::StageCheckpointProvider;
+    use katana_rpc::starknet::SyncProgress;
+    use katana_rpc_types::SyncingStatus;
+    use tokio::sync::watch;
+
+    use super::PipelineSyncProgress;
+    use crate::full::tip_watcher::ChainTip;
+
+    #[test]
+    fn syncing_status() {
+        let provider = test_provider();
+        let genesis_hash = provider.block_hash_by_num(0).unwrap().unwrap();
+
+        let (tip, rx) = watch::channel(None);
+        let stages = vec!["Blocks", "Classes"];
+        let progress = PipelineSyncProgress::new(provider.clone(), stages, rx).unwrap();
+
+        // the node isn't syncing until the tip is known
+        assert!(matches!(progress.status().unwrap(), SyncingStatus::NotSyncing));
+
+        let tip_hash = Felt::from(0x1337u16);
+        tip.send_replace(Some(ChainTip { number: 10, hash: tip_hash }));
+        provider.set_checkpoint("Blocks", 5).unwrap();
+
+        // the current block is bounded by the stage that is the furthest behind
+        let SyncingStatus::Syncing(status) = progress.status().unwrap() else {
+            panic!("should be syncing")
+        };
+        assert_eq!(status.starting_block_num, 0);
+        assert_eq!(status.starting_block_hash, genesis_hash);
+        assert_eq!(status.current_block_num, 0);
+        assert_eq!(status.current_block_hash, genesis_hash);
+        assert_eq!(status.highest_block_num, 10);
+        assert_eq!(status.highest_block_hash, tip_hash);
+
+        provider.set_checkpoint("Blocks", 10).unwrap();
+        provider.set_checkpoint("Classes", 10).unwrap();
+        assert!(matches!(progress.status().unwrap(), SyncingStatus::NotSyncing));
+    }
+}
@@ -5,11 +5,19 @@ use anyhow::Result;
 use futures::future::BoxFuture;
 use katana_feeder_gateway::client::SequencerGateway;
 use katana_pipeline::PipelineHandle;
-use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::block::{BlockHash, BlockIdOrTag, BlockNumber, BlockTag};
+use tokio::sync::watch;
 use tracing::{error, info, trace};
 
 type TipWatcherFut = BoxFuture<'static, Result<()>>;
 
+/// The latest block of the chain, as reported by the feeder gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainTip {
+    pub number: BlockNumber,
+    pub hash: BlockHash,
+}
+
 #[derive(Debug)]
 pub struct ChainTipWatcher {
     /// The feeder gateway client for fetching the latest block.
@@ -18,12 +26,23 @@ pub struct ChainTipWatcher {
     pipeline_handle: PipelineHandle,
     /// Interval for checking the new tip.
     watch_interval: Duration,
+    /// The latest tip received from the feeder gateway.
+    tip: watch::Sender<Option<ChainTip>>,
 }
 
 impl ChainTipWatcher {
-    pub fn new(client: SequencerGateway, pipeline_handle: PipelineHandle) -> Self {
-        let watch_interval = Duration::from_secs(30);
-        Self { client, pipeline_handle, watch_interval }
+    pub fn new(
+        client: SequencerGateway,
+        pipeline_handle: PipelineHandle,
+        watch_interval: Duration,
+    ) -> Self {
+        let (tip, _) = watch::channel(None);
+        Self { client, pipeline_handle, watch_interval, tip }
+    }
+
+    /// Returns a receiver that is notified every time a new tip is received.
+    pub fn subscribe(&self) -> watch::Receiver<Option<ChainTip>> {
+        self.tip.subscribe()
     }
 
     pub async fn run(&self) -> Result<()> {
@@ -35,9 +54,11 @@ impl ChainTipWatcher {
         loop {
             let block = self.client.get_block(BlockIdOrTag::Tag(BlockTag::Latest)).await?;
             let block_number = block.block_number.expect("must exist for latest block");
+            let block_hash = block.block_hash.expect("must exist for latest block");
 
             if prev_tip != block_number {
                 trace!(target: "node", block = %block_number, "New tip received");
+                self.tip.send_replace(Some(ChainTip { number: block_number, hash: block_hash }));
                 self.pipeline_handle.set_tip(block_number);
                 prev_tip = block_number;
             }
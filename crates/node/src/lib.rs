@@ -10,17 +10,20 @@ use std::future::IntoFuture;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use config::dev::L2GasPriceConfig;
 use config::rpc::RpcModuleKind;
+use config::sequencing::TxOrderingKind;
 use config::Config;
 use http::header::CONTENT_TYPE;
 use http::Method;
 use jsonrpsee::RpcModule;
 use katana_chain_spec::{ChainSpec, SettlementLayer};
-use katana_core::backend::gas_oracle::GasOracle;
+use katana_core::backend::gas_oracle::{Eip1559GasOracle, Eip1559Params, GasOracle, L2GasOracle};
 use katana_core::backend::storage::Blockchain;
 use katana_core::backend::Backend;
 use katana_core::constants::{
-    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
+    DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE,
+    DEFAULT_L2_GAS_PRICE_MAX_CHANGE_DENOMINATOR, DEFAULT_STRK_L1_DATA_GAS_PRICE,
     DEFAULT_STRK_L1_GAS_PRICE,
 };
 use katana_core::env::BlockContextGenerator;
@@ -32,7 +35,7 @@ use katana_executor::ExecutionFlags;
 use katana_metrics::exporters::prometheus::PrometheusRecorder;
 use katana_metrics::sys::DiskReporter;
 use katana_metrics::{Report, Server as MetricsServer};
-use katana_pool::ordering::FiFo;
+use katana_pool::ordering::{FeeMarket, FiFo, TxOrdering};
 use katana_pool::TxPool;
 use katana_primitives::block::GasPrice;
 use katana_primitives::env::{CfgEnv, FeeTokenAddressses};
@@ -40,6 +43,8 @@ use katana_primitives::env::{CfgEnv, FeeTokenAddressses};
 use katana_rpc::cartridge::CartridgeApi;
 use katana_rpc::cors::Cors;
 use katana_rpc::dev::DevApi;
+use katana_rpc::feeder_gateway::FeederGatewayLayer;
+use katana_rpc::katana::KatanaApi;
 use katana_rpc::starknet::forking::ForkedClient;
 #[cfg(feature = "cartridge")]
 use katana_rpc::starknet::PaymasterConfig;
@@ -47,8 +52,13 @@ use katana_rpc::starknet::{StarknetApi, StarknetApiConfig};
 use katana_rpc::{RpcServer, RpcServerHandle};
 #[cfg(feature = "cartridge")]
 use katana_rpc_api::cartridge::CartridgeApiServer;
+use katana_rpc_api::debug::DebugApiServer;
 use katana_rpc_api::dev::DevApiServer;
-use katana_rpc_api::starknet::{StarknetApiServer, StarknetTraceApiServer, StarknetWriteApiServer};
+use katana_rpc_api::katana::KatanaApiServer;
+use katana_rpc_api::starknet::{
+    StarknetApiServer, StarknetTraceApiServer, StarknetWriteApiServer, StarknetWsApiServer,
+    WS_UNSUBSCRIBE_METHODS,
+};
 use katana_stage::Sequencing;
 use katana_tasks::TaskManager;
 use tracing::info;
@@ -134,14 +144,20 @@ impl Node {
         let (blockchain, db, forked_client) = if let Some(cfg) = &config.forking {
             let chain_spec = Arc::get_mut(&mut config.chain).expect("get mut Arc");
 
-            let ChainSpec::Dev(chain_spec) = chain_spec else {
-                return Err(anyhow::anyhow!("Forking is only supported in dev mode for now"));
+            let db = if let Some(db_path) = &config.db.dir {
+                katana_db::init_db(db_path)?
+            } else {
+                katana_db::init_ephemeral_db()?
             };
 
-            let db = katana_db::init_ephemeral_db()?;
-            let (bc, block_num) =
-                Blockchain::new_from_forked(db.clone(), cfg.url.clone(), cfg.block, chain_spec)
-                    .await?;
+            let (bc, block_num) = Blockchain::new_from_forked(
+                db.clone(),
+                cfg.url.clone(),
+                cfg.block,
+                cfg.backend.clone(),
+                chain_spec,
+            )
+            .await?;
 
             // TODO: it'd bee nice if the client can be shared on both the rpc and forked backend
             // side
@@ -187,6 +203,24 @@ impl Node {
             )
         };
 
+        // --- configure the l2 gas oracle
+
+        let gas_oracle = match &config.dev.l2_gas_prices {
+            Some(L2GasPriceConfig { gas_price, target_gas: None }) => {
+                gas_oracle.with_l2_oracle(L2GasOracle::Fixed(gas_price.clone()))
+            }
+            Some(L2GasPriceConfig { gas_price, target_gas: Some(target_gas) }) => {
+                let params = Eip1559Params {
+                    target_gas: *target_gas as u128,
+                    max_change_denominator: DEFAULT_L2_GAS_PRICE_MAX_CHANGE_DENOMINATOR,
+                    min_prices: gas_price.clone(),
+                };
+                let oracle = Eip1559GasOracle::new(gas_price.clone(), params);
+                gas_oracle.with_l2_oracle(L2GasOracle::Eip1559(oracle))
+            }
+            None => gas_oracle,
+        };
+
         let block_context_generator = BlockContextGenerator::default().into();
         let backend = Arc::new(Backend {
             gas_oracle,
@@ -197,6 +231,8 @@ impl Node {
         });
 
         backend.init_genesis().context("failed to initialize genesis")?;
+        // the L2 gas prices carry on from the latest block if the chain already has some
+        backend.sync_l2_gas_prices().context("failed to restore the l2 gas prices")?;
 
         // --- build block producer
 
@@ -214,7 +250,12 @@ impl Node {
         // --- build transaction pool
 
         let validator = block_producer.validator();
-        let pool = TxPool::new(validator.clone(), FiFo::new());
+        let ordering = match config.sequencing.tx_ordering {
+            TxOrderingKind::Fifo => TxOrdering::FiFo(FiFo::new()),
+            TxOrderingKind::FeeMarket => TxOrdering::FeeMarket(FeeMarket::new()),
+        };
+        let pool = TxPool::new(validator.clone(), ordering);
+        let block_producer = block_producer.with_pool(pool.clone());
 
         // --- build rpc server
 
@@ -247,7 +288,10 @@ impl Node {
             None
         };
 
-        if config.rpc.apis.contains(&RpcModuleKind::Starknet) {
+        let starknet_enabled = config.rpc.apis.contains(&RpcModuleKind::Starknet);
+        let debug_enabled = config.rpc.apis.contains(&RpcModuleKind::Debug);
+
+        if starknet_enabled || debug_enabled {
             let cfg = StarknetApiConfig {
                 max_event_page_size: config.rpc.max_event_page_size,
                 max_proof_keys: config.rpc.max_proof_keys,
@@ -271,16 +315,33 @@ impl Node {
                 StarknetApi::new(backend.clone(), pool.clone(), Some(block_producer.clone()), cfg)
             };
 
-            rpc_modules.merge(StarknetApiServer::into_rpc(api.clone()))?;
-            rpc_modules.merge(StarknetWriteApiServer::into_rpc(api.clone()))?;
-            rpc_modules.merge(StarknetTraceApiServer::into_rpc(api))?;
+            if starknet_enabled {
+                rpc_modules.merge(StarknetApiServer::into_rpc(api.clone()))?;
+                rpc_modules.merge(StarknetWriteApiServer::into_rpc(api.clone()))?;
+                rpc_modules.merge(StarknetTraceApiServer::into_rpc(api.clone()))?;
+
+                let mut ws_module = StarknetWsApiServer::into_rpc(api.clone());
+                for method in WS_UNSUBSCRIBE_METHODS {
+                    ws_module.remove_method(method);
+                }
+                rpc_modules.merge(ws_module)?;
+            }
+
+            if debug_enabled {
+                rpc_modules.merge(DebugApiServer::into_rpc(api))?;
+            }
         }
 
         if config.rpc.apis.contains(&RpcModuleKind::Dev) {
-            let api = DevApi::new(backend.clone(), block_producer.clone());
+            let api = DevApi::new(backend.clone(), block_producer.clone(), pool.clone());
             rpc_modules.merge(DevApiServer::into_rpc(api))?;
         }
 
+        if config.rpc.apis.contains(&RpcModuleKind::Katana) {
+            let api = KatanaApi::new(backend.blockchain.provider().clone());
+            rpc_modules.merge(KatanaApiServer::into_rpc(api))?;
+        }
+
         #[allow(unused_mut)]
         let mut rpc_server =
             RpcServer::new().metrics(true).health_check(true).cors(cors).module(rpc_modules)?;
@@ -290,6 +351,11 @@ impl Node {
             rpc_server = rpc_server.explorer(config.rpc.explorer);
         }
 
+        if config.rpc.feeder_gateway {
+            let provider = backend.blockchain.provider().clone();
+            rpc_server = rpc_server.feeder_gateway(FeederGatewayLayer::new(provider));
+        }
+
         if let Some(timeout) = config.rpc.timeout {
             rpc_server = rpc_server.timeout(timeout);
         };
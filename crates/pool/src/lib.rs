@@ -3,6 +3,8 @@
 pub mod ordering;
 pub mod pending;
 pub mod pool;
+pub mod queued;
+pub mod rejected;
 pub mod subscription;
 pub mod tx;
 pub mod validation;
@@ -11,16 +13,17 @@ use std::sync::Arc;
 
 use futures::channel::mpsc::Receiver;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxHash};
-use ordering::{FiFo, PoolOrd};
+use ordering::{PoolOrd, TxOrdering};
 use pending::PendingTransactions;
 use pool::Pool;
+use queued::QueueError;
 use tx::PoolTransaction;
 use validation::error::InvalidTransactionError;
 use validation::stateful::TxValidator;
 use validation::Validator;
 
 /// Katana default transacstion pool type.
-pub type TxPool = Pool<ExecutableTxWithHash, TxValidator, FiFo<ExecutableTxWithHash>>;
+pub type TxPool = Pool<ExecutableTxWithHash, TxValidator, TxOrdering<ExecutableTxWithHash>>;
 
 pub type PoolResult<T> = Result<T, PoolError>;
 
@@ -28,6 +31,18 @@ pub type PoolResult<T> = Result<T, PoolError>;
 pub enum PoolError {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(Box<InvalidTransactionError>),
+    #[error(transparent)]
+    Queue(#[from] QueueError),
+    #[error(
+        "Transaction {hash:#x} with the same sender and nonce is already in the pool with a tip of \
+         {tip}, replacement requires a higher tip"
+    )]
+    ReplacementUnderpriced { hash: TxHash, tip: u64 },
+    #[error(
+        "Transaction {hash:#x} with the same sender and nonce has already been picked up for \
+         execution and can no longer be replaced"
+    )]
+    ReplacementTooLate { hash: TxHash },
     #[error("Internal error: {0}")]
     Internal(Box<dyn std::error::Error>),
 }
@@ -62,7 +77,30 @@ pub trait TransactionPool {
     /// Removes a list of transactions from the pool according to their hashes.
     fn remove_transactions(&self, hashes: &[TxHash]);
 
-    /// Get the total number of transactions in the pool.
+    /// Removes all the transactions from the pool, both pending and queued.
+    ///
+    /// This is used when the chain is reverted, as the transactions were validated against a state
+    /// that no longer exists.
+    fn clear(&self);
+
+    /// Re-validates the queued transactions - transactions whose nonce is ahead of their sender's
+    /// next nonce - and moves to the pending pool those whose nonce gap has been filled.
+    ///
+    /// This should be called whenever the state of the pool's validator is updated, eg after a
+    /// block is mined.
+    fn promote_queued_transactions(&self);
+
+    /// Get the number of queued transactions in the pool.
+    fn queued_size(&self) -> usize;
+
+    /// Records a transaction as rejected along with the reason of its rejection, and removes it
+    /// from the pool if it's present.
+    fn add_rejected_transaction(&self, hash: TxHash, reason: String);
+
+    /// Returns the rejection reason of a transaction, if it was recently rejected.
+    fn rejected_transaction(&self, hash: TxHash) -> Option<String>;
+
+    /// Get the total number of pending transactions in the pool.
     fn size(&self) -> usize;
 
     /// Get a reference to the pool's validator.
@@ -2,6 +2,8 @@ use std::fmt;
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 
+use katana_primitives::fee::ResourceBoundsMapping;
+
 use crate::PoolTransaction;
 
 // evaluates the priority of a transaction which would be used to determine how txs are ordered in
@@ -83,8 +85,8 @@ impl PartialEq for TxSubmissionNonce {
 
 /// Tip-based ordering implementation.
 ///
-/// This ordering implementation uses the transaction's tip as the priority value. We don't have a
-/// use case for this ordering implementation yet, but it's mostly used for testing.
+/// This ordering implementation uses the transaction's tip as the priority value. It's mostly used
+/// for testing, see [`FeeMarket`] for an ordering that also accounts for the resource bounds.
 #[derive(Debug)]
 pub struct TipOrdering<T>(PhantomData<T>);
 
@@ -132,10 +134,109 @@ impl<T> Default for TipOrdering<T> {
     }
 }
 
+/// Fee market ordering implementation.
+///
+/// Transactions are prioritized by their tip first, and then by the maximum price per unit of L2
+/// gas they're willing to pay, as specified in their resource bounds. Both only exist for V3
+/// transactions, so transactions of older versions are given the lowest priority. Transactions with
+/// the same priority are ordered by their submission time.
+///
+/// The priority only orders transactions across senders. The transactions of a single sender are
+/// always ordered by nonce, as the pool never gives a transaction a higher priority than the ones
+/// of the same sender with a lower nonce.
+#[derive(Debug)]
+pub struct FeeMarket<T>(PhantomData<T>);
+
+impl<T> FeeMarket<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The priority value of [`FeeMarket`]. Higher values have higher priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeePriority {
+    tip: u64,
+    max_l2_gas_price: u128,
+}
+
+impl Ord for FeePriority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reverse the ordering so higher values have higher priority
+        (other.tip, other.max_l2_gas_price).cmp(&(self.tip, self.max_l2_gas_price))
+    }
+}
+
+impl PartialOrd for FeePriority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PoolTransaction> PoolOrd for FeeMarket<T> {
+    type Transaction = T;
+    type PriorityValue = FeePriority;
+
+    fn priority(&self, tx: &Self::Transaction) -> Self::PriorityValue {
+        // Legacy resource bounds (L1 gas only) don't bound the L2 gas price.
+        let max_l2_gas_price = match tx.resource_bounds() {
+            Some(ResourceBoundsMapping::All(bounds)) => bounds.l2_gas.max_price_per_unit,
+            Some(ResourceBoundsMapping::L1Gas(_)) | None => 0,
+        };
+
+        FeePriority { tip: tx.tip(), max_l2_gas_price }
+    }
+}
+
+impl<T> Default for FeeMarket<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The ordering used by the [`TxPool`](crate::TxPool), which can be selected at runtime.
+#[derive(Debug)]
+pub enum TxOrdering<T> {
+    /// See [`FiFo`].
+    FiFo(FiFo<T>),
+    /// See [`FeeMarket`].
+    FeeMarket(FeeMarket<T>),
+}
+
+/// The priority value of [`TxOrdering`].
+///
+/// A pool only ever uses one ordering, so values of different variants are never compared against
+/// each other.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TxPriority {
+    FiFo(TxSubmissionNonce),
+    FeeMarket(FeePriority),
+}
+
+impl<T: PoolTransaction> PoolOrd for TxOrdering<T> {
+    type Transaction = T;
+    type PriorityValue = TxPriority;
+
+    fn priority(&self, tx: &Self::Transaction) -> Self::PriorityValue {
+        match self {
+            Self::FiFo(ordering) => TxPriority::FiFo(ordering.priority(tx)),
+            Self::FeeMarket(ordering) => TxPriority::FeeMarket(ordering.priority(tx)),
+        }
+    }
+}
+
+impl<T> Default for TxOrdering<T> {
+    fn default() -> Self {
+        Self::FiFo(FiFo::new())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use futures::StreamExt;
+    use katana_primitives::fee::{AllResourceBoundsMapping, ResourceBounds, ResourceBoundsMapping};
+    use katana_primitives::{address, Felt};
 
     use crate::ordering::{self, FiFo};
     use crate::pool::test_utils::*;
@@ -166,6 +267,68 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fee_market_ordering() {
+        let bounds = |price: u128| {
+            ResourceBoundsMapping::All(AllResourceBoundsMapping {
+                l1_gas: ResourceBounds::ZERO,
+                l2_gas: ResourceBounds { max_amount: 1, max_price_per_unit: price },
+                l1_data_gas: ResourceBounds::ZERO,
+            })
+        };
+
+        let txs = [
+            PoolTx::new().with_tip(1).with_resource_bounds(bounds(100)),
+            PoolTx::new().with_tip(0),
+            PoolTx::new().with_tip(2).with_resource_bounds(bounds(1)),
+            PoolTx::new().with_tip(1).with_resource_bounds(bounds(200)),
+            PoolTx::new().with_tip(1).with_resource_bounds(bounds(100)),
+            PoolTx::new().with_tip(0).with_resource_bounds(bounds(5)),
+        ];
+
+        let pool = Pool::new(NoopValidator::new(), ordering::FeeMarket::new());
+
+        txs.iter().for_each(|tx| {
+            let _ = pool.add_transaction(tx.clone());
+        });
+
+        let mut pending = pool.pending_transactions();
+
+        // ordered by tip, then by the max L2 gas price, then by submission order
+        for expected in [2, 3, 0, 4, 5, 1] {
+            let tx = pending.next().await.unwrap();
+            assert_eq!(tx.tx.hash(), txs[expected].hash());
+        }
+    }
+
+    #[tokio::test]
+    async fn fee_market_ordering_keeps_sender_txs_in_nonce_order() {
+        let sender = address!("0x1337");
+
+        let txs = [
+            PoolTx::new().with_sender(sender).with_nonce(Felt::ZERO).with_tip(1),
+            PoolTx::new().with_sender(sender).with_nonce(Felt::ONE).with_tip(10),
+            PoolTx::new().with_tip(5),
+            PoolTx::new().with_sender(sender).with_nonce(Felt::TWO).with_tip(3),
+            PoolTx::new().with_tip(0),
+        ];
+
+        let pool = Pool::new(NoopValidator::new(), ordering::FeeMarket::new());
+
+        txs.iter().for_each(|tx| {
+            let _ = pool.add_transaction(tx.clone());
+        });
+
+        let mut pending = pool.pending_transactions();
+
+        // the tips only order the txs across senders, a tx with a higher tip never comes before
+        // the txs of the same sender with a lower nonce
+        for expected in [2, 0, 1, 3, 4] {
+            let tx = pending.next().await.unwrap();
+            assert_eq!(tx.tx.hash(), txs[expected].hash());
+        }
+    }
+
     #[tokio::test]
     async fn tip_based_ordering() {
         // Create mock transactions with different tips and in random order
@@ -28,10 +28,20 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
-        if let Some(tx) = this.all.next() {
-            Poll::Ready(Some(tx))
-        } else {
-            this.subscription.poll_next_unpin(cx)
+        loop {
+            let tx = if let Some(tx) = this.all.next() {
+                tx
+            } else {
+                match this.subscription.poll_next_unpin(cx) {
+                    Poll::Ready(Some(tx)) => tx,
+                    poll => return poll,
+                }
+            };
+
+            // skip the transactions that have been replaced since they were added to the pool
+            if tx.state.pick() {
+                return Poll::Ready(Some(tx));
+            }
         }
     }
 }
@@ -3,6 +3,7 @@ use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use futures::channel::mpsc::{channel, Receiver, Sender};
+use katana_primitives::contract::ContractAddress;
 use katana_primitives::transaction::TxHash;
 use parking_lot::RwLock;
 use tokio::sync::mpsc;
@@ -10,9 +11,10 @@ use tracing::{error, trace, warn};
 
 use crate::ordering::PoolOrd;
 use crate::pending::PendingTransactions;
+use crate::queued::{QueueConfig, QueuedTransactions};
+use crate::rejected::RejectedTransactions;
 use crate::subscription::Subscription;
 use crate::tx::{PendingTx, PoolTransaction, TxId};
-use crate::validation::error::InvalidTransactionError;
 use crate::validation::{ValidationOutcome, Validator};
 use crate::{PoolError, PoolResult, TransactionPool};
 
@@ -31,6 +33,9 @@ struct Inner<T, V, O: PoolOrd> {
     /// List of all valid txs in the pool.
     transactions: RwLock<BTreeSet<PendingTx<T, O>>>,
 
+    /// txs whose nonce is ahead of their sender's next nonce, waiting for the gap to be filled
+    queued: RwLock<QueuedTransactions<T>>,
+
     /// listeners for incoming txs
     listeners: RwLock<Vec<Sender<TxHash>>>,
 
@@ -42,6 +47,9 @@ struct Inner<T, V, O: PoolOrd> {
 
     /// the ordering mechanism used to order the txs in the pool
     ordering: O,
+
+    /// recently rejected txs, along with the reason of their rejection
+    rejected: RwLock<RejectedTransactions>,
 }
 
 impl<T, V, O> Pool<T, V, O>
@@ -52,13 +60,21 @@ where
 {
     /// Creates a new [Pool] with the given [Validator] and [PoolOrd] mechanism.
     pub fn new(validator: V, ordering: O) -> Self {
+        Self::with_queue_config(validator, ordering, QueueConfig::default())
+    }
+
+    /// Creates a new [Pool] with the given [Validator], [PoolOrd] mechanism and limits for the
+    /// queued transactions.
+    pub fn with_queue_config(validator: V, ordering: O, queue: QueueConfig) -> Self {
         Self {
             inner: Arc::new(Inner {
                 ordering,
                 validator,
                 transactions: Default::default(),
+                queued: RwLock::new(QueuedTransactions::new(queue)),
                 subscribers: Default::default(),
                 listeners: Default::default(),
+                rejected: Default::default(),
             }),
         }
     }
@@ -115,6 +131,138 @@ where
         self.notify_subscribers(tx);
     }
 
+    /// Inserts a validated transaction into the pending pool and notifies the listeners.
+    ///
+    /// If there's already a pending transaction with the same sender and nonce, it is replaced only
+    /// if the new transaction has a higher tip and the existing one hasn't been picked up for
+    /// execution yet.
+    fn add_pending(&self, id: TxId, tx: T) -> PoolResult<()> {
+        let mut txs = self.inner.transactions.write();
+
+        // the txs of a sender must be executed in nonce order, so a tx is never given a higher
+        // priority than the txs of the same sender with a lower nonce. the ordering's priority
+        // thus only decides the order of txs across senders.
+        let priority = txs
+            .iter()
+            .filter(|t| t.id.sender() == id.sender() && t.id.nonce() < id.nonce())
+            .map(|t| t.priority.clone())
+            .fold(self.inner.ordering.priority(&tx), std::cmp::max);
+
+        let tx = PendingTx::new(id, tx, priority);
+
+        if let Some(existing) = txs.iter().find(|t| t.id == tx.id).cloned() {
+            let (hash, tip) = (existing.tx.hash(), existing.tx.tip());
+
+            if tx.tx.tip() <= tip {
+                return Err(PoolError::ReplacementUnderpriced { hash, tip });
+            }
+
+            // a tx that has been picked up for execution will be included in the pending block,
+            // replacing it would only get the replacement rejected once executed.
+            if !existing.state.replace() {
+                return Err(PoolError::ReplacementTooLate { hash });
+            }
+
+            txs.retain(|t| t.id != tx.id);
+
+            let reason = format!("Replaced by transaction {:#x}", tx.tx.hash());
+            self.inner.rejected.write().insert(hash, reason);
+            let replaced = format!("{hash:#x}");
+            trace!(target: "pool", %replaced, "Transaction replaced.");
+        }
+
+        // the txs of the same sender with a higher nonce can't have a higher priority than this tx
+        let descendants = txs
+            .iter()
+            .filter(|t| t.id.sender() == tx.id.sender() && t.id.nonce() > tx.id.nonce())
+            .filter(|t| t.priority < tx.priority)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !descendants.is_empty() {
+            txs.retain(|t| !descendants.iter().any(|d| d.id == t.id));
+            for mut descendant in descendants {
+                descendant.priority = tx.priority.clone();
+                txs.insert(descendant);
+            }
+        }
+
+        txs.insert(tx.clone());
+        drop(txs);
+        trace!(target: "pool", "Transaction added to the pool");
+
+        self.notify(tx);
+
+        Ok(())
+    }
+
+    /// Moves the queued transactions of `sender` to the pending pool, in nonce order, for as long
+    /// as they're valid against the validator's current state.
+    fn promote(&self, sender: ContractAddress) {
+        loop {
+            let Some((id, tx)) = self.inner.queued.write().pop_first_of(sender) else { break };
+            let tx = Arc::unwrap_or_clone(tx);
+            let hash = tx.hash();
+            let tx_hash = format!("{hash:#x}");
+
+            match self.inner.validator.validate(tx) {
+                Ok(ValidationOutcome::Valid(tx)) => {
+                    trace!(target: "pool", %tx_hash, "Promoting queued transaction.");
+                    if let Err(error) = self.add_pending(id, tx) {
+                        warn!(target: "pool", %error, %tx_hash, "Dropping queued transaction.");
+                        self.inner.rejected.write().insert(hash, error.to_string());
+                    }
+                }
+
+                // the nonce gap hasn't been filled yet
+                Ok(ValidationOutcome::Dependent { tx, .. }) => {
+                    if let Err(error) = self.queue(id, tx) {
+                        warn!(target: "pool", %error, %tx_hash, "Failed to requeue transaction.");
+                    }
+                    break;
+                }
+
+                Ok(ValidationOutcome::Invalid { error, .. }) => {
+                    warn!(target: "pool", %error, %tx_hash, "Dropping invalid queued transaction.");
+                    self.inner.rejected.write().insert(hash, error.to_string());
+                }
+
+                Err(error) => {
+                    error!(target: "pool", %error, %tx_hash, "Failed to validate queued transaction.");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Inserts a transaction into the queued sub-pool.
+    ///
+    /// Like for pending transactions, a queued transaction can only be replaced by a transaction
+    /// with a higher tip.
+    fn queue(&self, id: TxId, tx: T) -> PoolResult<()> {
+        let mut queued = self.inner.queued.write();
+
+        if let Some(existing) = queued.get(&id) {
+            if tx.tip() <= existing.tip() {
+                let (hash, tip) = (existing.hash(), existing.tip());
+                return Err(PoolError::ReplacementUnderpriced { hash, tip });
+            }
+        }
+
+        let hash = tx.hash();
+        let removed = queued.insert(id, tx).map_err(PoolError::Queue)?;
+        drop(queued);
+
+        let mut rejected = self.inner.rejected.write();
+        for tx in removed {
+            let tx_hash = format!("{:#x}", tx.hash());
+            trace!(target: "pool", %tx_hash, "Queued transaction removed.");
+            rejected.insert(tx.hash(), format!("Evicted from the pool by transaction {hash:#x}"));
+        }
+
+        Ok(())
+    }
+
     fn subscribe(&self) -> Subscription<T, O> {
         let (tx, rx) = mpsc::unbounded_channel();
         let subscriber = Subscription::new(rx);
@@ -142,36 +290,31 @@ where
             Ok(outcome) => {
                 match outcome {
                     ValidationOutcome::Valid(tx) => {
-                        // get the priority of the validated tx
-                        let priority = self.inner.ordering.priority(&tx);
-                        let tx = PendingTx::new(id, tx, priority);
-
-                        // insert the tx in the pool
-                        self.inner.transactions.write().insert(tx.clone());
-                        trace!(target: "pool", "Transaction added to the pool");
+                        let sender = tx.sender();
+                        self.add_pending(id, tx)?;
 
-                        self.notify(tx);
+                        // the tx may have filled the nonce gap of the sender's queued txs
+                        self.promote(sender);
 
                         Ok(hash)
                     }
 
-                    // TODO: create a small cache for rejected transactions to respect the rpc spec
-                    // `getTransactionStatus`
                     ValidationOutcome::Invalid { error, .. } => {
                         warn!(target: "pool", %error, "Invalid transaction.");
+                        self.inner.rejected.write().insert(hash, error.to_string());
                         Err(PoolError::InvalidTransaction(Box::new(error)))
                     }
 
-                    // return as error for now but ideally we should kept the tx in a separate
-                    // queue and revalidate it when the parent tx is added to the pool
+                    // keep the tx in the queued sub-pool until the nonce gap is filled
                     ValidationOutcome::Dependent { tx, tx_nonce, current_nonce } => {
                         trace!(target: "pool", %tx_nonce, %current_nonce, "Dependent transaction.");
-                        let err = InvalidTransactionError::InvalidNonce {
-                            address: tx.sender(),
-                            current_nonce,
-                            tx_nonce,
-                        };
-                        Err(PoolError::InvalidTransaction(Box::new(err)))
+                        let sender = tx.sender();
+                        self.queue(id, tx)?;
+
+                        // the gap may have been filled while the tx was being validated
+                        self.promote(sender);
+
+                        Ok(hash)
                     }
                 }
             }
@@ -197,12 +340,15 @@ where
     }
 
     fn get(&self, hash: TxHash) -> Option<Arc<T>> {
-        self.inner
+        let pending = self
+            .inner
             .transactions
             .read()
             .iter()
             .find(|tx| tx.tx.hash() == hash)
-            .map(|t| Arc::clone(&t.tx))
+            .map(|t| Arc::clone(&t.tx));
+
+        pending.or_else(|| self.inner.queued.read().find(|tx| tx.hash() == hash))
     }
 
     fn add_listener(&self) -> Receiver<TxHash> {
@@ -215,7 +361,35 @@ where
     fn remove_transactions(&self, hashes: &[TxHash]) {
         // retain only transactions that aren't included in the list
         let mut txs = self.inner.transactions.write();
-        txs.retain(|t| !hashes.contains(&t.tx.hash()))
+        txs.retain(|t| !hashes.contains(&t.tx.hash()));
+        drop(txs);
+
+        self.inner.queued.write().remove_where(|tx| hashes.contains(&tx.hash()));
+    }
+
+    fn clear(&self) {
+        self.inner.transactions.write().clear();
+        self.inner.queued.write().remove_where(|_| true);
+    }
+
+    fn promote_queued_transactions(&self) {
+        let senders = self.inner.queued.read().senders();
+        for sender in senders {
+            self.promote(sender);
+        }
+    }
+
+    fn queued_size(&self) -> usize {
+        self.inner.queued.read().len()
+    }
+
+    fn add_rejected_transaction(&self, hash: TxHash, reason: String) {
+        self.remove_transactions(&[hash]);
+        self.inner.rejected.write().insert(hash, reason);
+    }
+
+    fn rejected_transaction(&self, hash: TxHash) -> Option<String> {
+        self.inner.rejected.read().get(&hash).map(ToString::to_string)
     }
 
     fn size(&self) -> usize {
@@ -241,12 +415,18 @@ where
 #[cfg(test)]
 pub(crate) mod test_utils {
 
+    use std::collections::HashMap;
+
     use katana_primitives::contract::{ContractAddress, Nonce};
+    use katana_primitives::fee::ResourceBoundsMapping;
     use katana_primitives::Felt;
+    use parking_lot::Mutex;
     use rand::Rng;
 
     use super::*;
     use crate::tx::PoolTransaction;
+    use crate::validation::error::InvalidTransactionError;
+    use crate::validation::ValidationResult;
 
     fn random_bytes<const SIZE: usize>() -> [u8; SIZE] {
         let mut bytes = [0u8; SIZE];
@@ -261,6 +441,7 @@ pub(crate) mod test_utils {
         hash: TxHash,
         max_fee: u128,
         sender: ContractAddress,
+        resource_bounds: Option<ResourceBoundsMapping>,
     }
 
     impl PoolTx {
@@ -275,6 +456,7 @@ pub(crate) mod test_utils {
                     let felt = Felt::from_bytes_be(&random_bytes::<32>());
                     ContractAddress::from(felt)
                 },
+                resource_bounds: None,
             }
         }
 
@@ -292,6 +474,47 @@ pub(crate) mod test_utils {
             self.nonce = nonce;
             self
         }
+
+        pub fn with_resource_bounds(mut self, resource_bounds: ResourceBoundsMapping) -> Self {
+            self.resource_bounds = Some(resource_bounds);
+            self
+        }
+    }
+
+    /// A validator that only checks the transactions nonce, against the nonces it has tracked from
+    /// the transactions it has validated.
+    #[derive(Debug, Default)]
+    pub struct NonceValidator {
+        nonces: Mutex<HashMap<ContractAddress, Nonce>>,
+    }
+
+    impl NonceValidator {
+        pub fn set_nonce(&self, address: ContractAddress, nonce: Nonce) {
+            self.nonces.lock().insert(address, nonce);
+        }
+    }
+
+    impl Validator for NonceValidator {
+        type Transaction = PoolTx;
+
+        fn validate(&self, tx: Self::Transaction) -> ValidationResult<Self::Transaction> {
+            let mut nonces = self.nonces.lock();
+
+            let address = tx.sender();
+            let tx_nonce = tx.nonce();
+            let current_nonce = nonces.get(&address).copied().unwrap_or_default();
+
+            if tx_nonce > current_nonce {
+                Ok(ValidationOutcome::Dependent { tx, tx_nonce, current_nonce })
+            } else if tx_nonce < current_nonce {
+                let error =
+                    InvalidTransactionError::InvalidNonce { address, current_nonce, tx_nonce };
+                Ok(ValidationOutcome::Invalid { tx, error })
+            } else {
+                nonces.insert(address, current_nonce + Felt::ONE);
+                Ok(ValidationOutcome::Valid(tx))
+            }
+        }
     }
 
     impl PoolTransaction for PoolTx {
@@ -314,23 +537,30 @@ pub(crate) mod test_utils {
         fn tip(&self) -> u64 {
             self.tip
         }
+
+        fn resource_bounds(&self) -> Option<&ResourceBoundsMapping> {
+            self.resource_bounds.as_ref()
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use futures::StreamExt;
+    use futures::{FutureExt, StreamExt};
     use katana_primitives::contract::{ContractAddress, Nonce};
     use katana_primitives::transaction::TxHash;
     use katana_primitives::Felt;
 
+    use rand::seq::SliceRandom;
+
     use super::test_utils::*;
     use super::Pool;
     use crate::ordering::FiFo;
+    use crate::queued::{QueueConfig, QueueError};
     use crate::tx::PoolTransaction;
     use crate::validation::NoopValidator;
-    use crate::TransactionPool;
+    use crate::{PoolError, TransactionPool};
 
     /// Tx pool that uses a noop validator and a first-come-first-serve ordering.
     type TestPool = Pool<PoolTx, NoopValidator<PoolTx>, FiFo<PoolTx>>;
@@ -462,10 +692,139 @@ mod tests {
         });
     }
 
+    #[test]
+    fn clear() {
+        let pool = Pool::new(NonceValidator::default(), FiFo::new());
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let pending = PoolTx::new().with_sender(sender).with_nonce(Nonce::from(0u8));
+        let queued = PoolTx::new().with_sender(sender).with_nonce(Nonce::from(2u8));
+        pool.add_transaction(pending.clone()).unwrap();
+        pool.add_transaction(queued.clone()).unwrap();
+
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.queued_size(), 1);
+
+        pool.clear();
+
+        assert!(!pool.contains(pending.hash()));
+        assert!(!pool.contains(queued.hash()));
+        assert_eq!(pool.size(), 0);
+        assert_eq!(pool.queued_size(), 0);
+    }
+
+    #[test]
+    fn add_rejected_transaction() {
+        let pool = TestPool::test();
+
+        let tx = PoolTx::new();
+        let _ = pool.add_transaction(tx.clone());
+        assert!(pool.contains(tx.hash()));
+        assert!(pool.rejected_transaction(tx.hash()).is_none());
+
+        pool.add_rejected_transaction(tx.hash(), "execution failed".to_string());
+
+        // the rejected transaction is no longer in the pool but its rejection reason is kept
+        assert!(!pool.contains(tx.hash()));
+        assert_eq!(pool.rejected_transaction(tx.hash()).as_deref(), Some("execution failed"));
+    }
+
     #[tokio::test]
-    #[ignore = "Txs dependency management not fully implemented yet"]
-    async fn dependent_txs_linear_insertion() {
+    async fn replace_tx_with_higher_tip() {
+        let pool = TestPool::test();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(10);
+        pool.add_transaction(tx.clone()).unwrap();
+
+        // a tx with the same sender and nonce but without a higher tip is rejected
+        let underpriced = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(10);
+        let result = pool.add_transaction(underpriced.clone());
+        assert!(matches!(result, Err(PoolError::ReplacementUnderpriced { tip: 10, .. })));
+        assert!(!pool.contains(underpriced.hash()));
+
+        // a tx with a higher tip replaces the existing one
+        let replacement = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(11);
+        pool.add_transaction(replacement.clone()).unwrap();
+
+        assert_eq!(pool.size(), 1);
+        assert!(pool.contains(replacement.hash()));
+        assert!(!pool.contains(tx.hash()));
+        assert!(pool.rejected_transaction(tx.hash()).is_some());
+
+        let mut pendings = pool.pending_transactions();
+        let pending = pendings.next().await.unwrap();
+        assert_eq!(pending.tx.hash(), replacement.hash());
+    }
+
+    #[tokio::test]
+    async fn replaced_tx_is_not_picked() {
         let pool = TestPool::test();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        // the stream is notified of every tx added to the pool, including the replaced one
+        let mut pendings = pool.pending_transactions();
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(10);
+        let replacement = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(11);
+        pool.add_transaction(tx.clone()).unwrap();
+        pool.add_transaction(replacement.clone()).unwrap();
+
+        let pending = pendings.next().await.unwrap();
+        assert_eq!(pending.tx.hash(), replacement.hash());
+        assert!(pendings.next().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn picked_tx_cannot_be_replaced() {
+        let pool = TestPool::test();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(10);
+        pool.add_transaction(tx.clone()).unwrap();
+
+        // the tx is picked up for execution
+        let mut pendings = pool.pending_transactions();
+        let pending = pendings.next().await.unwrap();
+        assert_eq!(pending.tx.hash(), tx.hash());
+
+        let replacement = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(11);
+        let result = pool.add_transaction(replacement.clone());
+        assert!(matches!(result, Err(PoolError::ReplacementTooLate { hash }) if hash == tx.hash()));
+
+        assert!(pool.contains(tx.hash()));
+        assert!(!pool.contains(replacement.hash()));
+        assert!(pool.rejected_transaction(tx.hash()).is_none());
+    }
+
+    #[tokio::test]
+    async fn replacement_keeps_sender_txs_in_nonce_order() {
+        let pool = TestPool::test();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let other = PoolTx::new();
+        let tx0 = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(0);
+        let tx1 = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE);
+        pool.add_transaction(other.clone()).unwrap();
+        pool.add_transaction(tx0.clone()).unwrap();
+        pool.add_transaction(tx1.clone()).unwrap();
+
+        // the replacement comes last, and so does the tx that follows it
+        let replacement = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(1);
+        pool.add_transaction(replacement.clone()).unwrap();
+        let last = PoolTx::new();
+        pool.add_transaction(last.clone()).unwrap();
+
+        let mut pendings = pool.pending_transactions();
+        for expected in [&other, &replacement, &tx1, &last] {
+            let pending = pendings.next().await.unwrap();
+            assert_eq!(pending.tx.hash(), expected.hash());
+        }
+    }
+
+    #[tokio::test]
+    async fn dependent_txs_linear_insertion() {
+        let pool = Pool::new(NonceValidator::default(), FiFo::new());
 
         // Create 100 transactions with the same sender but increasing nonce
         let total = 100u128;
@@ -490,7 +849,72 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn dependent_txs_random_insertion() {
+        let pool = Pool::new(NonceValidator::default(), FiFo::new());
+
+        let total = 100u128;
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+        let mut txs: Vec<PoolTx> = (0..total)
+            .map(|i| PoolTx::new().with_sender(sender).with_nonce(Nonce::from(i)))
+            .collect();
+
+        txs.shuffle(&mut rand::thread_rng());
+
+        // all the txs are accepted, regardless of their arrival order
+        for tx in &txs {
+            assert_eq!(pool.add_transaction(tx.clone()).unwrap(), tx.hash());
+        }
+
+        // once all the nonce gaps are filled, all the txs should be pending
+        assert_eq!(pool.size(), txs.len());
+        assert_eq!(pool.queued_size(), 0);
+
+        // the pending transactions are ordered by nonce
+        let mut pendings = pool.pending_transactions();
+        for i in 0..total {
+            let pending_tx = pendings.next().await.unwrap();
+            assert_eq!(pending_tx.tx.nonce(), Nonce::from(i));
+        }
+    }
+
     #[test]
-    #[ignore = "Txs dependency management not fully implemented yet"]
-    fn dependent_txs_random_insertion() {}
+    fn promote_queued_txs_after_validator_update() {
+        let pool = Pool::new(NonceValidator::default(), FiFo::new());
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::from(2u8));
+        pool.add_transaction(tx.clone()).unwrap();
+
+        // the tx is queued because of the nonce gap
+        assert!(pool.contains(tx.hash()));
+        assert_eq!(pool.size(), 0);
+        assert_eq!(pool.queued_size(), 1);
+
+        // the gap is filled by txs that didn't go through the pool (eg, mined in a block)
+        pool.validator().set_nonce(sender, Nonce::from(2u8));
+        pool.promote_queued_transactions();
+
+        assert!(pool.contains(tx.hash()));
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.queued_size(), 0);
+    }
+
+    #[test]
+    fn queued_txs_limit_per_account() {
+        let queue = QueueConfig { max_txs_per_account: 2, ..Default::default() };
+        let pool = Pool::with_queue_config(NonceValidator::default(), FiFo::new(), queue);
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let txs: Vec<PoolTx> = (1..=3u8)
+            .map(|i| PoolTx::new().with_sender(sender).with_nonce(Nonce::from(i)))
+            .collect();
+
+        pool.add_transaction(txs[0].clone()).unwrap();
+        pool.add_transaction(txs[1].clone()).unwrap();
+
+        let result = pool.add_transaction(txs[2].clone());
+        assert!(matches!(result, Err(PoolError::Queue(QueueError::AccountLimitReached(_)))));
+        assert_eq!(pool.queued_size(), 2);
+    }
 }
@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use katana_primitives::contract::{ContractAddress, Nonce};
+use katana_primitives::Felt;
+
+use crate::tx::TxId;
+
+/// The default maximum number of queued transactions per account.
+pub const DEFAULT_MAX_QUEUED_TXS_PER_ACCOUNT: usize = 64;
+
+/// The default maximum number of queued transactions in total.
+pub const DEFAULT_MAX_QUEUED_TXS: usize = 4096;
+
+/// Limits of the queued sub-pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// The maximum number of queued transactions a single account can have.
+    pub max_txs_per_account: usize,
+    /// The maximum number of queued transactions across all accounts.
+    pub max_txs: usize,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_txs_per_account: DEFAULT_MAX_QUEUED_TXS_PER_ACCOUNT,
+            max_txs: DEFAULT_MAX_QUEUED_TXS,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum QueueError {
+    #[error("Too many queued transactions for account {0}")]
+    AccountLimitReached(ContractAddress),
+    #[error("Too many queued transactions in the pool")]
+    PoolLimitReached,
+}
+
+/// Sub-pool of transactions that can't be executed yet because their nonce is higher than the
+/// next expected nonce of their sender (ie there's a nonce gap).
+///
+/// Transactions are keyed by their [`TxId`], so the queued transactions of the same sender are
+/// ordered by nonce.
+#[derive(Debug)]
+pub struct QueuedTransactions<T> {
+    config: QueueConfig,
+    /// The queued transactions, ordered by sender and then by nonce.
+    txs: BTreeMap<TxId, Arc<T>>,
+    /// The number of queued transactions of each sender.
+    counts: HashMap<ContractAddress, usize>,
+}
+
+impl<T> QueuedTransactions<T> {
+    pub fn new(config: QueueConfig) -> Self {
+        Self { config, txs: BTreeMap::new(), counts: HashMap::new() }
+    }
+
+    /// Inserts a transaction into the queue.
+    ///
+    /// If there's already a queued transaction with the same id, it is replaced. When a limit is
+    /// reached, the queued transaction with the highest nonce of the account with the most queued
+    /// transactions is evicted to make room, unless the new transaction would be that one itself,
+    /// in which case it is not inserted and an error is returned.
+    ///
+    /// Returns the transactions that were removed from the queue as a result of the insertion.
+    pub fn insert(&mut self, id: TxId, tx: T) -> Result<Vec<Arc<T>>, QueueError> {
+        let tx = Arc::new(tx);
+
+        if let Some(existing) = self.txs.get_mut(&id) {
+            let replaced = std::mem::replace(existing, tx);
+            return Ok(vec![replaced]);
+        }
+
+        let mut evicted = Vec::new();
+
+        if self.count(id.sender()) >= self.config.max_txs_per_account {
+            match self.last_of(id.sender()) {
+                Some((last, _)) if last.nonce() > id.nonce() => evicted.extend(self.remove(&last)),
+                _ => return Err(QueueError::AccountLimitReached(id.sender())),
+            }
+        }
+
+        if self.txs.len() >= self.config.max_txs {
+            let Some(candidate) = self.eviction_candidate() else {
+                return Err(QueueError::PoolLimitReached);
+            };
+
+            // check whether the new tx would itself be the one to evict
+            let is_last = if candidate.sender() == id.sender() {
+                candidate.nonce() < id.nonce()
+            } else {
+                self.count(id.sender()) + 1 > self.count(candidate.sender())
+            };
+
+            if is_last {
+                return Err(QueueError::PoolLimitReached);
+            }
+
+            evicted.extend(self.remove(&candidate));
+        }
+
+        self.txs.insert(id.clone(), tx);
+        *self.counts.entry(id.sender()).or_default() += 1;
+
+        Ok(evicted)
+    }
+
+    /// Returns the queued transaction with the given id.
+    pub fn get(&self, id: &TxId) -> Option<&Arc<T>> {
+        self.txs.get(id)
+    }
+
+    /// Removes a transaction from the queue.
+    pub fn remove(&mut self, id: &TxId) -> Option<Arc<T>> {
+        let tx = self.txs.remove(id)?;
+
+        if let Some(count) = self.counts.get_mut(&id.sender()) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&id.sender());
+            }
+        }
+
+        Some(tx)
+    }
+
+    /// Removes and returns the queued transaction of `sender` with the lowest nonce.
+    pub fn pop_first_of(&mut self, sender: ContractAddress) -> Option<(TxId, Arc<T>)> {
+        let id = self.sender_txs(sender).next().map(|(id, _)| id.clone())?;
+        let tx = self.remove(&id)?;
+        Some((id, tx))
+    }
+
+    /// Removes all the transactions that satisfy the predicate, and returns them.
+    pub fn remove_where(&mut self, mut f: impl FnMut(&T) -> bool) -> Vec<Arc<T>> {
+        let ids =
+            self.txs.iter().filter(|(_, tx)| f(tx)).map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        ids.iter().filter_map(|id| self.remove(id)).collect()
+    }
+
+    /// Returns the first queued transaction that satisfies the predicate.
+    pub fn find(&self, mut f: impl FnMut(&T) -> bool) -> Option<Arc<T>> {
+        self.txs.values().find(|tx| f(tx)).cloned()
+    }
+
+    /// Returns the senders that have queued transactions.
+    pub fn senders(&self) -> Vec<ContractAddress> {
+        self.counts.keys().copied().collect()
+    }
+
+    /// Returns the number of queued transactions of `sender`.
+    pub fn count(&self, sender: ContractAddress) -> usize {
+        self.counts.get(&sender).copied().unwrap_or_default()
+    }
+
+    /// Returns the total number of queued transactions.
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    /// Returns `true` if there are no queued transactions.
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    fn sender_txs(&self, sender: ContractAddress) -> impl Iterator<Item = (&TxId, &Arc<T>)> {
+        let start = TxId::new(sender, Nonce::ZERO);
+        let end = TxId::new(sender, Felt::MAX);
+        self.txs.range(start..=end)
+    }
+
+    fn last_of(&self, sender: ContractAddress) -> Option<(TxId, &Arc<T>)> {
+        self.sender_txs(sender).next_back().map(|(id, tx)| (id.clone(), tx))
+    }
+
+    /// The transaction with the highest nonce of the account with the most queued transactions.
+    fn eviction_candidate(&self) -> Option<TxId> {
+        let (sender, _) = self.counts.iter().max_by_key(|(_, count)| **count)?;
+        self.last_of(*sender).map(|(id, _)| id)
+    }
+}
+
+impl<T> Default for QueuedTransactions<T> {
+    fn default() -> Self {
+        Self::new(QueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::contract::{ContractAddress, Nonce};
+    use katana_primitives::Felt;
+
+    use super::{QueueConfig, QueueError, QueuedTransactions};
+    use crate::tx::TxId;
+
+    fn id(sender: u64, nonce: u64) -> TxId {
+        TxId::new(ContractAddress::from(Felt::from(sender)), Nonce::from(nonce))
+    }
+
+    #[test]
+    fn pop_in_nonce_order() {
+        let mut queue = QueuedTransactions::default();
+
+        queue.insert(id(1, 5), 5).unwrap();
+        queue.insert(id(1, 3), 3).unwrap();
+        queue.insert(id(2, 1), 1).unwrap();
+        queue.insert(id(1, 4), 4).unwrap();
+
+        let sender = id(1, 0).sender();
+        assert_eq!(queue.count(sender), 3);
+        assert_eq!(*queue.pop_first_of(sender).unwrap().1, 3);
+        assert_eq!(*queue.pop_first_of(sender).unwrap().1, 4);
+        assert_eq!(*queue.pop_first_of(sender).unwrap().1, 5);
+        assert!(queue.pop_first_of(sender).is_none());
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn account_limit() {
+        let config = QueueConfig { max_txs_per_account: 2, max_txs: 10 };
+        let mut queue = QueuedTransactions::new(config);
+
+        queue.insert(id(1, 2), 2).unwrap();
+        queue.insert(id(1, 4), 4).unwrap();
+
+        // a tx with a higher nonce than all the queued ones is rejected
+        let err = queue.insert(id(1, 5), 5).unwrap_err();
+        assert_eq!(err, QueueError::AccountLimitReached(id(1, 0).sender()));
+
+        // a tx with a lower nonce evicts the one with the highest nonce
+        let evicted = queue.insert(id(1, 3), 3).unwrap();
+        assert_eq!(evicted.iter().map(|tx| **tx).collect::<Vec<_>>(), vec![4]);
+        assert_eq!(queue.count(id(1, 0).sender()), 2);
+    }
+
+    #[test]
+    fn pool_limit() {
+        let config = QueueConfig { max_txs_per_account: 10, max_txs: 3 };
+        let mut queue = QueuedTransactions::new(config);
+
+        queue.insert(id(1, 2), 2).unwrap();
+        queue.insert(id(1, 3), 3).unwrap();
+        queue.insert(id(2, 2), 22).unwrap();
+
+        // evicts the highest nonce tx of the account with the most queued txs
+        let evicted = queue.insert(id(3, 2), 32).unwrap();
+        assert_eq!(evicted.iter().map(|tx| **tx).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(queue.len(), 3);
+
+        // the tx would make its account the one with the most queued txs, so it'd evict itself
+        let err = queue.insert(id(2, 3), 23).unwrap_err();
+        assert_eq!(err, QueueError::PoolLimitReached);
+    }
+}
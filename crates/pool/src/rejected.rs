@@ -0,0 +1,110 @@
+use std::collections::{HashMap, VecDeque};
+
+use katana_primitives::transaction::TxHash;
+
+/// The default number of rejected transactions kept by the pool.
+pub const DEFAULT_REJECTED_TXS_CAPACITY: usize = 1024;
+
+/// A bounded store of transactions that were rejected, along with the reason of their rejection.
+///
+/// Once the store is full, the oldest entries are evicted first to make room for new ones.
+#[derive(Debug)]
+pub struct RejectedTransactions {
+    /// The maximum number of entries in the store.
+    capacity: usize,
+    /// The hashes of the rejected transactions, ordered from the oldest to the newest.
+    hashes: VecDeque<TxHash>,
+    /// The rejection reasons, keyed by the transaction hash.
+    reasons: HashMap<TxHash, String>,
+}
+
+impl RejectedTransactions {
+    /// Creates a new store that can hold up to `capacity` rejected transactions.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, hashes: VecDeque::new(), reasons: HashMap::new() }
+    }
+
+    /// Records a rejected transaction. If the transaction is already in the store, only its
+    /// rejection reason is updated.
+    pub fn insert(&mut self, hash: TxHash, reason: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(existing) = self.reasons.get_mut(&hash) {
+            *existing = reason;
+            return;
+        }
+
+        if self.hashes.len() == self.capacity {
+            if let Some(oldest) = self.hashes.pop_front() {
+                self.reasons.remove(&oldest);
+            }
+        }
+
+        self.hashes.push_back(hash);
+        self.reasons.insert(hash, reason);
+    }
+
+    /// Returns the rejection reason of a transaction, if it's in the store.
+    pub fn get(&self, hash: &TxHash) -> Option<&str> {
+        self.reasons.get(hash).map(String::as_str)
+    }
+
+    /// Returns the number of rejected transactions in the store.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+impl Default for RejectedTransactions {
+    fn default() -> Self {
+        Self::new(DEFAULT_REJECTED_TXS_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::felt;
+
+    use super::RejectedTransactions;
+
+    #[test]
+    fn evicts_oldest_entries_when_full() {
+        let mut store = RejectedTransactions::new(2);
+
+        store.insert(felt!("0x1"), "first".to_string());
+        store.insert(felt!("0x2"), "second".to_string());
+        store.insert(felt!("0x3"), "third".to_string());
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(&felt!("0x1")), None);
+        assert_eq!(store.get(&felt!("0x2")), Some("second"));
+        assert_eq!(store.get(&felt!("0x3")), Some("third"));
+    }
+
+    #[test]
+    fn reinserting_updates_the_reason() {
+        let mut store = RejectedTransactions::new(2);
+
+        store.insert(felt!("0x1"), "first".to_string());
+        store.insert(felt!("0x2"), "second".to_string());
+        store.insert(felt!("0x1"), "updated".to_string());
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(&felt!("0x1")), Some("updated"));
+        assert_eq!(store.get(&felt!("0x2")), Some("second"));
+    }
+
+    #[test]
+    fn zero_capacity_store_keeps_nothing() {
+        let mut store = RejectedTransactions::new(0);
+        store.insert(felt!("0x1"), "first".to_string());
+        assert!(store.is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 use std::cmp::Ordering;
+use std::sync::atomic::{AtomicU8, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::Instant;
 
 use katana_primitives::contract::{ContractAddress, Nonce};
+use katana_primitives::fee::ResourceBoundsMapping;
 use katana_primitives::transaction::{
     DeclareTx, DeployAccountTx, ExecutableTx, ExecutableTxWithHash, InvokeTx, TxHash,
 };
@@ -26,6 +28,9 @@ pub trait PoolTransaction: Clone {
 
     /// return the tx tip.
     fn tip(&self) -> u64;
+
+    /// return the tx resource bounds. only applicable to V3 transactions.
+    fn resource_bounds(&self) -> Option<&ResourceBoundsMapping>;
 }
 
 /// the tx id in the pool. identified by its sender and nonce.
@@ -40,6 +45,14 @@ impl TxId {
         Self { sender, nonce }
     }
 
+    pub fn sender(&self) -> ContractAddress {
+        self.sender
+    }
+
+    pub fn nonce(&self) -> Nonce {
+        self.nonce
+    }
+
     pub fn parent(&self) -> Option<Self> {
         if self.nonce == Nonce::ZERO {
             None
@@ -59,11 +72,52 @@ pub struct PendingTx<T, O: PoolOrd> {
     pub tx: Arc<T>,
     pub priority: O::PriorityValue,
     pub added_at: std::time::Instant,
+    pub(crate) state: Arc<PendingTxState>,
 }
 
 impl<T, O: PoolOrd> PendingTx<T, O> {
     pub fn new(id: TxId, tx: T, priority: O::PriorityValue) -> Self {
-        Self { id, tx: Arc::new(tx), priority, added_at: Instant::now() }
+        let state = Arc::new(PendingTxState::default());
+        Self { id, tx: Arc::new(tx), priority, added_at: Instant::now(), state }
+    }
+}
+
+/// Tracks whether a pending transaction has been picked up for execution or replaced by another
+/// transaction. The state is shared by all the clones of a [`PendingTx`], and a transaction can
+/// only end up in one of the two states.
+#[derive(Debug, Default)]
+pub(crate) struct PendingTxState(AtomicU8);
+
+impl PendingTxState {
+    const PENDING: u8 = 0;
+    const PICKED: u8 = 1;
+    const REPLACED: u8 = 2;
+
+    /// Marks the transaction as picked up for execution. Returns `false` if the transaction has
+    /// been replaced and thus must not be executed.
+    pub(crate) fn pick(&self) -> bool {
+        match self.0.compare_exchange(
+            Self::PENDING,
+            Self::PICKED,
+            AtomicOrdering::SeqCst,
+            AtomicOrdering::SeqCst,
+        ) {
+            Ok(_) => true,
+            Err(state) => state == Self::PICKED,
+        }
+    }
+
+    /// Marks the transaction as replaced. Returns `false` if the transaction has already been
+    /// picked up for execution, in which case it can no longer be replaced.
+    pub(crate) fn replace(&self) -> bool {
+        self.0
+            .compare_exchange(
+                Self::PENDING,
+                Self::REPLACED,
+                AtomicOrdering::SeqCst,
+                AtomicOrdering::SeqCst,
+            )
+            .is_ok()
     }
 }
 
@@ -77,6 +131,7 @@ impl<T, O: PoolOrd> Clone for PendingTx<T, O> {
             added_at: self.added_at,
             tx: Arc::clone(&self.tx),
             priority: self.priority.clone(),
+            state: Arc::clone(&self.state),
         }
     }
 }
@@ -200,6 +255,18 @@ impl PoolTransaction for ExecutableTxWithHash {
             },
         }
     }
+
+    fn resource_bounds(&self) -> Option<&ResourceBoundsMapping> {
+        match &self.transaction {
+            ExecutableTx::Invoke(InvokeTx::V3(v3)) => Some(&v3.resource_bounds),
+            ExecutableTx::Declare(tx) => match &tx.transaction {
+                DeclareTx::V3(v3) => Some(&v3.resource_bounds),
+                _ => None,
+            },
+            ExecutableTx::DeployAccount(DeployAccountTx::V3(v3)) => Some(&v3.resource_bounds),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -70,6 +70,28 @@ impl TxValidator {
         this.state = Arc::new(new_state);
     }
 
+    /// Returns the block env that the transactions are validated against.
+    pub fn block_env(&self) -> BlockEnv {
+        self.inner.lock().block_env.clone()
+    }
+
+    /// Replaces the block env that the transactions are validated against, keeping the current
+    /// state. This method is used when the block env changes without a new block being mined, eg
+    /// when the gas prices are updated.
+    pub fn update_block_env(&self, block_env: BlockEnv) {
+        self.inner.lock().block_env = block_env;
+    }
+
+    /// Same as [`TxValidator::update`] but also discards the nonces tracked from the validated
+    /// transactions. This method is used when the chain is reverted to a previous block, as the
+    /// tracked nonces may no longer be valid.
+    pub fn reset(&self, new_state: Box<dyn StateProvider>, block_env: BlockEnv) {
+        let mut this = self.inner.lock();
+        this.block_env = block_env;
+        this.state = Arc::new(new_state);
+        this.pool_nonces.clear();
+    }
+
     // NOTE:
     // If you check the get_nonce method of StatefulValidator in blockifier, under the hood it
     // unwraps the Option to get the state of the TransactionExecutor struct. StatefulValidator
@@ -163,15 +185,18 @@ impl Validator for TxValidator {
         let result = validate(
             this.prepare(),
             tx,
-            !this.execution_flags.account_validation() || skip_validate,
+            !this.execution_flags.account_validation_for(address) || skip_validate,
             !this.execution_flags.fee(),
         );
 
         match result {
             res @ Ok(ValidationOutcome::Valid { .. }) => {
-                // update the nonce of the account in the pool only for valid tx
-                let updated_nonce = current_nonce + Felt::ONE;
-                this.pool_nonces.insert(address, updated_nonce);
+                // update the nonce of the account in the pool only for valid tx. a tx with a lower
+                // nonce (ie a replacement of a tx that is already in the pool) doesn't advance it.
+                if tx_nonce == current_nonce {
+                    let updated_nonce = current_nonce + Felt::ONE;
+                    this.pool_nonces.insert(address, updated_nonce);
+                }
                 res
             }
             _ => result,
@@ -39,3 +39,15 @@ pub fn transform(data: Vec<BigUint>) -> Vec<BigUint> {
 
     fft(data, xs, &BLS_MODULUS)
 }
+
+/// Splits the data into blobs of [`BLOB_LEN`] elements, padding the last one with zeros, and
+/// [`transform`]s each of them.
+pub fn to_blobs(data: &[BigUint]) -> Vec<Vec<BigUint>> {
+    data.chunks(BLOB_LEN)
+        .map(|chunk| {
+            let mut coefficients = chunk.to_vec();
+            coefficients.resize(BLOB_LEN, BigUint::default());
+            transform(coefficients)
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+//! EIP-4844 blobs of a state diff and their KZG commitments.
+//!
+//! The encoded state diff (see [`encode_state_updates`]) is treated as the coefficients of a
+//! polynomial, and each blob holds the evaluations of that polynomial, as done by Starknet when
+//! publishing its state diffs onto Ethereum. A state diff that doesn't fit in a single blob is
+//! split across multiple blobs.
+
+use alloy_primitives::{Bytes, FixedBytes, B256};
+use c_kzg::{KzgCommitment, KzgProof};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+use super::blob::to_blobs;
+use super::encoding::encode_state_updates;
+use crate::state::StateUpdates;
+
+/// The size of a blob in bytes.
+pub const BYTES_PER_BLOB: usize = c_kzg::BYTES_PER_BLOB;
+
+/// The version byte of the versioned hash of a KZG commitment.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// The size of a field element in a blob, in bytes.
+const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KzgError {
+    #[error("field element at index {0} doesn't fit in 32 bytes")]
+    FieldElementTooLarge(usize),
+    #[error("kzg error: {0}")]
+    Kzg(String),
+}
+
+impl From<c_kzg::Error> for KzgError {
+    fn from(error: c_kzg::Error) -> Self {
+        Self::Kzg(format!("{error:?}"))
+    }
+}
+
+/// A blob along with its KZG commitment and proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct BlobSidecar {
+    /// The content of the blob.
+    pub blob: Bytes,
+    /// The KZG commitment of the blob.
+    pub commitment: FixedBytes<48>,
+    /// The KZG proof that the blob matches its commitment.
+    pub proof: FixedBytes<48>,
+    /// The versioned hash of the commitment, as referenced by the blob transaction.
+    pub versioned_hash: B256,
+}
+
+/// Encodes the state diff into blobs and computes their KZG commitments.
+pub fn state_updates_to_blobs(state_updates: StateUpdates) -> Result<Vec<BlobSidecar>, KzgError> {
+    let encoded = encode_state_updates(state_updates);
+    to_blobs(&encoded).iter().map(|blob| blob_sidecar(blob)).collect()
+}
+
+/// Computes the KZG commitment and proof of the blob made of the given field elements.
+pub fn blob_sidecar(elements: &[BigUint]) -> Result<BlobSidecar, KzgError> {
+    let mut bytes = vec![0u8; BYTES_PER_BLOB];
+
+    for (i, element) in elements.iter().enumerate() {
+        let element = element.to_bytes_be();
+        if element.len() > BYTES_PER_FIELD_ELEMENT {
+            return Err(KzgError::FieldElementTooLarge(i));
+        }
+
+        // field elements are stored as 32-byte big-endian integers
+        let end = (i + 1) * BYTES_PER_FIELD_ELEMENT;
+        bytes[end - element.len()..end].copy_from_slice(&element);
+    }
+
+    let settings = c_kzg::ethereum_kzg_settings();
+    let blob = c_kzg::Blob::from_bytes(&bytes)?;
+    let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, settings)?.to_bytes();
+    let proof = KzgProof::compute_blob_kzg_proof(&blob, &commitment, settings)?.to_bytes();
+
+    Ok(BlobSidecar {
+        blob: Bytes::from(bytes),
+        commitment: FixedBytes::from(*commitment),
+        proof: FixedBytes::from(*proof),
+        versioned_hash: kzg_to_versioned_hash(commitment.as_slice()),
+    })
+}
+
+/// Computes the versioned hash of a KZG commitment, as defined in EIP-4844.
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    B256::from(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::address;
+    use crate::da::blob::recover;
+    use crate::da::encoding::decode_state_updates;
+    use crate::Felt;
+
+    #[test]
+    fn state_diff_blob_roundtrip() {
+        let mut state_updates = StateUpdates::default();
+        let storage = BTreeMap::from([(Felt::from(100u8), Felt::from(200u8))]);
+        state_updates.storage_updates.insert(address!("0x1337"), storage);
+        state_updates.nonce_updates.insert(address!("0x1337"), Felt::ONE);
+
+        let blobs = state_updates_to_blobs(state_updates.clone()).unwrap();
+        assert_eq!(blobs.len(), 1);
+
+        let sidecar = &blobs[0];
+        assert_eq!(sidecar.blob.len(), BYTES_PER_BLOB);
+        assert_eq!(sidecar.versioned_hash[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(sidecar.versioned_hash, kzg_to_versioned_hash(sidecar.commitment.as_slice()));
+
+        // the state diff can be recovered from the blob content
+        let evaluations = sidecar
+            .blob
+            .chunks(BYTES_PER_FIELD_ELEMENT)
+            .map(BigUint::from_bytes_be)
+            .collect::<Vec<_>>();
+
+        let decoded = decode_state_updates(&recover(evaluations)).unwrap();
+        assert_eq!(decoded, state_updates);
+    }
+}
@@ -1,16 +1,19 @@
 pub mod blob;
 pub mod eip4844;
 pub mod encoding;
+pub mod kzg;
 pub mod math;
+pub mod payload;
 pub mod serde;
 
 /// L1 da mode.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum L1DataAvailabilityMode {
     #[serde(rename = "BLOB")]
     Blob,
+    #[default]
     #[serde(rename = "CALLDATA")]
     Calldata,
 }
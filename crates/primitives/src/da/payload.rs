@@ -0,0 +1,105 @@
+//! Data availability payload of a state diff.
+//!
+//! The payload contains everything needed to publish a state diff onto a data availability layer:
+//! the state diff encoded as field elements (see [`encode_state_updates`]), the encoded data
+//! split into blobs of field elements (see [`to_blobs`]), and the versioned hash of each blob's
+//! KZG commitment.
+
+use alloy_primitives::{B256, U256};
+use num_bigint::BigUint;
+
+use super::blob::{recover, to_blobs};
+use super::encoding::{decode_state_updates, encode_state_updates, EncodingError};
+use super::kzg::{blob_sidecar, KzgError};
+use crate::state::StateUpdates;
+use crate::Felt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PayloadError {
+    #[error(transparent)]
+    Kzg(#[from] KzgError),
+    #[error(transparent)]
+    Encoding(#[from] EncodingError),
+    #[error("state diff recovered from the blobs doesn't match the encoded state diff")]
+    RoundTripMismatch,
+}
+
+/// The data availability payload of a state diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct DaPayload {
+    /// The state diff encoded as field elements.
+    pub encoded: Vec<Felt>,
+    /// The BLS12-381 field elements of each blob.
+    pub blobs: Vec<Vec<U256>>,
+    /// The versioned hash of the KZG commitment of each blob.
+    pub versioned_hashes: Vec<B256>,
+}
+
+/// Computes the data availability payload of the state diff.
+///
+/// The payload is checked by recovering the encoded state diff from the blobs and decoding it
+/// back, which must yield the same encoding.
+pub fn state_updates_to_payload(state_updates: StateUpdates) -> Result<DaPayload, PayloadError> {
+    let encoded = encode_state_updates(state_updates);
+    let blobs = to_blobs(&encoded);
+
+    // the padding of the last blob is discarded when recovering the encoded state diff
+    let mut recovered = blobs.iter().flat_map(|blob| recover(blob.clone())).collect::<Vec<_>>();
+    recovered.truncate(encoded.len());
+
+    let decoded = decode_state_updates(&recovered)?;
+    if recovered != encoded || encode_state_updates(decoded) != encoded {
+        return Err(PayloadError::RoundTripMismatch);
+    }
+
+    let versioned_hashes = blobs
+        .iter()
+        .map(|blob| blob_sidecar(blob).map(|sidecar| sidecar.versioned_hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let encoded = encoded.iter().map(Felt::from).collect();
+    let blobs = blobs.iter().map(|blob| blob.iter().map(to_u256).collect()).collect();
+
+    Ok(DaPayload { encoded, blobs, versioned_hashes })
+}
+
+fn to_u256(value: &BigUint) -> U256 {
+    // blob elements are smaller than the BLS12-381 modulus, so they always fit in 256 bits
+    U256::from_be_slice(&value.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::address;
+    use crate::da::eip4844::BLOB_LEN;
+
+    #[test]
+    fn state_diff_payload() {
+        let mut state_updates = StateUpdates::default();
+        let storage = BTreeMap::from([(Felt::from(100u8), Felt::from(200u8))]);
+        state_updates.storage_updates.insert(address!("0x1337"), storage);
+        state_updates.nonce_updates.insert(address!("0x1337"), Felt::ONE);
+        state_updates.declared_classes.insert(Felt::from(1u8), Felt::from(2u8));
+
+        let payload = state_updates_to_payload(state_updates.clone()).unwrap();
+
+        let expected = encode_state_updates(state_updates);
+        assert_eq!(payload.encoded, expected.iter().map(Felt::from).collect::<Vec<_>>());
+        assert_eq!(payload.blobs.len(), 1);
+        assert_eq!(payload.blobs[0].len(), BLOB_LEN);
+        assert_eq!(payload.versioned_hashes.len(), 1);
+    }
+
+    #[test]
+    fn empty_state_diff_payload() {
+        // only the number of contract updates and class declarations are encoded
+        let payload = state_updates_to_payload(StateUpdates::default()).unwrap();
+        assert_eq!(payload.encoded, vec![Felt::ZERO, Felt::ZERO]);
+        assert_eq!(payload.blobs.len(), 1);
+        assert_eq!(payload.versioned_hashes.len(), 1);
+    }
+}
@@ -17,58 +17,58 @@ pub use starknet_api::executable_transaction::TransactionType;
 pub use starknet_api::execution_resources::{GasAmount, GasVector};
 pub use starknet_api::transaction::fields::{Fee, Resource};
 
+use crate::state::StateUpdates;
 use crate::transaction::TxType;
 
+/// The execution info of a transaction, along with its type and the state changes it made.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum TypedTransactionExecutionInfo {
-    Invoke(TransactionExecutionInfo),
-    Declare(TransactionExecutionInfo),
-    L1Handler(TransactionExecutionInfo),
-    DeployAccount(TransactionExecutionInfo),
+pub struct TypedTransactionExecutionInfo {
+    r#type: TxType,
+    info: TransactionExecutionInfo,
+    /// The state changes made by the transaction. [None] if they weren't recorded.
+    state_diff: Option<StateUpdates>,
 }
 
 impl TypedTransactionExecutionInfo {
     /// Constructs a new [`TypedTransactionExecutionInfo`].
+    ///
+    /// Legacy [`TxType::Deploy`] transactions are accepted as well, as they may be found in synced
+    /// blocks.
     pub fn new(r#type: TxType, execution_info: TransactionExecutionInfo) -> Self {
-        match r#type {
-            TxType::Declare => Self::Declare(execution_info),
-            TxType::Invoke => Self::Invoke(execution_info),
-            TxType::DeployAccount => Self::DeployAccount(execution_info),
-            TxType::L1Handler => Self::L1Handler(execution_info),
-            TxType::Deploy => unimplemented!("deploy tx is unsupported"),
-        }
+        Self { r#type, info: execution_info, state_diff: None }
+    }
+
+    /// Sets the state changes made by the transaction.
+    pub fn with_state_diff(mut self, state_diff: StateUpdates) -> Self {
+        self.state_diff = Some(state_diff);
+        self
     }
 
     /// Returns the [`TransactionExecutionInfo`]
     pub fn info(&self) -> &TransactionExecutionInfo {
-        match self {
-            Self::Invoke(info) => info,
-            Self::Declare(info) => info,
-            Self::L1Handler(info) => info,
-            Self::DeployAccount(info) => info,
-        }
+        &self.info
     }
 
     /// Returns the transaction tyoe of the execution info.
     pub fn r#type(&self) -> TxType {
-        match self {
-            Self::Invoke(_) => TxType::Invoke,
-            Self::Declare(_) => TxType::Declare,
-            Self::L1Handler(_) => TxType::L1Handler,
-            Self::DeployAccount(_) => TxType::DeployAccount,
-        }
+        self.r#type
+    }
+
+    /// Returns the state changes made by the transaction, if they were recorded.
+    pub fn state_diff(&self) -> Option<&StateUpdates> {
+        self.state_diff.as_ref()
+    }
+
+    /// Consumes the execution info and returns its parts.
+    pub fn into_parts(self) -> (TxType, TransactionExecutionInfo, Option<StateUpdates>) {
+        (self.r#type, self.info, self.state_diff)
     }
 }
 
 impl From<TypedTransactionExecutionInfo> for TransactionExecutionInfo {
     fn from(value: TypedTransactionExecutionInfo) -> Self {
-        match value {
-            TypedTransactionExecutionInfo::Invoke(info) => info,
-            TypedTransactionExecutionInfo::Declare(info) => info,
-            TypedTransactionExecutionInfo::L1Handler(info) => info,
-            TypedTransactionExecutionInfo::DeployAccount(info) => info,
-        }
+        value.info
     }
 }
 
@@ -80,7 +80,7 @@ impl From<(TxType, TransactionExecutionInfo)> for TypedTransactionExecutionInfo
 
 impl Default for TypedTransactionExecutionInfo {
     fn default() -> Self {
-        Self::Invoke(TransactionExecutionInfo::default())
+        Self::new(TxType::Invoke, TransactionExecutionInfo::default())
     }
 }
 
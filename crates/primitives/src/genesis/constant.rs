@@ -1,3 +1,5 @@
+use std::num::NonZeroU128;
+
 use lazy_static::lazy_static;
 use starknet::core::utils::get_storage_var_address;
 use starknet::macros::felt;
@@ -28,6 +30,19 @@ pub const DEFAULT_STRK_FEE_TOKEN_ADDRESS: ContractAddress =
 pub const DEFAULT_ACCOUNT_CLASS_PUBKEY_STORAGE_SLOT: StorageKey =
     felt!("0x1379ac0624b939ceb9dede92211d7db5ee174fe28be72245b0a1a2abd81c98f");
 
+/// The class hash of the OpenZeppelin account contract (v0.8.1).
+pub const OZ_ACCOUNT_CLASS_HASH: ClassHash =
+    felt!("0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f");
+
+/// The class hash of the Argent account contract (v0.3.0).
+pub const ARGENT_ACCOUNT_CLASS_HASH: ClassHash =
+    felt!("0x01a736d6ed154502257f02b1ccdf4d9d1089f80811cd6acad48e6b6a9d1f2003");
+
+/// The storage address for the owner's public key in the Argent account class.
+/// Corresponds to keccak("_signer")
+pub const ARGENT_ACCOUNT_CLASS_SIGNER_STORAGE_SLOT: StorageKey =
+    felt!("0x1ccc09c8a19948e048de7add6929589945e25f22059c7345aaf7837188d8d05");
+
 /// The standard storage address for `ERC20_name` in ERC20 contract.
 /// Corresponds to keccak("ERC20_name")
 pub const ERC20_NAME_STORAGE_SLOT: StorageKey =
@@ -51,6 +66,12 @@ pub const ERC20_TOTAL_SUPPLY_STORAGE_SLOT: StorageKey =
 /// The default fee token balance for dev accounts at genesis.
 pub const DEFAULT_PREFUNDED_ACCOUNT_BALANCE: u128 = 10 * u128::pow(10, 21);
 
+/// The default L2 gas price in wei, used for the genesis block.
+pub const DEFAULT_ETH_L2_GAS_PRICE: NonZeroU128 = NonZeroU128::new(5 * u128::pow(10, 5)).unwrap();
+
+/// The default L2 gas price in fri, used for the genesis block.
+pub const DEFAULT_STRK_L2_GAS_PRICE: NonZeroU128 = NonZeroU128::new(5 * u128::pow(10, 5)).unwrap();
+
 /// The class hash of DEFAULT_LEGACY_ERC20_CONTRACT_CASM.
 pub const DEFAULT_LEGACY_ERC20_CLASS_HASH: ClassHash =
     felt!("0xa2475bc66197c751d854ea8c39c6ad9781eb284103bcd856b58e6b500078ac");
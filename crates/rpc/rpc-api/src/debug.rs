@@ -0,0 +1,28 @@
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::BlockIdOrTag;
+use katana_primitives::transaction::TxHash;
+use katana_rpc_types::debug::{CallTrace, TxTrace};
+use katana_rpc_types::FunctionCall;
+
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "debug"))]
+pub trait DebugApi {
+    /// Executes a function call like `starknet_call` and returns its full call tree, including
+    /// the events, messages, storage accesses and resources of every call.
+    ///
+    /// Storage writes aren't available per call, they're returned in aggregate as the state diff
+    /// of the whole call.
+    ///
+    /// A call that fails during its execution is still traced, with its revert reason.
+    #[method(name = "traceCall")]
+    async fn trace_call(
+        &self,
+        request: FunctionCall,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<CallTrace>;
+
+    /// Returns the full call tree of a transaction, in the same format as `debug_traceCall`.
+    #[method(name = "traceTransaction")]
+    async fn trace_transaction(&self, transaction_hash: TxHash) -> RpcResult<TxTrace>;
+}
@@ -1,7 +1,13 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
-use katana_primitives::Felt;
-use katana_rpc_types::account::Account;
+use katana_primitives::block::GasPrice;
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::Nonce;
+use katana_primitives::fee::PriceUnit;
+use katana_primitives::{ContractAddress, Felt, U256};
+use katana_rpc_types::account::{Account, FeeToken};
+use katana_rpc_types::gas::GasPrices;
+use katana_rpc_types::mining::MiningMode;
 
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
 #[cfg_attr(feature = "client", rpc(client, server, namespace = "dev"))]
@@ -9,8 +15,17 @@ pub trait DevApi {
     #[method(name = "generateBlock")]
     async fn generate_block(&self) -> RpcResult<()>;
 
+    /// Mines `blocks` blocks at once, 1 if not specified. Pending transactions are included in the
+    /// first block and the remaining blocks are empty.
+    ///
+    /// If `interval_seconds` is given, the timestamp of every block after the first one is
+    /// `interval_seconds` after the timestamp of its parent block.
+    #[method(name = "mine")]
+    async fn mine(&self, blocks: Option<u64>, interval_seconds: Option<u64>) -> RpcResult<()>;
+
+    /// Returns the timestamp that the next block will have.
     #[method(name = "nextBlockTimestamp")]
-    async fn next_block_timestamp(&self) -> RpcResult<()>;
+    async fn next_block_timestamp(&self) -> RpcResult<u64>;
 
     #[method(name = "setNextBlockTimestamp")]
     async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()>;
@@ -18,10 +33,97 @@ pub trait DevApi {
     #[method(name = "increaseNextBlockTimestamp")]
     async fn increase_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()>;
 
+    /// Switches to _interval_ mining, mining a new block every `block_time` milliseconds.
+    #[method(name = "setBlockTime")]
+    async fn set_block_time(&self, block_time: u64) -> RpcResult<()>;
+
+    /// Changes the mode in which blocks are produced.
+    #[method(name = "setMiningMode")]
+    async fn set_mining_mode(&self, mode: MiningMode) -> RpcResult<()>;
+
+    /// Sets the L1 gas prices of the blocks created from now on.
+    #[method(name = "setGasPrices")]
+    async fn set_gas_prices(&self, prices: GasPrice) -> RpcResult<()>;
+
+    /// Sets the L1 data gas prices of the blocks created from now on.
+    #[method(name = "setDataGasPrices")]
+    async fn set_data_gas_prices(&self, prices: GasPrice) -> RpcResult<()>;
+
+    /// Returns the gas prices that the next block will use.
+    ///
+    /// In _interval_ mode, these are the prices of the pending block as it's already open. Prices
+    /// set while a block is pending only apply to the blocks after it.
+    #[method(name = "getGasPrices")]
+    async fn get_gas_prices(&self) -> RpcResult<GasPrices>;
+
+    /// Sets the value of a storage slot of a contract.
     #[method(name = "setStorageAt")]
     async fn set_storage_at(&self, contract_address: Felt, key: Felt, value: Felt)
         -> RpcResult<()>;
 
+    /// Sets the nonce of a contract.
+    #[method(name = "setNonce")]
+    async fn set_nonce(&self, contract_address: ContractAddress, nonce: Nonce) -> RpcResult<()>;
+
+    /// Replaces the class of a contract. The class must already be declared.
+    #[method(name = "setClassHash")]
+    async fn set_class_hash(
+        &self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> RpcResult<()>;
+
+    /// Mints fee tokens to an address. The fee token is determined by `unit`, ie `WEI` for ETH
+    /// and `FRI` for STRK. Defaults to STRK.
+    #[method(name = "mint")]
+    async fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        unit: Option<PriceUnit>,
+    ) -> RpcResult<()>;
+
+    /// Creates a new account with a randomly generated key pair and deploys it, returning the
+    /// account including its private key.
+    ///
+    /// The account is deployed by writing its state directly, without running its constructor, so
+    /// only the default account class is supported. Created accounts are persisted in the database
+    /// and listed by `dev_predeployedAccounts`.
+    #[method(name = "createAccount")]
+    async fn create_account(&self, class_hash: Option<ClassHash>) -> RpcResult<Account>;
+
+    /// Mints `amount` of the fee token `token` to an account. Defaults to STRK.
+    #[method(name = "fundAccount")]
+    async fn fund_account(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        token: Option<FeeToken>,
+    ) -> RpcResult<()>;
+
+    /// Takes a snapshot of the current chain state and returns its id. The chain can later be
+    /// reverted to this state using `dev_revert`.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self) -> RpcResult<u64>;
+
+    /// Reverts the chain to the state of the snapshot with the given id, removing every block
+    /// mined after it. The snapshot, and all the snapshots taken after it, are discarded.
+    #[method(name = "revert")]
+    async fn revert(&self, id: u64) -> RpcResult<()>;
+
+    /// Impersonates an account, allowing transactions to be sent from it without its signature.
+    ///
+    /// The account validation logic (ie `__validate__`) is skipped for the transactions sent by the
+    /// account until `dev_stopImpersonatingAccount` is called. Other accounts are still validated.
+    #[method(name = "impersonateAccount")]
+    async fn impersonate_account(&self, address: ContractAddress) -> RpcResult<()>;
+
+    /// Stops impersonating an account previously impersonated with `dev_impersonateAccount`.
+    #[method(name = "stopImpersonatingAccount")]
+    async fn stop_impersonating_account(&self, address: ContractAddress) -> RpcResult<()>;
+
+    /// Returns the accounts allocated in the genesis block followed by the accounts created with
+    /// `dev_createAccount` that are still deployed, ie that weren't removed by `dev_revert`.
     #[method(name = "predeployedAccounts")]
     async fn predeployed_accounts(&self) -> RpcResult<Vec<Account>>;
 }
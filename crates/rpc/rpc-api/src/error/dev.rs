@@ -5,6 +5,28 @@ use jsonrpsee::types::ErrorObjectOwned;
 pub enum DevApiError {
     #[error("Wait for pending transactions.")]
     PendingTransactions,
+    #[error("Failed to update the state.")]
+    FailedToUpdateState,
+    #[error("Class hash is not declared.")]
+    ClassNotDeclared,
+    #[error("Failed to take a snapshot.")]
+    FailedToTakeSnapshot,
+    #[error("Snapshot not found.")]
+    SnapshotNotFound,
+    #[error("Failed to revert the chain.")]
+    FailedToRevert,
+    #[error("Failed to mine blocks.")]
+    FailedToMine,
+    #[error("Block time must be greater than zero.")]
+    InvalidBlockTime,
+    #[error("Failed to change the mining mode.")]
+    FailedToChangeMiningMode,
+    #[error("Failed to create the account.")]
+    FailedToCreateAccount,
+    #[error("Failed to get the accounts.")]
+    FailedToGetAccounts,
+    #[error("Only accounts of the default, OpenZeppelin or Argent account class can be created.")]
+    UnsupportedAccountClass,
 }
 
 impl From<DevApiError> for ErrorObjectOwned {
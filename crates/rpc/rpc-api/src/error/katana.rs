@@ -9,6 +9,20 @@ pub enum KatanaApiError {
     FailedToDumpState = 2,
     #[error("Failed to update storage.")]
     FailedToUpdateStorage = 3,
+    #[error("Block not found.")]
+    BlockNotFound = 4,
+    #[error("Block was not produced in the blob data availability mode.")]
+    BlobDataAvailabilityNotEnabled = 5,
+    #[error("Failed to compute the blobs of the block.")]
+    FailedToComputeBlobs = 6,
+    #[error("An unexpected error occurred.")]
+    UnexpectedError = 7,
+    #[error("Invalid block range.")]
+    InvalidBlockRange = 8,
+    #[error("Block range is too large.")]
+    BlockRangeTooLarge = 9,
+    #[error("Failed to compute the data availability payload of the block.")]
+    FailedToComputeDaPayload = 10,
 }
 
 impl From<KatanaApiError> for ErrorObjectOwned {
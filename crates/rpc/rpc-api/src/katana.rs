@@ -0,0 +1,28 @@
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::BlockIdOrTag;
+use katana_rpc_types::da::{BlockBlobs, BlockDaPayload};
+
+/// The maximum number of blocks whose data availability payloads can be requested at once.
+pub const MAX_DA_PAYLOAD_BLOCK_RANGE: u64 = 100;
+
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "katana"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "katana"))]
+pub trait KatanaApi {
+    /// Returns the blobs encoding the state diff of a block, along with their KZG commitments
+    /// and proofs. Only available for blocks produced in the `BLOB` data availability mode.
+    #[method(name = "getBlockBlobs")]
+    async fn get_block_blobs(&self, block_id: BlockIdOrTag) -> RpcResult<BlockBlobs>;
+
+    /// Returns the data availability payload of the state diff of a block, ie the encoded state
+    /// diff, its blobs and their versioned hashes.
+    ///
+    /// If `to_block` is specified, the payloads of all the blocks from `block_id` to `to_block`
+    /// (inclusive) are returned, up to [`MAX_DA_PAYLOAD_BLOCK_RANGE`] blocks.
+    #[method(name = "getBlockDaPayload")]
+    async fn get_block_da_payload(
+        &self,
+        block_id: BlockIdOrTag,
+        to_block: Option<BlockIdOrTag>,
+    ) -> RpcResult<Vec<BlockDaPayload>>;
+}
@@ -1,23 +1,25 @@
 //! Starknet JSON-RPC specifications: <https://github.com/starkware-libs/starknet-specs>
 
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
 use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::SubscriptionId;
 use katana_primitives::block::{BlockIdOrTag, BlockNumber};
 use katana_primitives::class::ClassHash;
 use katana_primitives::transaction::TxHash;
 use katana_primitives::{ContractAddress, Felt};
 use katana_rpc_types::block::{
-    BlockHashAndNumber, BlockTxCount, MaybePendingBlockWithReceipts, MaybePendingBlockWithTxHashes,
-    MaybePendingBlockWithTxs,
+    BlockHashAndNumber, BlockHeader, BlockTxCount, MaybePendingBlockWithReceipts,
+    MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
 };
 use katana_rpc_types::class::RpcContractClass;
 use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
 use katana_rpc_types::message::MsgFromL1;
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
+use katana_rpc_types::subscription::{NewTransactionStatus, PendingTransaction};
 use katana_rpc_types::transaction::{
     BroadcastedDeclareTx, BroadcastedDeployAccountTx, BroadcastedInvokeTx, BroadcastedTx,
-    DeclareTxResult, DeployAccountTxResult, InvokeTxResult, Tx,
+    DeclareTxResult, DeployAccountTxResult, InvokeTxResult, Tx, TxStatus,
 };
 use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
 use katana_rpc_types::{
@@ -25,7 +27,7 @@ use katana_rpc_types::{
     SyncingStatus,
 };
 use starknet::core::types::{
-    SimulatedTransaction, TransactionStatus, TransactionTrace, TransactionTraceWithHash,
+    EmittedEvent, SimulatedTransaction, TransactionTrace, TransactionTraceWithHash,
 };
 
 /// The currently supported version of the Starknet JSON-RPC specification.
@@ -78,10 +80,7 @@ pub trait StarknetApi {
     /// Gets the transaction status (possibly reflecting that the tx is still in the mempool, or
     /// dropped from it).
     #[method(name = "getTransactionStatus")]
-    async fn get_transaction_status(
-        &self,
-        transaction_hash: TxHash,
-    ) -> RpcResult<TransactionStatus>;
+    async fn get_transaction_status(&self, transaction_hash: TxHash) -> RpcResult<TxStatus>;
 
     /// Get the details and status of a submitted transaction.
     #[method(name = "getTransactionByHash")]
@@ -249,3 +248,71 @@ pub trait StarknetTraceApi {
         block_id: BlockIdOrTag,
     ) -> RpcResult<Vec<TransactionTraceWithHash>>;
 }
+
+/// The unsubscribe methods generated for the subscriptions of [`StarknetWsApi`].
+///
+/// The subscription server requires every subscription to be paired with its own unsubscribe
+/// method, but they're not part of the specification. They must be removed from the RPC module,
+/// as subscriptions are cancelled using `starknet_unsubscribe` instead.
+pub const WS_UNSUBSCRIBE_METHODS: [&str; 4] = [
+    "starknet_unsubscribeNewHeads",
+    "starknet_unsubscribeEvents",
+    "starknet_unsubscribeTransactionStatus",
+    "starknet_unsubscribePendingTransactions",
+];
+
+/// WebSocket subscription API.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "starknet"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "starknet"))]
+pub trait StarknetWsApi {
+    /// Closes a previously opened subscription. Returns `true` if the subscription was closed.
+    #[method(name = "unsubscribe", with_extensions)]
+    async fn unsubscribe(&self, subscription_id: SubscriptionId<'static>) -> RpcResult<bool>;
+
+    /// Creates a WebSocket stream which will fire events for new block headers.
+    ///
+    /// If `block_id` is provided, the headers of all blocks starting from that block are sent
+    /// first. The block must not be more than 1024 blocks in the past.
+    #[subscription(
+        name = "subscribeNewHeads" => "subscriptionNewHeads",
+        unsubscribe = "unsubscribeNewHeads",
+        item = BlockHeader
+    )]
+    async fn subscribe_new_heads(&self, block_id: Option<BlockIdOrTag>) -> SubscriptionResult;
+
+    /// Creates a WebSocket stream which will fire events for new Starknet events with the
+    /// applied filters.
+    #[subscription(
+        name = "subscribeEvents" => "subscriptionEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = EmittedEvent
+    )]
+    async fn subscribe_events(
+        &self,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<Vec<Felt>>>,
+        block_id: Option<BlockIdOrTag>,
+    ) -> SubscriptionResult;
+
+    /// Creates a WebSocket stream which will fire events when the status of a transaction
+    /// changes. The stream ends once the transaction reaches a final status.
+    #[subscription(
+        name = "subscribeTransactionStatus" => "subscriptionTransactionStatus",
+        unsubscribe = "unsubscribeTransactionStatus",
+        item = NewTransactionStatus
+    )]
+    async fn subscribe_transaction_status(&self, transaction_hash: TxHash) -> SubscriptionResult;
+
+    /// Creates a WebSocket stream which will fire events when a new pending transaction is
+    /// added to the pool.
+    #[subscription(
+        name = "subscribePendingTransactions" => "subscriptionPendingTransactions",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = PendingTransaction
+    )]
+    async fn subscribe_pending_transactions(
+        &self,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult;
+}
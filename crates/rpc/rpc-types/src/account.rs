@@ -33,3 +33,12 @@ impl Account {
         }
     }
 }
+
+/// The tokens that transaction fees can be paid in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FeeToken {
+    Eth,
+    #[default]
+    Strk,
+}
@@ -246,6 +246,55 @@ impl From<(BlockHash, BlockNumber)> for BlockHashAndNumber {
     }
 }
 
+/// The header of a block. Used as the notification item of the `starknet_subscribeNewHeads`
+/// subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BlockHeader(starknet::core::types::BlockHeader);
+
+impl BlockHeader {
+    pub fn new(hash: BlockHash, header: Header) -> Self {
+        let l1_gas_price = ResourcePrice {
+            price_in_wei: header.l1_gas_prices.eth.get().into(),
+            price_in_fri: header.l1_gas_prices.strk.get().into(),
+        };
+
+        let l2_gas_price = ResourcePrice {
+            price_in_wei: header.l2_gas_prices.eth.get().into(),
+            price_in_fri: header.l2_gas_prices.strk.get().into(),
+        };
+
+        let l1_data_gas_price = ResourcePrice {
+            price_in_wei: header.l1_data_gas_prices.eth.get().into(),
+            price_in_fri: header.l1_data_gas_prices.strk.get().into(),
+        };
+
+        Self(starknet::core::types::BlockHeader {
+            block_hash: hash,
+            parent_hash: header.parent_hash,
+            block_number: header.number,
+            new_root: header.state_root,
+            timestamp: header.timestamp,
+            sequencer_address: header.sequencer_address.into(),
+            l1_gas_price,
+            l2_gas_price,
+            l1_data_gas_price,
+            l1_da_mode: match header.l1_da_mode {
+                katana_primitives::da::L1DataAvailabilityMode::Blob => L1DataAvailabilityMode::Blob,
+                katana_primitives::da::L1DataAvailabilityMode::Calldata => {
+                    L1DataAvailabilityMode::Calldata
+                }
+            },
+            starknet_version: header.protocol_version.to_string(),
+        })
+    }
+
+    /// Returns the number of the block.
+    pub fn block_number(&self) -> BlockNumber {
+        self.0.block_number
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct BlockWithReceipts(starknet::core::types::BlockWithReceipts);
@@ -0,0 +1,21 @@
+use katana_primitives::block::{BlockHash, BlockNumber};
+use katana_primitives::da::kzg::BlobSidecar;
+use katana_primitives::da::payload::DaPayload;
+use serde::{Deserialize, Serialize};
+
+/// The blobs encoding the state diff of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockBlobs {
+    pub block_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub blobs: Vec<BlobSidecar>,
+}
+
+/// The data availability payload of the state diff of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockDaPayload {
+    pub block_hash: BlockHash,
+    pub block_number: BlockNumber,
+    #[serde(flatten)]
+    pub payload: DaPayload,
+}
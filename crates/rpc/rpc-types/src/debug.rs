@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::execution::{
+    self, CallInfo, TrackedResource, TypedTransactionExecutionInfo,
+};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::Felt;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{CallType, EntryPointType, OrderedEvent, OrderedMessage, StateDiff};
+
+/// A call in the call tree of a traced execution.
+///
+/// The storage writes aren't recorded per call by the executor, so a frame only lists the storage
+/// keys it accessed and the values it read. The writes are only available in aggregate, as the
+/// state diff of the whole call or transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallFrame {
+    pub caller_address: ContractAddress,
+    pub contract_address: ContractAddress,
+    pub class_hash: Option<ClassHash>,
+    pub entry_point_selector: Felt,
+    pub entry_point_type: EntryPointType,
+    pub call_type: CallType,
+    pub calldata: Vec<Felt>,
+    pub result: Vec<Felt>,
+    pub is_reverted: bool,
+    /// The events emitted by the call, excluding the ones emitted by its inner calls.
+    pub events: Vec<OrderedEvent>,
+    /// The messages sent to L1 by the call, excluding the ones sent by its inner calls.
+    pub messages: Vec<OrderedMessage>,
+    /// The storage keys read or written by the call.
+    pub accessed_storage_keys: Vec<Felt>,
+    /// The values read from the storage by the call, in the order they were read.
+    pub storage_read_values: Vec<Felt>,
+    /// The number of Cairo steps executed by the call, including its inner calls.
+    pub cairo_steps: u64,
+    /// The number of memory holes of the call, including its inner calls.
+    pub memory_holes: u64,
+    /// The number of builtin instances used by the call, including its inner calls.
+    pub builtins: BTreeMap<String, u64>,
+    /// The Sierra gas consumed by the call, including its inner calls. Zero if the call is
+    /// metered in Cairo steps.
+    pub sierra_gas: u64,
+    /// The calls made by the call, in the order they were made.
+    pub calls: Vec<CallFrame>,
+}
+
+/// The type returned by the `debug_traceCall` RPC method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallTrace {
+    pub invocation: CallFrame,
+    /// The error of the call if its execution failed.
+    pub revert_reason: Option<String>,
+    /// The storage writes of the call and all of its inner calls. They're not applied to the
+    /// chain.
+    pub state_diff: StateDiff,
+}
+
+/// The type returned by the `debug_traceTransaction` RPC method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxTrace {
+    pub validate_invocation: Option<CallFrame>,
+    /// The execution of the transaction. `None` if the transaction was reverted.
+    pub execute_invocation: Option<CallFrame>,
+    pub fee_transfer_invocation: Option<CallFrame>,
+    pub revert_reason: Option<String>,
+    /// The state changes of the transaction, if they were recorded.
+    pub state_diff: Option<StateDiff>,
+}
+
+impl CallTrace {
+    pub fn new(call_info: CallInfo, revert_reason: Option<String>, states: StateUpdates) -> Self {
+        let state_diff = crate::state_update::StateDiff::from(states).0;
+        Self { invocation: call_info.into(), revert_reason, state_diff }
+    }
+}
+
+impl From<TypedTransactionExecutionInfo> for TxTrace {
+    fn from(trace: TypedTransactionExecutionInfo) -> Self {
+        let (_, trace, state_diff) = trace.into_parts();
+        Self {
+            validate_invocation: trace.validate_call_info.map(CallFrame::from),
+            execute_invocation: trace.execute_call_info.map(CallFrame::from),
+            fee_transfer_invocation: trace.fee_transfer_call_info.map(CallFrame::from),
+            revert_reason: trace.revert_error.map(|e| e.to_string()),
+            state_diff: state_diff.map(|diff| crate::state_update::StateDiff::from(diff).0),
+        }
+    }
+}
+
+impl From<CallInfo> for CallFrame {
+    fn from(info: CallInfo) -> Self {
+        let contract_address = info.call.storage_address;
+
+        let entry_point_type = match info.call.entry_point_type {
+            execution::EntryPointType::External => EntryPointType::External,
+            execution::EntryPointType::L1Handler => EntryPointType::L1Handler,
+            execution::EntryPointType::Constructor => EntryPointType::Constructor,
+        };
+
+        let call_type = match info.call.call_type {
+            execution::CallType::Call => CallType::Call,
+            execution::CallType::Delegate => CallType::Delegate,
+        };
+
+        let events = info
+            .execution
+            .events
+            .into_iter()
+            .map(|e| OrderedEvent {
+                order: e.order as u64,
+                data: e.event.data.0,
+                keys: e.event.keys.into_iter().map(|k| k.0).collect(),
+            })
+            .collect();
+
+        let messages = info
+            .execution
+            .l2_to_l1_messages
+            .into_iter()
+            .map(|m| OrderedMessage {
+                order: m.order as u64,
+                payload: m.message.payload.0,
+                to_address: m.message.to_address,
+                from_address: contract_address.into(),
+            })
+            .collect();
+
+        let mut accessed_storage_keys: Vec<Felt> =
+            info.accessed_storage_keys.into_iter().map(|k| *k.0.key()).collect();
+        accessed_storage_keys.sort();
+
+        let builtins = info
+            .resources
+            .builtin_instance_counter
+            .into_iter()
+            .map(|(name, count)| (name.to_str().to_string(), count as u64))
+            .collect();
+
+        let sierra_gas = match info.tracked_resource {
+            TrackedResource::CairoSteps => 0,
+            TrackedResource::SierraGas => info.execution.gas_consumed,
+        };
+
+        Self {
+            events,
+            messages,
+            call_type,
+            entry_point_type,
+            accessed_storage_keys,
+            builtins,
+            sierra_gas,
+            result: info.execution.retdata.0,
+            is_reverted: info.execution.failed,
+            caller_address: info.call.caller_address.into(),
+            contract_address: contract_address.into(),
+            class_hash: info.call.class_hash.map(|h| h.0),
+            calldata: Arc::unwrap_or_clone(info.call.calldata.0),
+            entry_point_selector: info.call.entry_point_selector.0,
+            storage_read_values: info.storage_read_values,
+            cairo_steps: info.resources.n_steps as u64,
+            memory_holes: info.resources.n_memory_holes as u64,
+            calls: info.inner_calls.into_iter().map(CallFrame::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use katana_primitives::block::GasPrice;
+use serde::{Deserialize, Serialize};
+
+/// The prices of the different kinds of gas of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPrices {
+    /// The price of L1 gas.
+    pub l1_gas_prices: GasPrice,
+    /// The price of L1 data gas (ie blob gas).
+    pub l1_data_gas_prices: GasPrice,
+    /// The price of L2 gas (ie Sierra gas).
+    pub l2_gas_prices: GasPrice,
+}
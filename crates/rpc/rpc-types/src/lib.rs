@@ -8,11 +8,16 @@
 pub mod account;
 pub mod block;
 pub mod class;
+pub mod da;
+pub mod debug;
 pub mod event;
+pub mod gas;
 pub mod message;
+pub mod mining;
 pub mod outside_execution;
 pub mod receipt;
 pub mod state_update;
+pub mod subscription;
 pub mod trace;
 pub mod transaction;
 pub mod trie;
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// The mode in which the node produces blocks.
+///
+/// Serialized as `"instant"`, `"onDemand"` or `{ "interval": <block time in milliseconds> }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MiningMode {
+    /// A new block is mined as soon as a transaction is received.
+    Instant,
+    /// A new block is mined after the given number of milliseconds since its first transaction.
+    Interval(u64),
+    /// Blocks are only mined on demand, through `dev_generateBlock` or `dev_mine`.
+    OnDemand,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::MiningMode;
+
+    #[test]
+    fn mining_mode_serde() {
+        let modes = [
+            (MiningMode::Instant, json!("instant")),
+            (MiningMode::Interval(1000), json!({ "interval": 1000 })),
+            (MiningMode::OnDemand, json!("onDemand")),
+        ];
+
+        for (mode, value) in modes {
+            assert_eq!(serde_json::to_value(mode).unwrap(), value);
+            assert_eq!(serde_json::from_value::<MiningMode>(value).unwrap(), mode);
+        }
+    }
+}
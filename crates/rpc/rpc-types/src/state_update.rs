@@ -1,7 +1,8 @@
 use katana_primitives::class::ClassHash;
 use serde::{Deserialize, Serialize};
 use starknet::core::types::{
-    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate, StorageEntry,
+    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate,
+    ReplacedClassItem, StorageEntry,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,13 +81,22 @@ impl From<katana_primitives::state::StateUpdates> for StateDiff {
             })
             .collect();
 
+        let replaced_classes: Vec<ReplacedClassItem> = value
+            .replaced_classes
+            .into_iter()
+            .map(|(addr, class_hash)| ReplacedClassItem {
+                contract_address: addr.into(),
+                class_hash,
+            })
+            .collect();
+
         Self(starknet::core::types::StateDiff {
             nonces,
             storage_diffs,
             declared_classes,
             deployed_contracts,
             deprecated_declared_classes,
-            replaced_classes: Default::default(),
+            replaced_classes,
         })
     }
 }
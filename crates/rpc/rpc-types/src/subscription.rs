@@ -0,0 +1,33 @@
+//! Types used by the Starknet WebSocket subscription methods.
+
+use katana_primitives::transaction::TxHash;
+use serde::{Deserialize, Serialize};
+
+use crate::transaction::{Tx, TxStatus};
+
+/// The maximum number of blocks in the past that a subscription can start from.
+///
+/// See `TOO_MANY_BLOCKS_BACK` error in the Starknet WebSocket specification.
+pub const MAX_BLOCKS_BACK: u64 = 1024;
+
+/// The maximum number of addresses that can be specified in the `sender_address` filter of
+/// `starknet_subscribePendingTransactions`.
+pub const MAX_SENDER_ADDRESSES: usize = 1024;
+
+/// Notification item of the `starknet_subscribeTransactionStatus` subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTransactionStatus {
+    pub transaction_hash: TxHash,
+    pub status: TxStatus,
+}
+
+/// Notification item of the `starknet_subscribePendingTransactions` subscription.
+///
+/// Depending on the `transaction_details` flag of the subscription request, either only the
+/// transaction hash or the full transaction is sent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PendingTransaction {
+    Hash(TxHash),
+    Full(Tx),
+}
@@ -14,6 +14,7 @@ use starknet::core::types::{
     RevertedInvocation, TransactionTrace,
 };
 
+use crate::state_update::StateDiff;
 use crate::FeeEstimate;
 
 /// The type returned by the `saya_getTransactionExecutionsByBlock` RPC method.
@@ -26,15 +27,14 @@ pub struct TxExecutionInfo {
 }
 
 pub fn to_rpc_trace(trace: TypedTransactionExecutionInfo) -> TransactionTrace {
-    let tx_type = trace.r#type();
-    let trace: TransactionExecutionInfo = trace.into();
+    let (tx_type, trace, state_diff) = trace.into_parts();
 
     let execution_resources = to_rpc_resources(trace.receipt);
     let fee_transfer_invocation = trace.fee_transfer_call_info.map(to_function_invocation);
     let validate_invocation = trace.validate_call_info.map(to_function_invocation);
     let execute_invocation = trace.execute_call_info.map(to_function_invocation);
     let revert_reason = trace.revert_error.map(|e| e.to_string());
-    let state_diff = None; // TODO: compute the state diff
+    let state_diff = state_diff.map(|diff| StateDiff::from(diff).0);
 
     match tx_type {
         TxType::Invoke => {
@@ -62,7 +62,9 @@ pub fn to_rpc_trace(trace: TypedTransactionExecutionInfo) -> TransactionTrace {
             state_diff,
         }),
 
-        TxType::DeployAccount => {
+        // The spec has no trace for legacy deploy transactions, the closest one being the deploy
+        // account trace.
+        TxType::DeployAccount | TxType::Deploy => {
             let constructor_invocation = execute_invocation.expect("should exist if not reverted");
             TransactionTrace::DeployAccount(DeployAccountTransactionTrace {
                 fee_transfer_invocation,
@@ -81,10 +83,6 @@ pub fn to_rpc_trace(trace: TypedTransactionExecutionInfo) -> TransactionTrace {
                 state_diff,
             })
         }
-
-        TxType::Deploy => {
-            unimplemented!("unsupported legacy tx type")
-        }
     }
 }
 
@@ -21,9 +21,9 @@ use starknet::core::types::{
     DeclareTransactionV2Content, DeclareTransactionV3Content, DeployAccountTransactionContent,
     DeployAccountTransactionResult, DeployAccountTransactionV1, DeployAccountTransactionV1Content,
     DeployAccountTransactionV3, DeployAccountTransactionV3Content, DeployTransactionContent,
-    InvokeTransactionContent, InvokeTransactionResult, InvokeTransactionV0Content,
+    ExecutionResult, InvokeTransactionContent, InvokeTransactionResult, InvokeTransactionV0Content,
     InvokeTransactionV1Content, InvokeTransactionV3Content, L1HandlerTransactionContent,
-    TransactionContent,
+    SequencerTransactionStatus, TransactionContent, TransactionExecutionStatus, TransactionStatus,
 };
 use starknet::core::utils::get_contract_address;
 
@@ -596,6 +596,66 @@ pub struct TransactionsPage {
     pub cursor: TransactionsPageCursor,
 }
 
+/// The status of a transaction, as returned by `starknet_getTransactionStatus`.
+///
+/// Unlike [`TransactionStatus`](starknet::core::types::TransactionStatus), this also carries the
+/// reason of why a transaction was rejected in its `failure_reason` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxStatus {
+    pub finality_status: SequencerTransactionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+    /// The revert reason of a reverted transaction, or the reason of why a transaction was
+    /// rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+impl TxStatus {
+    /// Creates the status of a transaction that was rejected with the given reason.
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        Self {
+            finality_status: SequencerTransactionStatus::Rejected,
+            execution_status: None,
+            failure_reason: Some(reason.into()),
+        }
+    }
+
+    /// Returns `true` if the status can no longer change, ie the transaction is either rejected or
+    /// accepted on L1.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self.finality_status,
+            SequencerTransactionStatus::Rejected | SequencerTransactionStatus::AcceptedOnL1
+        )
+    }
+}
+
+impl From<TransactionStatus> for TxStatus {
+    fn from(status: TransactionStatus) -> Self {
+        let (finality_status, result) = match status {
+            TransactionStatus::Received => (SequencerTransactionStatus::Received, None),
+            TransactionStatus::Rejected => (SequencerTransactionStatus::Rejected, None),
+            TransactionStatus::AcceptedOnL2(res) => {
+                (SequencerTransactionStatus::AcceptedOnL2, Some(res))
+            }
+            TransactionStatus::AcceptedOnL1(res) => {
+                (SequencerTransactionStatus::AcceptedOnL1, Some(res))
+            }
+        };
+
+        let (execution_status, failure_reason) = match result {
+            Some(ExecutionResult::Succeeded) => (Some(TransactionExecutionStatus::Succeeded), None),
+            Some(ExecutionResult::Reverted { reason }) => {
+                (Some(TransactionExecutionStatus::Reverted), Some(reason))
+            }
+            None => (None, None),
+        };
+
+        Self { finality_status, execution_status, failure_reason }
+    }
+}
+
 // TODO: find a solution to avoid doing this conversion, this is not pretty at all. the reason why
 // we had to do this in the first place is because of the orphan rule. i think eventually we should
 // not rely on `starknet-rs` rpc types anymore and should instead define the types ourselves to have
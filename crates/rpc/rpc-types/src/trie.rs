@@ -172,6 +172,69 @@ impl From<MerkleNode> for ProofNode {
     }
 }
 
+impl From<starknet::core::types::StorageProof> for GetStorageProofResponse {
+    fn from(value: starknet::core::types::StorageProof) -> Self {
+        let global_roots = GlobalRoots {
+            block_hash: value.global_roots.block_hash,
+            classes_tree_root: value.global_roots.classes_tree_root,
+            contracts_tree_root: value.global_roots.contracts_tree_root,
+        };
+
+        let contract_leaves_data = value
+            .contracts_proof
+            .contract_leaves_data
+            .into_iter()
+            .map(|leaf| ContractLeafData {
+                storage_root: leaf.storage_root.unwrap_or_default(),
+                nonce: leaf.nonce,
+                class_hash: leaf.class_hash,
+            })
+            .collect();
+
+        Self {
+            global_roots,
+            classes_proof: ClassesProof { nodes: value.classes_proof.into() },
+            contracts_proof: ContractsProof {
+                nodes: value.contracts_proof.nodes.into(),
+                contract_leaves_data,
+            },
+            contracts_storage_proofs: ContractStorageProofs {
+                nodes: value.contracts_storage_proofs.into_iter().map(Nodes::from).collect(),
+            },
+        }
+    }
+}
+
+impl From<Vec<starknet::core::types::NodeHashToNodeMappingItem>> for Nodes {
+    fn from(value: Vec<starknet::core::types::NodeHashToNodeMappingItem>) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|item| NodeWithHash { node_hash: item.node_hash, node: item.node.into() })
+                .collect(),
+        )
+    }
+}
+
+impl From<starknet::core::types::MerkleNode> for MerkleNode {
+    fn from(value: starknet::core::types::MerkleNode) -> Self {
+        match value {
+            starknet::core::types::MerkleNode::BinaryNode(node) => {
+                Self::Binary { left: node.left, right: node.right }
+            }
+            starknet::core::types::MerkleNode::EdgeNode(node) => {
+                Self::Edge { path: node.path, length: node.length as u8, child: node.child }
+            }
+        }
+    }
+}
+
+impl From<ContractStorageKeys> for starknet::core::types::ContractStorageKeys {
+    fn from(value: ContractStorageKeys) -> Self {
+        Self { contract_address: value.address.into(), storage_keys: value.keys }
+    }
+}
+
 fn felt_to_path(felt: Felt, length: u8) -> Path {
     let length = length as usize;
     let mut bits = BitVec::new();
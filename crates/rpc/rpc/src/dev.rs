@@ -1,23 +1,64 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use jsonrpsee::core::{async_trait, RpcResult};
 use katana_core::backend::Backend;
-use katana_core::service::block_producer::{BlockProducer, BlockProducerMode, PendingExecutor};
+use katana_core::service::block_producer::{
+    BlockProducer, BlockProducerMode, MiningMode as ProducerMiningMode, PendingExecutor,
+};
+use katana_db::models::account::DevAccount;
 use katana_executor::ExecutorFactory;
-use katana_primitives::Felt;
+use katana_pool::{TransactionPool, TxPool};
+use katana_primitives::block::{BlockNumber, GasPrice};
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::{Nonce, StorageKey, StorageValue};
+use katana_primitives::env::BlockEnv;
+use katana_primitives::fee::PriceUnit;
+use katana_primitives::genesis::allocation::GenesisAccount;
+use katana_primitives::genesis::constant::{
+    get_fee_token_balance_base_storage_address, ARGENT_ACCOUNT_CLASS_HASH,
+    ARGENT_ACCOUNT_CLASS_SIGNER_STORAGE_SLOT, DEFAULT_ACCOUNT_CLASS_HASH,
+    DEFAULT_ACCOUNT_CLASS_PUBKEY_STORAGE_SLOT, ERC20_TOTAL_SUPPLY_STORAGE_SLOT,
+    OZ_ACCOUNT_CLASS_HASH,
+};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::utils::split_u256;
+use katana_primitives::{ContractAddress, Felt, U256};
+use katana_provider::traits::account::DevAccountProvider;
+use katana_provider::traits::block::BlockNumberProvider;
+use katana_provider::traits::contract::ContractClassProvider;
+use katana_provider::traits::state::{StateFactoryProvider, StateProvider};
+use katana_provider::ProviderResult;
 use katana_rpc_api::dev::DevApiServer;
 use katana_rpc_api::error::dev::DevApiError;
-use katana_rpc_types::account::Account;
+use katana_rpc_types::account::{Account, FeeToken};
+use katana_rpc_types::gas::GasPrices;
+use katana_rpc_types::mining::MiningMode;
+use parking_lot::Mutex;
+use starknet::core::utils::get_contract_address;
+use starknet::signers::SigningKey;
+use tracing::error;
 
 #[allow(missing_debug_implementations)]
 pub struct DevApi<EF: ExecutorFactory> {
     backend: Arc<Backend<EF>>,
     block_producer: BlockProducer<EF>,
+    pool: TxPool,
+    snapshots: Mutex<Snapshots>,
+}
+
+/// Chain snapshots taken through `dev_snapshot`.
+#[derive(Debug, Default)]
+struct Snapshots {
+    /// The id to assign to the next snapshot.
+    next_id: u64,
+    /// The block number at which each snapshot was taken, keyed by the snapshot id.
+    blocks: BTreeMap<u64, BlockNumber>,
 }
 
 impl<EF: ExecutorFactory> DevApi<EF> {
-    pub fn new(backend: Arc<Backend<EF>>, block_producer: BlockProducer<EF>) -> Self {
-        Self { backend, block_producer }
+    pub fn new(backend: Arc<Backend<EF>>, block_producer: BlockProducer<EF>, pool: TxPool) -> Self {
+        Self { backend, block_producer, pool, snapshots: Mutex::default() }
     }
 
     /// Returns the pending state if the sequencer is running in _interval_ mode. Otherwise `None`.
@@ -36,6 +77,79 @@ impl<EF: ExecutorFactory> DevApi<EF> {
         }
     }
 
+    /// Mines `blocks` blocks, with the timestamps of consecutive blocks `interval` seconds apart
+    /// if given.
+    pub fn mine(&self, blocks: u64, interval: Option<u64>) -> Result<(), DevApiError> {
+        self.block_producer.mine(blocks, interval).map_err(|error| {
+            error!(%error, %blocks, "Failed to mine blocks.");
+            DevApiError::FailedToMine
+        })
+    }
+
+    /// Returns the timestamp of the pending block in _interval_ mode, as it's already open, or the
+    /// timestamp that the next block will be created with otherwise.
+    pub fn next_block_timestamp(&self) -> u64 {
+        match self.pending_executor() {
+            Some(exec) => exec.read().block_env().timestamp,
+            None => self.backend.next_block_timestamp(),
+        }
+    }
+
+    pub fn set_mining_mode(&self, mode: MiningMode) -> Result<(), DevApiError> {
+        let mode = match mode {
+            MiningMode::Instant => ProducerMiningMode::Instant,
+            MiningMode::Interval(0) => return Err(DevApiError::InvalidBlockTime),
+            MiningMode::Interval(block_time) => ProducerMiningMode::Interval(block_time),
+            MiningMode::OnDemand => ProducerMiningMode::OnDemand,
+        };
+
+        self.block_producer.set_mining_mode(mode).map_err(|error| {
+            error!(%error, ?mode, "Failed to change mining mode.");
+            DevApiError::FailedToChangeMiningMode
+        })
+    }
+
+    pub fn set_gas_prices(&self, prices: GasPrice) {
+        self.backend.gas_oracle.set_gas_prices(prices);
+        self.refresh_validator_gas_prices();
+    }
+
+    pub fn set_data_gas_prices(&self, prices: GasPrice) {
+        self.backend.gas_oracle.set_data_gas_prices(prices);
+        self.refresh_validator_gas_prices();
+    }
+
+    /// Returns the gas prices of the pending block in _interval_ mode, as it's already open, or the
+    /// prices that the next block will be created with otherwise.
+    pub fn gas_prices(&self) -> GasPrices {
+        let block_env = match self.pending_executor() {
+            Some(exec) => exec.read().block_env(),
+            None => {
+                let mut block_env = BlockEnv::default();
+                self.backend.update_block_gas_prices(&mut block_env);
+                block_env
+            }
+        };
+
+        gas_prices_of(&block_env)
+    }
+
+    /// Updates the block env of the pool validator with the current gas prices, so that new
+    /// transactions are validated against them.
+    ///
+    /// This is only done in _instant_ mode. Otherwise, the pending block is already open with the
+    /// previous prices, and the validator picks up the new ones once it's mined.
+    fn refresh_validator_gas_prices(&self) {
+        if self.pending_executor().is_some() {
+            return;
+        }
+
+        let validator = self.block_producer.validator();
+        let mut block_env = validator.block_env();
+        self.backend.update_block_gas_prices(&mut block_env);
+        validator.update_block_env(block_env);
+    }
+
     pub fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), DevApiError> {
         if self.has_pending_transactions() {
             return Err(DevApiError::PendingTransactions);
@@ -57,6 +171,270 @@ impl<EF: ExecutorFactory> DevApi<EF> {
 
         Ok(())
     }
+
+    /// Returns the state that the cheatcodes are applied on top of, ie the pending state in
+    /// _interval_ mode or the latest state otherwise.
+    fn state(&self) -> Result<Box<dyn StateProvider>, DevApiError> {
+        if let Some(exec) = self.pending_executor() {
+            Ok(exec.read().state())
+        } else {
+            self.backend.blockchain.provider().latest().map_err(|error| {
+                error!(%error, "Failed to get latest state.");
+                DevApiError::FailedToUpdateState
+            })
+        }
+    }
+
+    fn apply_state_updates(&self, updates: StateUpdates) -> Result<(), DevApiError> {
+        self.block_producer.apply_state_updates(updates).map_err(|error| {
+            error!(%error, "Failed to apply state updates.");
+            DevApiError::FailedToUpdateState
+        })
+    }
+
+    pub fn set_storage_at(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), DevApiError> {
+        let mut updates = StateUpdates::default();
+        updates.storage_updates.entry(address).or_default().insert(key, value);
+        self.apply_state_updates(updates)
+    }
+
+    pub fn set_nonce(&self, address: ContractAddress, nonce: Nonce) -> Result<(), DevApiError> {
+        let mut updates = StateUpdates::default();
+        updates.nonce_updates.insert(address, nonce);
+        self.apply_state_updates(updates)
+    }
+
+    pub fn set_class_hash(
+        &self,
+        address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> Result<(), DevApiError> {
+        self.ensure_class_declared(class_hash)?;
+
+        let mut updates = StateUpdates::default();
+        updates.replaced_classes.insert(address, class_hash);
+        self.apply_state_updates(updates)
+    }
+
+    fn ensure_class_declared(&self, class_hash: ClassHash) -> Result<(), DevApiError> {
+        let state = self.state()?;
+        let declared = state.compiled_class_hash_of_class_hash(class_hash).map_err(|error| {
+            error!(%error, "Failed to get compiled class hash.");
+            DevApiError::FailedToUpdateState
+        })?;
+
+        if declared.is_none() {
+            return Err(DevApiError::ClassNotDeclared);
+        }
+
+        Ok(())
+    }
+
+    /// Creates an account of class `class_hash` with a random key pair. The account is deployed
+    /// by writing its class and public key directly to the state, in the same way as the genesis
+    /// accounts, and is recorded in the database.
+    ///
+    /// As the constructor isn't run, only the account classes whose public key slot is known are
+    /// supported: the default account class, and the OpenZeppelin and Argent ones if declared.
+    pub fn create_account(&self, class_hash: ClassHash) -> Result<Account, DevApiError> {
+        // the Argent constructor takes the guardian along with the owner key, which is left unset
+        let (pubkey_slot, guardian) =
+            if class_hash == DEFAULT_ACCOUNT_CLASS_HASH || class_hash == OZ_ACCOUNT_CLASS_HASH {
+                (DEFAULT_ACCOUNT_CLASS_PUBKEY_STORAGE_SLOT, None)
+            } else if class_hash == ARGENT_ACCOUNT_CLASS_HASH {
+                (ARGENT_ACCOUNT_CLASS_SIGNER_STORAGE_SLOT, Some(Felt::ZERO))
+            } else {
+                return Err(DevApiError::UnsupportedAccountClass);
+            };
+
+        self.ensure_class_declared(class_hash)?;
+
+        let private_key = SigningKey::from_random().secret_scalar();
+        let public_key = SigningKey::from_secret_scalar(private_key).verifying_key().scalar();
+
+        let calldata = [public_key].into_iter().chain(guardian).collect::<Vec<_>>();
+        let address: ContractAddress =
+            get_contract_address(GenesisAccount::DEFAULT_SALT, class_hash, &calldata, Felt::ZERO)
+                .into();
+
+        let mut updates = StateUpdates::default();
+        updates.deployed_contracts.insert(address, class_hash);
+        updates.storage_updates.entry(address).or_default().insert(pubkey_slot, public_key);
+        self.apply_state_updates(updates)?;
+
+        let record = DevAccount { private_key, public_key, class_hash };
+        self.backend.blockchain.provider().set_dev_account(address, record).map_err(|error| {
+            error!(%error, %address, "Failed to store account.");
+            DevApiError::FailedToCreateAccount
+        })?;
+
+        let balance = self.balance(&*self.state()?, address).map_err(|error| {
+            error!(%error, %address, "Failed to get account balance.");
+            DevApiError::FailedToCreateAccount
+        })?;
+
+        Ok(Account { address, public_key, private_key: Some(private_key), class_hash, balance })
+    }
+
+    /// Returns the genesis accounts followed by the accounts created at runtime, with their
+    /// current fee token balances.
+    ///
+    /// The created accounts are kept in the database when the chain is reverted, so the ones that
+    /// are no longer deployed in the current state are skipped.
+    pub fn accounts(&self) -> Result<Vec<Account>, DevApiError> {
+        let created = self.backend.blockchain.provider().dev_accounts().map_err(|error| {
+            error!(%error, "Failed to get accounts.");
+            DevApiError::FailedToGetAccounts
+        })?;
+
+        let state = self.state()?;
+        let balance = |address| {
+            self.balance(&*state, address).map_err(|error| {
+                error!(%error, %address, "Failed to get account balance.");
+                DevApiError::FailedToGetAccounts
+            })
+        };
+
+        let mut accounts = Vec::new();
+
+        for (address, account) in self.backend.chain_spec.genesis().accounts() {
+            let mut account = Account::new(*address, account);
+            account.balance = balance(*address)?;
+            accounts.push(account);
+        }
+
+        for (address, account) in created {
+            let class_hash = state.class_hash_of_contract(address).map_err(|error| {
+                error!(%error, %address, "Failed to get account class hash.");
+                DevApiError::FailedToGetAccounts
+            })?;
+
+            if class_hash.is_some() {
+                accounts.push(Account {
+                    address,
+                    public_key: account.public_key,
+                    private_key: Some(account.private_key),
+                    class_hash: account.class_hash,
+                    balance: balance(address)?,
+                });
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    /// Returns the balance of `address` in the default fee token (STRK).
+    fn balance(&self, state: &dyn StateProvider, address: ContractAddress) -> ProviderResult<U256> {
+        let token = self.backend.executor_factory.cfg().fee_token_addresses.strk;
+        read_u256(state, token, get_fee_token_balance_base_storage_address(address))
+    }
+
+    /// Mints `amount` of the fee token to `address`, increasing the token's total supply
+    /// accordingly.
+    pub fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        unit: PriceUnit,
+    ) -> Result<(), DevApiError> {
+        let fee_tokens = &self.backend.executor_factory.cfg().fee_token_addresses;
+        let token = match unit {
+            PriceUnit::Wei => fee_tokens.eth,
+            PriceUnit::Fri => fee_tokens.strk,
+        };
+
+        let state = self.state()?;
+        let read = |slot: StorageKey| {
+            read_u256(&*state, token, slot).map_err(|error| {
+                error!(%error, "Failed to read fee token storage.");
+                DevApiError::FailedToUpdateState
+            })
+        };
+
+        let balance_slot = get_fee_token_balance_base_storage_address(address);
+        let balance = read(balance_slot)?.saturating_add(amount);
+        let total_supply = read(ERC20_TOTAL_SUPPLY_STORAGE_SLOT)?.saturating_add(amount);
+
+        let mut updates = StateUpdates::default();
+        let storage = updates.storage_updates.entry(token).or_default();
+
+        let (low, high) = split_u256(balance);
+        storage.insert(balance_slot, low);
+        storage.insert(balance_slot + Felt::ONE, high);
+
+        let (low, high) = split_u256(total_supply);
+        storage.insert(ERC20_TOTAL_SUPPLY_STORAGE_SLOT, low);
+        storage.insert(ERC20_TOTAL_SUPPLY_STORAGE_SLOT + Felt::ONE, high);
+
+        self.apply_state_updates(updates)
+    }
+
+    /// Records the latest block as a snapshot and returns the snapshot id.
+    pub fn snapshot(&self) -> Result<u64, DevApiError> {
+        let block = self.backend.blockchain.provider().latest_number().map_err(|error| {
+            error!(%error, "Failed to get latest block number.");
+            DevApiError::FailedToTakeSnapshot
+        })?;
+
+        let mut snapshots = self.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += 1;
+        snapshots.blocks.insert(id, block);
+
+        Ok(id)
+    }
+
+    /// Reverts the chain back to the block of the snapshot `id`. The snapshot and all the ones
+    /// taken after it are discarded, and so are the transactions in the pool.
+    pub fn revert(&self, id: u64) -> Result<(), DevApiError> {
+        let mut snapshots = self.snapshots.lock();
+        let block = *snapshots.blocks.get(&id).ok_or(DevApiError::SnapshotNotFound)?;
+
+        self.block_producer.revert_to(block).map_err(|error| {
+            error!(%error, %block, "Failed to revert chain.");
+            DevApiError::FailedToRevert
+        })?;
+
+        self.pool.clear();
+        snapshots.blocks.split_off(&id);
+        Ok(())
+    }
+
+    /// Skips the account validation of the transactions sent by `address`, both in the pool and
+    /// during execution.
+    pub fn impersonate_account(&self, address: ContractAddress) {
+        self.backend.executor_factory.execution_flags().impersonated_accounts().add(address);
+    }
+
+    pub fn stop_impersonating_account(&self, address: ContractAddress) {
+        self.backend.executor_factory.execution_flags().impersonated_accounts().remove(address);
+    }
+}
+
+fn gas_prices_of(block_env: &BlockEnv) -> GasPrices {
+    GasPrices {
+        l1_gas_prices: block_env.l1_gas_prices.clone(),
+        l1_data_gas_prices: block_env.l1_data_gas_prices.clone(),
+        l2_gas_prices: block_env.l2_gas_prices.clone(),
+    }
+}
+
+/// Reads the `u256` stored across the two consecutive slots starting at `slot` of `contract`.
+fn read_u256(
+    state: &dyn StateProvider,
+    contract: ContractAddress,
+    slot: StorageKey,
+) -> ProviderResult<U256> {
+    let low = state.storage(contract, slot)?.unwrap_or_default();
+    let high = state.storage(contract, slot + Felt::ONE)?.unwrap_or_default();
+    let low = U256::from_be_bytes(low.to_bytes_be());
+    let high = U256::from_be_bytes(high.to_bytes_be());
+    Ok(low + (high << 128))
 }
 
 #[async_trait]
@@ -66,9 +444,20 @@ impl<EF: ExecutorFactory> DevApiServer for DevApi<EF> {
         Ok(())
     }
 
-    async fn next_block_timestamp(&self) -> RpcResult<()> {
-        // Ok(self.sequencer.backend().env.read().block.block_timestamp.0)
-        Ok(())
+    async fn mine(&self, blocks: Option<u64>, interval_seconds: Option<u64>) -> RpcResult<()> {
+        Ok(self.mine(blocks.unwrap_or(1), interval_seconds)?)
+    }
+
+    async fn next_block_timestamp(&self) -> RpcResult<u64> {
+        Ok(self.next_block_timestamp())
+    }
+
+    async fn set_block_time(&self, block_time: u64) -> RpcResult<()> {
+        Ok(self.set_mining_mode(MiningMode::Interval(block_time))?)
+    }
+
+    async fn set_mining_mode(&self, mode: MiningMode) -> RpcResult<()> {
+        Ok(self.set_mining_mode(mode)?)
     }
 
     async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()> {
@@ -79,20 +468,86 @@ impl<EF: ExecutorFactory> DevApiServer for DevApi<EF> {
         Ok(self.increase_next_block_timestamp(timestamp)?)
     }
 
+    async fn set_gas_prices(&self, prices: GasPrice) -> RpcResult<()> {
+        self.set_gas_prices(prices);
+        Ok(())
+    }
+
+    async fn set_data_gas_prices(&self, prices: GasPrice) -> RpcResult<()> {
+        self.set_data_gas_prices(prices);
+        Ok(())
+    }
+
+    async fn get_gas_prices(&self) -> RpcResult<GasPrices> {
+        Ok(self.gas_prices())
+    }
+
     async fn set_storage_at(
         &self,
-        _contract_address: Felt,
-        _key: Felt,
-        _value: Felt,
+        contract_address: Felt,
+        key: Felt,
+        value: Felt,
+    ) -> RpcResult<()> {
+        Ok(self.set_storage_at(contract_address.into(), key, value)?)
+    }
+
+    async fn set_nonce(&self, contract_address: ContractAddress, nonce: Nonce) -> RpcResult<()> {
+        Ok(self.set_nonce(contract_address, nonce)?)
+    }
+
+    async fn set_class_hash(
+        &self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> RpcResult<()> {
+        Ok(self.set_class_hash(contract_address, class_hash)?)
+    }
+
+    async fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        unit: Option<PriceUnit>,
+    ) -> RpcResult<()> {
+        Ok(self.mint(address, amount, unit.unwrap_or_default())?)
+    }
+
+    async fn create_account(&self, class_hash: Option<ClassHash>) -> RpcResult<Account> {
+        Ok(self.create_account(class_hash.unwrap_or(DEFAULT_ACCOUNT_CLASS_HASH))?)
+    }
+
+    async fn fund_account(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        token: Option<FeeToken>,
     ) -> RpcResult<()> {
-        // self.sequencer
-        //     .set_storage_at(contract_address.into(), key, value)
-        //     .await
-        //     .map_err(|_| Error::from(KatanaApiError::FailedToUpdateStorage))
+        let unit = match token.unwrap_or_default() {
+            FeeToken::Eth => PriceUnit::Wei,
+            FeeToken::Strk => PriceUnit::Fri,
+        };
+        Ok(self.mint(address, amount, unit)?)
+    }
+
+    async fn snapshot(&self) -> RpcResult<u64> {
+        Ok(self.snapshot()?)
+    }
+
+    async fn revert(&self, id: u64) -> RpcResult<()> {
+        Ok(self.revert(id)?)
+    }
+
+    async fn impersonate_account(&self, address: ContractAddress) -> RpcResult<()> {
+        self.impersonate_account(address);
+        Ok(())
+    }
+
+    async fn stop_impersonating_account(&self, address: ContractAddress) -> RpcResult<()> {
+        self.stop_impersonating_account(address);
         Ok(())
     }
 
     async fn predeployed_accounts(&self) -> RpcResult<Vec<Account>> {
-        Ok(self.backend.chain_spec.genesis().accounts().map(|e| Account::new(*e.0, e.1)).collect())
+        Ok(self.accounts()?)
     }
 }
@@ -0,0 +1,374 @@
+//! Starknet feeder gateway API served from the node's own storage.
+//!
+//! Only the read-only endpoints required to sync a node are supported:
+//!
+//! - `GET /feeder_gateway/get_block`
+//! - `GET /feeder_gateway/get_state_update`
+//! - `GET /feeder_gateway/get_class_by_hash`
+//! - `GET /feeder_gateway/get_compiled_class_by_class_hash`
+//!
+//! The block is selected using the `blockNumber` (a number, `latest` or `pending`) or `blockHash`
+//! query parameters, and defaults to the latest block if none is specified. Errors are returned in
+//! the same format as the Starknet feeder gateway so that existing clients (eg,
+//! [`SequencerGateway`](katana_feeder_gateway::client::SequencerGateway)) can handle them.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+use jsonrpsee::core::http_helpers::{Body, Request, Response};
+use jsonrpsee::core::BoxError;
+use katana_feeder_gateway::client::{ErrorCode, SequencerError};
+use katana_feeder_gateway::types::{
+    Block, ContractClass, StateDiff, StateUpdate, StateUpdateWithBlock,
+};
+use katana_primitives::block::{BlockIdOrTag, BlockNumber, BlockTag};
+use katana_primitives::class::{ClassHash, CompiledClass};
+use katana_primitives::Felt;
+use katana_provider::error::ProviderError;
+use katana_provider::traits::block::{
+    BlockHashProvider, BlockIdReader, BlockNumberProvider, BlockProvider, BlockStatusProvider,
+    HeaderProvider,
+};
+use katana_provider::traits::contract::{ContractClassProvider, ContractClassProviderExt};
+use katana_provider::traits::state::{StateFactoryProvider, StateProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::ReceiptProvider;
+use katana_rpc_types::class::ConversionError;
+use serde::Serialize;
+use tower::{Layer, Service};
+use tracing::error;
+
+const LOG_TARGET: &str = "rpc::feeder_gateway";
+
+/// The base path of the feeder gateway endpoints.
+const BASE_PATH: &str = "/feeder_gateway";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Block not found")]
+    BlockNotFound,
+
+    #[error("Class with hash {0:#x} is not declared")]
+    ClassNotFound(ClassHash),
+
+    #[error("Unsupported endpoint: {0}")]
+    UnsupportedEndpoint(String),
+
+    #[error("Malformed request: {0}")]
+    MalformedRequest(String),
+
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+
+    #[error(transparent)]
+    ClassConversion(#[from] ConversionError),
+}
+
+impl Error {
+    /// Returns the feeder gateway error code of this error, or `None` if it's an internal error.
+    fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::BlockNotFound => Some(ErrorCode::BlockNotFound),
+            Self::ClassNotFound(_) => Some(ErrorCode::UndeclaredClass),
+            Self::UnsupportedEndpoint(_) | Self::MalformedRequest(_) => {
+                Some(ErrorCode::MalformedRequest)
+            }
+            Self::Provider(_) | Self::ClassConversion(_) => None,
+        }
+    }
+}
+
+/// Implementation of the feeder gateway endpoints on top of a storage provider.
+#[derive(Debug, Clone)]
+pub struct FeederGateway<P> {
+    provider: P,
+}
+
+impl<P> FeederGateway<P>
+where
+    P: BlockIdReader + BlockProvider + ReceiptProvider + StateUpdateProvider + StateFactoryProvider,
+{
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Returns the block with the given id, along with the receipts of its transactions.
+    pub fn block(&self, block_id: BlockIdOrTag) -> Result<Block, Error> {
+        let number = self.block_number(block_id)?;
+
+        let block = self.provider.block(number.into())?.ok_or(Error::BlockNotFound)?;
+        let hash = self.provider.block_hash_by_num(number)?.ok_or(Error::BlockNotFound)?;
+        let status = self.provider.block_status(number.into())?.ok_or(Error::BlockNotFound)?;
+        let receipts =
+            self.provider.receipts_by_block(number.into())?.ok_or(Error::BlockNotFound)?;
+
+        Ok(Block::new(block.seal_with_hash_and_status(hash, status), receipts))
+    }
+
+    /// Returns the state update of the block with the given id.
+    pub fn state_update(&self, block_id: BlockIdOrTag) -> Result<StateUpdate, Error> {
+        let number = self.block_number(block_id)?;
+
+        let header = self.provider.header(number.into())?.ok_or(Error::BlockNotFound)?;
+        let hash = self.provider.block_hash_by_num(number)?.ok_or(Error::BlockNotFound)?;
+        let state_diff = self.provider.state_update(number.into())?.ok_or(Error::BlockNotFound)?;
+
+        // The state root before the genesis block is zero.
+        let old_root = match number.checked_sub(1) {
+            Some(parent) => {
+                let parent = self.provider.header(parent.into())?.ok_or(Error::BlockNotFound)?;
+                parent.state_root
+            }
+            None => Felt::ZERO,
+        };
+
+        Ok(StateUpdate {
+            old_root,
+            block_hash: Some(hash),
+            new_root: Some(header.state_root),
+            state_diff: StateDiff::from(state_diff),
+        })
+    }
+
+    /// Returns the state update of the block with the given id, along with the block itself.
+    pub fn state_update_with_block(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> Result<StateUpdateWithBlock, Error> {
+        let number = self.block_number(block_id)?;
+        let state_update = self.state_update(BlockIdOrTag::Number(number))?;
+        let block = self.block(BlockIdOrTag::Number(number))?;
+        Ok(StateUpdateWithBlock { state_update, block })
+    }
+
+    /// Returns the definition of the class with the given hash, as of the given block.
+    pub fn class(&self, hash: ClassHash, block_id: BlockIdOrTag) -> Result<ContractClass, Error> {
+        let state = self.state(block_id)?;
+        let class = state.class(hash)?.ok_or(Error::ClassNotFound(hash))?;
+        Ok(ContractClass::try_from(class)?)
+    }
+
+    /// Returns the compiled (ie CASM) definition of the class with the given hash, as of the given
+    /// block.
+    pub fn compiled_class(
+        &self,
+        hash: ClassHash,
+        block_id: BlockIdOrTag,
+    ) -> Result<CompiledClass, Error> {
+        let state = self.state(block_id)?;
+        state.compiled_class(hash)?.ok_or(Error::ClassNotFound(hash))
+    }
+
+    fn block_number(&self, block_id: BlockIdOrTag) -> Result<BlockNumber, Error> {
+        let number = self.provider.convert_block_id(block_id)?.ok_or(Error::BlockNotFound)?;
+
+        // Block numbers are returned as is by `convert_block_id`, so make sure the block exists.
+        if number > self.provider.latest_number()? {
+            return Err(Error::BlockNotFound);
+        }
+
+        Ok(number)
+    }
+
+    fn state(&self, block_id: BlockIdOrTag) -> Result<Box<dyn StateProvider>, Error> {
+        let number = self.block_number(block_id)?;
+        self.provider.historical(number.into())?.ok_or(Error::BlockNotFound)
+    }
+
+    fn serve(&self, endpoint: &str, query: Option<&str>) -> Result<serde_json::Value, Error> {
+        let params = QueryParams::parse(query);
+        let block_id = params.block_id()?;
+
+        let value = match endpoint {
+            "get_block" => serde_json::to_value(self.block(block_id)?),
+            "get_state_update" if params.include_block() => {
+                serde_json::to_value(self.state_update_with_block(block_id)?)
+            }
+            "get_state_update" => serde_json::to_value(self.state_update(block_id)?),
+            "get_class_by_hash" => {
+                serde_json::to_value(self.class(params.class_hash()?, block_id)?)
+            }
+            "get_compiled_class_by_class_hash" => {
+                serde_json::to_value(self.compiled_class(params.class_hash()?, block_id)?)
+            }
+            _ => return Err(Error::UnsupportedEndpoint(endpoint.to_string())),
+        };
+
+        Ok(value.expect("failed to serialize response"))
+    }
+}
+
+/// The query parameters of a feeder gateway request.
+#[derive(Debug)]
+struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    fn parse(query: Option<&str>) -> Self {
+        let query = query.unwrap_or_default();
+        Self(url::form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn block_id(&self) -> Result<BlockIdOrTag, Error> {
+        if let Some(hash) = self.get("blockHash") {
+            return Ok(BlockIdOrTag::Hash(parse_felt("blockHash", hash)?));
+        }
+
+        match self.get("blockNumber") {
+            None | Some("latest") => Ok(BlockIdOrTag::Tag(BlockTag::Latest)),
+            Some("pending") => Ok(BlockIdOrTag::Tag(BlockTag::Pending)),
+            Some(number) => number
+                .parse::<BlockNumber>()
+                .map(BlockIdOrTag::Number)
+                .map_err(|_| Error::MalformedRequest(format!("invalid block number: {number}"))),
+        }
+    }
+
+    fn class_hash(&self) -> Result<ClassHash, Error> {
+        let hash = self
+            .get("classHash")
+            .ok_or_else(|| Error::MalformedRequest("missing `classHash` parameter".to_string()))?;
+        parse_felt("classHash", hash)
+    }
+
+    fn include_block(&self) -> bool {
+        self.get("includeBlock").is_some_and(|value| value.eq_ignore_ascii_case("true"))
+    }
+}
+
+fn parse_felt(key: &str, value: &str) -> Result<Felt, Error> {
+    Felt::from_hex(value)
+        .map_err(|_| Error::MalformedRequest(format!("invalid `{key}` parameter: {value}")))
+}
+
+/// Type-erased feeder gateway, so that the layer doesn't depend on the provider type.
+trait Handler: Debug + Send + Sync {
+    fn handle(&self, endpoint: &str, query: Option<&str>) -> Response;
+}
+
+impl<P> Handler for FeederGateway<P>
+where
+    P: BlockIdReader
+        + BlockProvider
+        + ReceiptProvider
+        + StateUpdateProvider
+        + StateFactoryProvider
+        + Debug,
+{
+    fn handle(&self, endpoint: &str, query: Option<&str>) -> Response {
+        match self.serve(endpoint, query) {
+            Ok(value) => json_response(StatusCode::OK, &value),
+            Err(error) => match error.code() {
+                Some(code) => {
+                    let error = SequencerError { code, message: error.to_string() };
+                    json_response(StatusCode::BAD_REQUEST, &error)
+                }
+                None => {
+                    error!(target: LOG_TARGET, %error, %endpoint, "Failed to serve request.");
+                    Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(error.to_string()))
+                        .expect("good data; qed")
+                }
+            },
+        }
+    }
+}
+
+fn json_response<T: Serialize>(status: StatusCode, value: &T) -> Response {
+    let body = serde_json::to_vec(value).expect("failed to serialize response");
+    let mut response = Response::builder().status(status).body(Body::from(body)).unwrap();
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// Layer that serves the feeder gateway endpoints under `/feeder_gateway`. All other requests are
+/// passed to the inner service.
+#[derive(Debug, Clone)]
+pub struct FeederGatewayLayer {
+    gateway: Arc<dyn Handler>,
+}
+
+impl FeederGatewayLayer {
+    pub fn new<P>(provider: P) -> Self
+    where
+        P: BlockIdReader
+            + BlockProvider
+            + ReceiptProvider
+            + StateUpdateProvider
+            + StateFactoryProvider
+            + Debug
+            + 'static,
+    {
+        Self { gateway: Arc::new(FeederGateway::new(provider)) }
+    }
+}
+
+impl<S> Layer<S> for FeederGatewayLayer {
+    type Service = FeederGatewayService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FeederGatewayService { inner, gateway: self.gateway.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeederGatewayService<S> {
+    inner: S,
+    gateway: Arc<dyn Handler>,
+}
+
+impl<S, B> Service<Request<B>> for FeederGatewayService<S>
+where
+    B::Data: Send,
+    S::Response: 'static,
+    B::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+    S: Service<Request<B>, Response = Response>,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        // If the path is not a feeder gateway endpoint, pass the request to the inner service.
+        let Some(endpoint) = req.uri().path().strip_prefix(BASE_PATH) else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let endpoint = endpoint.trim_start_matches('/').to_string();
+        let query = req.uri().query().map(ToString::to_string);
+        let gateway = self.gateway.clone();
+
+        Box::pin(async move {
+            // Reading from the storage is blocking, so we don't want to do it on the async runtime.
+            let result =
+                tokio::task::spawn_blocking(move || gateway.handle(&endpoint, query.as_deref()))
+                    .await;
+
+            let response = result.unwrap_or_else(|error| {
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(error.to_string()))
+                    .expect("good data; qed")
+            });
+
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use jsonrpsee::core::{async_trait, RpcResult};
+use katana_primitives::block::{BlockHash, BlockIdOrTag, BlockNumber};
+use katana_primitives::da::kzg::state_updates_to_blobs;
+use katana_primitives::da::payload::state_updates_to_payload;
+use katana_primitives::da::L1DataAvailabilityMode;
+use katana_primitives::state::StateUpdates;
+use katana_provider::error::ProviderError;
+use katana_provider::traits::block::{BlockHashProvider, BlockIdReader, HeaderProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_rpc_api::error::katana::KatanaApiError;
+use katana_rpc_api::katana::{KatanaApiServer, MAX_DA_PAYLOAD_BLOCK_RANGE};
+use katana_rpc_types::da::{BlockBlobs, BlockDaPayload};
+use katana_tasks::TokioTaskSpawner;
+use tracing::error;
+
+#[derive(Debug, Clone)]
+pub struct KatanaApi<P> {
+    provider: P,
+}
+
+impl<P> KatanaApi<P>
+where
+    P: BlockIdReader + BlockHashProvider + HeaderProvider + StateUpdateProvider,
+{
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Returns the blobs encoding the state diff of the block with the given id.
+    ///
+    /// The blobs aren't stored but are recomputed from the state diff of the block on every call.
+    pub fn block_blobs(&self, block_id: BlockIdOrTag) -> Result<BlockBlobs, KatanaApiError> {
+        let block_number = self.block_number(block_id)?;
+
+        let header = self
+            .provider
+            .header(block_number.into())
+            .map_err(unexpected)?
+            .ok_or(KatanaApiError::BlockNotFound)?;
+
+        if header.l1_da_mode != L1DataAvailabilityMode::Blob {
+            return Err(KatanaApiError::BlobDataAvailabilityNotEnabled);
+        }
+
+        let block_hash = self.block_hash(block_number)?;
+        let state_updates = self.state_updates(block_number)?;
+
+        let blobs = state_updates_to_blobs(state_updates).map_err(|error| {
+            error!(%error, block = %block_number, "Failed to compute block blobs.");
+            KatanaApiError::FailedToComputeBlobs
+        })?;
+
+        Ok(BlockBlobs { block_hash, block_number, blobs })
+    }
+
+    /// Returns the data availability payloads of the blocks from `from_block` to `to_block`
+    /// (inclusive), or only of `from_block` if `to_block` is not specified.
+    pub fn block_da_payloads(
+        &self,
+        from_block: BlockIdOrTag,
+        to_block: Option<BlockIdOrTag>,
+    ) -> Result<Vec<BlockDaPayload>, KatanaApiError> {
+        let from = self.block_number(from_block)?;
+        let to = match to_block {
+            Some(id) => self.block_number(id)?,
+            None => from,
+        };
+
+        if to < from {
+            return Err(KatanaApiError::InvalidBlockRange);
+        }
+
+        if to - from >= MAX_DA_PAYLOAD_BLOCK_RANGE {
+            return Err(KatanaApiError::BlockRangeTooLarge);
+        }
+
+        (from..=to).map(|block_number| self.block_da_payload(block_number)).collect()
+    }
+
+    fn block_da_payload(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<BlockDaPayload, KatanaApiError> {
+        let block_hash = self.block_hash(block_number)?;
+        let state_updates = self.state_updates(block_number)?;
+
+        let payload = state_updates_to_payload(state_updates).map_err(|error| {
+            error!(%error, block = %block_number, "Failed to compute block DA payload.");
+            KatanaApiError::FailedToComputeDaPayload
+        })?;
+
+        Ok(BlockDaPayload { block_hash, block_number, payload })
+    }
+
+    fn block_number(&self, block_id: BlockIdOrTag) -> Result<BlockNumber, KatanaApiError> {
+        let number = self.provider.convert_block_id(block_id).map_err(unexpected)?;
+        number.ok_or(KatanaApiError::BlockNotFound)
+    }
+
+    fn block_hash(&self, block_number: BlockNumber) -> Result<BlockHash, KatanaApiError> {
+        let hash = self.provider.block_hash_by_num(block_number).map_err(unexpected)?;
+        hash.ok_or(KatanaApiError::BlockNotFound)
+    }
+
+    fn state_updates(&self, block_number: BlockNumber) -> Result<StateUpdates, KatanaApiError> {
+        let state_updates = self.provider.state_update(block_number.into()).map_err(unexpected)?;
+        state_updates.ok_or(KatanaApiError::BlockNotFound)
+    }
+}
+
+fn unexpected(error: ProviderError) -> KatanaApiError {
+    error!(%error, "Failed to read block data.");
+    KatanaApiError::UnexpectedError
+}
+
+#[async_trait]
+impl<P> KatanaApiServer for KatanaApi<P>
+where
+    P: BlockIdReader
+        + BlockHashProvider
+        + HeaderProvider
+        + StateUpdateProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn get_block_blobs(&self, block_id: BlockIdOrTag) -> RpcResult<BlockBlobs> {
+        Ok(self.on_io_blocking_task(move |this| this.block_blobs(block_id)).await?)
+    }
+
+    async fn get_block_da_payload(
+        &self,
+        block_id: BlockIdOrTag,
+        to_block: Option<BlockIdOrTag>,
+    ) -> RpcResult<Vec<BlockDaPayload>> {
+        Ok(self.on_io_blocking_task(move |this| this.block_da_payloads(block_id, to_block)).await?)
+    }
+}
+
+impl<P> KatanaApi<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    async fn on_io_blocking_task<F, T>(&self, func: F) -> Result<T, KatanaApiError>
+    where
+        F: FnOnce(Self) -> Result<T, KatanaApiError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let this = self.clone();
+        let spawner = TokioTaskSpawner::new().map_err(|error| {
+            error!(%error, "Failed to create task spawner.");
+            KatanaApiError::UnexpectedError
+        })?;
+
+        spawner.spawn_blocking(move || func(this)).await.map_err(|error| {
+            error!(%error, "Blocking task failed.");
+            KatanaApiError::UnexpectedError
+        })?
+    }
+}
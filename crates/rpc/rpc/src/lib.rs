@@ -20,7 +20,9 @@ pub mod cartridge;
 
 pub mod cors;
 pub mod dev;
+pub mod feeder_gateway;
 pub mod health;
+pub mod katana;
 pub mod metrics;
 pub mod permit;
 pub mod starknet;
@@ -28,9 +30,12 @@ pub mod starknet;
 mod logger;
 mod utils;
 use cors::Cors;
+use feeder_gateway::FeederGatewayLayer;
 use health::HealthCheck;
 #[cfg(feature = "client")]
 pub use jsonrpsee::http_client::HttpClient;
+#[cfg(feature = "client")]
+pub use jsonrpsee::ws_client::WsClient;
 pub use katana_rpc_api as api;
 use metrics::RpcServerMetricsLayer;
 
@@ -38,6 +43,8 @@ use metrics::RpcServerMetricsLayer;
 pub const DEFAULT_RPC_MAX_CONNECTIONS: u32 = 100;
 /// The default maximum number of concurrent estimate_fee requests.
 pub const DEFAULT_ESTIMATE_FEE_MAX_CONCURRENT_REQUESTS: u32 = 10;
+/// The default maximum gas of the `starknet_call` and `debug_traceCall` methods.
+pub const DEFAULT_MAX_CALL_GAS: u64 = 1_000_000_000;
 /// The default maximum size in bytes for an RPC request body.
 pub const DEFAULT_MAX_REQUEST_BODY_SIZE: u32 = TEN_MB_SIZE_BYTES;
 /// The default maximum size in bytes for an RPC response body.
@@ -92,6 +99,14 @@ impl RpcServerHandle {
         let url = format!("http://{}", self.addr);
         Ok(HttpClientBuilder::default().build(url)?)
     }
+
+    /// Returns a WebSocket client associated with the server.
+    #[cfg(feature = "client")]
+    pub async fn ws_client(&self) -> Result<WsClient, Error> {
+        use jsonrpsee::ws_client::WsClientBuilder;
+        let url = format!("ws://{}", self.addr);
+        Ok(WsClientBuilder::default().build(url).await?)
+    }
 }
 
 #[derive(Debug)]
@@ -100,6 +115,7 @@ pub struct RpcServer {
     cors: Option<Cors>,
     health_check: bool,
     explorer: bool,
+    feeder_gateway: Option<FeederGatewayLayer>,
 
     module: RpcModule<()>,
     max_connections: u32,
@@ -115,6 +131,7 @@ impl RpcServer {
             metrics: false,
             explorer: false,
             health_check: false,
+            feeder_gateway: None,
             module: RpcModule::new(()),
             max_connections: 100,
             max_request_body_size: TEN_MB_SIZE_BYTES,
@@ -167,6 +184,14 @@ impl RpcServer {
         self
     }
 
+    /// Enables the feeder gateway endpoints via HTTP `GET /feeder_gateway/<endpoint>`.
+    ///
+    /// See [`crate::feeder_gateway`] for the list of supported endpoints.
+    pub fn feeder_gateway(mut self, layer: FeederGatewayLayer) -> Self {
+        self.feeder_gateway = Some(layer);
+        self
+    }
+
     pub fn cors(mut self, cors: Cors) -> Self {
         self.cors = Some(cors);
         self
@@ -211,6 +236,7 @@ impl RpcServer {
             .layer(http_tracer)
             .option_layer(self.cors.clone())
             .option_layer(health_check_proxy)
+            .option_layer(self.feeder_gateway.clone())
             .timeout(self.timeout);
 
         #[cfg(feature = "explorer")]
@@ -243,6 +269,11 @@ impl RpcServer {
 
         info!(target: "rpc", addr = %handle.addr, "RPC server started.");
 
+        if self.feeder_gateway.is_some() {
+            let addr = format!("{}/feeder_gateway", handle.addr);
+            info!(target: "rpc", %addr, "Feeder gateway started.");
+        }
+
         if self.explorer {
             let addr = format!("{}/explorer", handle.addr);
             info!(target: "explorer", %addr, "Explorer started.");
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use katana_executor::implementation::blockifier::cache::ClassCache;
+use katana_executor::implementation::blockifier::call::CallTrace;
 use katana_executor::implementation::blockifier::state::CachedState;
 use katana_executor::implementation::blockifier::utils::{self, block_context_from_envs};
 use katana_executor::{
@@ -31,7 +32,8 @@ pub fn simulate(
             // Safe to unwrap here because the only way the call to `transact` can return an error
             // is when bouncer is `Some`.
             let result = utils::transact(state, &block_context, &flags, tx, None).unwrap();
-            let simulated_result = ResultAndStates { result, states: Default::default() };
+            let states = result.state_diff().cloned().unwrap_or_default();
+            let simulated_result = ResultAndStates { result, states };
 
             results.push(simulated_result);
         }
@@ -119,3 +121,24 @@ pub fn call<P: StateProvider>(
         )
     })
 }
+
+#[tracing::instrument(level = "trace", target = "rpc", skip_all)]
+pub fn trace_call<P: StateProvider>(
+    state: P,
+    block_env: BlockEnv,
+    cfg_env: CfgEnv,
+    call: EntryPointCall,
+    max_call_gas: u64,
+) -> Result<CallTrace, ExecutionError> {
+    let block_context = Arc::new(block_context_from_envs(&block_env, &cfg_env));
+    let state = CachedState::new(state, ClassCache::global().clone());
+
+    state.with_mut_cached_state(|state| {
+        katana_executor::implementation::blockifier::call::trace_call(
+            call,
+            state,
+            block_context,
+            max_call_gas,
+        )
+    })
+}
@@ -10,6 +10,9 @@ pub struct StarknetApiConfig {
     /// If `None`, the maximum keys size is bounded by [`u64::MAX`].
     pub max_proof_keys: Option<u64>,
 
+    /// The maximum gas that a call from the `call` method can consume.
+    ///
+    /// If `None`, defaults to [`DEFAULT_MAX_CALL_GAS`](crate::DEFAULT_MAX_CALL_GAS).
     pub max_call_gas: Option<u64>,
 
     /// The maximum number of concurrent `estimate_fee` requests allowed.
@@ -0,0 +1,56 @@
+use jsonrpsee::core::{async_trait, RpcResult};
+use katana_executor::{EntryPointCall, ExecutorFactory};
+use katana_primitives::block::BlockIdOrTag;
+use katana_primitives::transaction::TxHash;
+use katana_rpc_api::debug::DebugApiServer;
+use katana_rpc_api::error::starknet::StarknetApiError;
+use katana_rpc_types::debug::{CallTrace, TxTrace};
+use katana_rpc_types::FunctionCall;
+
+use super::StarknetApi;
+use crate::DEFAULT_MAX_CALL_GAS;
+
+impl<EF: ExecutorFactory> StarknetApi<EF> {
+    fn trace_call(
+        &self,
+        request: FunctionCall,
+        block_id: BlockIdOrTag,
+    ) -> Result<CallTrace, StarknetApiError> {
+        let request = EntryPointCall {
+            calldata: request.calldata,
+            contract_address: request.contract_address.into(),
+            entry_point_selector: request.entry_point_selector,
+        };
+
+        // get the state and block env at the specified block for function call execution
+        let state = self.state(&block_id)?;
+        let env = self.block_env_at(&block_id)?;
+        let cfg_env = self.inner.backend.executor_factory.cfg().clone();
+        let max_call_gas = self.inner.config.max_call_gas.unwrap_or(DEFAULT_MAX_CALL_GAS);
+
+        match super::blockifier::trace_call(state, env, cfg_env, request, max_call_gas) {
+            Ok(trace) => {
+                Ok(CallTrace::new(trace.call_info, trace.revert_error, trace.state_updates))
+            }
+            Err(err) => Err(StarknetApiError::ContractError { revert_error: err.to_string() }),
+        }
+    }
+}
+
+#[async_trait]
+impl<EF: ExecutorFactory> DebugApiServer for StarknetApi<EF> {
+    async fn trace_call(
+        &self,
+        request: FunctionCall,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<CallTrace> {
+        self.on_io_blocking_task(move |this| Ok(this.trace_call(request, block_id)?)).await
+    }
+
+    async fn trace_transaction(&self, transaction_hash: TxHash) -> RpcResult<TxTrace> {
+        self.on_io_blocking_task(move |this| {
+            Ok(TxTrace::from(this.transaction_execution(transaction_hash)?))
+        })
+        .await
+    }
+}
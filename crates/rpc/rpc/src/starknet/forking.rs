@@ -1,4 +1,5 @@
 use katana_primitives::block::{BlockHash, BlockIdOrTag, BlockNumber};
+use katana_primitives::class::ClassHash;
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::transaction::TxHash;
 use katana_primitives::Felt;
@@ -10,7 +11,8 @@ use katana_rpc_types::event::EventsPage;
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
 use katana_rpc_types::transaction::Tx;
-use starknet::core::types::{EventFilter, TransactionStatus};
+use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
+use starknet::core::types::{ConfirmedBlockId, EventFilter, TransactionStatus};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider, ProviderError};
 use url::Url;
@@ -285,6 +287,42 @@ impl<P: Provider> ForkedClient<P> {
 
         Ok(events)
     }
+
+    pub async fn get_storage_proof(
+        &self,
+        block_id: BlockIdOrTag,
+        class_hashes: Vec<ClassHash>,
+        contract_addresses: Vec<ContractAddress>,
+        contracts_storage_keys: Vec<ContractStorageKeys>,
+    ) -> Result<GetStorageProofResponse, Error> {
+        let block_id = match block_id {
+            BlockIdOrTag::Number(num) if num > self.block => {
+                return Err(Error::BlockOutOfRange);
+            }
+            BlockIdOrTag::Number(num) => ConfirmedBlockId::Number(num),
+            BlockIdOrTag::Hash(hash) => {
+                // Make sure the block is within the forked range.
+                self.get_block_number_by_hash(hash).await?;
+                ConfirmedBlockId::Hash(hash)
+            }
+            BlockIdOrTag::Tag(_) => {
+                return Err(Error::BlockTagNotAllowed);
+            }
+        };
+
+        let contract_addresses = contract_addresses.into_iter().map(Felt::from).collect::<Vec<_>>();
+        let contracts_storage_keys = contracts_storage_keys
+            .into_iter()
+            .map(starknet::core::types::ContractStorageKeys::from)
+            .collect::<Vec<_>>();
+
+        let proof = self
+            .provider
+            .get_storage_proof(block_id, class_hashes, contract_addresses, contracts_storage_keys)
+            .await?;
+
+        Ok(proof.into())
+    }
 }
 
 impl From<Error> for StarknetApiError {
@@ -12,13 +12,11 @@ use katana_primitives::block::{
 };
 use katana_primitives::class::ClassHash;
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
-use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::BlockEnv;
 use katana_primitives::event::MaybeForkedContinuationToken;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxHash, TxWithHash};
 use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::Felt;
-use katana_provider::error::ProviderError;
 use katana_provider::traits::block::{BlockHashProvider, BlockIdReader, BlockNumberProvider};
 use katana_provider::traits::contract::ContractClassProvider;
 use katana_provider::traits::env::BlockEnvProvider;
@@ -35,24 +33,28 @@ use katana_rpc_types::class::RpcContractClass;
 use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
 use katana_rpc_types::receipt::{ReceiptBlock, TxReceiptWithBlockInfo};
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
-use katana_rpc_types::transaction::Tx;
+use katana_rpc_types::transaction::{Tx, TxStatus};
 use katana_rpc_types::trie::{
     ClassesProof, ContractLeafData, ContractStorageKeys, ContractStorageProofs, ContractsProof,
     GetStorageProofResponse, GlobalRoots, Nodes,
 };
-use katana_rpc_types::FeeEstimate;
+use katana_rpc_types::{FeeEstimate, SyncingStatus};
 use katana_rpc_types_builder::ReceiptBuilder;
 use katana_tasks::{BlockingTaskPool, TokioTaskSpawner};
 use starknet::core::types::{ResultPageRequest, TransactionStatus};
 
 use crate::permit::Permits;
 use crate::utils::events::{Cursor, EventBlockId};
+use crate::utils::subscription::SubscriptionRegistry;
 use crate::{utils, DEFAULT_ESTIMATE_FEE_MAX_CONCURRENT_REQUESTS};
 
 mod blockifier;
 mod config;
+mod debug;
 pub mod forking;
 mod read;
+mod subscription;
+mod sync;
 mod trace;
 mod write;
 
@@ -60,6 +62,7 @@ mod write;
 pub use config::PaymasterConfig;
 pub use config::StarknetApiConfig;
 use forking::ForkedClient;
+pub use sync::SyncProgress;
 
 type StarknetApiResult<T> = Result<T, StarknetApiError>;
 
@@ -67,8 +70,9 @@ type StarknetApiResult<T> = Result<T, StarknetApiError>;
 ///
 /// This struct implements all the JSON-RPC traits required to serve the Starknet API (ie,
 /// [read](katana_rpc_api::starknet::StarknetApi),
-/// [write](katana_rpc_api::starknet::StarknetWriteApi), and
-/// [trace](katana_rpc_api::starknet::StarknetTraceApi) APIs.
+/// [write](katana_rpc_api::starknet::StarknetWriteApi),
+/// [trace](katana_rpc_api::starknet::StarknetTraceApi), and
+/// [subscription](katana_rpc_api::starknet::StarknetWsApi) APIs.
 #[allow(missing_debug_implementations)]
 pub struct StarknetApi<EF>
 where
@@ -86,7 +90,9 @@ where
     forked_client: Option<ForkedClient>,
     blocking_task_pool: BlockingTaskPool,
     block_producer: Option<BlockProducer<EF>>,
+    sync_progress: Option<Arc<dyn SyncProgress>>,
     estimate_fee_permit: Permits,
+    subscriptions: SubscriptionRegistry,
     config: StarknetApiConfig,
 }
 
@@ -100,7 +106,7 @@ where
         block_producer: Option<BlockProducer<EF>>,
         config: StarknetApiConfig,
     ) -> Self {
-        Self::new_inner(backend, pool, block_producer, None, config)
+        Self::new_inner(backend, pool, block_producer, None, None, config)
     }
 
     pub fn new_forked(
@@ -110,7 +116,20 @@ where
         forked_client: ForkedClient,
         config: StarknetApiConfig,
     ) -> Self {
-        Self::new_inner(backend, pool, Some(block_producer), Some(forked_client), config)
+        Self::new_inner(backend, pool, Some(block_producer), Some(forked_client), None, config)
+    }
+
+    /// Creates a handler for a node that syncs the chain from an external source instead of
+    /// producing its own blocks.
+    ///
+    /// The syncing progress of the node is reported by `sync_progress`.
+    pub fn new_syncing(
+        backend: Arc<Backend<EF>>,
+        pool: TxPool,
+        sync_progress: Arc<dyn SyncProgress>,
+        config: StarknetApiConfig,
+    ) -> Self {
+        Self::new_inner(backend, pool, None, None, Some(sync_progress), config)
     }
 
     fn new_inner(
@@ -118,6 +137,7 @@ where
         pool: TxPool,
         block_producer: Option<BlockProducer<EF>>,
         forked_client: Option<ForkedClient>,
+        sync_progress: Option<Arc<dyn SyncProgress>>,
         config: StarknetApiConfig,
     ) -> Self {
         let blocking_task_pool =
@@ -134,7 +154,9 @@ where
             block_producer,
             blocking_task_pool,
             forked_client,
+            sync_progress,
             estimate_fee_permit,
+            subscriptions: SubscriptionRegistry::default(),
             config,
         };
 
@@ -356,6 +378,14 @@ where
         }
     }
 
+    async fn syncing_status(&self) -> StarknetApiResult<SyncingStatus> {
+        let Some(progress) = self.inner.sync_progress.clone() else {
+            return Ok(SyncingStatus::NotSyncing);
+        };
+
+        self.on_io_blocking_task(move |_| Ok(progress.status()?)).await
+    }
+
     async fn latest_block_number(&self) -> StarknetApiResult<BlockNumber> {
         self.on_io_blocking_task(move |this| {
             Ok(this.inner.backend.blockchain.provider().latest_number()?)
@@ -373,7 +403,11 @@ where
             //
             // TODO: this is a temporary solution, we should have a better way to handle this.
             // perhaps a pending/pool state provider that implements all the state provider traits.
-            let result = if let BlockIdOrTag::Tag(BlockTag::Pending) = block_id {
+            //
+            // A node without a block producer doesn't accept transactions so its pool state would
+            // be stale, thus the pending nonce is simply the latest one.
+            let is_pending = matches!(block_id, BlockIdOrTag::Tag(BlockTag::Pending));
+            let result = if is_pending && this.inner.block_producer.is_some() {
                 this.inner.pool.validator().pool_nonce(contract_address)?
             } else {
                 let state = this.state(&block_id)?;
@@ -514,6 +548,10 @@ where
 
         if let Some(receipt) = receipt {
             Ok(receipt)
+        } else if self.inner.pool.rejected_transaction(hash).is_some() {
+            // Rejected transactions don't have a receipt. Their rejection reason is reported by
+            // `starknet_getTransactionStatus` instead.
+            Err(StarknetApiError::TxnHashNotFound)
         } else if let Some(client) = &self.inner.forked_client {
             Ok(client.get_transaction_receipt(hash).await?)
         } else {
@@ -521,7 +559,7 @@ where
         }
     }
 
-    async fn transaction_status(&self, hash: TxHash) -> StarknetApiResult<TransactionStatus> {
+    async fn transaction_status(&self, hash: TxHash) -> StarknetApiResult<TxStatus> {
         let status = self
             .on_io_blocking_task(move |this| {
                 let provider = this.inner.backend.blockchain.provider();
@@ -554,7 +592,7 @@ where
                         }
                     };
 
-                    return Ok(Some(status));
+                    return Ok(Some(TxStatus::from(status)));
                 }
 
                 // seach in the pending block if the transaction is not found
@@ -568,9 +606,9 @@ where
 
                     // TODO: should impl From<ExecutionResult> for TransactionStatus
                     let status = match res {
-                        ExecutionResult::Failed { .. } => TransactionStatus::Rejected,
+                        ExecutionResult::Failed { error } => TxStatus::rejected(error.to_string()),
                         ExecutionResult::Success { receipt, .. } => {
-                            if let Some(reason) = receipt.revert_reason() {
+                            let status = if let Some(reason) = receipt.revert_reason() {
                                 TransactionStatus::AcceptedOnL2(
                                     starknet::core::types::ExecutionResult::Reverted {
                                         reason: reason.to_string(),
@@ -580,7 +618,9 @@ where
                                 TransactionStatus::AcceptedOnL2(
                                     starknet::core::types::ExecutionResult::Succeeded,
                                 )
-                            }
+                            };
+
+                            TxStatus::from(status)
                         }
                     };
 
@@ -594,11 +634,13 @@ where
 
         if let Some(status) = status {
             Ok(status)
+        } else if let Some(reason) = self.inner.pool.rejected_transaction(hash) {
+            Ok(TxStatus::rejected(reason))
         } else if let Some(client) = &self.inner.forked_client {
-            Ok(client.get_transaction_status(hash).await?)
+            Ok(client.get_transaction_status(hash).await?.into())
         } else {
             let _ = self.inner.pool.get(hash).ok_or(StarknetApiError::TxnHashNotFound)?;
-            Ok(TransactionStatus::Received)
+            Ok(TxStatus::from(TransactionStatus::Received))
         }
     }
 
@@ -620,7 +662,7 @@ where
                         let l1_data_gas_prices = block_env.l1_data_gas_prices.clone();
 
                         let header = PartialHeader {
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
+                            l1_da_mode: block_env.l1_da_mode,
                             l2_gas_prices,
                             l1_gas_prices,
                             l1_data_gas_prices,
@@ -694,7 +736,7 @@ where
                             number: block_env.number,
                             parent_hash: latest_hash,
                             timestamp: block_env.timestamp,
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
+                            l1_da_mode: block_env.l1_da_mode,
                             sequencer_address: block_env.sequencer_address,
                             protocol_version: CURRENT_STARKNET_VERSION,
                         };
@@ -755,7 +797,7 @@ where
                         let l1_data_gas_prices = block_env.l1_data_gas_prices.clone();
 
                         let header = PartialHeader {
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
+                            l1_da_mode: block_env.l1_da_mode,
                             l1_data_gas_prices,
                             l2_gas_prices,
                             l1_gas_prices,
@@ -1156,85 +1198,115 @@ where
         contract_addresses: Option<Vec<ContractAddress>>,
         contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
     ) -> StarknetApiResult<GetStorageProofResponse> {
-        self.on_io_blocking_task(move |this| {
-            let provider = this.inner.backend.blockchain.provider();
+        // Check if the total number of keys requested exceeds the RPC limit.
+        if let Some(limit) = self.inner.config.max_proof_keys {
+            let total_keys = class_hashes.as_ref().map(|v| v.len()).unwrap_or(0)
+                + contract_addresses.as_ref().map(|v| v.len()).unwrap_or(0)
+                + contracts_storage_keys.as_ref().map(|v| v.len()).unwrap_or(0);
+
+            let total_keys = total_keys as u64;
+            if total_keys > limit {
+                return Err(StarknetApiError::ProofLimitExceeded { limit, total: total_keys });
+            }
+        }
 
-            let Some(block_num) = provider.convert_block_id(block_id)? else {
-                return Err(StarknetApiError::BlockNotFound);
-            };
+        // Keep a copy of the keys in case the block belongs to the forked network.
+        let forked_keys = self.inner.forked_client.as_ref().map(|_| {
+            (class_hashes.clone(), contract_addresses.clone(), contracts_storage_keys.clone())
+        });
 
-            // Check if the total number of keys requested exceeds the RPC limit.
-            if let Some(limit) = this.inner.config.max_proof_keys {
-                let total_keys = class_hashes.as_ref().map(|v| v.len()).unwrap_or(0)
-                    + contract_addresses.as_ref().map(|v| v.len()).unwrap_or(0)
-                    + contracts_storage_keys.as_ref().map(|v| v.len()).unwrap_or(0);
+        let proofs = self
+            .on_io_blocking_task(move |this| {
+                let provider = this.inner.backend.blockchain.provider();
 
-                let total_keys = total_keys as u64;
-                if total_keys > limit {
-                    return Err(StarknetApiError::ProofLimitExceeded { limit, total: total_keys });
-                }
-            }
+                let Some(block_num) = provider.convert_block_id(block_id)? else {
+                    return Ok(None);
+                };
 
-            // TODO: the way we handle the block id is very clanky. change it!
-            let state = this.state(&BlockIdOrTag::Number(block_num))?;
-            let block_hash = provider
-                .block_hash_by_num(block_num)?
-                .ok_or(ProviderError::MissingBlockHeader(block_num))?;
+                let Some(block_hash) = provider.block_hash_by_num(block_num)? else {
+                    return Ok(None);
+                };
 
-            // --- Get classes proof (if any)
+                // TODO: the way we handle the block id is very clanky. change it!
+                let state = this.state(&BlockIdOrTag::Number(block_num))?;
 
-            let classes_proof = if let Some(classes) = class_hashes {
-                let proofs = state.class_multiproof(classes)?;
-                ClassesProof { nodes: proofs.into() }
-            } else {
-                ClassesProof::default()
-            };
+                // --- Get classes proof (if any)
 
-            // --- Get contracts proof (if any)
+                let classes_proof = if let Some(classes) = class_hashes {
+                    let proofs = state.class_multiproof(classes)?;
+                    ClassesProof { nodes: proofs.into() }
+                } else {
+                    ClassesProof::default()
+                };
 
-            let contracts_proof = if let Some(addresses) = contract_addresses {
-                let proofs = state.contract_multiproof(addresses.clone())?;
-                let mut contract_leaves_data = Vec::new();
+                // --- Get contracts proof (if any)
 
-                for address in addresses {
-                    let nonce = state.nonce(address)?.unwrap_or_default();
-                    let class_hash = state.class_hash_of_contract(address)?.unwrap_or_default();
-                    let storage_root = state.storage_root(address)?.unwrap_or_default();
-                    contract_leaves_data.push(ContractLeafData { storage_root, class_hash, nonce });
-                }
+                let contracts_proof = if let Some(addresses) = contract_addresses {
+                    let proofs = state.contract_multiproof(addresses.clone())?;
+                    let mut contract_leaves_data = Vec::new();
 
-                ContractsProof { nodes: proofs.into(), contract_leaves_data }
-            } else {
-                ContractsProof::default()
-            };
+                    for address in addresses {
+                        let nonce = state.nonce(address)?.unwrap_or_default();
+                        let class_hash = state.class_hash_of_contract(address)?.unwrap_or_default();
+                        let storage_root = state.storage_root(address)?.unwrap_or_default();
+                        contract_leaves_data.push(ContractLeafData {
+                            storage_root,
+                            class_hash,
+                            nonce,
+                        });
+                    }
 
-            // --- Get contracts storage proof (if any)
+                    ContractsProof { nodes: proofs.into(), contract_leaves_data }
+                } else {
+                    ContractsProof::default()
+                };
 
-            let contracts_storage_proofs = if let Some(contract_storage) = contracts_storage_keys {
-                let mut nodes: Vec<Nodes> = Vec::new();
+                // --- Get contracts storage proof (if any)
 
-                for ContractStorageKeys { address, keys } in contract_storage {
-                    let proofs = state.storage_multiproof(address, keys)?;
-                    nodes.push(proofs.into());
-                }
+                let contracts_storage_proofs =
+                    if let Some(contract_storage) = contracts_storage_keys {
+                        let mut nodes: Vec<Nodes> = Vec::new();
 
-                ContractStorageProofs { nodes }
-            } else {
-                ContractStorageProofs::default()
-            };
+                        for ContractStorageKeys { address, keys } in contract_storage {
+                            let proofs = state.storage_multiproof(address, keys)?;
+                            nodes.push(proofs.into());
+                        }
 
-            let classes_tree_root = state.classes_root()?;
-            let contracts_tree_root = state.contracts_root()?;
-            let global_roots = GlobalRoots { block_hash, classes_tree_root, contracts_tree_root };
+                        ContractStorageProofs { nodes }
+                    } else {
+                        ContractStorageProofs::default()
+                    };
 
-            Ok(GetStorageProofResponse {
-                global_roots,
-                classes_proof,
-                contracts_proof,
-                contracts_storage_proofs,
+                let classes_tree_root = state.classes_root()?;
+                let contracts_tree_root = state.contracts_root()?;
+                let global_roots =
+                    GlobalRoots { block_hash, classes_tree_root, contracts_tree_root };
+
+                Result::<_, StarknetApiError>::Ok(Some(GetStorageProofResponse {
+                    global_roots,
+                    classes_proof,
+                    contracts_proof,
+                    contracts_storage_proofs,
+                }))
             })
-        })
-        .await
+            .await?;
+
+        if let Some(proofs) = proofs {
+            Ok(proofs)
+        } else if let (Some(client), Some(keys)) = (&self.inner.forked_client, forked_keys) {
+            let (class_hashes, contract_addresses, contracts_storage_keys) = keys;
+            let proofs = client
+                .get_storage_proof(
+                    block_id,
+                    class_hashes.unwrap_or_default(),
+                    contract_addresses.unwrap_or_default(),
+                    contracts_storage_keys.unwrap_or_default(),
+                )
+                .await?;
+            Ok(proofs)
+        } else {
+            Err(StarknetApiError::BlockNotFound)
+        }
     }
 }
 
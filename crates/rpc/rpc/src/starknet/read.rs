@@ -25,14 +25,16 @@ use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
 use katana_rpc_types::message::MsgFromL1;
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
-use katana_rpc_types::transaction::{BroadcastedTx, Tx};
+use katana_rpc_types::transaction::{BroadcastedTx, Tx, TxStatus};
 use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
-use katana_rpc_types::{FeeEstimate, FeltAsHex, FunctionCall, SimulationFlagForEstimateFee};
-use starknet::core::types::TransactionStatus;
+use katana_rpc_types::{
+    FeeEstimate, FeltAsHex, FunctionCall, SimulationFlagForEstimateFee, SyncingStatus,
+};
 
 use super::StarknetApi;
 #[cfg(feature = "cartridge")]
 use crate::cartridge;
+use crate::DEFAULT_MAX_CALL_GAS;
 
 #[async_trait]
 impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
@@ -52,6 +54,10 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
         Ok(self.latest_block_number().await?)
     }
 
+    async fn syncing(&self) -> RpcResult<SyncingStatus> {
+        Ok(self.syncing_status().await?)
+    }
+
     async fn get_transaction_by_hash(&self, transaction_hash: Felt) -> RpcResult<Tx> {
         Ok(self.transaction(transaction_hash).await?)
     }
@@ -152,7 +158,7 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
             let state = this.state(&block_id)?;
             let env = this.block_env_at(&block_id)?;
             let cfg_env = this.inner.backend.executor_factory.cfg().clone();
-            let max_call_gas = this.inner.config.max_call_gas.unwrap_or(1_000_000_000);
+            let max_call_gas = this.inner.config.max_call_gas.unwrap_or(DEFAULT_MAX_CALL_GAS);
 
             match super::blockifier::call(state, env, cfg_env, request, max_call_gas) {
                 Ok(retdata) => Ok(retdata.into_iter().map(|v| v.into()).collect()),
@@ -221,6 +227,10 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
         let should_validate = !skip_validate
             && self.inner.backend.executor_factory.execution_flags().account_validation();
 
+        // Transactions of impersonated accounts are never validated.
+        let impersonated =
+            self.inner.backend.executor_factory.execution_flags().impersonated_accounts().clone();
+
         // We don't care about the nonce when estimating the fee as the nonce value
         // doesn't affect transaction execution.
         //
@@ -228,7 +238,8 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
         // return an error. It only 'relaxes' the check for nonce >= account nonce.
         let flags = katana_executor::ExecutionFlags::new()
             .with_account_validation(should_validate)
-            .with_nonce_check(false);
+            .with_nonce_check(false)
+            .with_impersonated_accounts(impersonated);
 
         // Hook the estimate fee to pre-deploy the controller contract
         // and enhance UX on the client side.
@@ -359,10 +370,7 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
         .await
     }
 
-    async fn get_transaction_status(
-        &self,
-        transaction_hash: TxHash,
-    ) -> RpcResult<TransactionStatus> {
+    async fn get_transaction_status(&self, transaction_hash: TxHash) -> RpcResult<TxStatus> {
         Ok(self.transaction_status(transaction_hash).await?)
     }
 
@@ -0,0 +1,433 @@
+use futures::channel::mpsc::Receiver;
+use futures::StreamExt;
+use jsonrpsee::core::{async_trait, RpcResult, SubscriptionResult};
+use jsonrpsee::types::SubscriptionId;
+use jsonrpsee::{ConnectionId, Extensions, PendingSubscriptionSink, SubscriptionSink};
+use katana_core::service::block_producer::BlockProducerEvent;
+use katana_executor::ExecutorFactory;
+use katana_pool::tx::PoolTransaction;
+use katana_pool::TransactionPool;
+use katana_primitives::block::{BlockIdOrTag, BlockNumber};
+use katana_primitives::transaction::{TxHash, TxWithHash};
+use katana_primitives::{ContractAddress, Felt};
+use katana_provider::traits::block::{BlockHashProvider, BlockIdReader, HeaderProvider};
+use katana_rpc_api::error::starknet::StarknetApiError;
+use katana_rpc_api::starknet::StarknetWsApiServer;
+use katana_rpc_types::block::BlockHeader;
+use katana_rpc_types::subscription::{
+    NewTransactionStatus, PendingTransaction, MAX_BLOCKS_BACK, MAX_SENDER_ADDRESSES,
+};
+use katana_rpc_types::transaction::{Tx, TxStatus};
+use katana_tasks::TokioTaskSpawner;
+use serde::Serialize;
+use starknet::core::types::EmittedEvent;
+use tracing::{debug, error};
+
+use super::{StarknetApi, StarknetApiResult};
+use crate::utils::events::{self, Filter};
+
+const LOG_TARGET: &str = "rpc::starknet::subscription";
+
+impl<EF: ExecutorFactory> StarknetApi<EF> {
+    /// Registers a new listener for mined blocks and chain reverts.
+    ///
+    /// Returns an error if the node is not producing blocks (ie, no block producer).
+    fn block_listener(&self) -> StarknetApiResult<Receiver<BlockProducerEvent>> {
+        let Some(producer) = &self.inner.block_producer else {
+            return Err(StarknetApiError::UnexpectedError {
+                reason: "Block subscriptions are not supported by this node".to_string(),
+            });
+        };
+
+        Ok(producer.add_listener())
+    }
+
+    /// Resolves the block from which a block-based subscription should start sending
+    /// notifications. Defaults to the latest block if `block_id` is `None`.
+    fn subscription_start_block(
+        &self,
+        block_id: Option<BlockIdOrTag>,
+    ) -> StarknetApiResult<BlockNumber> {
+        let provider = self.inner.backend.blockchain.provider();
+        let latest = provider.latest_number()?;
+
+        let start = match block_id {
+            Some(id) => provider.convert_block_id(id)?.ok_or(StarknetApiError::BlockNotFound)?,
+            None => latest,
+        };
+
+        if latest.saturating_sub(start) > MAX_BLOCKS_BACK {
+            return Err(StarknetApiError::TooManyBlocksBack);
+        }
+
+        Ok(start)
+    }
+
+    /// Sends the header of every block in `from..=to` to the subscriber.
+    ///
+    /// Returns `false` if the subscriber has disconnected.
+    async fn send_headers(
+        &self,
+        sink: &SubscriptionSink,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> bool {
+        for num in from..=to {
+            let header = self.on_io_blocking_task(move |this| {
+                let provider = this.inner.backend.blockchain.provider();
+                let hash = provider.block_hash_by_num(num)?;
+                let header = provider.header(num.into())?;
+                Ok::<_, StarknetApiError>(
+                    hash.zip(header).map(|(hash, header)| BlockHeader::new(hash, header)),
+                )
+            });
+
+            match header.await {
+                Ok(Some(header)) => {
+                    if !send(sink, &header).await {
+                        return false;
+                    }
+                }
+                Ok(None) => {
+                    error!(target: LOG_TARGET, block = %num, "Missing block header.");
+                    return false;
+                }
+                Err(error) => {
+                    error!(target: LOG_TARGET, %error, block = %num, "Failed to fetch header.");
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Sends all the events in `from..=to` matching `filter` to the subscriber.
+    ///
+    /// Returns `false` if the subscriber has disconnected.
+    async fn send_events(
+        &self,
+        sink: &SubscriptionSink,
+        filter: Filter,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> bool {
+        if from > to {
+            return true;
+        }
+
+        let result = self
+            .on_io_blocking_task(move |this| {
+                let provider = this.inner.backend.blockchain.provider();
+                let mut buffer: Vec<EmittedEvent> = Vec::new();
+                events::fetch_events_at_blocks(
+                    provider,
+                    from..=to,
+                    &filter,
+                    u64::MAX,
+                    None,
+                    &mut buffer,
+                )?;
+                Ok::<_, events::Error>(buffer)
+            })
+            .await;
+
+        match result {
+            Ok(events) => {
+                for event in events {
+                    if !send(sink, &event).await {
+                        return false;
+                    }
+                }
+                true
+            }
+            Err(error) => {
+                error!(target: LOG_TARGET, %error, %from, %to, "Failed to fetch events.");
+                false
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<EF: ExecutorFactory> StarknetWsApiServer for StarknetApi<EF> {
+    async fn unsubscribe(
+        &self,
+        ext: &Extensions,
+        subscription_id: SubscriptionId<'static>,
+    ) -> RpcResult<bool> {
+        // Subscriptions can only be cancelled from the connection they were created on.
+        let unsubscribed = ext
+            .get::<ConnectionId>()
+            .is_some_and(|conn_id| self.inner.subscriptions.unsubscribe(*conn_id, subscription_id));
+
+        if unsubscribed {
+            Ok(true)
+        } else {
+            Err(StarknetApiError::InvalidSubscriptionId.into())
+        }
+    }
+
+    async fn subscribe_new_heads(
+        &self,
+        pending: PendingSubscriptionSink,
+        block_id: Option<BlockIdOrTag>,
+    ) -> SubscriptionResult {
+        // The listener must be registered before resolving the starting block so that no block
+        // is missed in between.
+        let setup = self
+            .block_listener()
+            .and_then(|blocks| Ok((blocks, self.subscription_start_block(block_id)?)));
+
+        let (mut blocks, mut next) = match setup {
+            Ok(value) => value,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let subscription = self.inner.subscriptions.register(&sink);
+        let this = self.clone();
+
+        TokioTaskSpawner::new()?.spawn(async move {
+            let latest = match this.latest_block_number().await {
+                Ok(latest) => latest,
+                Err(error) => {
+                    error!(target: LOG_TARGET, %error, "Failed to get latest block number.");
+                    return;
+                }
+            };
+
+            if !this.send_headers(&sink, next, latest).await {
+                return;
+            }
+
+            next = latest + 1;
+
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    _ = subscription.unsubscribed() => break,
+                    event = blocks.next() => match event {
+                        Some(BlockProducerEvent::Mined(outcome)) => {
+                            if !this.send_headers(&sink, next, outcome.block_number).await {
+                                break;
+                            }
+
+                            next = next.max(outcome.block_number + 1);
+                        }
+                        // The blocks mined after the revert replace the ones already sent.
+                        Some(BlockProducerEvent::Reverted(block)) => next = next.min(block + 1),
+                        None => break,
+                    },
+                }
+            }
+
+            debug!(target: LOG_TARGET, "New heads subscription closed.");
+        });
+
+        Ok(())
+    }
+
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<Vec<Felt>>>,
+        block_id: Option<BlockIdOrTag>,
+    ) -> SubscriptionResult {
+        let setup = self
+            .block_listener()
+            .and_then(|blocks| Ok((blocks, self.subscription_start_block(block_id)?)));
+
+        let (mut blocks, mut next) = match setup {
+            Ok(value) => value,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let subscription = self.inner.subscriptions.register(&sink);
+        let filter = Filter { address: from_address, keys };
+        let this = self.clone();
+
+        TokioTaskSpawner::new()?.spawn(async move {
+            let latest = match this.latest_block_number().await {
+                Ok(latest) => latest,
+                Err(error) => {
+                    error!(target: LOG_TARGET, %error, "Failed to get latest block number.");
+                    return;
+                }
+            };
+
+            if !this.send_events(&sink, filter.clone(), next, latest).await {
+                return;
+            }
+
+            next = latest + 1;
+
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    _ = subscription.unsubscribed() => break,
+                    event = blocks.next() => match event {
+                        Some(BlockProducerEvent::Mined(outcome)) => {
+                            let to = outcome.block_number;
+                            if !this.send_events(&sink, filter.clone(), next, to).await {
+                                break;
+                            }
+
+                            next = next.max(outcome.block_number + 1);
+                        }
+                        // The blocks mined after the revert replace the ones already sent.
+                        Some(BlockProducerEvent::Reverted(block)) => next = next.min(block + 1),
+                        None => break,
+                    },
+                }
+            }
+
+            debug!(target: LOG_TARGET, "Events subscription closed.");
+        });
+
+        Ok(())
+    }
+
+    async fn subscribe_transaction_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_hash: TxHash,
+    ) -> SubscriptionResult {
+        let mut blocks = match self.block_listener() {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+
+        let mut pool_txs = self.inner.pool.add_listener();
+        let sink = pending.accept().await?;
+        let subscription = self.inner.subscriptions.register(&sink);
+        let this = self.clone();
+
+        TokioTaskSpawner::new()?.spawn(async move {
+            let mut last_status: Option<TxStatus> = None;
+
+            loop {
+                // A transaction that is not yet known to the node simply has no status yet.
+                let status = match this.transaction_status(transaction_hash).await {
+                    Ok(status) => Some(status),
+                    Err(StarknetApiError::TxnHashNotFound) => None,
+                    Err(error) => {
+                        error!(target: LOG_TARGET, %error, "Failed to get transaction status.");
+                        break;
+                    }
+                };
+
+                if let Some(status) = status {
+                    if last_status.as_ref() != Some(&status) {
+                        let is_final = status.is_final();
+                        let status_clone = status.clone();
+                        let item = NewTransactionStatus { transaction_hash, status: status_clone };
+                        if !send(&sink, &item).await || is_final {
+                            break;
+                        }
+
+                        last_status = Some(status);
+                    }
+                }
+
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    _ = subscription.unsubscribed() => break,
+                    event = blocks.next() => match event {
+                        // The status is sent again if the transaction is mined again after being
+                        // reverted.
+                        Some(BlockProducerEvent::Reverted(_)) => last_status = None,
+                        Some(BlockProducerEvent::Mined(_)) => {}
+                        None => break,
+                    },
+                    hash = pool_txs.next() => if hash.is_none() { break },
+                }
+            }
+
+            let hash = format!("{transaction_hash:#x}");
+            debug!(target: LOG_TARGET, %hash, "Transaction status subscription closed.");
+        });
+
+        Ok(())
+    }
+
+    async fn subscribe_pending_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_details: Option<bool>,
+        sender_address: Option<Vec<ContractAddress>>,
+    ) -> SubscriptionResult {
+        if sender_address.as_ref().is_some_and(|s| s.len() > MAX_SENDER_ADDRESSES) {
+            pending.reject(StarknetApiError::TooManyAddressesInFilter).await;
+            return Ok(());
+        }
+
+        let mut pool_txs = self.inner.pool.add_listener();
+        let sink = pending.accept().await?;
+        let subscription = self.inner.subscriptions.register(&sink);
+        let details = transaction_details.unwrap_or_default();
+        let this = self.clone();
+
+        TokioTaskSpawner::new()?.spawn(async move {
+            loop {
+                let hash = tokio::select! {
+                    _ = sink.closed() => break,
+                    _ = subscription.unsubscribed() => break,
+                    hash = pool_txs.next() => match hash {
+                        Some(hash) => hash,
+                        None => break,
+                    },
+                };
+
+                // The transaction may have already been removed from the pool (eg, mined) by the
+                // time we get here, in which case it is no longer pending.
+                let Some(tx) = this.inner.pool.get(hash) else { continue };
+
+                if let Some(senders) = &sender_address {
+                    if !senders.contains(&tx.sender()) {
+                        continue;
+                    }
+                }
+
+                let item = if details {
+                    PendingTransaction::Full(Tx::from(TxWithHash::from(tx.as_ref())))
+                } else {
+                    PendingTransaction::Hash(hash)
+                };
+
+                if !send(&sink, &item).await {
+                    break;
+                }
+            }
+
+            debug!(target: LOG_TARGET, "Pending transactions subscription closed.");
+        });
+
+        Ok(())
+    }
+}
+
+/// Sends a notification to the subscriber. Returns `false` if the notification couldn't be sent
+/// (ie, the subscriber has disconnected).
+async fn send<T: Serialize>(sink: &SubscriptionSink, item: &T) -> bool {
+    let message = match serde_json::value::to_raw_value(item) {
+        Ok(message) => message,
+        Err(error) => {
+            error!(target: LOG_TARGET, %error, "Failed to serialize subscription notification.");
+            return false;
+        }
+    };
+
+    sink.send(message.into()).await.is_ok()
+}
@@ -0,0 +1,13 @@
+use std::fmt::Debug;
+
+use katana_provider::ProviderResult;
+use katana_rpc_types::SyncingStatus;
+
+/// Source of the node's syncing progress, reported through the `starknet_syncing` method.
+///
+/// Only nodes that sync the chain from an external source (ie a full node) have one. Otherwise,
+/// the node is always reported as not syncing.
+pub trait SyncProgress: Debug + Send + Sync + 'static {
+    /// Returns the current syncing status of the node.
+    fn status(&self) -> ProviderResult<SyncingStatus>;
+}
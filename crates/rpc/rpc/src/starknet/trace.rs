@@ -68,10 +68,15 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
         let should_charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge)
             && self.inner.backend.executor_factory.execution_flags().fee();
 
+        // Transactions of impersonated accounts are never validated.
+        let impersonated =
+            self.inner.backend.executor_factory.execution_flags().impersonated_accounts().clone();
+
         let flags = katana_executor::ExecutionFlags::new()
             .with_account_validation(should_validate)
             .with_fee(should_charge_fee)
-            .with_nonce_check(false);
+            .with_nonce_check(false)
+            .with_impersonated_accounts(impersonated);
 
         // get the state and block env at the specified block for execution
         let state = self.state(&block_id)?;
@@ -84,8 +89,9 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
         let mut simulated = Vec::with_capacity(results.len());
         for (i, ResultAndStates { result, .. }) in results.into_iter().enumerate() {
             match result {
-                ExecutionResult::Success { trace, receipt } => {
-                    let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace);
+                ExecutionResult::Success { trace, receipt, state_diff } => {
+                    let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace)
+                        .with_state_diff(state_diff);
 
                     let transaction_trace = to_rpc_trace(trace);
                     let fee_estimation =
@@ -123,9 +129,11 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
 
                     // extract the txs from the pending block
                     let traces = pending_block.transactions().iter().filter_map(|(t, r)| {
-                        if let Some(trace) = r.trace().cloned() {
+                        if let ExecutionResult::Success { trace, state_diff, .. } = r {
                             let transaction_hash = t.hash;
-                            let trace = TypedTransactionExecutionInfo::new(t.r#type(), trace);
+                            let trace =
+                                TypedTransactionExecutionInfo::new(t.r#type(), trace.clone())
+                                    .with_state_diff(state_diff.clone());
                             let trace_root = to_rpc_trace(trace);
 
                             Some(TransactionTraceWithHash { transaction_hash, trace_root })
@@ -161,6 +169,15 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
     }
 
     fn trace(&self, tx_hash: TxHash) -> Result<TransactionTrace, StarknetApiError> {
+        Ok(to_rpc_trace(self.transaction_execution(tx_hash)?))
+    }
+
+    /// Returns the execution info of a transaction, either from the pending block or from the
+    /// storage.
+    pub(super) fn transaction_execution(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<TypedTransactionExecutionInfo, StarknetApiError> {
         use StarknetApiError::TxnHashNotFound;
 
         // Check in the pending block first
@@ -168,18 +185,17 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
             let pending_block = state.read();
             let tx = pending_block.transactions().iter().find(|(t, _)| t.hash == tx_hash);
 
-            if let Some((tx, res)) = tx {
-                if let Some(trace) = res.trace() {
-                    let trace = TypedTransactionExecutionInfo::new(tx.r#type(), trace.clone());
-                    return Ok(to_rpc_trace(trace));
-                }
+            if let Some((tx, ExecutionResult::Success { trace, state_diff, .. })) = tx {
+                let trace = TypedTransactionExecutionInfo::new(tx.r#type(), trace.clone())
+                    .with_state_diff(state_diff.clone());
+                return Ok(trace);
             }
         }
 
         // If not found in pending block, fallback to the provider
         let provider = self.inner.backend.blockchain.provider();
         let trace = provider.transaction_execution(tx_hash)?.ok_or(TxnHashNotFound)?;
-        Ok(to_rpc_trace(trace))
+        Ok(trace)
     }
 }
 
@@ -3,6 +3,7 @@ use std::ops::RangeInclusive;
 
 use anyhow::Context;
 use katana_core::service::block_producer::PendingExecutor;
+use katana_db::models::event::EventPosition;
 use katana_primitives::block::{BlockHash, BlockNumber};
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::event::ContinuationToken;
@@ -11,12 +12,16 @@ use katana_primitives::transaction::TxHash;
 use katana_primitives::Felt;
 use katana_provider::error::ProviderError;
 use katana_provider::traits::block::BlockProvider;
+use katana_provider::traits::event::EventIndexProvider;
 use katana_provider::traits::transaction::ReceiptProvider;
 use katana_rpc_api::error::starknet::StarknetApiError;
 use starknet::core::types::EmittedEvent;
 
 pub type EventQueryResult<T> = Result<T, Error>;
 
+/// The maximum number of event positions to read from the event index at once.
+const INDEX_QUERY_LIMIT: usize = 1024;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Invalid cursor")]
@@ -45,6 +50,20 @@ pub struct Filter {
     pub keys: Option<Vec<Vec<Felt>>>,
 }
 
+impl Filter {
+    /// Returns the values that the first key of the events must be one of, if the filter
+    /// restricts it.
+    fn first_keys(&self) -> Option<&[Felt]> {
+        self.keys.as_ref()?.first().map(Vec::as_slice).filter(|keys| !keys.is_empty())
+    }
+
+    /// Returns `true` if the events matching the filter can be located using the event index, ie
+    /// the filter restricts either the contract address or the first key of the events.
+    fn is_indexable(&self) -> bool {
+        self.address.is_some() || self.first_keys().is_some()
+    }
+}
+
 /// Internal cursor
 #[derive(Debug, Clone, PartialEq)]
 pub struct Cursor {
@@ -139,7 +158,27 @@ pub fn fetch_pending_events(
 }
 
 /// Returns `true` if reach the end of the block range.
+///
+/// The events are looked up in the event index if the filter allows it and all the blocks in the
+/// range have been indexed. Otherwise, the events of every block in the range are scanned.
 pub fn fetch_events_at_blocks(
+    provider: impl BlockProvider + ReceiptProvider + EventIndexProvider,
+    block_range: RangeInclusive<BlockNumber>,
+    filter: &Filter,
+    chunk_size: u64,
+    cursor: Option<Cursor>,
+    buffer: &mut Vec<EmittedEvent>,
+) -> EventQueryResult<Option<Cursor>> {
+    let end = *block_range.end();
+    if filter.is_indexable() && provider.event_index_checkpoint()?.is_some_and(|c| c >= end) {
+        fetch_indexed_events_at_blocks(provider, block_range, filter, chunk_size, cursor, buffer)
+    } else {
+        scan_events_at_blocks(provider, block_range, filter, chunk_size, cursor, buffer)
+    }
+}
+
+/// Fetches the events by going through the receipts of every block in the range.
+fn scan_events_at_blocks(
     provider: impl BlockProvider + ReceiptProvider,
     block_range: RangeInclusive<BlockNumber>,
     filter: &Filter,
@@ -220,6 +259,132 @@ pub fn fetch_events_at_blocks(
     Ok(None)
 }
 
+/// Fetches the events by only going through the transactions that the event index reports to have
+/// emitted events from the filtered contract or with the filtered first keys.
+///
+/// The transactions are processed the same way as [`scan_events_at_blocks`] does, so the returned
+/// cursors are interchangeable.
+fn fetch_indexed_events_at_blocks(
+    provider: impl BlockProvider + ReceiptProvider + EventIndexProvider,
+    block_range: RangeInclusive<BlockNumber>,
+    filter: &Filter,
+    chunk_size: u64,
+    cursor: Option<Cursor>,
+    buffer: &mut Vec<EmittedEvent>,
+) -> EventQueryResult<Option<Cursor>> {
+    let cursor = cursor.unwrap_or(Cursor::new_block(*block_range.start()));
+    let to_block = *block_range.end();
+
+    let mut from = EventPosition {
+        block_number: cursor.block,
+        tx_index: cursor.txn.idx as u32,
+        event_index: 0,
+    };
+
+    // the hash and first transaction number of the block being processed
+    let mut current_block: Option<(BlockNumber, BlockHash, u64)> = None;
+
+    loop {
+        let positions = indexed_event_positions(&provider, filter, from, to_block)?;
+        let Some(last) = positions.last().copied() else { return Ok(None) };
+
+        // a transaction may emit multiple matching events, but it only needs to be processed once.
+        let mut txs =
+            positions.iter().map(|p| (p.block_number, p.tx_index as usize)).collect::<Vec<_>>();
+        txs.dedup();
+
+        for (block_num, tx_idx) in txs {
+            let (block_hash, tx_offset) = match current_block {
+                Some((num, hash, offset)) if num == block_num => (hash, offset),
+                _ => {
+                    let hash =
+                        provider.block_hash_by_num(block_num)?.context("Missing block hash")?;
+                    let offset = provider
+                        .block_body_indices(block_num.into())?
+                        .context("Missing block body index")?
+                        .tx_offset;
+
+                    current_block = Some((block_num, hash, offset));
+                    (hash, offset)
+                }
+            };
+
+            let tx_number = tx_offset + tx_idx as u64;
+            let tx_hash = provider
+                .transaction_hashes_in_range(tx_number..tx_number + 1)?
+                .pop()
+                .context("Missing transaction hash")?;
+            let receipt = provider.receipt_by_hash(tx_hash)?.context("Missing receipt")?;
+            let events = receipt.events();
+
+            // Same as in `scan_events_at_blocks`, only the transaction pointed by the cursor is
+            // processed from the event pointed by the cursor.
+            let next_event = if block_num == cursor.block && tx_idx == cursor.txn.idx {
+                match cursor.txn.event.cmp(&events.len()) {
+                    Ordering::Less => cursor.txn.event,
+                    Ordering::Greater | Ordering::Equal => continue,
+                }
+            } else {
+                0
+            };
+
+            let partial_cursor = fetch_tx_events(
+                next_event,
+                Some(block_num),
+                Some(block_hash),
+                tx_idx,
+                tx_hash,
+                events,
+                filter,
+                chunk_size as usize,
+                buffer,
+            )?;
+
+            if let Some(c) = partial_cursor {
+                return Ok(Some(c.into_full(block_num)));
+            }
+        }
+
+        // all the indexed events in the range have been processed.
+        if positions.len() < INDEX_QUERY_LIMIT {
+            return Ok(None);
+        }
+
+        // continue from the transaction after the last processed one.
+        from = EventPosition {
+            block_number: last.block_number,
+            tx_index: last.tx_index + 1,
+            event_index: 0,
+        };
+    }
+}
+
+/// Returns, in ascending order, the positions of at most [`INDEX_QUERY_LIMIT`] events starting
+/// from `from` up to the end of block `to_block` that may match the filter.
+fn indexed_event_positions(
+    provider: &impl EventIndexProvider,
+    filter: &Filter,
+    from: EventPosition,
+    to_block: BlockNumber,
+) -> EventQueryResult<Vec<EventPosition>> {
+    if let Some(address) = filter.address {
+        return Ok(provider.events_by_address(address, from, to_block, INDEX_QUERY_LIMIT)?);
+    }
+
+    let mut positions = Vec::new();
+    for key in filter.first_keys().unwrap_or_default() {
+        positions.extend(provider.events_by_key(*key, from, to_block, INDEX_QUERY_LIMIT)?);
+    }
+
+    // the first `INDEX_QUERY_LIMIT` positions of all keys are all within the first
+    // `INDEX_QUERY_LIMIT` positions of each key.
+    positions.sort();
+    positions.dedup();
+    positions.truncate(INDEX_QUERY_LIMIT);
+
+    Ok(positions)
+}
+
 /// An iterator that yields events that match the given filters.
 #[derive(Debug)]
 struct FilteredEvents<'a, I: Iterator<Item = &'a Event>> {
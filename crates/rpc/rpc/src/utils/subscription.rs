@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use jsonrpsee::types::SubscriptionId;
+use jsonrpsee::{ConnectionId, SubscriptionSink};
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+type SubscriptionKey = (ConnectionId, SubscriptionId<'static>);
+
+/// Keeps track of the active WebSocket subscriptions, so that they can be cancelled through the
+/// `starknet_unsubscribe` method.
+///
+/// Subscriptions are identified by their id and the connection they were created on, so that a
+/// client can only cancel its own subscriptions.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionRegistry {
+    subscriptions: Arc<Mutex<HashMap<SubscriptionKey, Arc<Notify>>>>,
+}
+
+impl SubscriptionRegistry {
+    /// Registers the subscription of `sink`. It stays registered until the returned handle is
+    /// dropped.
+    pub fn register(&self, sink: &SubscriptionSink) -> ActiveSubscription {
+        let key = (sink.connection_id(), sink.subscription_id());
+        let unsubscribed = Arc::new(Notify::new());
+        self.subscriptions.lock().insert(key.clone(), unsubscribed.clone());
+        ActiveSubscription { key, unsubscribed, registry: self.clone() }
+    }
+
+    /// Cancels the subscription `id` of the connection `conn_id`.
+    ///
+    /// Returns `false` if there is no such subscription.
+    pub fn unsubscribe(&self, conn_id: ConnectionId, id: SubscriptionId<'static>) -> bool {
+        match self.subscriptions.lock().remove(&(conn_id, id)) {
+            Some(unsubscribed) => {
+                unsubscribed.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Handle to a subscription registered in a [`SubscriptionRegistry`].
+#[derive(Debug)]
+pub struct ActiveSubscription {
+    key: SubscriptionKey,
+    unsubscribed: Arc<Notify>,
+    registry: SubscriptionRegistry,
+}
+
+impl ActiveSubscription {
+    /// Completes once the subscription has been cancelled by the client.
+    pub async fn unsubscribed(&self) {
+        self.unsubscribed.notified().await
+    }
+}
+
+impl Drop for ActiveSubscription {
+    fn drop(&mut self) {
+        self.registry.subscriptions.lock().remove(&self.key);
+    }
+}
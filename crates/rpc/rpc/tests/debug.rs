@@ -0,0 +1,78 @@
+use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::{felt, Felt};
+use katana_rpc_api::debug::DebugApiClient;
+use katana_rpc_types::FunctionCall;
+use katana_utils::TestNode;
+use starknet::accounts::Account;
+use starknet::core::types::Call;
+use starknet::macros::selector;
+
+#[tokio::test]
+async fn trace_call() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+    let account = sequencer.account();
+
+    let strk = sequencer.backend().executor_factory.cfg().fee_token_addresses.strk;
+    let request = FunctionCall {
+        contract_address: strk.into(),
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![account.address()],
+    };
+
+    let block_id = BlockIdOrTag::Tag(BlockTag::Latest);
+    let trace = client.trace_call(request.clone(), block_id).await.unwrap();
+
+    assert!(trace.revert_reason.is_none());
+    assert!(!trace.invocation.is_reverted);
+    assert_eq!(trace.invocation.contract_address, strk);
+    assert_eq!(trace.invocation.entry_point_selector, selector!("balanceOf"));
+    // the balance is returned as a u256, ie (low, high)
+    assert_eq!(trace.invocation.result.len(), 2);
+    assert!(!trace.invocation.storage_read_values.is_empty());
+    assert!(trace.invocation.cairo_steps > 0 || trace.invocation.sierra_gas > 0);
+    // reading the balance doesn't change the state
+    assert!(trace.state_diff.storage_diffs.is_empty());
+
+    // calling a function that doesn't exist is an error
+    let request = FunctionCall { entry_point_selector: selector!("missing"), ..request };
+    let result = client.trace_call(request, block_id).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn trace_transaction() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+    let provider = sequencer.starknet_provider();
+    let account = sequencer.account();
+
+    let strk = sequencer.backend().executor_factory.cfg().fee_token_addresses.strk;
+    let transfer = Call {
+        to: strk.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1337"), Felt::ONE, Felt::ZERO],
+    };
+
+    let res = account.execute_v3(vec![transfer]).send().await.unwrap();
+    katana_utils::TxWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    let trace = client.trace_transaction(res.transaction_hash).await.unwrap();
+    assert!(trace.revert_reason.is_none());
+    assert!(trace.validate_invocation.is_some());
+    assert!(trace.fee_transfer_invocation.is_some());
+    assert!(trace.state_diff.is_some());
+
+    // the account calls the token contract, which emits the transfer event
+    let execute = trace.execute_invocation.unwrap();
+    assert_eq!(execute.contract_address, account.address().into());
+
+    let inner = &execute.calls[0];
+    assert_eq!(inner.contract_address, strk);
+    assert_eq!(inner.entry_point_selector, selector!("transfer"));
+    assert!(!inner.events.is_empty());
+    assert!(!inner.accessed_storage_keys.is_empty());
+
+    let result = client.trace_transaction(felt!("0x1337")).await;
+    assert!(result.is_err());
+}
@@ -1,7 +1,32 @@
+use std::time::Duration;
+
+use jsonrpsee::core::client::Error as ClientError;
+use katana_executor::ExecutorFactory;
+use katana_pool::TransactionPool;
+use katana_primitives::block::GasPrice;
+use katana_primitives::fee::PriceUnit;
+use katana_primitives::genesis::constant::{
+    get_fee_token_balance_base_storage_address, ARGENT_ACCOUNT_CLASS_HASH,
+    DEFAULT_ACCOUNT_CLASS_HASH, DEFAULT_PREFUNDED_ACCOUNT_BALANCE, OZ_ACCOUNT_CLASS_HASH,
+};
+use katana_primitives::{address, felt, ContractAddress, Felt, U256};
 use katana_provider::traits::block::{BlockNumberProvider, BlockProvider};
 use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::state::{StateFactoryProvider, StateProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
 use katana_rpc::api::dev::DevApiClient;
-use katana_utils::TestNode;
+use katana_rpc::api::error::dev::DevApiError;
+use katana_rpc::api::starknet::StarknetApiClient;
+use katana_rpc_types::account::FeeToken;
+use katana_rpc_types::mining::MiningMode;
+use katana_utils::node::test_config;
+use katana_utils::{TestNode, TxWaiter};
+use num_traits::ToPrimitive;
+use starknet::accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag, Call, SequencerTransactionStatus};
+use starknet::macros::selector;
+use starknet::providers::Provider;
+use starknet::signers::{LocalWallet, SigningKey};
 
 #[tokio::test]
 async fn test_next_block_timestamp_in_past() {
@@ -103,26 +128,472 @@ async fn test_dev_api_enabled() {
     assert!(!accounts.is_empty(), "predeployed accounts should not be empty");
 }
 
-// #[tokio::test]
-// async fn test_set_storage_at_on_instant_mode() {
-//     let sequencer = create_test_sequencer().await;
-//     sequencer.backend().mine_empty_block();
+#[tokio::test]
+async fn test_set_storage_at_on_instant_mode() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let contract_address = address!("0x1337");
+    let key = felt!("0x20");
+    let value = felt!("0xabc");
+
+    let latest_num = provider.latest_number().unwrap();
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, None, "latest storage value should be empty");
+
+    client.set_storage_at(contract_address.into(), key, value).await.unwrap();
+
+    // a new block is mined with the storage update as its state diff
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 1);
+
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, Some(value), "latest storage value should be updated");
+
+    let state_update = provider.state_update((latest_num + 1).into()).unwrap().unwrap();
+    let storage_updates = state_update.storage_updates.get(&contract_address).unwrap();
+    assert_eq!(storage_updates.get(&key), Some(&value));
+}
+
+#[tokio::test]
+async fn test_set_storage_at_on_interval_mode() {
+    let mut config = test_config();
+    config.sequencing.no_mining = true;
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let contract_address = address!("0x1337");
+    let key = felt!("0x20");
+    let value = felt!("0xabc");
+
+    let latest_num = provider.latest_number().unwrap();
+    client.set_storage_at(contract_address.into(), key, value).await.unwrap();
+
+    // the update is only applied to the pending block
+    assert_eq!(provider.latest_number().unwrap(), latest_num);
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, None, "latest storage value should be empty");
+
+    let storage = sequencer
+        .starknet_provider()
+        .get_storage_at(Felt::from(contract_address), key, BlockId::Tag(BlockTag::Pending))
+        .await
+        .unwrap();
+    assert_eq!(storage, value, "pending storage value should be updated");
+
+    client.generate_block().await.unwrap();
+
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, Some(value), "latest storage value should be updated");
+}
+
+#[tokio::test]
+async fn test_set_nonce_and_class_hash() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let (address, account) = sequencer.backend().chain_spec.genesis().accounts().next().unwrap();
+    let address = *address;
+
+    client.set_nonce(address, felt!("0x42")).await.unwrap();
+    let nonce = provider.latest().unwrap().nonce(address).unwrap();
+    assert_eq!(nonce, Some(felt!("0x42")));
+
+    // replacing with an undeclared class must fail
+    let result = client.set_class_hash(address, felt!("0xdead")).await;
+    assert!(result.is_err(), "undeclared class should be rejected");
+
+    client.set_class_hash(address, account.class_hash()).await.unwrap();
+    let class_hash = provider.latest().unwrap().class_hash_of_contract(address).unwrap();
+    assert_eq!(class_hash, Some(account.class_hash()));
+}
+
+#[tokio::test]
+async fn test_mint() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let address = address!("0x1337");
+    let strk = sequencer.backend().executor_factory.cfg().fee_token_addresses.strk;
+    let balance_slot = get_fee_token_balance_base_storage_address(address);
+
+    let amount = U256::from(1_000_000u64);
+    client.mint(address, amount, None).await.unwrap();
+    client.mint(address, amount, Some(PriceUnit::Fri)).await.unwrap();
+
+    let state = provider.latest().unwrap();
+    let low = state.storage(strk, balance_slot).unwrap();
+    let high = state.storage(strk, balance_slot + Felt::ONE).unwrap();
+
+    assert_eq!(low, Some(Felt::from(2_000_000u64)));
+    assert_eq!(high, Some(Felt::ZERO));
+}
+
+#[tokio::test]
+async fn test_snapshot_and_revert() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let contract_address = address!("0x1337");
+    let key = felt!("0x20");
+
+    client.set_storage_at(contract_address.into(), key, felt!("0x1")).await.unwrap();
+
+    let snapshot_block = provider.latest_number().unwrap();
+    let id = client.snapshot().await.unwrap();
+
+    client.set_storage_at(contract_address.into(), key, felt!("0x2")).await.unwrap();
+    client.generate_block().await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), snapshot_block + 2);
+
+    client.revert(id).await.unwrap();
+
+    // the blocks mined after the snapshot are removed and the state is reverted
+    assert_eq!(provider.latest_number().unwrap(), snapshot_block);
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, Some(felt!("0x1")));
+
+    // the snapshot can only be reverted to once
+    assert!(client.revert(id).await.is_err());
+
+    // new blocks are mined on top of the reverted chain
+    client.generate_block().await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), snapshot_block + 1);
+}
+
+#[tokio::test]
+async fn test_impersonate_account() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+
+    // an account whose private key we don't own
+    let address = *sequencer.backend().chain_spec.genesis().accounts().next().unwrap().0;
+    let signer = LocalWallet::from(SigningKey::from_random());
+    let mut account = SingleOwnerAccount::new(
+        sequencer.starknet_provider(),
+        signer,
+        address.into(),
+        sequencer.backend().chain_spec.id().into(),
+        ExecutionEncoding::New,
+    );
+    account.set_block_id(BlockId::Tag(BlockTag::Pending));
+
+    let strk = sequencer.backend().executor_factory.cfg().fee_token_addresses.strk;
+    let transfer = Call {
+        to: strk.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1337"), Felt::ONE, Felt::ZERO],
+    };
+
+    // the transaction isn't signed by the account's key
+    let result = account.execute_v3(vec![transfer.clone()]).send().await;
+    assert!(result.is_err(), "unsigned transaction should be rejected");
+
+    client.impersonate_account(address).await.unwrap();
+    let res = account.execute_v3(vec![transfer.clone()]).send().await.unwrap();
+    // the waiter fails if the transaction is reverted
+    TxWaiter::new(res.transaction_hash, &sequencer.starknet_provider()).await.unwrap();
+
+    // the transfer was applied and the account's nonce was bumped
+    let state = sequencer.blockchain().latest().unwrap();
+    let balance_slot = get_fee_token_balance_base_storage_address(address!("0x1337"));
+    assert_eq!(state.storage(strk, balance_slot).unwrap(), Some(Felt::ONE));
+    assert_eq!(state.nonce(address).unwrap(), Some(Felt::ONE));
+
+    client.stop_impersonating_account(address).await.unwrap();
+    let result = account.execute_v3(vec![transfer]).send().await;
+    assert!(result.is_err(), "account should be validated after impersonation is stopped");
+}
+
+#[tokio::test]
+async fn test_mine() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let timestamp = |num: u64| provider.block(num.into()).unwrap().unwrap().header.timestamp;
+
+    let latest_num = provider.latest_number().unwrap();
+    let next_timestamp = client.next_block_timestamp().await.unwrap();
+
+    client.mine(Some(3), Some(60)).await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 3);
+
+    // the first block is mined with the next block timestamp, the clock may have moved since
+    assert!(timestamp(latest_num + 1) >= next_timestamp);
+    assert_eq!(timestamp(latest_num + 2), timestamp(latest_num + 1) + 60);
+    assert_eq!(timestamp(latest_num + 3), timestamp(latest_num + 2) + 60);
+
+    // a single block is mined by default
+    client.mine(None, None).await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 4);
+}
+
+#[tokio::test]
+async fn test_set_mining_mode() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let contract_address = address!("0x1337");
+    let key = felt!("0x20");
+
+    client.set_mining_mode(MiningMode::OnDemand).await.unwrap();
+
+    // the state updates are applied to the pending block, which is only mined on demand
+    let latest_num = provider.latest_number().unwrap();
+    client.set_storage_at(contract_address.into(), key, felt!("0x1")).await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), latest_num);
+
+    // the next block timestamp is the one of the already opened pending block
+    let next_timestamp = client.next_block_timestamp().await.unwrap();
+    client.generate_block().await.unwrap();
+    let block = provider.block((latest_num + 1).into()).unwrap().unwrap();
+    assert_eq!(block.header.timestamp, next_timestamp);
+
+    assert!(client.set_block_time(0).await.is_err(), "zero block time should be rejected");
+    client.set_block_time(60_000).await.unwrap();
+
+    // the pending block is mined when switching back to instant mode
+    client.set_storage_at(contract_address.into(), key, felt!("0x2")).await.unwrap();
+    client.set_mining_mode(MiningMode::Instant).await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 2);
+
+    let storage = provider.latest().unwrap().storage(contract_address, key).unwrap();
+    assert_eq!(storage, Some(felt!("0x2")));
+
+    client.set_storage_at(contract_address.into(), key, felt!("0x3")).await.unwrap();
+    assert_eq!(provider.latest_number().unwrap(), latest_num + 3);
+}
+
+#[tokio::test]
+async fn test_generate_block_with_rejected_transaction() {
+    let mut config = test_config();
+    config.sequencing.no_mining = true;
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let provider = sequencer.starknet_provider();
+    let pool = sequencer.handle().node().pool().clone();
+    let client = sequencer.rpc_http_client();
+
+    let mut account = sequencer.account();
+    account.set_block_id(BlockId::Tag(BlockTag::Pending));
+
+    let strk = sequencer.backend().executor_factory.cfg().fee_token_addresses.strk;
+    let transfer = Call {
+        to: strk.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1337"), Felt::ONE, Felt::ZERO],
+    };
+
+    // mine a first transaction so that the pool tracks the account's next nonce
+    let res = account.execute_v3(vec![transfer.clone()]).send().await.unwrap();
+    TxWaiter::new(res.transaction_hash, &provider).await.unwrap();
+    client.generate_block().await.unwrap();
+
+    let fee = account.execute_v3(vec![transfer.clone()]).estimate_fee().await.unwrap();
+
+    // the nonce of the account in the pending block no longer matches the one tracked by the pool,
+    // so the next transaction passes the pool validation but fails the nonce check on execution
+    client.set_nonce(account.address().into(), Felt::ZERO).await.unwrap();
+
+    let res = account
+        .execute_v3(vec![transfer])
+        .nonce(Felt::ONE)
+        .l1_gas(fee.l1_gas_consumed.to_u64().unwrap())
+        .l2_gas(fee.l2_gas_consumed.to_u64().unwrap())
+        .l1_data_gas(fee.l1_data_gas_consumed.to_u64().unwrap())
+        .l1_gas_price(fee.l1_gas_price.to_u128().unwrap())
+        .l2_gas_price(fee.l2_gas_price.to_u128().unwrap())
+        .l1_data_gas_price(fee.l1_data_gas_price.to_u128().unwrap())
+        .send()
+        .await
+        .unwrap();
+    let tx_hash = res.transaction_hash;
+
+    // wait for the transaction to be executed in the pending block
+    let mut executed = false;
+    for _ in 0..50 {
+        let status = client.get_transaction_status(tx_hash).await.unwrap();
+        if status.finality_status == SequencerTransactionStatus::Rejected {
+            executed = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(executed, "transaction should have been executed");
+    assert!(pool.contains(tx_hash));
+
+    client.generate_block().await.unwrap();
+
+    // the pool is updated with the outcome of the block mined by `dev_generateBlock`
+    let status = client.get_transaction_status(tx_hash).await.unwrap();
+    assert_eq!(status.finality_status, SequencerTransactionStatus::Rejected);
+    assert!(status.failure_reason.is_some());
+    assert!(!pool.contains(tx_hash), "rejected transaction should be removed from the pool");
+}
+
+#[tokio::test]
+async fn test_set_gas_prices() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let gas_prices = unsafe { GasPrice::new_unchecked(100, 200) };
+    let data_gas_prices = unsafe { GasPrice::new_unchecked(300, 400) };
+
+    client.set_gas_prices(gas_prices.clone()).await.unwrap();
+    client.set_data_gas_prices(data_gas_prices.clone()).await.unwrap();
+
+    let prices = client.get_gas_prices().await.unwrap();
+    assert_eq!(prices.l1_gas_prices, gas_prices);
+    assert_eq!(prices.l1_data_gas_prices, data_gas_prices);
+
+    // the new prices are used by the next block
+    client.generate_block().await.unwrap();
+
+    let latest_num = provider.latest_number().unwrap();
+    let header = provider.block(latest_num.into()).unwrap().unwrap().header;
+    assert_eq!(header.l1_gas_prices, gas_prices);
+    assert_eq!(header.l1_data_gas_prices, data_gas_prices);
+    assert_eq!(header.l2_gas_prices, prices.l2_gas_prices);
+}
+
+#[tokio::test]
+async fn test_set_gas_prices_on_demand_mode() {
+    let mut config = test_config();
+    config.sequencing.no_mining = true;
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let provider = sequencer.blockchain();
+    let validator = sequencer.handle().node().pool().validator();
+    let client = sequencer.rpc_http_client();
+
+    let initial_prices = client.get_gas_prices().await.unwrap();
+    let gas_prices = unsafe { GasPrice::new_unchecked(100, 200) };
+    client.set_gas_prices(gas_prices.clone()).await.unwrap();
+
+    // the pending block is already open, so it and the validator keep the previous prices
+    let prices = client.get_gas_prices().await.unwrap();
+    assert_eq!(prices.l1_gas_prices, initial_prices.l1_gas_prices);
+    assert_eq!(validator.block_env().l1_gas_prices, initial_prices.l1_gas_prices);
+
+    client.generate_block().await.unwrap();
+
+    let latest_num = provider.latest_number().unwrap();
+    let header = provider.block(latest_num.into()).unwrap().unwrap().header;
+    assert_eq!(header.l1_gas_prices, initial_prices.l1_gas_prices);
+
+    // the new prices are used from the next pending block onwards
+    let prices = client.get_gas_prices().await.unwrap();
+    assert_eq!(prices.l1_gas_prices, gas_prices);
+
+    client.generate_block().await.unwrap();
+
+    let header = provider.block((latest_num + 1).into()).unwrap().unwrap().header;
+    assert_eq!(header.l1_gas_prices, gas_prices);
+    assert_eq!(validator.block_env().l1_gas_prices, gas_prices);
+}
+
+#[tokio::test]
+async fn test_create_and_fund_account() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+    let client = sequencer.rpc_http_client();
+
+    let created = client.create_account(None).await.unwrap();
+    assert_eq!(created.class_hash, DEFAULT_ACCOUNT_CLASS_HASH);
+
+    let state = provider.latest().unwrap();
+    let class_hash = state.class_hash_of_contract(created.address).unwrap();
+    assert_eq!(class_hash, Some(DEFAULT_ACCOUNT_CLASS_HASH));
+
+    // the account is listed along with the genesis accounts
+    let accounts = client.predeployed_accounts().await.unwrap();
+    let listed = accounts.iter().find(|a| a.address == created.address).unwrap();
+    assert_eq!(listed.private_key, created.private_key);
+
+    let fee_tokens = &sequencer.backend().executor_factory.cfg().fee_token_addresses;
+    let balance_slot = get_fee_token_balance_base_storage_address(created.address);
 
-//     let contract_address = ContractAddress(patricia_key!("0x1337"));
-//     let key = StorageKey(patricia_key!("0x20"));
-//     let val = stark_felt!("0xABC");
+    client.fund_account(created.address, U256::from(1000u64), Some(FeeToken::Eth)).await.unwrap();
+    client
+        .fund_account(created.address, U256::from(DEFAULT_PREFUNDED_ACCOUNT_BALANCE), None)
+        .await
+        .unwrap();
 
-//     {
-//         let mut state = sequencer.backend().state.write().await;
-//         let read_val = state.get_storage_at(contract_address, key).unwrap();
-//         assert_eq!(stark_felt!("0x0"), read_val, "latest storage value should be 0");
-//     }
+    let state = provider.latest().unwrap();
+    let eth_balance = state.storage(fee_tokens.eth, balance_slot).unwrap();
+    assert_eq!(eth_balance, Some(Felt::from(1000u64)));
 
-//     sequencer.set_storage_at(contract_address, key, val).await.unwrap();
+    // the listed balances are read from the state
+    assert_eq!(created.balance, U256::ZERO);
+    let accounts = client.predeployed_accounts().await.unwrap();
+    let listed = accounts.iter().find(|a| a.address == created.address).unwrap();
+    assert_eq!(listed.balance, U256::from(DEFAULT_PREFUNDED_ACCOUNT_BALANCE));
 
-//     {
-//         let mut state = sequencer.backend().state.write().await;
-//         let read_val = state.get_storage_at(contract_address, key).unwrap();
-//         assert_eq!(val, read_val, "latest storage value incorrect after generate");
-//     }
-// }
+    // the account can send transactions signed with its key once funded with STRK
+    let signer = LocalWallet::from(SigningKey::from_secret_scalar(created.private_key.unwrap()));
+    let mut account = SingleOwnerAccount::new(
+        sequencer.starknet_provider(),
+        signer,
+        created.address.into(),
+        sequencer.backend().chain_spec.id().into(),
+        ExecutionEncoding::New,
+    );
+    account.set_block_id(BlockId::Tag(BlockTag::Pending));
+
+    let transfer = Call {
+        to: fee_tokens.strk.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1337"), Felt::ONE, Felt::ZERO],
+    };
+    let result = account.execute_v3(vec![transfer]).send().await;
+    assert!(result.is_ok(), "created account should be able to send transactions");
+}
+
+#[tokio::test]
+async fn test_create_account_unsupported_class() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+
+    // the account constructor isn't run, so only the account classes with a known public key
+    // slot can be used
+    let result = client.create_account(Some(felt!("0x1337"))).await;
+    assert_dev_error(result, DevApiError::UnsupportedAccountClass);
+
+    // the OpenZeppelin and Argent classes are supported but aren't declared at genesis
+    let result = client.create_account(Some(OZ_ACCOUNT_CLASS_HASH)).await;
+    assert_dev_error(result, DevApiError::ClassNotDeclared);
+    let result = client.create_account(Some(ARGENT_ACCOUNT_CLASS_HASH)).await;
+    assert_dev_error(result, DevApiError::ClassNotDeclared);
+}
+
+fn assert_dev_error<T: std::fmt::Debug>(result: Result<T, ClientError>, expected: DevApiError) {
+    match result {
+        Err(ClientError::Call(error)) => assert_eq!(error.code(), expected as i32),
+        other => panic!("expected {expected:?} error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_created_account_reverted() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+
+    let snapshot = client.snapshot().await.unwrap();
+    let created = client.create_account(None).await.unwrap();
+
+    let accounts = client.predeployed_accounts().await.unwrap();
+    assert!(accounts.iter().any(|a| a.address == created.address));
+
+    // the account is no longer listed once its deployment is reverted
+    client.revert(snapshot).await.unwrap();
+    let accounts = client.predeployed_accounts().await.unwrap();
+    assert!(!accounts.iter().any(|a| a.address == created.address));
+}
@@ -0,0 +1,100 @@
+use assert_matches::assert_matches;
+use cainome::rs::abigen_legacy;
+use katana_feeder_gateway::client::{self, ErrorCode, SequencerGateway};
+use katana_feeder_gateway::types::{BlockStatus, ContractClass, TypedTransaction};
+use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::genesis::constant::{
+    DEFAULT_ACCOUNT_CLASS_HASH, DEFAULT_ETH_FEE_TOKEN_ADDRESS,
+};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::ContractAddress;
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider, HeaderProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_utils::node::test_config;
+use katana_utils::TestNode;
+use starknet::accounts::Account;
+use starknet::core::types::Felt;
+use starknet::macros::felt;
+use url::Url;
+
+abigen_legacy!(Erc20Contract, "crates/rpc/rpc/tests/test_data/erc20.json", derives(Clone));
+
+async fn setup() -> (TestNode, SequencerGateway) {
+    let mut config = test_config();
+    config.rpc.feeder_gateway = true;
+    let sequencer = TestNode::new_with_config(config).await;
+
+    let url = Url::parse(&format!("http://{}/", sequencer.rpc_addr())).unwrap();
+    (sequencer, SequencerGateway::new(url))
+}
+
+#[tokio::test]
+async fn get_block_and_state_update() {
+    let (sequencer, gateway) = setup().await;
+
+    // send a transaction so that there's a block with a transaction in it
+    let account = sequencer.account();
+    let provider = sequencer.starknet_provider();
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+    let amount = Uint256 { low: felt!("0x1"), high: Felt::ZERO };
+    let res = contract.transfer(&felt!("0x1"), &amount).send().await.unwrap();
+    katana_utils::TxWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    let storage = sequencer.blockchain();
+    let latest = storage.latest_number().unwrap();
+
+    let block = gateway.get_block(BlockIdOrTag::Number(latest)).await.unwrap();
+    assert_eq!(block.block_number, Some(latest));
+    assert_eq!(block.block_hash, storage.block_hash_by_num(latest).unwrap());
+    assert_eq!(block.status, BlockStatus::AcceptedOnL2);
+    assert_eq!(block.transactions.len(), 1);
+    assert_eq!(block.transaction_receipts.len(), 1);
+    assert_eq!(block.transactions[0].hash, res.transaction_hash);
+    assert_eq!(block.transaction_receipts[0].transaction_hash, res.transaction_hash);
+    assert_matches!(&block.transactions[0].tx, TypedTransaction::InvokeFunction(tx) => {
+        assert_eq!(tx.sender_address, ContractAddress::from(account.address()));
+    });
+
+    // the latest block is returned if no block id is specified
+    let latest_block = gateway.get_block(BlockIdOrTag::Tag(BlockTag::Latest)).await.unwrap();
+    assert_eq!(latest_block, block);
+
+    let with_block =
+        gateway.get_state_update_with_block(BlockIdOrTag::Number(latest)).await.unwrap();
+    assert_eq!(with_block.block, block);
+
+    let state_update = with_block.state_update;
+    let parent = storage.header(latest.saturating_sub(1).into()).unwrap().unwrap();
+    let header = storage.header(latest.into()).unwrap().unwrap();
+    let expected_diff = storage.state_update(latest.into()).unwrap().unwrap();
+    assert_eq!(state_update.block_hash, block.block_hash);
+    assert_eq!(state_update.old_root, parent.state_root);
+    assert_eq!(state_update.new_root, Some(header.state_root));
+    assert_eq!(StateUpdates::from(state_update.state_diff), expected_diff);
+}
+
+#[tokio::test]
+async fn get_classes() {
+    let (_sequencer, gateway) = setup().await;
+    let latest = BlockIdOrTag::Tag(BlockTag::Latest);
+
+    let class = gateway.get_class(DEFAULT_ACCOUNT_CLASS_HASH, latest).await.unwrap();
+    assert_matches!(class, ContractClass::Class(_));
+
+    let casm = gateway.get_compiled_class(DEFAULT_ACCOUNT_CLASS_HASH, latest).await;
+    assert!(casm.is_ok());
+
+    let err = gateway.get_class(felt!("0x1337"), latest).await.unwrap_err();
+    assert_matches!(err, client::Error::Sequencer(e) if e.code == ErrorCode::UndeclaredClass);
+}
+
+#[tokio::test]
+async fn block_not_found() {
+    let (_sequencer, gateway) = setup().await;
+
+    let err = gateway.get_block(BlockIdOrTag::Number(1000)).await.unwrap_err();
+    assert_matches!(err, client::Error::Sequencer(e) if e.code == ErrorCode::BlockNotFound);
+
+    let err = gateway.get_state_update(BlockIdOrTag::Hash(felt!("0x1337"))).await.unwrap_err();
+    assert_matches!(err, client::Error::Sequencer(e) if e.code == ErrorCode::BlockNotFound);
+}
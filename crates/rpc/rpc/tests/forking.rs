@@ -1,15 +1,22 @@
 use anyhow::Result;
 use assert_matches::assert_matches;
 use cainome::rs::abigen_legacy;
-use katana_node::config::fork::ForkingConfig;
+use katana_node::config::fork::{BackendConfig, ForkingConfig};
+use katana_node::config::Config;
 use katana_primitives::block::{BlockHash, BlockHashOrNumber, BlockIdOrTag, BlockNumber, BlockTag};
 use katana_primitives::chain::NamedChainId;
 use katana_primitives::event::MaybeForkedContinuationToken;
 use katana_primitives::genesis::constant::DEFAULT_STRK_FEE_TOKEN_ADDRESS;
 use katana_primitives::transaction::TxHash;
-use katana_primitives::{felt, Felt};
+use katana_primitives::{felt, hash, ContractAddress, Felt};
+use katana_rpc_api::starknet::StarknetApiClient;
+use katana_rpc_types::trie::ContractStorageKeys;
+use katana_trie::{compute_contract_state_hash, MultiProof};
 use katana_utils::TestNode;
-use starknet::core::types::{EventFilter, MaybePendingBlockWithTxHashes, StarknetError};
+use starknet::accounts::Account;
+use starknet::core::types::{
+    EventFilter, MaybePendingBlockWithTxHashes, MaybePendingStateUpdate, StarknetError,
+};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider, ProviderError};
 use url::Url;
@@ -26,6 +33,8 @@ fn forking_cfg() -> ForkingConfig {
     ForkingConfig {
         url: Url::parse(SEPOLIA_URL).unwrap(),
         block: Some(BlockHashOrNumber::Num(FORK_BLOCK_NUMBER)),
+        // storage proofs of old blocks aren't necessarily served by public nodes
+        backend: BackendConfig { remote_tries: false, ..Default::default() },
     }
 }
 
@@ -679,3 +688,141 @@ async fn get_events_with_invalid_block_hash(#[case] hash: BlockHash) -> Result<(
 
     Ok(())
 }
+
+/// Returns the configuration of a node that forks the given local node at `block`.
+fn local_forking_config(network: &TestNode, block: BlockNumber, remote_tries: bool) -> Config {
+    let mut config = katana_utils::node::test_config();
+    config.forking = Some(ForkingConfig {
+        url: Url::parse(&format!("http://{}", network.rpc_addr())).unwrap(),
+        block: Some(BlockHashOrNumber::Num(block)),
+        backend: BackendConfig { remote_tries, ..Default::default() },
+    });
+    config
+}
+
+/// Sends a transfer of the fee token from the first account of the node, and waits for it to be
+/// included in a block.
+async fn transfer(node: &TestNode) {
+    abigen_legacy!(FeeToken, "crates/rpc/rpc/tests/test_data/erc20.json");
+    let contract = FeeToken::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), node.account());
+
+    let amount = Uint256 { low: felt!("0x1337"), high: Felt::ZERO };
+    let res = contract.transfer(&felt!("0x1234"), &amount).send().await.unwrap();
+    katana_utils::TxWaiter::new(res.transaction_hash, &node.starknet_provider()).await.unwrap();
+}
+
+// The forked node blocks on its requests to the forked network, which is served by the same
+// runtime.
+#[tokio::test(flavor = "multi_thread")]
+async fn forked_state_roots_and_proofs() -> Result<()> {
+    let network = TestNode::new().await;
+    let fork = TestNode::new_with_config(local_forking_config(&network, 0, true)).await;
+
+    let network_client = network.rpc_http_client();
+    let fork_client = fork.rpc_http_client();
+
+    // The forked block re-applies the same genesis states on top of the forked network's tries.
+    let expected = network_client.get_storage_proof(BlockIdOrTag::Number(0), None, None, None);
+    let expected = expected.await?.global_roots;
+    let actual = fork_client.get_storage_proof(BlockIdOrTag::Number(0), None, None, None);
+    let actual = actual.await?.global_roots;
+    assert_eq!(actual.classes_tree_root, expected.classes_tree_root);
+    assert_eq!(actual.contracts_tree_root, expected.contracts_tree_root);
+
+    // Apply the same state updates on both chains, the forked network computing its tries locally.
+    transfer(&network).await;
+    transfer(&fork).await;
+
+    let block_id = BlockIdOrTag::Number(1);
+    let expected = network.starknet_provider().get_block_with_tx_hashes(block_id).await?;
+    let actual = fork.starknet_provider().get_block_with_tx_hashes(block_id).await?;
+    let (
+        MaybePendingBlockWithTxHashes::Block(expected),
+        MaybePendingBlockWithTxHashes::Block(actual),
+    ) = (expected, actual)
+    else {
+        panic!("expected confirmed blocks")
+    };
+    assert_eq!(actual.new_root, expected.new_root);
+
+    let state_update = network.starknet_provider().get_state_update(block_id).await?;
+    let MaybePendingStateUpdate::Update(state_update) = state_update else {
+        panic!("expected a confirmed state update")
+    };
+
+    let storage_diffs = state_update.state_diff.storage_diffs;
+    let contracts: Vec<ContractAddress> = storage_diffs.iter().map(|d| d.address.into()).collect();
+    let storages = storage_diffs
+        .iter()
+        .map(|diff| ContractStorageKeys {
+            address: diff.address.into(),
+            keys: diff.storage_entries.iter().map(|entry| entry.key).collect(),
+        })
+        .collect::<Vec<_>>();
+
+    let expected = network_client.get_storage_proof(
+        block_id,
+        None,
+        Some(contracts.clone()),
+        Some(storages.clone()),
+    );
+    let expected = expected.await?;
+    let actual =
+        fork_client.get_storage_proof(block_id, None, Some(contracts.clone()), Some(storages));
+    let actual = actual.await?;
+
+    assert_eq!(actual.global_roots.classes_tree_root, expected.global_roots.classes_tree_root);
+    assert_eq!(actual.global_roots.contracts_tree_root, expected.global_roots.contracts_tree_root);
+
+    // The proofs of the updated contracts must prove the leaves computed by the forked network.
+    let leaves = expected
+        .contracts_proof
+        .contract_leaves_data
+        .iter()
+        .map(|leaf| compute_contract_state_hash(&leaf.class_hash, &leaf.storage_root, &leaf.nonce))
+        .collect::<Vec<_>>();
+
+    let contracts_proof = MultiProof::from(actual.contracts_proof.nodes);
+    let results = katana_trie::verify_proof::<hash::Pedersen>(
+        &contracts_proof,
+        actual.global_roots.contracts_tree_root,
+        contracts.into_iter().map(Felt::from).collect(),
+    );
+    assert_eq!(results, leaves);
+
+    // The storage proofs must prove the updated values against the storage roots computed by the
+    // forked network.
+    let proofs = actual.contracts_storage_proofs.nodes;
+    let leaves = expected.contracts_proof.contract_leaves_data;
+    for ((diff, proof), leaf) in storage_diffs.iter().zip(proofs).zip(leaves) {
+        let keys = diff.storage_entries.iter().map(|entry| entry.key).collect();
+        let values = diff.storage_entries.iter().map(|entry| entry.value).collect::<Vec<_>>();
+
+        let proof = MultiProof::from(proof);
+        let results = katana_trie::verify_proof::<hash::Pedersen>(&proof, leaf.storage_root, keys);
+        assert_eq!(results, values);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pre_fork_storage_proofs_are_delegated() -> Result<()> {
+    let network = TestNode::new().await;
+    transfer(&network).await;
+
+    let fork = TestNode::new_with_config(local_forking_config(&network, 1, false)).await;
+
+    let contracts = Some(vec![DEFAULT_STRK_FEE_TOKEN_ADDRESS, network.account().address().into()]);
+    let block_id = BlockIdOrTag::Number(0);
+
+    let client = network.rpc_http_client();
+    let expected = client.get_storage_proof(block_id, None, contracts.clone(), None).await?;
+
+    let client = fork.rpc_http_client();
+    let actual = client.get_storage_proof(block_id, None, contracts, None).await?;
+
+    assert_eq!(serde_json::to_value(actual)?, serde_json::to_value(expected)?);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use assert_matches::assert_matches;
+use jsonrpsee::core::ClientError;
+use katana_chain_spec::{dev, ChainSpec};
+use katana_primitives::address;
+use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::chain::ChainId;
+use katana_primitives::da::kzg::state_updates_to_blobs;
+use katana_primitives::da::payload::state_updates_to_payload;
+use katana_primitives::da::L1DataAvailabilityMode;
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider};
+use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_rpc_api::error::katana::KatanaApiError;
+use katana_rpc_api::katana::KatanaApiClient;
+use katana_utils::node::test_config;
+use katana_utils::TestNode;
+
+#[tokio::test]
+async fn get_block_blobs() {
+    let mut chain = dev::ChainSpec {
+        id: ChainId::SEPOLIA,
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        ..Default::default()
+    };
+    chain.genesis.sequencer_address = address!("0x1");
+
+    let mut config = test_config();
+    config.chain = ChainSpec::Dev(chain).into();
+
+    let sequencer = TestNode::new_with_config(config).await;
+    let backend = sequencer.backend();
+    let provider = backend.blockchain.provider();
+    let client = sequencer.rpc_http_client();
+
+    let mut block_env = provider.block_env_at(0.into()).unwrap().unwrap();
+    backend.update_block_env(&mut block_env);
+    backend.mine_empty_block(&block_env).unwrap();
+
+    for block_number in 0..=provider.latest_number().unwrap() {
+        let result = client.get_block_blobs(BlockIdOrTag::Number(block_number)).await.unwrap();
+
+        let state_updates = provider.state_update(block_number.into()).unwrap().unwrap();
+        let expected = state_updates_to_blobs(state_updates).unwrap();
+
+        assert_eq!(result.block_number, block_number);
+        assert_eq!(result.block_hash, provider.block_hash_by_num(block_number).unwrap().unwrap());
+        assert_eq!(result.blobs, expected);
+        assert!(!result.blobs.is_empty());
+    }
+
+    let latest = client.get_block_blobs(BlockIdOrTag::Tag(BlockTag::Latest)).await.unwrap();
+    assert_eq!(latest.block_number, provider.latest_number().unwrap());
+
+    let err = client.get_block_blobs(BlockIdOrTag::Number(1000)).await.unwrap_err();
+    assert_matches!(err, ClientError::Call(e) => {
+        assert_eq!(e.code(), KatanaApiError::BlockNotFound as i32);
+    });
+}
+
+#[tokio::test]
+async fn get_block_blobs_calldata_mode() {
+    // the default chain spec uses the calldata data availability mode
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+
+    let err = client.get_block_blobs(BlockIdOrTag::Tag(BlockTag::Latest)).await.unwrap_err();
+    assert_matches!(err, ClientError::Call(e) => {
+        assert_eq!(e.code(), KatanaApiError::BlobDataAvailabilityNotEnabled as i32);
+    });
+}
+
+#[tokio::test]
+async fn get_block_da_payload() {
+    let sequencer = TestNode::new().await;
+    let backend = sequencer.backend();
+    let provider = backend.blockchain.provider();
+    let client = sequencer.rpc_http_client();
+
+    for _ in 0..2 {
+        let latest = provider.latest_number().unwrap();
+        let mut block_env = provider.block_env_at(latest.into()).unwrap().unwrap();
+        backend.update_block_env(&mut block_env);
+        backend.mine_empty_block(&block_env).unwrap();
+    }
+
+    // the payload is available regardless of the data availability mode of the block
+    let result = client.get_block_da_payload(BlockIdOrTag::Number(0), None).await.unwrap();
+    assert_eq!(result.len(), 1);
+
+    let state_updates = provider.state_update(0.into()).unwrap().unwrap();
+    let expected = state_updates_to_payload(state_updates).unwrap();
+    assert_eq!(result[0].block_number, 0);
+    assert_eq!(result[0].block_hash, provider.block_hash_by_num(0).unwrap().unwrap());
+    assert_eq!(result[0].payload, expected);
+
+    let latest = BlockIdOrTag::Tag(BlockTag::Latest);
+    let result = client.get_block_da_payload(BlockIdOrTag::Number(0), Some(latest)).await.unwrap();
+    let block_numbers = result.iter().map(|payload| payload.block_number).collect::<Vec<_>>();
+    assert_eq!(block_numbers, vec![0, 1, 2]);
+
+    let from = BlockIdOrTag::Number(2);
+    let err = client.get_block_da_payload(from, Some(BlockIdOrTag::Number(1))).await.unwrap_err();
+    assert_matches!(err, ClientError::Call(e) => {
+        assert_eq!(e.code(), KatanaApiError::InvalidBlockRange as i32);
+    });
+}
@@ -6,12 +6,17 @@ use assert_matches::assert_matches;
 use cainome::rs::{abigen, abigen_legacy};
 use common::split_felt;
 use indexmap::IndexSet;
+use katana_pool::TransactionPool;
+use katana_primitives::da::DataAvailabilityMode;
 use katana_primitives::event::ContinuationToken;
+use katana_primitives::fee::{AllResourceBoundsMapping, ResourceBounds, ResourceBoundsMapping};
 use katana_primitives::genesis::constant::{
     DEFAULT_ACCOUNT_CLASS_HASH, DEFAULT_ETH_FEE_TOKEN_ADDRESS, DEFAULT_PREFUNDED_ACCOUNT_BALANCE,
-    DEFAULT_STRK_FEE_TOKEN_ADDRESS, DEFAULT_UDC_ADDRESS,
+    DEFAULT_STRK_FEE_TOKEN_ADDRESS, DEFAULT_STRK_L2_GAS_PRICE, DEFAULT_UDC_ADDRESS,
 };
+use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, InvokeTx, InvokeTxV3};
 use katana_rpc_api::dev::DevApiClient;
+use katana_rpc_api::starknet::StarknetApiClient;
 use katana_utils::TestNode;
 use num_traits::ToPrimitive;
 use starknet::accounts::{
@@ -22,8 +27,8 @@ use starknet::core::types::{
     BlockId, BlockTag, Call, DeclareTransactionReceipt, DeployAccountTransactionReceipt,
     EventFilter, EventsPage, ExecutionResult, Felt, MaybePendingBlockWithReceipts,
     MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate,
-    StarknetError, TransactionExecutionStatus, TransactionFinalityStatus, TransactionReceipt,
-    TransactionTrace,
+    SequencerTransactionStatus, StarknetError, TransactionExecutionStatus,
+    TransactionFinalityStatus, TransactionReceipt, TransactionStatus, TransactionTrace,
 };
 use starknet::core::utils::get_contract_address;
 use starknet::macros::{felt, selector};
@@ -586,6 +591,152 @@ async fn send_txs_with_invalid_signature(
     Ok(())
 }
 
+#[tokio::test]
+async fn rejected_transaction_status() -> Result<()> {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.starknet_provider();
+    let client = sequencer.rpc_http_client();
+
+    let transfer = Call {
+        to: DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    };
+
+    let fee = sequencer.account().execute_v3(vec![transfer.clone()]).estimate_fee().await?;
+    let nonce = sequencer.account().get_nonce().await?;
+
+    // an account with a random signer, so that the transaction fails the account validation
+    let account = SingleOwnerAccount::new(
+        sequencer.starknet_provider(),
+        LocalWallet::from(SigningKey::from_random()),
+        sequencer.account().address(),
+        provider.chain_id().await?,
+        ExecutionEncoding::New,
+    );
+
+    let execution = account
+        .execute_v3(vec![transfer])
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed.to_u64().unwrap())
+        .l2_gas(fee.l2_gas_consumed.to_u64().unwrap())
+        .l1_data_gas(fee.l1_data_gas_consumed.to_u64().unwrap())
+        .l1_gas_price(fee.l1_gas_price.to_u128().unwrap())
+        .l2_gas_price(fee.l2_gas_price.to_u128().unwrap())
+        .l1_data_gas_price(fee.l1_data_gas_price.to_u128().unwrap());
+
+    let tx_hash = execution.prepared()?.transaction_hash(false);
+    let res = execution.send().await;
+    assert_account_starknet_err!(res.unwrap_err(), StarknetError::ValidationFailure(_));
+
+    let status = client.get_transaction_status(tx_hash).await?;
+    assert_eq!(status.finality_status, SequencerTransactionStatus::Rejected);
+    assert_eq!(status.execution_status, None);
+    assert!(status.failure_reason.is_some());
+
+    // rejected transactions don't have a receipt
+    let err = provider.get_transaction_receipt(tx_hash).await.unwrap_err();
+    assert_matches!(err, ProviderError::StarknetError(StarknetError::TransactionHashNotFound));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn replace_pending_transaction() -> Result<()> {
+    // the transactions are only mined on demand
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.no_mining = true;
+    let sequencer = TestNode::new_with_config(config).await;
+
+    let provider = sequencer.starknet_provider();
+    let client = sequencer.rpc_http_client();
+    let pool = sequencer.handle().node().pool().clone();
+
+    let transfer = Call {
+        to: DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    };
+
+    let fee = sequencer.account().execute_v3(vec![transfer.clone()]).estimate_fee().await?;
+
+    let chain_spec = &sequencer.backend().chain_spec;
+    let (address, account) = chain_spec.genesis().accounts().next().unwrap();
+    let signer = SigningKey::from_secret_scalar(account.private_key().unwrap());
+
+    // the same transfer from the test account, with a different tip
+    let transfer_with_tip = |tip: u64| -> Result<ExecutableTxWithHash> {
+        let mut calldata = vec![Felt::ONE, transfer.to, transfer.selector];
+        calldata.push(transfer.calldata.len().into());
+        calldata.extend(transfer.calldata.iter().copied());
+
+        let resource_bounds = ResourceBoundsMapping::All(AllResourceBoundsMapping {
+            l1_gas: ResourceBounds {
+                max_amount: fee.l1_gas_consumed.to_u64().unwrap(),
+                max_price_per_unit: fee.l1_gas_price.to_u128().unwrap(),
+            },
+            l2_gas: ResourceBounds {
+                max_amount: fee.l2_gas_consumed.to_u64().unwrap(),
+                max_price_per_unit: fee.l2_gas_price.to_u128().unwrap(),
+            },
+            l1_data_gas: ResourceBounds {
+                max_amount: fee.l1_data_gas_consumed.to_u64().unwrap(),
+                max_price_per_unit: fee.l1_data_gas_price.to_u128().unwrap(),
+            },
+        });
+
+        let mut tx = InvokeTxV3 {
+            chain_id: chain_spec.id(),
+            sender_address: *address,
+            nonce: Felt::ZERO,
+            calldata,
+            signature: Vec::new(),
+            resource_bounds,
+            tip,
+            paymaster_data: Vec::new(),
+            account_deployment_data: Vec::new(),
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+        };
+
+        let signature = signer.sign(&InvokeTx::V3(tx.clone()).calculate_hash(false))?;
+        tx.signature = vec![signature.r, signature.s];
+
+        Ok(ExecutableTxWithHash::new(ExecutableTx::Invoke(InvokeTx::V3(tx))))
+    };
+
+    let tx = transfer_with_tip(0)?;
+    let replacement = transfer_with_tip(1)?;
+
+    // both txs go through the pool's validator, and the first one is replaced before the block
+    // producer gets the chance to pick it up
+    pool.add_transaction(tx.clone()).unwrap();
+    pool.add_transaction(replacement.clone()).unwrap();
+    assert!(!pool.contains(tx.hash));
+
+    let receipt = katana_utils::TxWaiter::new(replacement.hash, &provider).await?;
+    assert_eq!(receipt.receipt.execution_result(), &ExecutionResult::Succeeded);
+    client.generate_block().await?;
+
+    // only the replacement is included in the block
+    let block = provider.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest)).await?;
+    let MaybePendingBlockWithTxHashes::Block(block) = block else { panic!("expected a block") };
+    assert_eq!(block.transactions, vec![replacement.hash]);
+
+    // the replaced tx is rejected and is never executed
+    let status = client.get_transaction_status(tx.hash).await?;
+    assert_eq!(status.finality_status, SequencerTransactionStatus::Rejected);
+    assert!(status.failure_reason.is_some());
+
+    let err = provider.get_transaction_receipt(tx.hash).await.unwrap_err();
+    assert_matches!(err, ProviderError::StarknetError(StarknetError::TransactionHashNotFound));
+
+    let nonce = sequencer.account().get_nonce().await?;
+    assert_eq!(nonce, Felt::ONE);
+
+    Ok(())
+}
+
 #[rstest::rstest]
 #[tokio::test]
 async fn send_txs_with_invalid_nonces(
@@ -661,12 +812,10 @@ async fn send_txs_with_invalid_nonces(
     assert_eq!(nonce, Felt::TWO, "Nonce should be 2 after sending two valid txs.");
 
     // -----------------------------------------------------------------------
-    //  transaction with nonce >= account nonce.
+    //  transaction with nonce > account nonce.
     //
-    // ideally, tx with nonce >= account nonce should be considered as valid BUT not to be executed
-    // immediately and should be kept around in the pool until the nonce is reached. however,
-    // katana doesn't support this feature yet so the current behaviour is to treat the tx as
-    // invalid with nonce mismatch error.
+    // the tx is accepted but not executed, it is kept around in the pool until the nonce gap is
+    // filled.
 
     let new_nonce = felt!("0x100");
     let res = contract
@@ -679,12 +828,62 @@ async fn send_txs_with_invalid_nonces(
         .l2_gas_price(fee.l2_gas_price.to_u128().unwrap())
         .l1_data_gas_price(fee.l1_data_gas_price.to_u128().unwrap())
         .send()
-        .await;
+        .await?;
 
-    assert_account_starknet_err!(res.unwrap_err(), StarknetError::InvalidTransactionNonce);
+    let status = provider.get_transaction_status(res.transaction_hash).await?;
+    assert_matches!(status, TransactionStatus::Received);
 
     let nonce = account.get_nonce().await?;
-    assert_eq!(nonce, Felt::TWO, "Nonce shouldn't change bcs the tx is still invalid.");
+    assert_eq!(nonce, Felt::TWO, "Nonce shouldn't change bcs the tx is still queued.");
+
+    Ok(())
+}
+
+#[rstest::rstest]
+#[tokio::test]
+async fn send_txs_with_nonce_gap(
+    #[values(None, Some(1000))] block_time: Option<u64>,
+) -> Result<()> {
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.block_time = block_time;
+    let sequencer = TestNode::new_with_config(config).await;
+
+    let provider = sequencer.starknet_provider();
+    let account = sequencer.account();
+
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+    let recipient = Felt::ONE;
+    let amount = Uint256 { low: Felt::ONE, high: Felt::ZERO };
+
+    let initial_nonce = account.get_nonce().await?;
+    let fee = contract.transfer(&recipient, &amount).estimate_fee().await?;
+
+    // send the txs in the reverse order of their nonces, so that every tx but the last one is
+    // received with a nonce gap.
+    let mut hashes = Vec::new();
+    for i in (0..3u64).rev() {
+        let res = contract
+            .transfer(&recipient, &amount)
+            .nonce(initial_nonce + Felt::from(i))
+            .l1_gas(fee.l1_gas_consumed.to_u64().unwrap())
+            .l2_gas(fee.l2_gas_consumed.to_u64().unwrap())
+            .l1_data_gas(fee.l1_data_gas_consumed.to_u64().unwrap())
+            .l1_gas_price(fee.l1_gas_price.to_u128().unwrap())
+            .l2_gas_price(fee.l2_gas_price.to_u128().unwrap())
+            .l1_data_gas_price(fee.l1_data_gas_price.to_u128().unwrap())
+            .send()
+            .await?;
+
+        hashes.push(res.transaction_hash);
+    }
+
+    // once the gap is filled, all the txs should be executed
+    for hash in hashes {
+        katana_utils::TxWaiter::new(hash, &provider).await?;
+    }
+
+    let nonce = account.get_nonce().await?;
+    assert_eq!(nonce, initial_nonce + Felt::THREE);
 
     Ok(())
 }
@@ -917,6 +1116,43 @@ async fn trace() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn trace_state_diff() -> Result<()> {
+    let sequencer = TestNode::new().await;
+
+    let provider = sequencer.starknet_provider();
+    let account = sequencer.account();
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+
+    let recipient = felt!("0x1");
+    let amount = Uint256 { low: felt!("0x1"), high: Felt::ZERO };
+
+    let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Pending), account.address()).await?;
+    let res = contract.transfer(&recipient, &amount).send().await?;
+    katana_utils::TxWaiter::new(res.transaction_hash, &provider).await?;
+
+    let trace = provider.trace_transaction(res.transaction_hash).await?;
+    let TransactionTrace::Invoke(trace) = trace else { panic!("expected an invoke trace") };
+    let state_diff = trace.state_diff.expect("state diff must be recorded");
+
+    // the sender's nonce is incremented
+    let sender_nonce = state_diff.nonces.iter().find(|n| n.contract_address == account.address());
+    assert_eq!(sender_nonce.map(|n| n.nonce), Some(nonce + Felt::ONE));
+
+    // the balances are updated in the fee token contract
+    let token = Felt::from(DEFAULT_ETH_FEE_TOKEN_ADDRESS);
+    assert!(state_diff.storage_diffs.iter().any(|diff| diff.address == token));
+
+    // simulated transactions also include their state diff
+    let simulated = contract.transfer(&recipient, &amount).simulate(false, false).await?;
+    let TransactionTrace::Invoke(trace) = simulated.transaction_trace else {
+        panic!("expected an invoke trace")
+    };
+    assert!(trace.state_diff.is_some_and(|diff| !diff.nonces.is_empty()));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn block_traces() -> Result<()> {
     let mut config = katana_utils::node::test_config();
@@ -1303,3 +1539,37 @@ async fn simulate_should_skip_strict_nonce_check(#[case] nonce: Felt, #[case] sh
     let res = contract.transfer(&recipient, &amount).nonce(nonce).simulate(false, false).await;
     assert_eq!(res.is_ok(), should_ok)
 }
+
+#[tokio::test]
+async fn l2_gas_prices() -> Result<()> {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.starknet_provider();
+    let account = sequencer.account();
+
+    let l2_gas_price = Felt::from(DEFAULT_STRK_L2_GAS_PRICE.get());
+
+    // the genesis block uses the default L2 gas prices, not the L1 ones
+    assert_eq!(latest_l2_gas_price(&provider).await?, l2_gas_price);
+
+    let transfer = Call {
+        to: DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    };
+
+    let fee = account.execute_v3(vec![transfer.clone()]).estimate_fee().await?;
+    assert_eq!(fee.l2_gas_price, l2_gas_price);
+
+    let res = account.execute_v3(vec![transfer]).send().await?;
+    katana_utils::TxWaiter::new(res.transaction_hash, &provider).await?;
+    assert_eq!(latest_l2_gas_price(&provider).await?, l2_gas_price);
+
+    Ok(())
+}
+
+async fn latest_l2_gas_price(provider: &impl Provider) -> Result<Felt> {
+    match provider.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest)).await? {
+        MaybePendingBlockWithTxHashes::Block(block) => Ok(block.l2_gas_price.price_in_fri),
+        MaybePendingBlockWithTxHashes::PendingBlock(_) => panic!("expected a mined block"),
+    }
+}
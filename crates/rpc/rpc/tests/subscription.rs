@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use jsonrpsee::client_transport::ws::{Url, WsTransportClientBuilder};
+use jsonrpsee::core::client::{
+    ReceivedMessage, SubscriptionKind, TransportReceiverT, TransportSenderT,
+};
+use katana_primitives::genesis::constant::DEFAULT_STRK_FEE_TOKEN_ADDRESS;
+use katana_provider::traits::block::BlockNumberProvider;
+use katana_rpc::api::dev::DevApiClient;
+use katana_rpc::api::starknet::StarknetWsApiClient;
+use katana_rpc_types::subscription::PendingTransaction;
+use katana_utils::TestNode;
+use num_traits::ToPrimitive;
+use serde_json::{json, Value};
+use starknet::accounts::{Account, ConnectedAccount};
+use starknet::core::types::{Call, Felt, SequencerTransactionStatus, TransactionExecutionStatus};
+use starknet::macros::{felt, selector};
+
+#[tokio::test]
+async fn subscribe_new_heads() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+
+    let client = sequencer.rpc_http_client();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let latest = provider.latest_number().unwrap();
+    let mut subscription = ws_client.subscribe_new_heads(None).await.unwrap();
+
+    // the latest block is sent right after subscribing
+    let header = subscription.next().await.unwrap().unwrap();
+    assert_eq!(header.block_number(), latest);
+
+    for i in 1..=3 {
+        client.generate_block().await.unwrap();
+        let header = subscription.next().await.unwrap().unwrap();
+        assert_eq!(header.block_number(), latest + i);
+    }
+}
+
+#[tokio::test]
+async fn subscribe_new_heads_across_revert() {
+    let sequencer = TestNode::new().await;
+    let provider = sequencer.blockchain();
+
+    let client = sequencer.rpc_http_client();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let latest = provider.latest_number().unwrap();
+    let snapshot = client.snapshot().await.unwrap();
+
+    let mut subscription = ws_client.subscribe_new_heads(None).await.unwrap();
+    let header = subscription.next().await.unwrap().unwrap();
+    assert_eq!(header.block_number(), latest);
+
+    for i in 1..=2 {
+        client.generate_block().await.unwrap();
+        let header = subscription.next().await.unwrap().unwrap();
+        assert_eq!(header.block_number(), latest + i);
+    }
+
+    // the blocks mined after the revert replace the reverted ones
+    client.revert(snapshot).await.unwrap();
+    client.generate_block().await.unwrap();
+
+    let header = subscription.next().await.unwrap().unwrap();
+    assert_eq!(header.block_number(), latest + 1);
+}
+
+#[tokio::test]
+async fn subscription_notification_method() {
+    let sequencer = TestNode::new().await;
+
+    let url = Url::parse(&format!("ws://{}", sequencer.rpc_addr())).unwrap();
+    let (mut sender, mut receiver) = WsTransportClientBuilder::default().build(url).await.unwrap();
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "starknet_subscribeNewHeads",
+        "params": []
+    });
+    sender.send(request.to_string()).await.unwrap();
+
+    let response = receive_json(&mut receiver).await;
+    let subscription_id = response["result"].clone();
+    assert!(!subscription_id.is_null(), "unexpected response: {response}");
+
+    // the latest block header is sent right after subscribing, as a `subscriptionNewHeads`
+    // notification as per the spec
+    let notification = receive_json(&mut receiver).await;
+    assert_eq!(notification["method"], "starknet_subscriptionNewHeads");
+    assert_eq!(notification["params"]["subscription"], subscription_id);
+    assert!(notification["params"]["result"].is_object());
+}
+
+#[tokio::test]
+async fn unsubscribe() {
+    let sequencer = TestNode::new().await;
+    let client = sequencer.rpc_http_client();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let mut subscription = ws_client.subscribe_new_heads(None).await.unwrap();
+    subscription.next().await.unwrap().unwrap();
+
+    let SubscriptionKind::Subscription(id) = subscription.kind().clone() else {
+        panic!("expected a subscription id")
+    };
+
+    // a subscription can only be cancelled from the connection it was created on
+    let other_ws_client = sequencer.rpc_ws_client().await;
+    assert!(other_ws_client.unsubscribe(id.clone()).await.is_err());
+
+    assert!(ws_client.unsubscribe(id.clone()).await.unwrap());
+    assert!(ws_client.unsubscribe(id).await.is_err(), "subscription is already closed");
+
+    // no more headers are sent once unsubscribed
+    client.generate_block().await.unwrap();
+    let next = tokio::time::timeout(Duration::from_millis(500), subscription.next()).await;
+    assert!(next.is_err());
+}
+
+#[tokio::test]
+async fn subscribe_events() {
+    let sequencer = TestNode::new().await;
+    let account = sequencer.account();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let fee_token = DEFAULT_STRK_FEE_TOKEN_ADDRESS;
+    let mut subscription = ws_client.subscribe_events(Some(fee_token), None, None).await.unwrap();
+
+    let res = account.execute_v3(vec![transfer_call()]).send().await.unwrap();
+
+    // the transfer and the fee transfer events
+    for _ in 0..2 {
+        let event = subscription.next().await.unwrap().unwrap();
+        assert_eq!(event.from_address, fee_token.into());
+        assert_eq!(event.transaction_hash, res.transaction_hash);
+        assert_eq!(event.keys[0], selector!("Transfer"));
+    }
+}
+
+#[tokio::test]
+async fn subscribe_transaction_status() {
+    let sequencer = TestNode::new().await;
+    let account = sequencer.account();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let fee = account.execute_v3(vec![transfer_call()]).estimate_fee().await.unwrap();
+    let nonce = account.get_nonce().await.unwrap();
+
+    let execution = account
+        .execute_v3(vec![transfer_call()])
+        .nonce(nonce)
+        .l1_gas(fee.l1_gas_consumed.to_u64().unwrap())
+        .l2_gas(fee.l2_gas_consumed.to_u64().unwrap())
+        .l1_data_gas(fee.l1_data_gas_consumed.to_u64().unwrap())
+        .l1_gas_price(fee.l1_gas_price.to_u128().unwrap())
+        .l2_gas_price(fee.l2_gas_price.to_u128().unwrap())
+        .l1_data_gas_price(fee.l1_data_gas_price.to_u128().unwrap());
+
+    // subscribe before the transaction is known to the node
+    let tx_hash = execution.prepared().unwrap().transaction_hash(false);
+    let mut subscription = ws_client.subscribe_transaction_status(tx_hash).await.unwrap();
+
+    execution.send().await.unwrap();
+
+    let status = loop {
+        let item = subscription.next().await.unwrap().unwrap();
+        assert_eq!(item.transaction_hash, tx_hash);
+
+        if item.status.finality_status != SequencerTransactionStatus::Received {
+            break item.status;
+        }
+    };
+
+    assert_eq!(status.finality_status, SequencerTransactionStatus::AcceptedOnL2);
+    assert_eq!(status.execution_status, Some(TransactionExecutionStatus::Succeeded));
+}
+
+#[tokio::test]
+async fn subscribe_pending_transactions() {
+    // the transactions stay in the pool until a block is mined
+    let mut config = katana_utils::node::test_config();
+    config.sequencing.no_mining = true;
+    let sequencer = TestNode::new_with_config(config).await;
+
+    let account = sequencer.account();
+    let ws_client = sequencer.rpc_ws_client().await;
+
+    let senders = Some(vec![account.address().into()]);
+    let mut hashes = ws_client.subscribe_pending_transactions(None, senders.clone()).await.unwrap();
+    let mut txs = ws_client.subscribe_pending_transactions(Some(true), senders).await.unwrap();
+
+    let res = account.execute_v3(vec![transfer_call()]).send().await.unwrap();
+
+    let PendingTransaction::Hash(hash) = hashes.next().await.unwrap().unwrap() else {
+        panic!("expected the transaction hash")
+    };
+    assert_eq!(hash, res.transaction_hash);
+
+    let PendingTransaction::Full(tx) = txs.next().await.unwrap().unwrap() else {
+        panic!("expected the full transaction")
+    };
+    assert_eq!(*tx.0.transaction_hash(), res.transaction_hash);
+}
+
+async fn receive_json(receiver: &mut impl TransportReceiverT) -> Value {
+    match receiver.receive().await.unwrap() {
+        ReceivedMessage::Text(text) => serde_json::from_str(&text).unwrap(),
+        ReceivedMessage::Bytes(bytes) => serde_json::from_slice(&bytes).unwrap(),
+        ReceivedMessage::Pong => panic!("unexpected pong message"),
+    }
+}
+
+fn transfer_call() -> Call {
+    Call {
+        to: DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    }
+}
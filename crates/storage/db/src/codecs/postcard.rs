@@ -1,13 +1,17 @@
 use katana_primitives::contract::{ContractAddress, GenericContractInfo};
-use katana_primitives::execution::TypedTransactionExecutionInfo;
+use katana_primitives::execution::{TransactionExecutionInfo, TypedTransactionExecutionInfo};
 use katana_primitives::receipt::Receipt;
+use katana_primitives::transaction::TxType;
 use katana_primitives::Felt;
+use serde::Deserialize;
 use {postcard, zstd};
 
 use super::{Compress, Decompress};
 use crate::error::CodecError;
+use crate::models::account::DevAccount;
 use crate::models::block::StoredBlockBodyIndices;
 use crate::models::contract::ContractInfoChangeList;
+use crate::models::fork::ForkInfo;
 use crate::models::list::BlockList;
 use crate::models::stage::StageCheckpoint;
 use crate::models::trie::TrieDatabaseValue;
@@ -45,7 +49,41 @@ impl Decompress for TypedTransactionExecutionInfo {
         let compressed = bytes.as_ref();
         let serialized =
             zstd::decode_all(compressed).map_err(|e| CodecError::Decompress(e.to_string()))?;
-        postcard::from_bytes(&serialized).map_err(|e| CodecError::Decompress(e.to_string()))
+
+        if let Ok(info) = postcard::from_bytes::<Self>(&serialized) {
+            return Ok(info);
+        }
+
+        // Fall back to the format used before the state diff of the transaction was recorded.
+        postcard::from_bytes::<LegacyTypedTransactionExecutionInfo>(&serialized)
+            .map(Self::from)
+            .map_err(|e| CodecError::Decompress(e.to_string()))
+    }
+}
+
+/// The format of [`TypedTransactionExecutionInfo`] before the state diff of the transaction was
+/// recorded.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+enum LegacyTypedTransactionExecutionInfo {
+    Invoke(TransactionExecutionInfo),
+    Declare(TransactionExecutionInfo),
+    L1Handler(TransactionExecutionInfo),
+    DeployAccount(TransactionExecutionInfo),
+}
+
+impl From<LegacyTypedTransactionExecutionInfo> for TypedTransactionExecutionInfo {
+    fn from(value: LegacyTypedTransactionExecutionInfo) -> Self {
+        match value {
+            LegacyTypedTransactionExecutionInfo::Invoke(info) => Self::new(TxType::Invoke, info),
+            LegacyTypedTransactionExecutionInfo::Declare(info) => Self::new(TxType::Declare, info),
+            LegacyTypedTransactionExecutionInfo::L1Handler(info) => {
+                Self::new(TxType::L1Handler, info)
+            }
+            LegacyTypedTransactionExecutionInfo::DeployAccount(info) => {
+                Self::new(TxType::DeployAccount, info)
+            }
+        }
     }
 }
 
@@ -57,7 +95,55 @@ impl_compress_and_decompress_for_table_values!(
     ContractAddress,
     BlockList,
     StageCheckpoint,
+    ForkInfo,
+    DevAccount,
     GenericContractInfo,
     StoredBlockBodyIndices,
     ContractInfoChangeList
 );
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::execution::{TransactionExecutionInfo, TypedTransactionExecutionInfo};
+    use katana_primitives::state::StateUpdates;
+    use katana_primitives::transaction::TxType;
+    use katana_primitives::{address, ContractAddress, Felt};
+
+    use super::LegacyTypedTransactionExecutionInfo;
+    use crate::codecs::{Compress, Decompress};
+
+    #[test]
+    fn trace_with_state_diff_roundtrip() {
+        let mut state_diff = StateUpdates::default();
+        state_diff.nonce_updates.insert(address!("0x1"), Felt::ONE);
+
+        let trace = TypedTransactionExecutionInfo::new(TxType::Declare, Default::default())
+            .with_state_diff(state_diff);
+
+        let compressed = trace.clone().compress().unwrap();
+        let decompressed = TypedTransactionExecutionInfo::decompress(compressed).unwrap();
+        assert_eq!(decompressed, trace);
+    }
+
+    #[test]
+    fn deploy_trace_roundtrip() {
+        let trace = TypedTransactionExecutionInfo::new(TxType::Deploy, Default::default());
+
+        let compressed = trace.clone().compress().unwrap();
+        let decompressed = TypedTransactionExecutionInfo::decompress(compressed).unwrap();
+        assert_eq!(decompressed.r#type(), TxType::Deploy);
+        assert_eq!(decompressed, trace);
+    }
+
+    #[test]
+    fn legacy_trace_decompression() {
+        let legacy = LegacyTypedTransactionExecutionInfo::L1Handler(Default::default());
+        let serialized = postcard::to_stdvec(&legacy).unwrap();
+        let compressed = zstd::encode_all(serialized.as_slice(), 0).unwrap();
+
+        let trace = TypedTransactionExecutionInfo::decompress(compressed).unwrap();
+        assert_eq!(trace.r#type(), TxType::L1Handler);
+        assert_eq!(trace.info(), &TransactionExecutionInfo::default());
+        assert_eq!(trace.state_diff(), None);
+    }
+}
@@ -0,0 +1,14 @@
+use katana_primitives::class::ClassHash;
+use katana_primitives::Felt;
+use serde::{Deserialize, Serialize};
+
+/// A development account created at runtime, ie not allocated in the genesis block.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct DevAccount {
+    /// The private key of the account.
+    pub private_key: Felt,
+    /// The public key associated with the account for validation.
+    pub public_key: Felt,
+    /// The hash of the account class.
+    pub class_hash: ClassHash,
+}
@@ -0,0 +1,66 @@
+use katana_primitives::block::BlockNumber;
+
+use crate::codecs::{Compress, Decode, Decompress, Encode};
+use crate::error::CodecError;
+
+/// The id of the checkpoint, in the `StageCheckpoints` table, of the block up to which the events
+/// have been indexed. All the blocks before the checkpoint are indexed as well.
+pub const EVENT_INDEX_CHECKPOINT: &str = "EventIndex";
+
+/// The position of an event in the chain.
+///
+/// Positions are ordered by their block, transaction and event index, in that order. This is also
+/// the order of their encoded bytes, which is what the event index tables are sorted by.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(test, derive(::arbitrary::Arbitrary))]
+pub struct EventPosition {
+    /// The number of the block the event was emitted in.
+    pub block_number: BlockNumber,
+    /// The index of the transaction, within its block, that emitted the event.
+    pub tx_index: u32,
+    /// The index of the event within the transaction receipt.
+    pub event_index: u32,
+}
+
+impl Encode for EventPosition {
+    type Encoded = [u8; 16];
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; 16];
+        buf[0..8].copy_from_slice(&self.block_number.to_be_bytes());
+        buf[8..12].copy_from_slice(&self.tx_index.to_be_bytes());
+        buf[12..16].copy_from_slice(&self.event_index.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for EventPosition {
+    fn decode<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
+        let bytes = bytes.as_ref();
+        if bytes.len() != 16 {
+            return Err(CodecError::Decode(format!(
+                "invalid event position length {}",
+                bytes.len()
+            )));
+        }
+
+        let block_number = BlockNumber::from_be_bytes(bytes[0..8].try_into().unwrap());
+        let tx_index = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        let event_index = u32::from_be_bytes(bytes[12..16].try_into().unwrap());
+        Ok(Self { block_number, tx_index, event_index })
+    }
+}
+
+// The position is both the subkey and the value of the event index tables, so it must be stored
+// in its encoded form for the entries to be sorted by their position.
+impl Compress for EventPosition {
+    type Compressed = [u8; 16];
+    fn compress(self) -> Result<Self::Compressed, CodecError> {
+        Ok(self.encode())
+    }
+}
+
+impl Decompress for EventPosition {
+    fn decompress<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
+        Self::decode(bytes)
+    }
+}
@@ -0,0 +1,13 @@
+use katana_primitives::block::{BlockHash, BlockNumber};
+use serde::{Deserialize, Serialize};
+
+/// The chain that a database was forked from.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ForkInfo {
+    /// The JSON-RPC URL of the forked network.
+    pub url: String,
+    /// The number of the block the chain was forked at.
+    pub block_number: BlockNumber,
+    /// The hash of the block the chain was forked at.
+    pub block_hash: BlockHash,
+}
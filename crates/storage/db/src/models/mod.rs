@@ -1,6 +1,9 @@
+pub mod account;
 pub mod block;
 pub mod class;
 pub mod contract;
+pub mod event;
+pub mod fork;
 pub mod list;
 pub mod stage;
 pub mod storage;
@@ -4,10 +4,14 @@ use katana_primitives::contract::{ContractAddress, GenericContractInfo, StorageK
 use katana_primitives::execution::TypedTransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
 use katana_primitives::transaction::{TxHash, TxNumber};
+use katana_primitives::Felt;
 
 use crate::codecs::{Compress, Decode, Decompress, Encode};
+use crate::models::account::DevAccount;
 use crate::models::block::StoredBlockBodyIndices;
 use crate::models::contract::{ContractClassChange, ContractInfoChangeList, ContractNonceChange};
+use crate::models::event::EventPosition;
+use crate::models::fork::ForkInfo;
 use crate::models::list::BlockList;
 use crate::models::stage::{StageCheckpoint, StageId};
 use crate::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
@@ -54,7 +58,7 @@ pub enum TableType {
     DupSort,
 }
 
-pub const NUM_TABLES: usize = 32;
+pub const NUM_TABLES: usize = 36;
 
 /// Macro to declare `libmdbx` tables.
 #[macro_export]
@@ -186,7 +190,11 @@ define_tables_enum! {[
     (StoragesTrieHistory, TableType::DupSort),
     (ClassesTrieChangeSet, TableType::Table),
     (ContractsTrieChangeSet, TableType::Table),
-    (StoragesTrieChangeSet, TableType::Table)
+    (StoragesTrieChangeSet, TableType::Table),
+    (Forks, TableType::Table),
+    (EventsByAddress, TableType::DupSort),
+    (EventsByKey, TableType::DupSort),
+    (DevAccounts, TableType::Table)
 ]}
 
 tables! {
@@ -263,7 +271,20 @@ tables! {
     /// contract trie change set
     ContractsTrieChangeSet: (TrieDatabaseKey) => BlockList,
     /// contract storage trie change set
-    StoragesTrieChangeSet: (TrieDatabaseKey) => BlockList
+    StoragesTrieChangeSet: (TrieDatabaseKey) => BlockList,
+
+    /// The chain the database was forked from, according to the number of the block it was
+    /// forked at. A database is forked from at most one block.
+    Forks: (BlockNumber) => ForkInfo,
+
+    /// Stores the positions of the events according to the address of the contract that emitted
+    /// them.
+    EventsByAddress: (ContractAddress, EventPosition) => EventPosition,
+    /// Stores the positions of the events according to their first key.
+    EventsByKey: (Felt, EventPosition) => EventPosition,
+
+    /// Development accounts created at runtime, according to their address.
+    DevAccounts: (ContractAddress) => DevAccount
 }
 
 impl Trie for ClassesTrie {
@@ -321,6 +342,10 @@ mod tests {
         assert_eq!(Tables::ALL[29].name(), ClassesTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[30].name(), ContractsTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[31].name(), StoragesTrieChangeSet::NAME);
+        assert_eq!(Tables::ALL[32].name(), Forks::NAME);
+        assert_eq!(Tables::ALL[33].name(), EventsByAddress::NAME);
+        assert_eq!(Tables::ALL[34].name(), EventsByKey::NAME);
+        assert_eq!(Tables::ALL[35].name(), DevAccounts::NAME);
 
         assert_eq!(Tables::Headers.table_type(), TableType::Table);
         assert_eq!(Tables::BlockHashes.table_type(), TableType::Table);
@@ -354,6 +379,10 @@ mod tests {
         assert_eq!(Tables::ClassesTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::ContractsTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::StoragesTrieChangeSet.table_type(), TableType::Table);
+        assert_eq!(Tables::Forks.table_type(), TableType::Table);
+        assert_eq!(Tables::EventsByAddress.table_type(), TableType::DupSort);
+        assert_eq!(Tables::EventsByKey.table_type(), TableType::DupSort);
+        assert_eq!(Tables::DevAccounts.table_type(), TableType::Table);
     }
 
     use katana_primitives::address;
@@ -366,10 +395,13 @@ mod tests {
     use starknet::macros::felt;
 
     use crate::codecs::{Compress, Decode, Decompress, Encode};
+    use crate::models::account::DevAccount;
     use crate::models::block::StoredBlockBodyIndices;
     use crate::models::contract::{
         ContractClassChange, ContractInfoChangeList, ContractNonceChange,
     };
+    use crate::models::event::EventPosition;
+    use crate::models::fork::ForkInfo;
     use crate::models::list::BlockList;
     use crate::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
     use crate::models::trie::{
@@ -414,7 +446,8 @@ mod tests {
             (TxNumber, 100),
             (ClassHash, felt!("0x123456789")),
             (ContractAddress, address!("0x123456789")),
-            (ContractStorageKey, ContractStorageKey { contract_address : address!("0x123456789"), key : felt!("0x123456789")})
+            (ContractStorageKey, ContractStorageKey { contract_address : address!("0x123456789"), key : felt!("0x123456789")}),
+            (EventPosition, EventPosition { block_number: 100, tx_index: 5, event_index: 2 })
         }
     }
 
@@ -442,6 +475,17 @@ mod tests {
             (ContractClassChange, ContractClassChange::default()),
             (BlockList, BlockList::default()),
             (ContractStorageEntry, ContractStorageEntry::default()),
+            (EventPosition, EventPosition { block_number: 100, tx_index: 5, event_index: 2 }),
+            (ForkInfo, ForkInfo {
+                url: "http://localhost:5050/".to_string(),
+                block_number: 100,
+                block_hash: felt!("0x123456789"),
+            }),
+            (DevAccount, DevAccount {
+                private_key: felt!("0x1"),
+                public_key: felt!("0x2"),
+                class_hash: felt!("0x3"),
+            }),
             (Receipt, Receipt::Invoke(InvokeTxReceipt {
                 revert_error: None,
                 events: Vec::new(),
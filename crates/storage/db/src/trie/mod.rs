@@ -11,6 +11,7 @@ use katana_trie::CommitId;
 use smallvec::ToSmallVec;
 
 use crate::abstraction::{DbCursor, DbTxMutRef, DbTxRef};
+use crate::error::DatabaseError;
 use crate::models::trie::{TrieDatabaseKey, TrieDatabaseKeyType, TrieHistoryEntry};
 use crate::models::{self};
 use crate::tables::{self, Trie};
@@ -40,9 +41,32 @@ impl<'a, Tx: DbTxRef<'a>> TrieDbFactory<'a, Tx> {
         GlobalTrie { tx: self.tx.clone(), _phantom: &PhantomData }
     }
 
-    // TODO: check that the snapshot for the block number is available
-    pub fn historical(&self, block: BlockNumber) -> Option<HistoricalGlobalTrie<'a, Tx>> {
-        Some(HistoricalGlobalTrie { tx: self.tx.clone(), block, _phantom: &PhantomData })
+    /// Returns the tries at the given block, or `None` if the trie snapshot of the block is not
+    /// available, i.e. the block doesn't exist or its trie history is no longer retained.
+    pub fn historical(
+        &self,
+        block: BlockNumber,
+    ) -> Result<Option<HistoricalGlobalTrie<'a, Tx>>, DatabaseError> {
+        let latest = self.tx.cursor::<tables::BlockHashes>()?.last()?.map(|(num, _)| num);
+        if latest.is_none_or(|latest| block > latest) {
+            return Ok(None);
+        }
+
+        // The oldest block whose trie changes are still retained in the database.
+        let oldest = [
+            self.tx.cursor_dup::<tables::ClassesTrieHistory>()?.first()?.map(|(num, _)| num),
+            self.tx.cursor_dup::<tables::ContractsTrieHistory>()?.first()?.map(|(num, _)| num),
+            self.tx.cursor_dup::<tables::StoragesTrieHistory>()?.first()?.map(|(num, _)| num),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+
+        if oldest.is_none_or(|oldest| block < oldest) {
+            return Ok(None);
+        }
+
+        Ok(Some(HistoricalGlobalTrie { tx: self.tx.clone(), block, _phantom: &PhantomData }))
     }
 }
 
@@ -173,9 +197,10 @@ where
 
     fn get_by_prefix(
         &self,
-        _: &DatabaseKey<'_>,
+        prefix: &DatabaseKey<'_>,
     ) -> Result<Vec<(ByteVec, ByteVec)>, Self::DatabaseError> {
-        todo!()
+        let mut cursor = self.tx.cursor::<Tb>()?;
+        get_by_prefix::<Tb, _>(&mut cursor, prefix)
     }
 
     fn insert(
@@ -213,7 +238,7 @@ where
 {
     tx: Tx,
     /// List of key-value pairs that has been added throughout the duration of the trie
-    /// transaction.
+    /// transaction. Removed keys are stored with an empty value.
     ///
     /// This will be used to create the trie snapshot.
     write_cache: HashMap<TrieDatabaseKey, ByteVec>,
@@ -264,7 +289,8 @@ where
         }
 
         for key in keys_to_remove {
-            let _ = self.tx.delete::<Tb>(key, None)?;
+            let _ = self.tx.delete::<Tb>(key.clone(), None)?;
+            self.write_cache.insert(key, ByteVec::new());
         }
 
         Ok(())
@@ -279,8 +305,8 @@ where
         &self,
         prefix: &DatabaseKey<'_>,
     ) -> Result<Vec<(ByteVec, ByteVec)>, Self::DatabaseError> {
-        let _ = prefix;
-        todo!()
+        let mut cursor = self.tx.cursor::<Tb>()?;
+        get_by_prefix::<Tb, _>(&mut cursor, prefix)
     }
 
     fn insert(
@@ -309,8 +335,10 @@ where
         let key = to_db_key(key);
 
         let old_value = self.tx.get::<Tb>(key.clone())?;
-        self.tx.delete::<Tb>(key, None)?;
+        self.tx.delete::<Tb>(key.clone(), None)?;
 
+        // Record the removal as an empty value so that the key is also tracked in the trie history.
+        self.write_cache.insert(key, ByteVec::new());
         Ok(old_value)
     }
 
@@ -375,6 +403,36 @@ where
     }
 }
 
+/// Returns all the entries of the trie table whose key is of the same type as `prefix` and starts
+/// with its bytes.
+fn get_by_prefix<Tb, C>(
+    cursor: &mut C,
+    prefix: &DatabaseKey<'_>,
+) -> Result<Vec<(ByteVec, ByteVec)>, Error>
+where
+    Tb: Trie,
+    C: DbCursor<Tb>,
+{
+    let prefix = to_db_key(prefix);
+    let start = TrieDatabaseKey { r#type: prefix.r#type, key: Vec::new() };
+
+    let mut entries = Vec::new();
+    for entry in cursor.walk(Some(start))? {
+        let (key, value) = entry?;
+
+        // Keys are sorted by their type first, so there are no more keys of the same type.
+        if key.r#type != prefix.r#type {
+            break;
+        }
+
+        if key.key.starts_with(&prefix.key) {
+            entries.push((ByteVec::from(key.key), value));
+        }
+    }
+
+    Ok(entries)
+}
+
 fn to_db_key(key: &DatabaseKey<'_>) -> models::trie::TrieDatabaseKey {
     match key {
         DatabaseKey::Flat(bytes) => {
@@ -392,12 +450,13 @@ fn to_db_key(key: &DatabaseKey<'_>) -> models::trie::TrieDatabaseKey {
 #[cfg(test)]
 mod tests {
     use katana_primitives::hash::{Poseidon, StarkHash};
-    use katana_primitives::{felt, hash};
+    use katana_primitives::{felt, hash, Felt};
+    use katana_trie::bonsai::{BonsaiDatabase, BonsaiPersistentDatabase, DatabaseKey};
     use katana_trie::{verify_proof, ClassesTrie, CommitId};
     use starknet::macros::short_string;
 
-    use super::TrieDbMut;
-    use crate::abstraction::Database;
+    use super::{TrieDbFactory, TrieDbMut};
+    use crate::abstraction::{Database, DbCursor, DbDupSortCursorMut, DbTxMut};
     use crate::mdbx::test_utils;
     use crate::tables;
     use crate::trie::SnapshotTrieDb;
@@ -488,4 +547,72 @@ mod tests {
             assert_eq!(vec![value0, value1], result);
         }
     }
+
+    #[test]
+    fn get_by_prefix() {
+        let db = test_utils::create_test_db();
+        let db_tx = db.tx_mut().expect("failed to get tx");
+
+        let mut trie = TrieDbMut::<tables::ClassesTrie, _>::new(&db_tx);
+        trie.insert(&DatabaseKey::Flat(&[1, 1]), &[1], None).unwrap();
+        trie.insert(&DatabaseKey::Flat(&[1, 2, 3]), &[2], None).unwrap();
+        trie.insert(&DatabaseKey::Flat(&[2, 1]), &[3], None).unwrap();
+        trie.insert(&DatabaseKey::Trie(&[1, 1]), &[4], None).unwrap();
+        trie.snapshot(CommitId::new(0));
+
+        trie.remove(&DatabaseKey::Flat(&[1, 1]), None).unwrap();
+        trie.insert(&DatabaseKey::Flat(&[1, 2, 3]), &[5], None).unwrap();
+        trie.snapshot(CommitId::new(1));
+
+        let entries = trie.get_by_prefix(&DatabaseKey::Flat(&[1])).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.as_slice(), &[1, 2, 3]);
+        assert_eq!(entries[0].1.as_slice(), &[5]);
+
+        let snapshot0 = SnapshotTrieDb::<tables::ClassesTrie, _>::new(&db_tx, CommitId::new(0));
+        let mut entries = snapshot0.get_by_prefix(&DatabaseKey::Flat(&[1])).unwrap();
+        entries.sort();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].0.as_slice(), entries[0].1.as_slice()), (&[1, 1][..], &[1][..]));
+        assert_eq!((entries[1].0.as_slice(), entries[1].1.as_slice()), (&[1, 2, 3][..], &[2][..]));
+        assert!(snapshot0.contains(&DatabaseKey::Flat(&[1, 1])).unwrap());
+
+        let snapshot1 = SnapshotTrieDb::<tables::ClassesTrie, _>::new(&db_tx, CommitId::new(1));
+        let entries = snapshot1.get_by_prefix(&DatabaseKey::Flat(&[1])).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].0.as_slice(), entries[0].1.as_slice()), (&[1, 2, 3][..], &[5][..]));
+        assert!(!snapshot1.contains(&DatabaseKey::Flat(&[1, 1])).unwrap());
+        assert!(snapshot1.contains(&DatabaseKey::Trie(&[1, 1])).unwrap());
+    }
+
+    #[test]
+    fn historical_trie_availability() {
+        let db = test_utils::create_test_db();
+        let db_tx = db.tx_mut().expect("failed to get tx");
+
+        // no trie history at all
+        db_tx.put::<tables::BlockHashes>(0, Felt::ZERO).unwrap();
+        assert!(TrieDbFactory::new(&db_tx).historical(0).unwrap().is_none());
+
+        let mut trie = ClassesTrie::new(TrieDbMut::<tables::ClassesTrie, _>::new(&db_tx));
+        for block in 1..=3 {
+            trie.insert(Felt::from(block), felt!("0x1337"));
+            trie.commit(block);
+            db_tx.put::<tables::BlockHashes>(block, Felt::from(block)).unwrap();
+        }
+
+        let factory = TrieDbFactory::new(&db_tx);
+        assert!(factory.historical(0).unwrap().is_none());
+        assert!(factory.historical(1).unwrap().is_some());
+        assert!(factory.historical(3).unwrap().is_some());
+        assert!(factory.historical(4).unwrap().is_none());
+
+        // prune the trie history of block 1
+        let mut cursor = db_tx.cursor_dup_mut::<tables::ClassesTrieHistory>().unwrap();
+        cursor.first().unwrap();
+        cursor.delete_current_duplicates().unwrap();
+
+        assert!(factory.historical(1).unwrap().is_none());
+        assert!(factory.historical(2).unwrap().is_some());
+    }
 }
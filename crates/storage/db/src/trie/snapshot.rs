@@ -7,8 +7,9 @@ use katana_trie::bonsai::{BonsaiDatabase, ByteVec, DatabaseKey};
 use katana_trie::CommitId;
 
 use super::Error;
-use crate::abstraction::{DbDupSortCursor, DbTxRef};
+use crate::abstraction::{DbCursor, DbDupSortCursor, DbTxRef};
 use crate::models::list::BlockList;
+use crate::models::trie::TrieDatabaseKey;
 use crate::tables::Trie;
 use crate::trie::to_db_key;
 
@@ -58,6 +59,31 @@ where
     pub(crate) fn new(tx: Tx, id: CommitId) -> Self {
         Self { tx, snapshot_id: id, _table: &PhantomData }
     }
+
+    /// Returns the value of `key` at the snapshot block, given the list of blocks where the key
+    /// was changed.
+    fn value_at_snapshot(
+        &self,
+        key: &TrieDatabaseKey,
+        change_set: &BlockList,
+    ) -> Result<Option<ByteVec>, Error> {
+        let block_number = self.snapshot_id.into();
+
+        if let Some(num) = recent_change_from_block(block_number, change_set) {
+            let mut cursor = self.tx.cursor_dup::<Tb::History>()?;
+
+            // The history entry should exist if the block is in the change set, but a missing one
+            // is treated the same as a key that doesn't exist at the snapshot.
+            // An empty value means the key was removed from the trie at that block.
+            if let Some(entry) = cursor.seek_by_key_subkey(num, key.clone())? {
+                if entry.key == *key && !entry.value.is_empty() {
+                    return Ok(Some(entry.value));
+                }
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl<'tx, Tb, Tx> BonsaiDatabase for SnapshotTrieDb<'tx, Tb, Tx>
@@ -76,28 +102,40 @@ where
 
     fn get(&self, key: &DatabaseKey<'_>) -> Result<Option<ByteVec>, Self::DatabaseError> {
         let key = to_db_key(key);
-        let block_number = self.snapshot_id.into();
-
-        let change_set = self.tx.get::<Tb::Changeset>(key.clone())?;
-        if let Some(num) = change_set.and_then(|set| recent_change_from_block(block_number, &set)) {
-            let mut cursor = self.tx.cursor_dup::<Tb::History>()?;
-            let entry = cursor
-                .seek_by_key_subkey(num, key.clone())?
-                .expect("entry should exist if in change set");
-
-            if entry.key == key {
-                return Ok(Some(entry.value));
-            }
+        match self.tx.get::<Tb::Changeset>(key.clone())? {
+            Some(change_set) => self.value_at_snapshot(&key, &change_set),
+            None => Ok(None),
         }
-
-        Ok(None)
     }
 
     fn get_by_prefix(
         &self,
-        _: &DatabaseKey<'_>,
+        prefix: &DatabaseKey<'_>,
     ) -> Result<Vec<(ByteVec, ByteVec)>, Self::DatabaseError> {
-        todo!()
+        let prefix = to_db_key(prefix);
+
+        // Every key that has ever existed in the trie is recorded in the change set table, so we
+        // iterate over it instead and resolve the value of each matching key at the snapshot.
+        let mut cursor = self.tx.cursor::<Tb::Changeset>()?;
+        let start = TrieDatabaseKey { r#type: prefix.r#type, key: Vec::new() };
+
+        let mut entries = Vec::new();
+        for entry in cursor.walk(Some(start))? {
+            let (key, change_set) = entry?;
+
+            // Keys are sorted by their type first, so there are no more keys of the same type.
+            if key.r#type != prefix.r#type {
+                break;
+            }
+
+            if key.key.starts_with(&prefix.key) {
+                if let Some(value) = self.value_at_snapshot(&key, &change_set)? {
+                    entries.push((ByteVec::from(key.key), value));
+                }
+            }
+        }
+
+        Ok(entries)
     }
 
     fn insert(
@@ -117,8 +155,8 @@ where
         unimplemented!("modifying trie snapshot is not supported")
     }
 
-    fn contains(&self, _: &DatabaseKey<'_>) -> Result<bool, Self::DatabaseError> {
-        todo!()
+    fn contains(&self, key: &DatabaseKey<'_>) -> Result<bool, Self::DatabaseError> {
+        Ok(self.get(key)?.is_some())
     }
 
     fn write_batch(&mut self, _: Self::Batch) -> Result<(), Self::DatabaseError> {
@@ -137,7 +175,7 @@ mod tests {
     use proptest::strategy;
 
     use super::*;
-    use crate::abstraction::{Database, DbTx};
+    use crate::abstraction::{Database, DbTx, DbTxMut};
     use crate::mdbx::test_utils;
     use crate::models::trie::TrieDatabaseKeyType;
     use crate::tables;
@@ -200,6 +238,21 @@ mod tests {
         strategy::Strategy::prop_map(strats, move |strats| strats)
     }
 
+    #[test]
+    fn missing_history_entry() {
+        let db = test_utils::create_test_db();
+        let tx = db.tx_mut().expect("failed to create rw tx");
+
+        // the key is recorded as changed at block 5 but has no history entry for it
+        let key = felt!("0x1337").to_bytes_be();
+        let db_key = to_db_key(&DatabaseKey::Trie(key.as_ref()));
+        tx.put::<tables::ClassesTrieChangeSet>(db_key, BlockList::from([5])).unwrap();
+
+        let snapshot_db = SnapshotTrieDb::<tables::ClassesTrie, _>::new(&tx, CommitId::from(5));
+        let result = snapshot_db.get(&DatabaseKey::Trie(key.as_ref())).unwrap();
+        assert_eq!(result, None);
+    }
+
     proptest! {
         #[test]
         fn test_get_insert(blocks in arb_blocklist(10, 1000)) {
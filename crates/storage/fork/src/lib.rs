@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::pin::Pin;
 use std::sync::mpsc::{
@@ -7,14 +7,15 @@ use std::sync::mpsc::{
 };
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
 use std::{io, thread};
 
 use anyhow::anyhow;
 use futures::channel::mpsc::{channel as async_channel, Receiver, SendError, Sender};
-use futures::future::BoxFuture;
+use futures::future::{join_all, BoxFuture};
 use futures::stream::Stream;
 use futures::{Future, FutureExt};
-use katana_primitives::block::BlockHashOrNumber;
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber};
 use katana_primitives::class::{
     ClassHash, CompiledClassHash, ComputeClassHashError, ContractClass,
     ContractClassCompilationError,
@@ -22,15 +23,70 @@ use katana_primitives::class::{
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::Felt;
 use katana_rpc_types::class::RpcContractClass;
+use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
 use parking_lot::Mutex;
-use starknet::core::types::{BlockId, ContractClass as StarknetRsClass, StarknetError};
-use starknet::providers::{Provider, ProviderError as StarknetProviderError};
-use tracing::{error, trace};
+use starknet::core::types::requests::{
+    GetClassHashAtRequest, GetClassRequest, GetNonceRequest, GetStateUpdateRequest,
+    GetStorageAtRequest,
+};
+use starknet::core::types::{
+    BlockId, ConfirmedBlockId, ContractClass as StarknetRsClass, MaybePendingStateUpdate,
+    StarknetError, StorageProof,
+};
+use starknet::providers::{
+    Provider, ProviderError as StarknetProviderError, ProviderRequestData, ProviderResponseData,
+};
+use tracing::{error, trace, warn};
+
+use crate::metrics::BackendMetrics;
+
+mod metrics;
 
 const LOG_TARGET: &str = "forking::backend";
 
+/// The default maximum number of requests sent to the forked network in a single JSON-RPC batch.
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+/// The default number of blocks whose state updates are used to prefetch contract storage.
+pub const DEFAULT_PREFETCH_BLOCKS: u64 = 100;
+
 type BackendResult<T> = Result<T, BackendError>;
 
+/// Prefetched storage values of contracts at the forked block.
+type StorageCache = HashMap<(ContractAddress, StorageKey), StorageValue>;
+
+/// Configurations for the [`Backend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendConfig {
+    /// The maximum number of requests to send to the forked network in a single JSON-RPC batch.
+    pub batch_size: usize,
+    /// The contracts whose storage will be prefetched when the backend starts.
+    ///
+    /// The storage is built from the storage diffs of the contracts in the last
+    /// [`prefetch_blocks`](Self::prefetch_blocks) blocks up to the forked block. Storage slots
+    /// that were not updated within those blocks are still fetched on demand.
+    pub prefetch_contracts: Vec<ContractAddress>,
+    /// The number of blocks, ending at the forked block, to prefetch the contracts storage from.
+    pub prefetch_blocks: u64,
+    /// Whether to compute the state roots and storage proofs of the local blocks on top of the
+    /// tries of the forked network.
+    ///
+    /// The nodes of the forked network's tries are fetched through its `starknet_getStorageProof`
+    /// method, so the forked network must support it. When disabled, the tries only contain the
+    /// state updates of the local blocks. Enabled by default.
+    pub remote_tries: bool,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            prefetch_contracts: Vec::new(),
+            prefetch_blocks: DEFAULT_PREFETCH_BLOCKS,
+            remote_tries: true,
+        }
+    }
+}
+
 /// The types of response from [`Backend`].
 ///
 /// This enum implements `Clone` because responses often need to be sent to multiple senders
@@ -45,6 +101,7 @@ enum BackendResponse {
     Storage(BackendResult<StorageValue>),
     ClassHashAt(BackendResult<ClassHash>),
     ClassAt(BackendResult<StarknetRsClass>),
+    StorageProof(BackendResult<StorageProof>),
 }
 
 /// Errors that can occur when interacting with the backend.
@@ -72,11 +129,20 @@ enum BackendRequest {
     Class(Request<ClassHash>),
     ClassHash(Request<ContractAddress>),
     Storage(Request<(ContractAddress, StorageKey)>),
+    StorageProof(Request<StorageProofRequest>),
     // Test-only request kind for requesting the backend stats
     #[cfg(test)]
     Stats(OneshotSender<usize>),
 }
 
+/// The keys to fetch the proofs of from the tries of the forked block.
+#[derive(Debug)]
+struct StorageProofRequest {
+    classes: Vec<ClassHash>,
+    contracts: Vec<ContractAddress>,
+    storage_keys: Vec<ContractStorageKeys>,
+}
+
 impl BackendRequest {
     /// Create a new request for fetching the nonce of a contract.
     fn nonce(address: ContractAddress) -> (BackendRequest, OneshotReceiver<BackendResponse>) {
@@ -105,6 +171,17 @@ impl BackendRequest {
         (BackendRequest::Storage(Request { payload: (address, key), sender }), receiver)
     }
 
+    /// Create a new request for fetching the storage proofs of the forked block.
+    fn storage_proof(
+        classes: Vec<ClassHash>,
+        contracts: Vec<ContractAddress>,
+        storage_keys: Vec<ContractStorageKeys>,
+    ) -> (BackendRequest, OneshotReceiver<BackendResponse>) {
+        let (sender, receiver) = oneshot();
+        let payload = StorageProofRequest { classes, contracts, storage_keys };
+        (BackendRequest::StorageProof(Request { payload, sender }), receiver)
+    }
+
     #[cfg(test)]
     fn stats() -> (BackendRequest, OneshotReceiver<usize>) {
         let (sender, receiver) = oneshot();
@@ -112,7 +189,8 @@ impl BackendRequest {
     }
 }
 
-type BackendRequestFuture = BoxFuture<'static, BackendResponse>;
+/// A future that resolves to the responses of one or more requests sent to the remote provider.
+type BackendRequestFuture = BoxFuture<'static, Vec<(BackendRequestIdentifier, BackendResponse)>>;
 
 // Identifier for pending requests.
 // This is used for request deduplication.
@@ -122,25 +200,82 @@ enum BackendRequestIdentifier {
     Class(ClassHash),
     ClassHash(ContractAddress),
     Storage((ContractAddress, StorageKey)),
+    // Storage proof requests are not deduplicated, each request is identified by a unique id.
+    StorageProof(u64),
+}
+
+impl BackendRequestIdentifier {
+    /// Returns the JSON-RPC request for fetching the data identified by this id, or `None` if the
+    /// request can't be sent as part of a batch.
+    fn rpc_request(&self, block_id: BlockId) -> Option<ProviderRequestData> {
+        let request = match *self {
+            Self::Nonce(address) => ProviderRequestData::GetNonce(GetNonceRequest {
+                block_id,
+                contract_address: address.into(),
+            }),
+            Self::Class(class_hash) => {
+                ProviderRequestData::GetClass(GetClassRequest { block_id, class_hash })
+            }
+            Self::ClassHash(address) => {
+                ProviderRequestData::GetClassHashAt(GetClassHashAtRequest {
+                    block_id,
+                    contract_address: address.into(),
+                })
+            }
+            Self::Storage((address, key)) => {
+                ProviderRequestData::GetStorageAt(GetStorageAtRequest {
+                    contract_address: address.into(),
+                    key,
+                    block_id,
+                })
+            }
+            Self::StorageProof(_) => return None,
+        };
+
+        Some(request)
+    }
+
+    /// Returns the response for this request with the given error.
+    fn error_response(&self, error: BackendError) -> BackendResponse {
+        match self {
+            Self::Nonce(_) => BackendResponse::Nonce(Err(error)),
+            Self::Class(_) => BackendResponse::ClassAt(Err(error)),
+            Self::ClassHash(_) => BackendResponse::ClassHashAt(Err(error)),
+            Self::Storage(_) => BackendResponse::Storage(Err(error)),
+            Self::StorageProof(_) => BackendResponse::StorageProof(Err(error)),
+        }
+    }
 }
 
 /// The backend for the forked provider.
 ///
 /// It is responsible for processing [requests](BackendRequest) to fetch data from the remote
-/// provider.
+/// provider. Requests that are received together are sent to the remote provider as JSON-RPC
+/// batches of at most [`BackendConfig::batch_size`] requests.
 pub struct Backend<P> {
     /// The Starknet RPC provider that will be used to fetch data from.
     provider: Arc<P>,
     // HashMap that keep track of current requests, for dedup purposes.
     request_dedup_map: HashMap<BackendRequestIdentifier, Vec<OneshotSender<BackendResponse>>>,
     /// Requests that are currently being poll.
-    pending_requests: Vec<(BackendRequestIdentifier, BackendRequestFuture)>,
+    pending_requests: Vec<BackendRequestFuture>,
     /// Requests that are queued to be polled.
     queued_requests: VecDeque<BackendRequest>,
+    /// Requests that are waiting to be sent to the remote provider in the next batch.
+    batched_requests: Vec<BackendRequestIdentifier>,
     /// A channel for receiving requests from the [BackendHandle]s.
     incoming: Receiver<BackendRequest>,
     /// Pinned block id for all requests.
     block: BlockId,
+    /// The id of the next storage proof request.
+    next_storage_proof_id: u64,
+    /// The backend configurations.
+    config: BackendConfig,
+    /// The future for prefetching the storage of the configured contracts, if it's still running.
+    prefetch: Option<BoxFuture<'static, BackendResult<StorageCache>>>,
+    /// The prefetched storage values.
+    storage_cache: StorageCache,
+    metrics: BackendMetrics,
 }
 
 /////////////////////////////////////////////////////////////////
@@ -156,8 +291,12 @@ where
     /// Create a new [Backend] with the given provider and block id, and returns a handle to it. The
     /// backend will start processing requests immediately upon creation.
     #[allow(clippy::new_ret_no_self)]
-    pub fn new(provider: P, block_id: BlockHashOrNumber) -> Result<BackendClient, BackendError> {
-        let (handle, backend) = Self::new_inner(provider, block_id);
+    pub fn new(
+        provider: P,
+        block_id: BlockHashOrNumber,
+        config: BackendConfig,
+    ) -> Result<BackendClient, BackendError> {
+        let (handle, backend) = Self::new_inner(provider, block_id, config);
 
         thread::Builder::new()
             .name("forking-backend".into())
@@ -175,21 +314,44 @@ where
         Ok(handle)
     }
 
-    fn new_inner(provider: P, block_id: BlockHashOrNumber) -> (BackendClient, Backend<P>) {
+    fn new_inner(
+        provider: P,
+        block_id: BlockHashOrNumber,
+        config: BackendConfig,
+    ) -> (BackendClient, Backend<P>) {
         let block = match block_id {
             BlockHashOrNumber::Hash(hash) => BlockId::Hash(hash),
             BlockHashOrNumber::Num(number) => BlockId::Number(number),
         };
 
+        let provider = Arc::new(provider);
+        let metrics = BackendMetrics::default();
+
+        let prefetch = if config.prefetch_contracts.is_empty() || config.prefetch_blocks == 0 {
+            None
+        } else if let BlockHashOrNumber::Num(number) = block_id {
+            let fut = prefetch_storage(provider.clone(), number, config.clone(), metrics.clone());
+            Some(fut.boxed())
+        } else {
+            warn!(target: LOG_TARGET, "Storage prefetching requires the forked block number, skipping.");
+            None
+        };
+
         // Create async channel to receive requests from the handle.
         let (tx, rx) = async_channel(100);
         let backend = Backend {
             block,
+            config,
+            metrics,
+            prefetch,
+            provider,
             incoming: rx,
-            provider: Arc::new(provider),
             request_dedup_map: HashMap::new(),
             pending_requests: Vec::new(),
             queued_requests: VecDeque::new(),
+            batched_requests: Vec::new(),
+            storage_cache: HashMap::new(),
+            next_storage_proof_id: 0,
         };
 
         (BackendClient(Mutex::new(tx)), backend)
@@ -197,109 +359,150 @@ where
 
     /// This method is responsible for transforming the incoming request
     /// sent from a [BackendHandle] into a RPC request to the remote network.
+    ///
+    /// Requests that can be batched are only queued here, and are sent to the remote network by
+    /// [`Backend::send_batched_requests`].
     fn handle_requests(&mut self, request: BackendRequest) {
-        let block = self.block;
-        let provider = self.provider.clone();
-
         // Check if there are similar requests in the queue before sending the request
         match request {
             BackendRequest::Nonce(Request { payload, sender }) => {
-                let req_key = BackendRequestIdentifier::Nonce(payload);
-
-                self.dedup_request(
-                    req_key,
-                    sender,
-                    Box::pin(async move {
-                        let res = provider
-                            .get_nonce(block, Felt::from(payload))
-                            .await
-                            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
-                        BackendResponse::Nonce(res)
-                    }),
-                );
+                self.batch_request(BackendRequestIdentifier::Nonce(payload), sender);
             }
 
             BackendRequest::Storage(Request { payload: (addr, key), sender }) => {
-                let req_key = BackendRequestIdentifier::Storage((addr, key));
-
-                self.dedup_request(
-                    req_key,
-                    sender,
-                    Box::pin(async move {
-                        let res = provider
-                            .get_storage_at(Felt::from(addr), key, block)
-                            .await
-                            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
-
-                        BackendResponse::Storage(res)
-                    }),
-                );
+                if let Some(value) = self.storage_cache.get(&(addr, key)) {
+                    self.metrics.cache_hits.increment(1);
+                    sender.send(BackendResponse::Storage(Ok(*value))).unwrap_or_else(
+                        |error| error!(target: LOG_TARGET, %error, "Failed to send result."),
+                    );
+                } else {
+                    self.metrics.cache_misses.increment(1);
+                    self.batch_request(BackendRequestIdentifier::Storage((addr, key)), sender);
+                }
             }
 
             BackendRequest::ClassHash(Request { payload, sender }) => {
-                let req_key = BackendRequestIdentifier::ClassHash(payload);
-
-                self.dedup_request(
-                    req_key,
-                    sender,
-                    Box::pin(async move {
-                        let res = provider
-                            .get_class_hash_at(block, Felt::from(payload))
-                            .await
-                            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
-
-                        BackendResponse::ClassHashAt(res)
-                    }),
-                );
+                self.batch_request(BackendRequestIdentifier::ClassHash(payload), sender);
             }
 
             BackendRequest::Class(Request { payload, sender }) => {
-                let req_key = BackendRequestIdentifier::Class(payload);
-
-                self.dedup_request(
-                    req_key,
-                    sender,
-                    Box::pin(async move {
-                        let res = provider
-                            .get_class(block, payload)
-                            .await
-                            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
-
-                        BackendResponse::ClassAt(res)
-                    }),
-                );
+                self.batch_request(BackendRequestIdentifier::Class(payload), sender);
+            }
+
+            BackendRequest::StorageProof(Request { payload, sender }) => {
+                let req_key = BackendRequestIdentifier::StorageProof(self.next_storage_proof_id);
+                self.next_storage_proof_id += 1;
+
+                if !self.dedup_request(req_key, sender) {
+                    return;
+                }
+
+                let provider = self.provider.clone();
+                let block = match self.block {
+                    BlockId::Hash(hash) => ConfirmedBlockId::Hash(hash),
+                    BlockId::Number(number) => ConfirmedBlockId::Number(number),
+                    // the backend is always pinned to a block hash or number
+                    BlockId::Tag(_) => ConfirmedBlockId::Latest,
+                };
+
+                let metrics = self.metrics.clone();
+                self.pending_requests.push(Box::pin(async move {
+                    let classes = payload.classes;
+                    let contracts =
+                        payload.contracts.into_iter().map(Felt::from).collect::<Vec<_>>();
+                    let storage_keys = payload
+                        .storage_keys
+                        .into_iter()
+                        .map(starknet::core::types::ContractStorageKeys::from)
+                        .collect::<Vec<_>>();
+
+                    metrics.rpc_calls.increment(1);
+                    let start = Instant::now();
+
+                    let res = provider
+                        .get_storage_proof(block, classes, contracts, storage_keys)
+                        .await
+                        .map_err(|e| BackendError::StarknetProvider(Arc::new(e)));
+
+                    metrics.request_time_seconds.record(start.elapsed().as_secs_f64());
+                    vec![(req_key, BackendResponse::StorageProof(res))]
+                }));
             }
 
             #[cfg(test)]
             BackendRequest::Stats(sender) => {
-                let total_ongoing_request = self.pending_requests.len();
+                let total_ongoing_request = self.request_dedup_map.len();
                 sender.send(total_ongoing_request).expect("failed to send backend stats");
             }
         }
     }
 
-    fn dedup_request(
+    /// Queues a request to be sent in the next batch, unless an identical request is already in
+    /// flight.
+    fn batch_request(
         &mut self,
         req_key: BackendRequestIdentifier,
         sender: OneshotSender<BackendResponse>,
-        rpc_call_future: BoxFuture<'static, BackendResponse>,
     ) {
-        if let Entry::Vacant(e) = self.request_dedup_map.entry(req_key) {
-            self.pending_requests.push((req_key, rpc_call_future));
-            e.insert(vec![sender]);
-        } else {
-            match self.request_dedup_map.get_mut(&req_key) {
-                Some(sender_vec) => {
-                    sender_vec.push(sender);
-                }
-                None => {
-                    // Log this and do nothing here, as this should never happen.
-                    // If this does happen it is an unexpected bug.
-                    error!(target: LOG_TARGET, "failed to get current request dedup vector");
-                }
+        if self.dedup_request(req_key, sender) {
+            self.batched_requests.push(req_key);
+        }
+    }
+
+    /// Registers the sender of a request. Returns `true` if there is no identical request in
+    /// flight, in which case the request must be sent to the remote provider.
+    fn dedup_request(
+        &mut self,
+        req_key: BackendRequestIdentifier,
+        sender: OneshotSender<BackendResponse>,
+    ) -> bool {
+        match self.request_dedup_map.entry(req_key) {
+            Entry::Vacant(e) => {
+                e.insert(vec![sender]);
+                true
+            }
+            Entry::Occupied(mut e) => {
+                self.metrics.deduplicated_requests.increment(1);
+                e.get_mut().push(sender);
+                false
             }
         }
     }
+
+    /// Sends all the queued batchable requests to the remote provider, split into batches of at
+    /// most [`BackendConfig::batch_size`] requests.
+    fn send_batched_requests(&mut self) {
+        if self.batched_requests.is_empty() {
+            return;
+        }
+
+        let requests = std::mem::take(&mut self.batched_requests);
+        let batch_size = self.config.batch_size.max(1);
+
+        for batch in requests.chunks(batch_size) {
+            trace!(target: LOG_TARGET, size = %batch.len(), "Sending batch request.");
+            let fut = fetch_batch(
+                self.provider.clone(),
+                self.block,
+                batch.to_vec(),
+                self.metrics.clone(),
+            );
+            self.pending_requests.push(fut.boxed());
+        }
+    }
+
+    /// Sends the response of a request to all the senders waiting on it.
+    fn send_response(&mut self, req_key: BackendRequestIdentifier, res: BackendResponse) {
+        let sender_vec =
+            self.request_dedup_map.remove(&req_key).expect("failed to get sender vector");
+
+        // Send the response to all the senders waiting on the same request
+        sender_vec.iter().for_each(|sender| {
+            sender.send(res.clone()).unwrap_or_else(|error| {
+                error!(target: LOG_TARGET, key = ?req_key, %error, "Failed to send result.")
+            });
+        });
+    }
 }
 
 impl<P> Future for Backend<P>
@@ -311,11 +514,29 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let pin = self.get_mut();
         loop {
+            if let Some(fut) = pin.prefetch.as_mut() {
+                if let Poll::Ready(result) = fut.poll_unpin(cx) {
+                    pin.prefetch = None;
+                    match result {
+                        Ok(storage) => {
+                            trace!(target: LOG_TARGET, entries = %storage.len(), "Contract storage prefetched.");
+                            pin.storage_cache = storage;
+                        }
+                        Err(error) => {
+                            warn!(target: LOG_TARGET, %error, "Failed to prefetch contract storage.");
+                        }
+                    }
+                }
+            }
+
             // convert all queued requests into futures to be polled
             while let Some(req) = pin.queued_requests.pop_front() {
                 pin.handle_requests(req);
             }
 
+            // send the requests collected from the queue in batches
+            pin.send_batched_requests();
+
             loop {
                 match Pin::new(&mut pin.incoming).poll_next(cx) {
                     Poll::Ready(Some(req)) => {
@@ -333,27 +554,17 @@ where
 
             // poll all pending requests
             for n in (0..pin.pending_requests.len()).rev() {
-                let (fut_key, mut fut) = pin.pending_requests.swap_remove(n);
+                let mut fut = pin.pending_requests.swap_remove(n);
                 // poll the future and if the future is still pending, push it back to the
                 // pending requests so that it will be polled again
                 match fut.poll_unpin(cx) {
                     Poll::Pending => {
-                        pin.pending_requests.push((fut_key, fut));
+                        pin.pending_requests.push(fut);
                     }
-                    Poll::Ready(res) => {
-                        let sender_vec = pin
-                            .request_dedup_map
-                            .get(&fut_key)
-                            .expect("failed to get sender vector");
-
-                        // Send the response to all the senders waiting on the same request
-                        sender_vec.iter().for_each(|sender| {
-                            sender.send(res.clone()).unwrap_or_else(|error| {
-                            	error!(target: LOG_TARGET, key = ?fut_key, %error, "Failed to send result.")
-                            });
-                        });
-
-                        pin.request_dedup_map.remove(&fut_key);
+                    Poll::Ready(responses) => {
+                        for (req_key, res) in responses {
+                            pin.send_response(req_key, res);
+                        }
                     }
                 }
             }
@@ -373,12 +584,192 @@ impl<P: Debug> Debug for Backend<P> {
             .field("request_dedup_map", &self.request_dedup_map)
             .field("pending_requests", &self.pending_requests.len())
             .field("queued_requests", &self.queued_requests.len())
+            .field("batched_requests", &self.batched_requests.len())
             .field("incoming", &self.incoming)
             .field("block", &self.block)
+            .field("config", &self.config)
+            .field("storage_cache", &self.storage_cache.len())
             .finish()
     }
 }
 
+/// Fetches the data of the given requests from the remote provider using a single JSON-RPC batch
+/// call.
+async fn fetch_batch<P: Provider>(
+    provider: Arc<P>,
+    block: BlockId,
+    requests: Vec<BackendRequestIdentifier>,
+    metrics: BackendMetrics,
+) -> Vec<(BackendRequestIdentifier, BackendResponse)> {
+    // a single request doesn't benefit from being batched
+    if let [req_key] = requests.as_slice() {
+        return vec![(*req_key, fetch(provider.as_ref(), block, *req_key, &metrics).await)];
+    }
+
+    let rpc_requests = requests.iter().filter_map(|r| r.rpc_request(block)).collect::<Vec<_>>();
+
+    metrics.rpc_calls.increment(1);
+    let start = Instant::now();
+    let result = provider.batch_requests(rpc_requests).await;
+    metrics.request_time_seconds.record(start.elapsed().as_secs_f64());
+
+    match result {
+        Ok(responses) if responses.len() == requests.len() => requests
+            .into_iter()
+            .zip(responses)
+            .map(|(req_key, res)| (req_key, into_backend_response(req_key, res)))
+            .collect(),
+
+        // The whole batch fails if any of its requests fails (eg. requesting the nonce of a
+        // non-existent contract), so we fall back to sending the requests individually to get the
+        // result of each request.
+        result => {
+            if let Err(error) = result {
+                trace!(target: LOG_TARGET, %error, "Batch request failed, sending requests individually.");
+            }
+
+            let provider = provider.as_ref();
+            let metrics = &metrics;
+            join_all(requests.into_iter().map(|req_key| async move {
+                (req_key, fetch(provider, block, req_key, metrics).await)
+            }))
+            .await
+        }
+    }
+}
+
+/// Fetches the data of a single request from the remote provider.
+async fn fetch<P: Provider>(
+    provider: &P,
+    block: BlockId,
+    req_key: BackendRequestIdentifier,
+    metrics: &BackendMetrics,
+) -> BackendResponse {
+    fn provider_err(e: StarknetProviderError) -> BackendError {
+        BackendError::StarknetProvider(Arc::new(e))
+    }
+
+    metrics.rpc_calls.increment(1);
+    let start = Instant::now();
+
+    let res = match req_key {
+        BackendRequestIdentifier::Nonce(address) => {
+            let res = provider.get_nonce(block, Felt::from(address)).await;
+            BackendResponse::Nonce(res.map_err(provider_err))
+        }
+
+        BackendRequestIdentifier::Storage((address, key)) => {
+            let res = provider.get_storage_at(Felt::from(address), key, block).await;
+            BackendResponse::Storage(res.map_err(provider_err))
+        }
+
+        BackendRequestIdentifier::ClassHash(address) => {
+            let res = provider.get_class_hash_at(block, Felt::from(address)).await;
+            BackendResponse::ClassHashAt(res.map_err(provider_err))
+        }
+
+        BackendRequestIdentifier::Class(class_hash) => {
+            let res = provider.get_class(block, class_hash).await;
+            BackendResponse::ClassAt(res.map_err(provider_err))
+        }
+
+        BackendRequestIdentifier::StorageProof(_) => {
+            let error = anyhow!("storage proof requests can't be batched");
+            return req_key.error_response(BackendError::UnexpectedReceiveResult(Arc::new(error)));
+        }
+    };
+
+    metrics.request_time_seconds.record(start.elapsed().as_secs_f64());
+    res
+}
+
+/// Converts the response of a batched request into a [`BackendResponse`].
+fn into_backend_response(
+    req_key: BackendRequestIdentifier,
+    res: ProviderResponseData,
+) -> BackendResponse {
+    match (req_key, res) {
+        (BackendRequestIdentifier::Nonce(_), ProviderResponseData::GetNonce(nonce)) => {
+            BackendResponse::Nonce(Ok(nonce))
+        }
+        (BackendRequestIdentifier::Storage(_), ProviderResponseData::GetStorageAt(value)) => {
+            BackendResponse::Storage(Ok(value))
+        }
+        (BackendRequestIdentifier::ClassHash(_), ProviderResponseData::GetClassHashAt(hash)) => {
+            BackendResponse::ClassHashAt(Ok(hash))
+        }
+        (BackendRequestIdentifier::Class(_), ProviderResponseData::GetClass(class)) => {
+            BackendResponse::ClassAt(Ok(class))
+        }
+        (req_key, _) => {
+            let error = anyhow!("unexpected batch response for request {req_key:?}");
+            req_key.error_response(BackendError::UnexpectedReceiveResult(Arc::new(error)))
+        }
+    }
+}
+
+/// Builds the storage of the given contracts at block `block` from the storage diffs of the
+/// blocks preceding it.
+async fn prefetch_storage<P: Provider>(
+    provider: Arc<P>,
+    block: BlockNumber,
+    config: BackendConfig,
+    metrics: BackendMetrics,
+) -> BackendResult<StorageCache> {
+    let contracts = config.prefetch_contracts.into_iter().map(Felt::from).collect::<HashSet<_>>();
+
+    // The blocks are fetched from the newest to the oldest so that only the latest value of each
+    // storage slot is kept.
+    let first_block = block.saturating_sub(config.prefetch_blocks - 1);
+    let blocks = (first_block..=block).rev().collect::<Vec<_>>();
+
+    trace!(target: LOG_TARGET, contracts = %contracts.len(), from = %first_block, to = %block, "Prefetching contract storage.");
+
+    let mut storage = StorageCache::new();
+
+    for batch in blocks.chunks(config.batch_size.max(1)) {
+        let requests = batch
+            .iter()
+            .map(|number| {
+                let block_id = BlockId::Number(*number);
+                ProviderRequestData::GetStateUpdate(GetStateUpdateRequest { block_id })
+            })
+            .collect::<Vec<_>>();
+
+        metrics.rpc_calls.increment(1);
+        let start = Instant::now();
+
+        let responses = provider
+            .batch_requests(requests)
+            .await
+            .map_err(|e| BackendError::StarknetProvider(Arc::new(e)))?;
+
+        metrics.request_time_seconds.record(start.elapsed().as_secs_f64());
+
+        for response in responses {
+            let ProviderResponseData::GetStateUpdate(MaybePendingStateUpdate::Update(update)) =
+                response
+            else {
+                let error = anyhow!("expected a state update of a confirmed block");
+                return Err(BackendError::UnexpectedReceiveResult(Arc::new(error)));
+            };
+
+            for diff in update.state_diff.storage_diffs {
+                if !contracts.contains(&diff.address) {
+                    continue;
+                }
+
+                let address = ContractAddress::from(diff.address);
+                for entry in diff.storage_entries {
+                    storage.entry((address, entry.key)).or_insert(entry.value);
+                }
+            }
+        }
+    }
+
+    Ok(storage)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BackendClientError {
     #[error("failed to send request to backend: {0}")]
@@ -491,6 +882,22 @@ impl BackendClient {
         }
     }
 
+    /// Fetches the proofs of the given keys from the tries of the forked block.
+    pub fn get_storage_proof(
+        &self,
+        classes: Vec<ClassHash>,
+        contracts: Vec<ContractAddress>,
+        storage_keys: Vec<ContractStorageKeys>,
+    ) -> Result<GetStorageProofResponse, BackendClientError> {
+        trace!(target: LOG_TARGET, classes = %classes.len(), contracts = %contracts.len(), "Requesting storage proof.");
+        let (req, rx) = BackendRequest::storage_proof(classes, contracts, storage_keys);
+        self.request(req)?;
+        match rx.recv()? {
+            BackendResponse::StorageProof(res) => Ok(res?.into()),
+            response => Err(BackendClientError::UnexpectedResponse(anyhow!("{response:?}"))),
+        }
+    }
+
     /// Send a request to the backend thread.
     fn request(&self, req: BackendRequest) -> Result<(), BackendClientError> {
         self.0.lock().try_send(req).map_err(|e| e.into_send_error())?;
@@ -532,10 +939,11 @@ pub(crate) mod test_utils {
     use std::sync::mpsc::{sync_channel, SyncSender};
 
     use katana_primitives::block::BlockNumber;
+    use serde_json::{json, Value};
     use starknet::providers::jsonrpc::HttpTransport;
     use starknet::providers::JsonRpcClient;
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
-    use tokio::net::TcpListener;
+    use tokio::net::{TcpListener, TcpStream};
     use url::Url;
 
     use super::*;
@@ -543,7 +951,7 @@ pub(crate) mod test_utils {
     pub fn create_forked_backend(rpc_url: &str, block_num: BlockNumber) -> BackendClient {
         let url = Url::parse(rpc_url).expect("valid url");
         let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
-        Backend::new(provider, block_num.into()).unwrap()
+        Backend::new(provider, block_num.into(), BackendConfig::default()).unwrap()
     }
 
     // Starts a TCP server that never close the connection.
@@ -604,6 +1012,99 @@ pub(crate) mod test_utils {
         // Returning the sender to allow controlling the response timing.
         tx
     }
+
+    /// Starts a JSON-RPC server that responds to each request (including the requests of a batch)
+    /// using `handler`.
+    ///
+    /// Returns the bodies of all the HTTP requests received by the server.
+    pub fn start_json_rpc_server<F>(addr: String, handler: F) -> Arc<Mutex<Vec<Value>>>
+    where
+        F: Fn(&Value) -> Value + Send + Sync + 'static,
+    {
+        use tokio::runtime::Builder;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+        let (tx, rx) = sync_channel::<()>(1);
+
+        let requests = received.clone();
+        thread::spawn(move || {
+            Builder::new_current_thread().enable_all().build().unwrap().block_on(async move {
+                let listener = TcpListener::bind(addr).await.unwrap();
+                tx.send(()).unwrap();
+
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let handler = handler.clone();
+                    let requests = requests.clone();
+
+                    tokio::spawn(async move {
+                        while let Some(body) = read_http_request(&mut socket).await {
+                            let request: Value = serde_json::from_slice(&body).unwrap();
+                            requests.lock().push(request.clone());
+
+                            let response = match &request {
+                                Value::Array(batch) => batch.iter().map(handler.as_ref()).collect(),
+                                request => handler(request),
+                            };
+
+                            let body = response.to_string();
+                            let http_response = format!(
+                                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\ncontent-type: \
+                                 application/json\r\n\r\n{}",
+                                body.len(),
+                                body
+                            );
+
+                            socket.write_all(http_response.as_bytes()).await.unwrap();
+                            socket.flush().await.unwrap();
+                        }
+                    });
+                }
+            });
+        });
+
+        rx.recv().unwrap();
+        received
+    }
+
+    /// Reads the body of the next HTTP request on the connection. Returns `None` once the
+    /// connection is closed.
+    async fn read_http_request(socket: &mut TcpStream) -> Option<Vec<u8>> {
+        let mut buffer = Vec::new();
+
+        loop {
+            if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                let headers = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+                let length = headers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .map(|value| value.trim().parse::<usize>().unwrap())
+                    .unwrap_or_default();
+
+                let body = end + 4..end + 4 + length;
+                if buffer.len() >= body.end {
+                    return Some(buffer[body].to_vec());
+                }
+            }
+
+            let mut chunk = [0; 4096];
+            match socket.read(&mut chunk).await {
+                Ok(0) | Err(_) => return None,
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            }
+        }
+    }
+
+    /// Returns the successful response to a JSON-RPC request.
+    pub fn rpc_result(request: &Value, result: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    /// Returns the error response to a JSON-RPC request.
+    pub fn rpc_error(request: &Value, code: i64, message: &str) -> Value {
+        json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": code, "message": message } })
+    }
 }
 
 #[cfg(test)]
@@ -611,7 +1112,10 @@ mod tests {
     use std::sync::Mutex;
     use std::time::Duration;
 
+    use serde_json::{json, Value};
     use starknet::macros::felt;
+    use starknet::providers::jsonrpc::HttpTransport;
+    use starknet::providers::JsonRpcClient;
 
     use super::test_utils::*;
     use super::*;
@@ -973,6 +1477,47 @@ mod tests {
         assert_eq!(stats, 3, "Backend should only have 3 ongoing requests.")
     }
 
+    #[test]
+    fn get_storage_request_should_be_served_from_prefetched_storage() {
+        // start a mock remote network that never responds
+        start_tcp_server("127.0.0.1:8088".to_string());
+
+        let url = url::Url::parse("http://127.0.0.1:8088").expect("valid url");
+        let provider = JsonRpcClient::new(HttpTransport::new(url));
+        let (handle, mut backend) =
+            Backend::new_inner(provider, BlockHashOrNumber::Num(1), BackendConfig::default());
+
+        let addr = ContractAddress(felt!("0x1"));
+        backend.storage_cache.insert((addr, felt!("0x1")), felt!("0x123"));
+
+        thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(backend);
+        });
+
+        // prefetched storage is returned without querying the remote network
+        let value = handle.get_storage(addr, felt!("0x1")).expect(ERROR_SEND_REQUEST);
+        assert_eq!(value, Some(felt!("0x123")));
+
+        let stats = handle.stats().expect(ERROR_STATS);
+        assert_eq!(stats, 0, "Backend should not have any ongoing requests.");
+
+        // storage that is not prefetched is fetched from the remote network
+        let h1 = handle.clone();
+        thread::spawn(move || {
+            h1.get_storage(addr, felt!("0x2")).expect(ERROR_SEND_REQUEST);
+        });
+
+        // wait for the requests to be handled
+        thread::sleep(Duration::from_secs(1));
+
+        let stats = handle.stats().expect(ERROR_STATS);
+        assert_eq!(stats, 1, "Backend should have 1 ongoing requests.");
+    }
+
     #[test]
     fn test_deduplicated_request_should_return_similar_results() {
         // Start mock server with a predefined nonce response
@@ -1019,4 +1564,143 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn concurrent_requests_should_be_sent_in_a_single_batch() {
+        let requests = start_json_rpc_server("127.0.0.1:8091".to_string(), |req| {
+            rpc_result(req, json!("0x5"))
+        });
+
+        let url = url::Url::parse("http://127.0.0.1:8091").expect("valid url");
+        let provider = JsonRpcClient::new(HttpTransport::new(url));
+        let (handle, backend) =
+            Backend::new_inner(provider, BlockHashOrNumber::Num(1), BackendConfig::default());
+
+        let addr = ContractAddress(felt!("0x1"));
+
+        // the requests are all queued before the backend starts processing them
+        let threads: Vec<_> = (1..=5u64)
+            .map(|key| {
+                let h = handle.clone();
+                thread::spawn(move || h.get_storage(addr, Felt::from(key)))
+            })
+            .collect();
+
+        thread::sleep(Duration::from_secs(1));
+
+        thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(backend);
+        });
+
+        for thread in threads {
+            let value = thread.join().unwrap().expect(ERROR_SEND_REQUEST);
+            assert_eq!(value, Some(felt!("0x5")));
+        }
+
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 1, "Requests should be sent in a single call.");
+        assert_eq!(requests[0].as_array().map(Vec::len), Some(5));
+    }
+
+    #[tokio::test]
+    async fn failed_batch_should_fall_back_to_individual_requests() {
+        // requesting the nonce of a non-existent contract fails the whole batch
+        let requests = start_json_rpc_server("127.0.0.1:8092".to_string(), |req| {
+            match req["method"].as_str() {
+                Some("starknet_getNonce") => rpc_error(req, 20, "Contract not found"),
+                _ => rpc_result(req, json!("0x5")),
+            }
+        });
+
+        let url = url::Url::parse("http://127.0.0.1:8092").expect("valid url");
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
+
+        let addr = ContractAddress(felt!("0x1"));
+        let storage = BackendRequestIdentifier::Storage((addr, felt!("0x1")));
+        let nonce = BackendRequestIdentifier::Nonce(addr);
+
+        let metrics = BackendMetrics::default();
+        let responses =
+            fetch_batch(provider, BlockId::Number(1), vec![storage, nonce], metrics).await;
+
+        assert!(matches!(
+            responses[0],
+            (BackendRequestIdentifier::Storage(_), BackendResponse::Storage(Ok(value)))
+                if value == felt!("0x5")
+        ));
+        assert!(matches!(
+            responses[1],
+            (BackendRequestIdentifier::Nonce(_), BackendResponse::Nonce(Err(_)))
+        ));
+
+        // the failed batch, followed by each of its requests
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].is_array());
+        assert!(requests[1..].iter().all(Value::is_object));
+    }
+
+    #[tokio::test]
+    async fn prefetched_storage_should_keep_the_newest_value() {
+        let requests = start_json_rpc_server("127.0.0.1:8093".to_string(), |req| {
+            let params = &req["params"];
+            let block_id = if params.is_array() { &params[0] } else { &params["block_id"] };
+
+            // slot 0x1 is updated in both blocks, while slot 0x2 is only updated in the oldest
+            let entries = match block_id["block_number"].as_u64() {
+                Some(2) => json!([{ "key": "0x1", "value": "0x2" }]),
+                Some(1) => {
+                    json!([{ "key": "0x1", "value": "0x1" }, { "key": "0x2", "value": "0x3" }])
+                }
+                block => panic!("unexpected block {block:?}"),
+            };
+
+            rpc_result(
+                req,
+                json!({
+                    "block_hash": "0x1",
+                    "old_root": "0x0",
+                    "new_root": "0x0",
+                    "state_diff": {
+                        "storage_diffs": [
+                            { "address": "0x1", "storage_entries": entries },
+                            // not a prefetched contract
+                            { "address": "0x2", "storage_entries": [{ "key": "0x1", "value": "0x4" }] }
+                        ],
+                        "deprecated_declared_classes": [],
+                        "declared_classes": [],
+                        "deployed_contracts": [],
+                        "replaced_classes": [],
+                        "nonces": []
+                    }
+                }),
+            )
+        });
+
+        let url = url::Url::parse("http://127.0.0.1:8093").expect("valid url");
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
+
+        let addr = ContractAddress(felt!("0x1"));
+        let config = BackendConfig {
+            batch_size: 1,
+            prefetch_contracts: vec![addr],
+            prefetch_blocks: 2,
+            ..Default::default()
+        };
+
+        let storage = prefetch_storage(provider, 2, config, BackendMetrics::default()).await;
+
+        let expected = StorageCache::from([
+            ((addr, felt!("0x1")), felt!("0x2")),
+            ((addr, felt!("0x2")), felt!("0x3")),
+        ]);
+        assert_eq!(storage.unwrap(), expected);
+
+        // the state updates are fetched in a separate batch for each block
+        assert_eq!(requests.lock().len(), 2);
+    }
 }
@@ -0,0 +1,17 @@
+use katana_metrics::Metrics;
+use metrics::{Counter, Histogram};
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "forking.backend")]
+pub(crate) struct BackendMetrics {
+    /// The number of storage requests served from the prefetched storage
+    pub(crate) cache_hits: Counter,
+    /// The number of storage requests that couldn't be served from the prefetched storage
+    pub(crate) cache_misses: Counter,
+    /// The number of requests merged into an identical request that is already in flight
+    pub(crate) deduplicated_requests: Counter,
+    /// The number of JSON-RPC calls sent to the forked network, counting a batch as a single call
+    pub(crate) rpc_calls: Counter,
+    /// Response time of a single JSON-RPC call to the forked network
+    pub(crate) request_time_seconds: Histogram,
+}
@@ -110,6 +110,11 @@ pub enum ProviderError {
     #[error("State root not found")]
     StateRootNotFound,
 
+    /// Error when the trie snapshot of a block is not available, either because the block doesn't
+    /// exist or because its trie history has been pruned.
+    #[error("Missing trie snapshot for block {0}")]
+    MissingTrieSnapshot(BlockNumber),
+
     #[error(transparent)]
     ContractClassCompilation(#[from] ContractClassCompilationError),
 
@@ -121,6 +126,17 @@ pub enum ProviderError {
     #[error(transparent)]
     ForkedBackend1(#[from] katana_fork::BackendClientError),
 
+    /// Error when the forked network didn't return the storage proof of a contract that was
+    /// requested from it.
+    #[cfg(feature = "fork")]
+    #[error("Missing storage proof for contract {0} from the forked network")]
+    MissingForkedStorageProof(ContractAddress),
+
+    /// Error when computing the tries of a forked chain.
+    #[cfg(feature = "fork")]
+    #[error(transparent)]
+    ForkedTrie(#[from] katana_trie::PartialTrieError),
+
     /// Any error that is not covered by the other variants.
     #[error("Something went wrong: {0}")]
     Other(String),
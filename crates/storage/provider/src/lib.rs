@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 use std::ops::{Range, RangeInclusive};
 use std::sync::Arc;
 
+use katana_db::models::account::DevAccount;
 use katana_db::models::block::StoredBlockBodyIndices;
+use katana_db::models::event::EventPosition;
+use katana_db::models::fork::ForkInfo;
 use katana_primitives::block::{
     Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
     SealedBlockWithStatus,
@@ -15,12 +18,17 @@ use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
 use katana_primitives::Felt;
+use traits::account::DevAccountProvider;
 use traits::block::{BlockIdReader, BlockStatusProvider, BlockWriter};
 use traits::contract::ContractClassWriter;
 use traits::env::BlockEnvProvider;
+use traits::event::EventIndexProvider;
+use traits::fork::ForkInfoProvider;
 use traits::stage::StageCheckpointProvider;
 use traits::state::StateWriter;
-use traits::transaction::{TransactionStatusProvider, TransactionTraceProvider};
+use traits::transaction::{
+    TransactionStatusProvider, TransactionTraceProvider, TransactionTraceWriter,
+};
 use traits::trie::TrieWriter;
 
 pub mod error;
@@ -145,6 +153,10 @@ where
     ) -> ProviderResult<()> {
         self.provider.insert_block_with_states_and_receipts(block, states, receipts, executions)
     }
+
+    fn unwind_to(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.provider.unwind_to(block)
+    }
 }
 
 impl<Db> TransactionProvider for BlockchainProvider<Db>
@@ -224,6 +236,19 @@ where
     }
 }
 
+impl<Db> TransactionTraceWriter for BlockchainProvider<Db>
+where
+    Db: TransactionTraceWriter,
+{
+    fn insert_transaction_executions(
+        &self,
+        block: BlockNumber,
+        executions: Vec<TypedTransactionExecutionInfo>,
+    ) -> ProviderResult<()> {
+        self.provider.insert_transaction_executions(block, executions)
+    }
+}
+
 impl<Db> TransactionsProviderExt for BlockchainProvider<Db>
 where
     Db: TransactionsProviderExt,
@@ -377,3 +402,58 @@ where
         self.provider.set_checkpoint(id, block_number)
     }
 }
+
+impl<Db> ForkInfoProvider for BlockchainProvider<Db>
+where
+    Db: ForkInfoProvider,
+{
+    fn fork_info(&self) -> ProviderResult<Option<ForkInfo>> {
+        self.provider.fork_info()
+    }
+
+    fn set_fork_info(&self, info: ForkInfo) -> ProviderResult<()> {
+        self.provider.set_fork_info(info)
+    }
+}
+
+impl<Db> DevAccountProvider for BlockchainProvider<Db>
+where
+    Db: DevAccountProvider,
+{
+    fn dev_accounts(&self) -> ProviderResult<Vec<(ContractAddress, DevAccount)>> {
+        self.provider.dev_accounts()
+    }
+
+    fn set_dev_account(&self, address: ContractAddress, account: DevAccount) -> ProviderResult<()> {
+        self.provider.set_dev_account(address, account)
+    }
+}
+
+impl<Db> EventIndexProvider for BlockchainProvider<Db>
+where
+    Db: EventIndexProvider,
+{
+    fn event_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.event_index_checkpoint()
+    }
+
+    fn events_by_address(
+        &self,
+        address: ContractAddress,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        self.provider.events_by_address(address, from, to_block, limit)
+    }
+
+    fn events_by_key(
+        &self,
+        key: Felt,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        self.provider.events_by_key(key, from, to_block, limit)
+    }
+}
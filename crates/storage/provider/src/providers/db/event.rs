@@ -0,0 +1,212 @@
+use std::ops::RangeInclusive;
+
+use katana_db::abstraction::{Database, DbCursor, DbDupSortCursor, DbTx, DbTxMut};
+use katana_db::models::event::{EventPosition, EVENT_INDEX_CHECKPOINT};
+use katana_db::models::stage::StageCheckpoint;
+use katana_db::tables::{self, DupSort};
+use katana_primitives::block::BlockNumber;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::receipt::Receipt;
+use katana_primitives::Felt;
+
+use crate::error::ProviderError;
+use crate::providers::db::DbProvider;
+use crate::traits::event::EventIndexProvider;
+use crate::ProviderResult;
+
+impl<Db: Database> DbProvider<Db> {
+    /// Indexes the events of at most `max_blocks` blocks that haven't been indexed yet, in a
+    /// single database transaction.
+    ///
+    /// Returns the range of the blocks that have been indexed, or `None` if all the blocks in the
+    /// database are already indexed.
+    pub fn index_events(
+        &self,
+        max_blocks: u64,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.0.update(|tx| {
+            let Some((latest, _)) = tx.cursor::<tables::BlockHashes>()?.last()? else {
+                return Ok(None);
+            };
+
+            let from = match event_index_checkpoint(tx)? {
+                Some(checkpoint) => checkpoint + 1,
+                None => match tx.cursor::<tables::BlockHashes>()?.first()? {
+                    Some((first, _)) => first,
+                    None => return Ok(None),
+                },
+            };
+
+            if from > latest || max_blocks == 0 {
+                return Ok(None);
+            }
+
+            let to = latest.min(from.saturating_add(max_blocks - 1));
+
+            for block_number in from..=to {
+                let indices = tx
+                    .get::<tables::BlockBodyIndices>(block_number)?
+                    .ok_or(ProviderError::MissingBlockBodyIndices(block_number))?;
+
+                let mut receipts = Vec::with_capacity(indices.tx_count as usize);
+                for tx_number in indices.tx_offset..indices.tx_offset + indices.tx_count {
+                    let receipt = tx
+                        .get::<tables::Receipts>(tx_number)?
+                        .ok_or(ProviderError::MissingTxReceipt(tx_number))?;
+                    receipts.push(receipt);
+                }
+
+                index_block_events(tx, block_number, &receipts)?;
+            }
+
+            Ok(Some(from..=to))
+        })?
+    }
+}
+
+impl<Db: Database> EventIndexProvider for DbProvider<Db> {
+    fn event_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        let tx = self.0.tx()?;
+        let checkpoint = event_index_checkpoint(&tx)?;
+        tx.commit()?;
+        Ok(checkpoint)
+    }
+
+    fn events_by_address(
+        &self,
+        address: ContractAddress,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        let tx = self.0.tx()?;
+        let positions =
+            indexed_positions::<tables::EventsByAddress>(&tx, address, from, to_block, limit)?;
+        tx.commit()?;
+        Ok(positions)
+    }
+
+    fn events_by_key(
+        &self,
+        key: Felt,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        let tx = self.0.tx()?;
+        let positions = indexed_positions::<tables::EventsByKey>(&tx, key, from, to_block, limit)?;
+        tx.commit()?;
+        Ok(positions)
+    }
+}
+
+/// Returns `true` if the events of the block should be indexed when the block is inserted, ie the
+/// events of all the blocks before it have been indexed.
+///
+/// The index is only maintained for databases where it has been built since their first block.
+/// Otherwise, it must be built first using [`DbProvider::index_events`].
+pub(super) fn should_index_events(
+    tx: &impl DbTx,
+    block_number: BlockNumber,
+) -> ProviderResult<bool> {
+    match event_index_checkpoint(tx)? {
+        Some(checkpoint) => Ok(checkpoint + 1 == block_number),
+        None => Ok(tx.entries::<tables::BlockHashes>()? == 0),
+    }
+}
+
+/// Indexes the events of the block and moves the index checkpoint to the block.
+pub(super) fn index_block_events(
+    tx: &impl DbTxMut,
+    block_number: BlockNumber,
+    receipts: &[Receipt],
+) -> ProviderResult<()> {
+    for (tx_index, receipt) in receipts.iter().enumerate() {
+        for (event_index, event) in receipt.events().iter().enumerate() {
+            let position = EventPosition {
+                block_number,
+                tx_index: tx_index as u32,
+                event_index: event_index as u32,
+            };
+
+            tx.put::<tables::EventsByAddress>(event.from_address, position)?;
+
+            if let Some(key) = event.keys.first() {
+                tx.put::<tables::EventsByKey>(*key, position)?;
+            }
+        }
+    }
+
+    let checkpoint = StageCheckpoint { block: block_number };
+    tx.put::<tables::StageCheckpoints>(EVENT_INDEX_CHECKPOINT.to_string(), checkpoint)?;
+
+    Ok(())
+}
+
+/// Removes the events of a transaction of an unwound block from the index.
+pub(super) fn unindex_tx_events(
+    tx: &impl DbTxMut,
+    block_number: BlockNumber,
+    tx_index: usize,
+    receipt: &Receipt,
+) -> ProviderResult<()> {
+    for (event_index, event) in receipt.events().iter().enumerate() {
+        let position = EventPosition {
+            block_number,
+            tx_index: tx_index as u32,
+            event_index: event_index as u32,
+        };
+
+        tx.delete::<tables::EventsByAddress>(event.from_address, Some(position))?;
+
+        if let Some(key) = event.keys.first() {
+            tx.delete::<tables::EventsByKey>(*key, Some(position))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the index checkpoint back to `block` if it's ahead of it, after the blocks after `block`
+/// have been unwound.
+pub(super) fn unwind_event_index_checkpoint(
+    tx: &impl DbTxMut,
+    block: BlockNumber,
+) -> ProviderResult<()> {
+    if event_index_checkpoint(tx)?.is_some_and(|checkpoint| checkpoint > block) {
+        let checkpoint = StageCheckpoint { block };
+        tx.put::<tables::StageCheckpoints>(EVENT_INDEX_CHECKPOINT.to_string(), checkpoint)?;
+    }
+    Ok(())
+}
+
+fn event_index_checkpoint(tx: &impl DbTx) -> ProviderResult<Option<BlockNumber>> {
+    let checkpoint = tx.get::<tables::StageCheckpoints>(EVENT_INDEX_CHECKPOINT.to_string())?;
+    Ok(checkpoint.map(|c| c.block))
+}
+
+fn indexed_positions<T>(
+    tx: &impl DbTx,
+    key: T::Key,
+    from: EventPosition,
+    to_block: BlockNumber,
+    limit: usize,
+) -> ProviderResult<Vec<EventPosition>>
+where
+    T: DupSort<SubKey = EventPosition, Value = EventPosition>,
+{
+    let mut positions = Vec::new();
+    let mut cursor = tx.cursor_dup::<T>()?;
+    let mut entry = cursor.seek_by_key_subkey(key, from)?;
+
+    while let Some(position) = entry {
+        if positions.len() >= limit || position.block_number > to_block {
+            break;
+        }
+
+        positions.push(position);
+        entry = cursor.next_dup_val()?;
+    }
+
+    Ok(positions)
+}
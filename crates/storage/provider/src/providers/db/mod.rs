@@ -1,3 +1,4 @@
+pub mod event;
 pub mod state;
 pub mod trie;
 
@@ -9,15 +10,18 @@ use katana_db::abstraction::{Database, DbCursor, DbCursorMut, DbDupSortCursor, D
 use katana_db::error::DatabaseError;
 use katana_db::init_ephemeral_db;
 use katana_db::mdbx::DbEnv;
+use katana_db::models::account::DevAccount;
 use katana_db::models::block::StoredBlockBodyIndices;
 use katana_db::models::contract::{
     ContractClassChange, ContractInfoChangeList, ContractNonceChange,
 };
+use katana_db::models::fork::ForkInfo;
 use katana_db::models::list::BlockList;
 use katana_db::models::stage::StageCheckpoint;
 use katana_db::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
+use katana_db::models::trie::TrieHistoryEntry;
 use katana_db::models::{VersionedHeader, VersionedTx};
-use katana_db::tables::{self, DupSort, Table};
+use katana_db::tables::{self, DupSort, Table, Trie};
 use katana_db::utils::KeyValue;
 use katana_primitives::block::{
     Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
@@ -34,17 +38,19 @@ use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
 
 use crate::error::ProviderError;
+use crate::traits::account::DevAccountProvider;
 use crate::traits::block::{
     BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
     HeaderProvider,
 };
 use crate::traits::env::BlockEnvProvider;
+use crate::traits::fork::ForkInfoProvider;
 use crate::traits::stage::StageCheckpointProvider;
 use crate::traits::state::{StateFactoryProvider, StateProvider};
 use crate::traits::state_update::StateUpdateProvider;
 use crate::traits::transaction::{
     ReceiptProvider, TransactionProvider, TransactionStatusProvider, TransactionTraceProvider,
-    TransactionsProviderExt,
+    TransactionTraceWriter, TransactionsProviderExt,
 };
 use crate::ProviderResult;
 
@@ -608,6 +614,27 @@ impl<Db: Database> TransactionTraceProvider for DbProvider<Db> {
     }
 }
 
+impl<Db: Database> TransactionTraceWriter for DbProvider<Db> {
+    fn insert_transaction_executions(
+        &self,
+        block: BlockNumber,
+        executions: Vec<TypedTransactionExecutionInfo>,
+    ) -> ProviderResult<()> {
+        self.0.update(move |db_tx| -> ProviderResult<()> {
+            let indices = db_tx
+                .get::<tables::BlockBodyIndices>(block)?
+                .ok_or(ProviderError::MissingBlockBodyIndices(block))?;
+
+            let range = indices.tx_offset..indices.tx_offset + indices.tx_count;
+            for (tx_number, execution) in range.zip(executions) {
+                db_tx.put::<tables::TxTraces>(tx_number, execution)?;
+            }
+
+            Ok(())
+        })?
+    }
+}
+
 impl<Db: Database> ReceiptProvider for DbProvider<Db> {
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
         let db_tx = self.0.tx()?;
@@ -656,6 +683,7 @@ impl<Db: Database> BlockEnvProvider for DbProvider<Db> {
             l1_gas_prices: header.l1_gas_prices,
             l1_data_gas_prices: header.l1_data_gas_prices,
             sequencer_address: header.sequencer_address,
+            l1_da_mode: header.l1_da_mode,
         }))
     }
 }
@@ -679,6 +707,10 @@ impl<Db: Database> BlockWriter for DbProvider<Db> {
             let tx_offset = db_tx.entries::<tables::Transactions>()? as u64;
             let block_body_indices = StoredBlockBodyIndices { tx_offset, tx_count };
 
+            if event::should_index_events(db_tx, block_number)? {
+                event::index_block_events(db_tx, block_number, &receipts)?;
+            }
+
             db_tx.put::<tables::BlockHashes>(block_number, block_hash)?;
             db_tx.put::<tables::BlockNumbers>(block_hash, block_number)?;
             db_tx.put::<tables::BlockStatusses>(block_number, block.status)?;
@@ -835,6 +867,251 @@ impl<Db: Database> BlockWriter for DbProvider<Db> {
             Ok(())
         })?
     }
+
+    fn unwind_to(&self, block: BlockNumber) -> ProviderResult<()> {
+        let db_tx = self.0.tx_mut()?;
+
+        let latest = db_tx.cursor::<tables::BlockHashes>()?.last()?.map(|(num, _)| num);
+        let latest = latest.ok_or(ProviderError::MissingLatestBlockNumber)?;
+
+        if block >= latest {
+            db_tx.abort();
+            return Ok(());
+        }
+
+        let start = block + 1;
+        let blocks = start..=latest;
+
+        // remove the blocks and their transactions
+
+        for number in blocks.clone() {
+            let hash = db_tx
+                .get::<tables::BlockHashes>(number)?
+                .ok_or(ProviderError::MissingBlockHash(number))?;
+            let indices = db_tx
+                .get::<tables::BlockBodyIndices>(number)?
+                .ok_or(ProviderError::MissingBlockBodyIndices(number))?;
+
+            for (tx_index, tx_number) in Range::from(indices).enumerate() {
+                if let Some(tx_hash) = db_tx.get::<tables::TxHashes>(tx_number)? {
+                    db_tx.delete::<tables::TxNumbers>(tx_hash, None)?;
+                }
+
+                if let Some(receipt) = db_tx.get::<tables::Receipts>(tx_number)? {
+                    event::unindex_tx_events(&db_tx, number, tx_index, &receipt)?;
+                }
+
+                db_tx.delete::<tables::TxHashes>(tx_number, None)?;
+                db_tx.delete::<tables::TxBlocks>(tx_number, None)?;
+                db_tx.delete::<tables::Transactions>(tx_number, None)?;
+                db_tx.delete::<tables::Receipts>(tx_number, None)?;
+                db_tx.delete::<tables::TxTraces>(tx_number, None)?;
+            }
+
+            db_tx.delete::<tables::BlockNumbers>(hash, None)?;
+            db_tx.delete::<tables::BlockHashes>(number, None)?;
+            db_tx.delete::<tables::BlockStatusses>(number, None)?;
+            db_tx.delete::<tables::Headers>(number, None)?;
+            db_tx.delete::<tables::BlockBodyIndices>(number, None)?;
+        }
+
+        event::unwind_event_index_checkpoint(&db_tx, block)?;
+
+        // remove the classes declared in the unwound blocks
+
+        let declarations = db_tx
+            .cursor::<tables::ClassDeclarations>()?
+            .walk(Some(start))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (_, class_hash) in declarations {
+            db_tx.delete::<tables::CompiledClassHashes>(class_hash, None)?;
+            db_tx.delete::<tables::ClassDeclarationBlock>(class_hash, None)?;
+            db_tx.delete::<tables::Classes>(class_hash, None)?;
+        }
+
+        // revert the storage changes
+
+        let storage_changes = db_tx
+            .cursor::<tables::StorageChangeHistory>()?
+            .walk(Some(start))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (_, ContractStorageEntry { key, .. }) in storage_changes {
+            let mut list = db_tx.get::<tables::StorageChangeSet>(key.clone())?.unwrap_or_default();
+            list.remove_range(start..);
+
+            // the value of the storage at `block` is the one from its most recent change
+            let value = if let Some(num) = list.max() {
+                let mut cursor = db_tx.cursor_dup::<tables::StorageChangeHistory>()?;
+                let entry = cursor.seek_by_key_subkey(num, key.clone())?;
+
+                match entry {
+                    Some(entry) if entry.key == key => Some(entry.value),
+                    _ => {
+                        return Err(ProviderError::MissingStorageChangeEntry {
+                            block: num,
+                            storage_key: key.key,
+                            contract_address: key.contract_address,
+                        })
+                    }
+                }
+            } else {
+                None
+            };
+
+            if list.is_empty() {
+                db_tx.delete::<tables::StorageChangeSet>(key.clone(), None)?;
+            } else {
+                db_tx.put::<tables::StorageChangeSet>(key.clone(), list)?;
+            }
+
+            let mut cursor = db_tx.cursor_dup_mut::<tables::ContractStorage>()?;
+            match cursor.seek_by_key_subkey(key.contract_address, key.key)? {
+                Some(current) if current.key == key.key => {
+                    cursor.delete_current()?;
+                }
+                _ => {}
+            }
+
+            if let Some(value) = value {
+                cursor.upsert(key.contract_address, StorageEntry { key: key.key, value })?;
+            }
+        }
+
+        // revert the contract info changes
+
+        let mut contracts = BTreeSet::new();
+        {
+            let mut cursor = db_tx.cursor::<tables::NonceChangeHistory>()?;
+            for entry in cursor.walk(Some(start))? {
+                contracts.insert(entry?.1.contract_address);
+            }
+
+            let mut cursor = db_tx.cursor::<tables::ClassChangeHistory>()?;
+            for entry in cursor.walk(Some(start))? {
+                contracts.insert(entry?.1.contract_address);
+            }
+        }
+
+        for address in contracts {
+            let Some(mut change_list) = db_tx.get::<tables::ContractInfoChangeSet>(address)? else {
+                continue;
+            };
+
+            change_list.nonce_change_list.remove_range(start..);
+            change_list.class_change_list.remove_range(start..);
+
+            let nonce = if let Some(num) = change_list.nonce_change_list.max() {
+                let mut cursor = db_tx.cursor_dup::<tables::NonceChangeHistory>()?;
+                let entry = cursor.seek_by_key_subkey(num, address)?;
+
+                match entry {
+                    Some(entry) if entry.contract_address == address => Some(entry.nonce),
+                    _ => {
+                        return Err(ProviderError::MissingContractNonceChangeEntry {
+                            block: num,
+                            contract_address: address,
+                        })
+                    }
+                }
+            } else {
+                None
+            };
+
+            let class_hash = if let Some(num) = change_list.class_change_list.max() {
+                let mut cursor = db_tx.cursor_dup::<tables::ClassChangeHistory>()?;
+                let entry = cursor.seek_by_key_subkey(num, address)?;
+
+                match entry {
+                    Some(entry) if entry.contract_address == address => Some(entry.class_hash),
+                    _ => {
+                        return Err(ProviderError::MissingContractClassChangeEntry {
+                            block: num,
+                            contract_address: address,
+                        })
+                    }
+                }
+            } else {
+                None
+            };
+
+            if nonce.is_none() && class_hash.is_none() {
+                db_tx.delete::<tables::ContractInfo>(address, None)?;
+                db_tx.delete::<tables::ContractInfoChangeSet>(address, None)?;
+            } else {
+                let info = GenericContractInfo {
+                    nonce: nonce.unwrap_or_default(),
+                    class_hash: class_hash.unwrap_or_default(),
+                };
+
+                db_tx.put::<tables::ContractInfo>(address, info)?;
+                db_tx.put::<tables::ContractInfoChangeSet>(address, change_list)?;
+            }
+        }
+
+        // revert the state tries
+
+        unwind_trie::<tables::ClassesTrie, _>(&db_tx, start)?;
+        unwind_trie::<tables::ContractsTrie, _>(&db_tx, start)?;
+        unwind_trie::<tables::StoragesTrie, _>(&db_tx, start)?;
+
+        // remove the history entries of the unwound blocks
+
+        for number in blocks {
+            db_tx.delete::<tables::ClassDeclarations>(number, None)?;
+            db_tx.delete::<tables::NonceChangeHistory>(number, None)?;
+            db_tx.delete::<tables::ClassChangeHistory>(number, None)?;
+            db_tx.delete::<tables::StorageChangeHistory>(number, None)?;
+            db_tx.delete::<tables::ClassesTrieHistory>(number, None)?;
+            db_tx.delete::<tables::ContractsTrieHistory>(number, None)?;
+            db_tx.delete::<tables::StoragesTrieHistory>(number, None)?;
+        }
+
+        db_tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Reverts the trie table `Tb` to its state before block `start`, using the trie history.
+fn unwind_trie<Tb, Tx>(db_tx: &Tx, start: BlockNumber) -> ProviderResult<()>
+where
+    Tb: Trie,
+    Tx: DbTxMut,
+{
+    let changes =
+        db_tx.cursor::<Tb::History>()?.walk(Some(start))?.collect::<Result<Vec<_>, _>>()?;
+
+    for (_, TrieHistoryEntry { key, .. }) in changes {
+        let mut list = db_tx.get::<Tb::Changeset>(key.clone())?.unwrap_or_default();
+        list.remove_range(start..);
+
+        let value = if let Some(num) = list.max() {
+            let mut cursor = db_tx.cursor_dup::<Tb::History>()?;
+            match cursor.seek_by_key_subkey(num, key.clone())? {
+                Some(entry) if entry.key == key => Some(entry.value),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if list.is_empty() {
+            db_tx.delete::<Tb::Changeset>(key.clone(), None)?;
+        } else {
+            db_tx.put::<Tb::Changeset>(key.clone(), list)?;
+        }
+
+        // an empty value means the key was removed from the trie at that block
+        match value {
+            Some(value) if !value.is_empty() => db_tx.put::<Tb>(key, value)?,
+            _ => {
+                db_tx.delete::<Tb>(key, None)?;
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl<Db: Database> StageCheckpointProvider for DbProvider<Db> {
@@ -857,10 +1134,51 @@ impl<Db: Database> StageCheckpointProvider for DbProvider<Db> {
     }
 }
 
+impl<Db: Database> ForkInfoProvider for DbProvider<Db> {
+    fn fork_info(&self) -> ProviderResult<Option<ForkInfo>> {
+        let tx = self.0.tx()?;
+        let result = tx.cursor::<tables::Forks>()?.first()?.map(|(_, info)| info);
+        tx.commit()?;
+        Ok(result)
+    }
+
+    fn set_fork_info(&self, info: ForkInfo) -> ProviderResult<()> {
+        let tx = self.0.tx_mut()?;
+
+        tx.clear::<tables::Forks>()?;
+        tx.put::<tables::Forks>(info.block_number, info)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl<Db: Database> DevAccountProvider for DbProvider<Db> {
+    fn dev_accounts(&self) -> ProviderResult<Vec<(ContractAddress, DevAccount)>> {
+        let tx = self.0.tx()?;
+        let accounts = tx
+            .cursor::<tables::DevAccounts>()?
+            .walk(None)?
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        tx.commit()?;
+        Ok(accounts)
+    }
+
+    fn set_dev_account(&self, address: ContractAddress, account: DevAccount) -> ProviderResult<()> {
+        let tx = self.0.tx_mut()?;
+        tx.put::<tables::DevAccounts>(address, account)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
+    use katana_db::models::account::DevAccount;
+    use katana_db::models::event::EventPosition;
+    use katana_db::models::fork::ForkInfo;
     use katana_primitives::address;
     use katana_primitives::block::{
         Block, BlockHashOrNumber, FinalityStatus, Header, SealedBlockWithStatus,
@@ -868,15 +1186,19 @@ mod tests {
     use katana_primitives::contract::ContractAddress;
     use katana_primitives::execution::TypedTransactionExecutionInfo;
     use katana_primitives::fee::FeeInfo;
-    use katana_primitives::receipt::{InvokeTxReceipt, Receipt};
+    use katana_primitives::receipt::{Event, InvokeTxReceipt, Receipt};
     use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
     use katana_primitives::transaction::{InvokeTx, Tx, TxHash, TxWithHash};
+    use katana_primitives::Felt;
     use starknet::macros::felt;
 
     use super::DbProvider;
+    use crate::traits::account::DevAccountProvider;
     use crate::traits::block::{
         BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
     };
+    use crate::traits::event::EventIndexProvider;
+    use crate::traits::fork::ForkInfoProvider;
     use crate::traits::state::StateFactoryProvider;
     use crate::traits::transaction::TransactionProvider;
 
@@ -1085,4 +1407,184 @@ mod tests {
         assert_eq!(storage1, felt!("100"));
         assert_eq!(storage2, felt!("200"));
     }
+
+    #[test]
+    fn unwind_to_block() {
+        let provider = create_db_provider();
+
+        let block1 = create_dummy_block();
+        let block2 = {
+            let header = Header { parent_hash: block1.block.hash, number: 1, ..Default::default() };
+            let body = vec![TxWithHash {
+                hash: 25u8.into(),
+                transaction: Tx::Invoke(InvokeTx::V1(Default::default())),
+            }];
+            let block = Block { header, body }.seal();
+            SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 }
+        };
+
+        let mut state_updates2 = create_dummy_state_updates_2();
+        state_updates2.state_updates.nonce_updates.insert(address!("3"), felt!("1"));
+
+        for (block, states) in
+            [(block1.clone(), create_dummy_state_updates()), (block2.clone(), state_updates2)]
+        {
+            BlockWriter::insert_block_with_states_and_receipts(
+                &provider,
+                block,
+                states,
+                vec![Receipt::Invoke(InvokeTxReceipt {
+                    revert_error: None,
+                    events: Vec::new(),
+                    messages_sent: Vec::new(),
+                    fee: FeeInfo::default(),
+                    execution_resources: Default::default(),
+                })],
+                vec![TypedTransactionExecutionInfo::default()],
+            )
+            .expect("failed to insert block");
+        }
+
+        provider.unwind_to(0).expect("failed to unwind");
+
+        // the second block and its transaction are removed
+
+        assert_eq!(provider.latest_number().unwrap(), 0);
+        assert_eq!(provider.latest_hash().unwrap(), block1.block.hash);
+        assert!(provider.block(block2.block.hash.into()).unwrap().is_none());
+        assert!(provider.transaction_by_hash(25u8.into()).unwrap().is_none());
+        assert!(provider.transaction_by_hash(24u8.into()).unwrap().is_some());
+
+        // the state is reverted to the first block
+
+        let state_prov = StateFactoryProvider::latest(&provider).unwrap();
+
+        assert_eq!(state_prov.nonce(address!("1")).unwrap(), Some(felt!("1")));
+        assert_eq!(state_prov.nonce(address!("2")).unwrap(), Some(felt!("2")));
+        assert_eq!(state_prov.nonce(address!("3")).unwrap(), None);
+        assert_eq!(state_prov.class_hash_of_contract(address!("1")).unwrap(), Some(felt!("3")));
+        assert_eq!(state_prov.class_hash_of_contract(address!("2")).unwrap(), Some(felt!("4")));
+        assert_eq!(state_prov.storage(address!("1"), felt!("1")).unwrap(), Some(felt!("1")));
+        assert_eq!(state_prov.storage(address!("1"), felt!("2")).unwrap(), Some(felt!("2")));
+    }
+
+    #[test]
+    fn fork_info() {
+        let provider = create_db_provider();
+        assert!(provider.fork_info().unwrap().is_none());
+
+        let info = ForkInfo {
+            url: "http://localhost:5050/".to_string(),
+            block_number: 10,
+            block_hash: felt!("0x1337"),
+        };
+        provider.set_fork_info(info.clone()).unwrap();
+        assert_eq!(provider.fork_info().unwrap(), Some(info));
+
+        // only the latest record is kept
+        let info = ForkInfo { block_number: 5, ..Default::default() };
+        provider.set_fork_info(info.clone()).unwrap();
+        assert_eq!(provider.fork_info().unwrap(), Some(info));
+    }
+
+    #[test]
+    fn dev_accounts() {
+        let provider = create_db_provider();
+        assert!(provider.dev_accounts().unwrap().is_empty());
+
+        let account = |key: Felt| DevAccount {
+            private_key: key,
+            public_key: key + Felt::ONE,
+            class_hash: felt!("0x123"),
+        };
+
+        provider.set_dev_account(address!("0x2"), account(felt!("0x20"))).unwrap();
+        provider.set_dev_account(address!("0x1"), account(felt!("0x10"))).unwrap();
+
+        let expected = vec![
+            (address!("0x1"), account(felt!("0x10"))),
+            (address!("0x2"), account(felt!("0x20"))),
+        ];
+        assert_eq!(provider.dev_accounts().unwrap(), expected);
+    }
+
+    #[test]
+    fn event_index() {
+        let provider = create_db_provider();
+        assert_eq!(provider.event_index_checkpoint().unwrap(), None);
+
+        let event = |from_address: ContractAddress, key: Felt| Event {
+            from_address,
+            keys: vec![key],
+            data: Vec::new(),
+        };
+
+        let receipt = |events: Vec<Event>| {
+            Receipt::Invoke(InvokeTxReceipt {
+                revert_error: None,
+                events,
+                messages_sent: Vec::new(),
+                fee: FeeInfo::default(),
+                execution_resources: Default::default(),
+            })
+        };
+
+        let block1 = create_dummy_block();
+        let block2 = {
+            let header = Header { parent_hash: block1.block.hash, number: 1, ..Default::default() };
+            let body = vec![TxWithHash {
+                hash: 25u8.into(),
+                transaction: Tx::Invoke(InvokeTx::V1(Default::default())),
+            }];
+            let block = Block { header, body }.seal();
+            SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 }
+        };
+
+        let receipts1 =
+            receipt(vec![event(address!("1"), felt!("0xa")), event(address!("2"), felt!("0xa"))]);
+        let receipts2 = receipt(vec![event(address!("1"), felt!("0xb"))]);
+
+        for (block, receipt) in [(block1, receipts1), (block2, receipts2)] {
+            BlockWriter::insert_block_with_states_and_receipts(
+                &provider,
+                block,
+                Default::default(),
+                vec![receipt],
+                vec![TypedTransactionExecutionInfo::default()],
+            )
+            .expect("failed to insert block");
+        }
+
+        // the blocks are indexed as they're inserted
+
+        let pos =
+            |block_number, event_index| EventPosition { block_number, tx_index: 0, event_index };
+
+        assert_eq!(provider.event_index_checkpoint().unwrap(), Some(1));
+        assert_eq!(provider.index_events(10).unwrap(), None);
+
+        let events = provider.events_by_address(address!("1"), pos(0, 0), 1, 10).unwrap();
+        assert_eq!(events, vec![pos(0, 0), pos(1, 0)]);
+        let events = provider.events_by_address(address!("1"), pos(0, 1), 1, 10).unwrap();
+        assert_eq!(events, vec![pos(1, 0)]);
+        let events = provider.events_by_address(address!("1"), pos(0, 0), 0, 10).unwrap();
+        assert_eq!(events, vec![pos(0, 0)]);
+        let events = provider.events_by_address(address!("1"), pos(0, 0), 1, 1).unwrap();
+        assert_eq!(events, vec![pos(0, 0)]);
+
+        let events = provider.events_by_key(felt!("0xa"), pos(0, 0), 1, 10).unwrap();
+        assert_eq!(events, vec![pos(0, 0), pos(0, 1)]);
+        let events = provider.events_by_key(felt!("0xc"), pos(0, 0), 1, 10).unwrap();
+        assert!(events.is_empty());
+
+        // the events of the unwound blocks are removed from the index
+
+        provider.unwind_to(0).expect("failed to unwind");
+
+        assert_eq!(provider.event_index_checkpoint().unwrap(), Some(0));
+        let events = provider.events_by_address(address!("1"), pos(0, 0), 1, 10).unwrap();
+        assert_eq!(events, vec![pos(0, 0)]);
+        let events = provider.events_by_key(felt!("0xb"), pos(0, 0), 1, 10).unwrap();
+        assert!(events.is_empty());
+    }
 }
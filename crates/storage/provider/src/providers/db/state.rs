@@ -339,8 +339,8 @@ where
 {
     fn class_multiproof(&self, classes: Vec<ClassHash>) -> ProviderResult<katana_trie::MultiProof> {
         let proofs = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .classes_trie()
             .multiproof(classes);
         Ok(proofs)
@@ -351,8 +351,8 @@ where
         addresses: Vec<ContractAddress>,
     ) -> ProviderResult<katana_trie::MultiProof> {
         let proofs = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .contracts_trie()
             .multiproof(addresses);
         Ok(proofs)
@@ -364,8 +364,8 @@ where
         storage_keys: Vec<StorageKey>,
     ) -> ProviderResult<katana_trie::MultiProof> {
         let proofs = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .storages_trie(address)
             .multiproof(storage_keys);
         Ok(proofs)
@@ -378,8 +378,8 @@ where
 {
     fn classes_root(&self) -> ProviderResult<katana_primitives::Felt> {
         let root = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .classes_trie()
             .root();
         Ok(root)
@@ -387,8 +387,8 @@ where
 
     fn contracts_root(&self) -> ProviderResult<katana_primitives::Felt> {
         let root = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .contracts_trie()
             .root();
         Ok(root)
@@ -396,8 +396,8 @@ where
 
     fn storage_root(&self, contract: ContractAddress) -> ProviderResult<Option<Felt>> {
         let root = TrieDbFactory::new(&self.tx)
-            .historical(self.block_number)
-            .expect("should exist")
+            .historical(self.block_number)?
+            .ok_or(ProviderError::MissingTrieSnapshot(self.block_number))?
             .storages_trie(contract)
             .root();
         Ok(Some(root))
@@ -4,8 +4,11 @@ use std::sync::Arc;
 
 use katana_db::abstraction::Database;
 use katana_db::mdbx::DbEnv;
+use katana_db::models::account::DevAccount;
 use katana_db::models::block::StoredBlockBodyIndices;
-use katana_fork::{Backend, BackendClient};
+use katana_db::models::event::EventPosition;
+use katana_db::models::fork::ForkInfo;
+use katana_fork::{Backend, BackendClient, BackendConfig};
 use katana_primitives::block::{
     Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
     SealedBlockWithStatus,
@@ -17,41 +20,55 @@ use katana_primitives::execution::TypedTransactionExecutionInfo;
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
+use katana_primitives::Felt;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 
 use super::db::{self, DbProvider};
+use crate::traits::account::DevAccountProvider;
 use crate::traits::block::{
     BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
     HeaderProvider,
 };
 use crate::traits::env::BlockEnvProvider;
+use crate::traits::event::EventIndexProvider;
+use crate::traits::fork::ForkInfoProvider;
 use crate::traits::stage::StageCheckpointProvider;
 use crate::traits::state_update::StateUpdateProvider;
 use crate::traits::transaction::{
     ReceiptProvider, TransactionProvider, TransactionStatusProvider, TransactionTraceProvider,
-    TransactionsProviderExt,
+    TransactionTraceWriter, TransactionsProviderExt,
 };
 use crate::ProviderResult;
 
 mod state;
 mod trie;
 
+use trie::ForkedTries;
+
 #[derive(Debug)]
 pub struct ForkedProvider<Db: Database = DbEnv> {
     backend: BackendClient,
     provider: Arc<DbProvider<Db>>,
+    /// The tries of the forked chain, if they're computed on top of the forked network's tries.
+    /// Otherwise, the local tries are used.
+    tries: Option<Arc<ForkedTries>>,
 }
 
 impl<Db: Database> ForkedProvider<Db> {
+    /// Creates a new [`ForkedProvider`] that forks the network at the given block.
     pub fn new(
         db: Db,
-        block_id: BlockHashOrNumber,
+        block_number: BlockNumber,
         provider: Arc<JsonRpcClient<HttpTransport>>,
+        config: BackendConfig,
     ) -> Self {
-        let backend = Backend::new(provider, block_id).expect("failed to create backend");
+        let block_id = BlockHashOrNumber::Num(block_number);
+        let remote_tries = config.remote_tries;
+        let backend = Backend::new(provider, block_id, config).expect("failed to create backend");
+        let tries = remote_tries.then(|| Arc::new(ForkedTries::new(block_number, backend.clone())));
         let provider = Arc::new(DbProvider::new(db));
-        Self { provider, backend }
+        Self { provider, backend, tries }
     }
 
     pub fn backend(&self) -> &BackendClient {
@@ -62,12 +79,14 @@ impl<Db: Database> ForkedProvider<Db> {
 impl ForkedProvider<DbEnv> {
     /// Creates a new [`ForkedProvider`] using an ephemeral database.
     pub fn new_ephemeral(
-        block_id: BlockHashOrNumber,
+        block_number: BlockNumber,
         provider: Arc<JsonRpcClient<HttpTransport>>,
     ) -> Self {
-        let backend = Backend::new(provider, block_id).expect("failed to create backend");
+        let block_id = BlockHashOrNumber::Num(block_number);
+        let backend = Backend::new(provider, block_id, BackendConfig::default())
+            .expect("failed to create backend");
         let provider = Arc::new(DbProvider::new_ephemeral());
-        Self { provider, backend }
+        Self { provider, backend, tries: None }
     }
 }
 
@@ -221,6 +240,16 @@ impl<Db: Database> TransactionTraceProvider for ForkedProvider<Db> {
     }
 }
 
+impl<Db: Database> TransactionTraceWriter for ForkedProvider<Db> {
+    fn insert_transaction_executions(
+        &self,
+        block: BlockNumber,
+        executions: Vec<TypedTransactionExecutionInfo>,
+    ) -> ProviderResult<()> {
+        self.provider.insert_transaction_executions(block, executions)
+    }
+}
+
 impl<Db: Database> ReceiptProvider for ForkedProvider<Db> {
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
         self.provider.receipt_by_hash(hash)
@@ -250,6 +279,30 @@ impl<Db: Database> BlockWriter for ForkedProvider<Db> {
     ) -> ProviderResult<()> {
         self.provider.insert_block_with_states_and_receipts(block, states, receipts, executions)
     }
+
+    fn unwind_to(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.provider.unwind_to(block)
+    }
+}
+
+impl<Db: Database> ForkInfoProvider for ForkedProvider<Db> {
+    fn fork_info(&self) -> ProviderResult<Option<ForkInfo>> {
+        self.provider.fork_info()
+    }
+
+    fn set_fork_info(&self, info: ForkInfo) -> ProviderResult<()> {
+        self.provider.set_fork_info(info)
+    }
+}
+
+impl<Db: Database> DevAccountProvider for ForkedProvider<Db> {
+    fn dev_accounts(&self) -> ProviderResult<Vec<(ContractAddress, DevAccount)>> {
+        self.provider.dev_accounts()
+    }
+
+    fn set_dev_account(&self, address: ContractAddress, account: DevAccount) -> ProviderResult<()> {
+        self.provider.set_dev_account(address, account)
+    }
 }
 
 impl<Db: Database> StageCheckpointProvider for ForkedProvider<Db> {
@@ -261,3 +314,29 @@ impl<Db: Database> StageCheckpointProvider for ForkedProvider<Db> {
         self.provider.set_checkpoint(id, block_number)
     }
 }
+
+impl<Db: Database> EventIndexProvider for ForkedProvider<Db> {
+    fn event_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.event_index_checkpoint()
+    }
+
+    fn events_by_address(
+        &self,
+        address: ContractAddress,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        self.provider.events_by_address(address, from, to_block, limit)
+    }
+
+    fn events_by_key(
+        &self,
+        key: Felt,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>> {
+        self.provider.events_by_key(key, from, to_block, limit)
+    }
+}
@@ -12,7 +12,7 @@ use katana_primitives::contract::{GenericContractInfo, Nonce, StorageKey, Storag
 use katana_primitives::{ContractAddress, Felt};
 
 use super::db::{self};
-use super::ForkedProvider;
+use super::{ForkedProvider, ForkedTries};
 use crate::error::ProviderError;
 use crate::providers::db::DbProvider;
 use crate::traits::block::BlockNumberProvider;
@@ -30,7 +30,8 @@ where
         let tx = self.provider.db().tx()?;
         let db = self.provider.clone();
         let provider = db::state::LatestStateProvider::new(tx);
-        Ok(Box::new(LatestStateProvider { db, backend: self.backend.clone(), provider }))
+        let tries = self.tries.clone();
+        Ok(Box::new(LatestStateProvider { db, backend: self.backend.clone(), provider, tries }))
     }
 
     fn historical(
@@ -56,8 +57,9 @@ where
         let db = self.provider.clone();
         let tx = db.db().tx()?;
         let client = self.backend.clone();
+        let tries = self.tries.clone();
 
-        Ok(Some(Box::new(HistoricalStateProvider::new(db, tx, block, client))))
+        Ok(Some(Box::new(HistoricalStateProvider::new(db, tx, block, client, tries))))
     }
 }
 
@@ -66,6 +68,7 @@ struct LatestStateProvider<Db: Database> {
     db: Arc<DbProvider<Db>>,
     backend: BackendClient,
     provider: db::state::LatestStateProvider<Db::Tx>,
+    tries: Option<Arc<ForkedTries>>,
 }
 
 impl<Db> ContractClassProvider for LatestStateProvider<Db>
@@ -165,14 +168,26 @@ where
     Db: Database,
 {
     fn class_multiproof(&self, classes: Vec<ClassHash>) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.class_multiproof(classes)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                tries.class_multiproof(self.db.as_ref(), block, classes)
+            }
+            None => self.provider.class_multiproof(classes),
+        }
     }
 
     fn contract_multiproof(
         &self,
         addresses: Vec<ContractAddress>,
     ) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.contract_multiproof(addresses)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                tries.contract_multiproof(self.db.as_ref(), block, addresses)
+            }
+            None => self.provider.contract_multiproof(addresses),
+        }
     }
 
     fn storage_multiproof(
@@ -180,7 +195,13 @@ where
         address: ContractAddress,
         storage_keys: Vec<StorageKey>,
     ) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.storage_multiproof(address, storage_keys)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                tries.storage_multiproof(self.db.as_ref(), block, address, storage_keys)
+            }
+            None => self.provider.storage_multiproof(address, storage_keys),
+        }
     }
 }
 
@@ -189,15 +210,33 @@ where
     Db: Database,
 {
     fn classes_root(&self) -> ProviderResult<Felt> {
-        self.provider.classes_root()
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                tries.classes_root(self.db.as_ref(), block)
+            }
+            None => self.provider.classes_root(),
+        }
     }
 
     fn contracts_root(&self) -> ProviderResult<Felt> {
-        self.provider.contracts_root()
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                tries.contracts_root(self.db.as_ref(), block)
+            }
+            None => self.provider.contracts_root(),
+        }
     }
 
     fn storage_root(&self, contract: ContractAddress) -> ProviderResult<Option<Felt>> {
-        self.provider.storage_root(contract)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.db.latest_number()?;
+                Ok(Some(tries.storage_root(self.db.as_ref(), block, contract)?))
+            }
+            None => self.provider.storage_root(contract),
+        }
     }
 }
 
@@ -206,6 +245,7 @@ struct HistoricalStateProvider<Db: Database> {
     db: Arc<DbProvider<Db>>,
     backend: BackendClient,
     provider: db::state::HistoricalStateProvider<Db::Tx>,
+    tries: Option<Arc<ForkedTries>>,
 }
 
 impl<Db: Database> HistoricalStateProvider<Db> {
@@ -214,9 +254,10 @@ impl<Db: Database> HistoricalStateProvider<Db> {
         tx: Db::Tx,
         block: BlockNumber,
         backend: BackendClient,
+        tries: Option<Arc<ForkedTries>>,
     ) -> Self {
         let provider = db::state::HistoricalStateProvider::new(tx, block);
-        Self { db, backend, provider }
+        Self { db, backend, provider, tries }
     }
 }
 
@@ -225,7 +266,7 @@ impl<Db: Database> ContractClassProvider for HistoricalStateProvider<Db> {
         if let res @ Some(..) = self.provider.class(hash)? {
             Ok(res)
         } else if let Some(class) = self.backend.get_class_at(hash)? {
-            self.db.db().tx_mut()?.put::<tables::Classes>(hash, class.clone())?;
+            self.db.db().update(|tx| tx.put::<tables::Classes>(hash, class.clone()))??;
             Ok(Some(class))
         } else {
             Ok(None)
@@ -239,7 +280,9 @@ impl<Db: Database> ContractClassProvider for HistoricalStateProvider<Db> {
         if let res @ Some(..) = self.provider.compiled_class_hash_of_class_hash(hash)? {
             Ok(res)
         } else if let Some(compiled_hash) = self.backend.get_compiled_class_hash(hash)? {
-            self.db.db().tx_mut()?.put::<tables::CompiledClassHashes>(hash, compiled_hash)?;
+            self.db
+                .db()
+                .update(|tx| tx.put::<tables::CompiledClassHashes>(hash, compiled_hash))??;
             Ok(Some(compiled_hash))
         } else {
             Ok(None)
@@ -255,7 +298,7 @@ impl<Db: Database> StateProvider for HistoricalStateProvider<Db> {
             let block = self.provider.block();
             let entry = ContractNonceChange { contract_address: address, nonce };
 
-            self.db.db().tx_mut()?.put::<tables::NonceChangeHistory>(block, entry)?;
+            self.db.db().update(|tx| tx.put::<tables::NonceChangeHistory>(block, entry))??;
             Ok(res)
         } else {
             Ok(None)
@@ -272,7 +315,7 @@ impl<Db: Database> StateProvider for HistoricalStateProvider<Db> {
             let block = self.provider.block();
             let entry = ContractClassChange { contract_address: address, class_hash };
 
-            self.db.db().tx_mut()?.put::<tables::ClassChangeHistory>(block, entry)?;
+            self.db.db().update(|tx| tx.put::<tables::ClassChangeHistory>(block, entry))??;
             Ok(res)
         } else {
             Ok(None)
@@ -294,9 +337,11 @@ impl<Db: Database> StateProvider for HistoricalStateProvider<Db> {
             let mut block_list = block_list.unwrap_or_default();
             block_list.insert(block);
 
-            self.db.db().tx_mut()?.put::<tables::StorageChangeSet>(key.clone(), block_list)?;
-            let change_entry = ContractStorageEntry { key, value };
-            self.db.db().tx_mut()?.put::<tables::StorageChangeHistory>(block, change_entry)?;
+            let change_entry = ContractStorageEntry { key: key.clone(), value };
+            self.db.db().update(|tx| {
+                tx.put::<tables::StorageChangeSet>(key, block_list)?;
+                tx.put::<tables::StorageChangeHistory>(block, change_entry)
+            })??;
 
             Ok(res)
         } else {
@@ -307,14 +352,22 @@ impl<Db: Database> StateProvider for HistoricalStateProvider<Db> {
 
 impl<Db: Database> StateProofProvider for HistoricalStateProvider<Db> {
     fn class_multiproof(&self, classes: Vec<ClassHash>) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.class_multiproof(classes)
+        match &self.tries {
+            Some(tries) => tries.class_multiproof(self.db.as_ref(), self.provider.block(), classes),
+            None => self.provider.class_multiproof(classes),
+        }
     }
 
     fn contract_multiproof(
         &self,
         addresses: Vec<ContractAddress>,
     ) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.contract_multiproof(addresses)
+        match &self.tries {
+            Some(tries) => {
+                tries.contract_multiproof(self.db.as_ref(), self.provider.block(), addresses)
+            }
+            None => self.provider.contract_multiproof(addresses),
+        }
     }
 
     fn storage_multiproof(
@@ -322,21 +375,39 @@ impl<Db: Database> StateProofProvider for HistoricalStateProvider<Db> {
         address: ContractAddress,
         storage_keys: Vec<StorageKey>,
     ) -> ProviderResult<katana_trie::MultiProof> {
-        self.provider.storage_multiproof(address, storage_keys)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.provider.block();
+                tries.storage_multiproof(self.db.as_ref(), block, address, storage_keys)
+            }
+            None => self.provider.storage_multiproof(address, storage_keys),
+        }
     }
 }
 
 impl<Db: Database> StateRootProvider for HistoricalStateProvider<Db> {
     fn classes_root(&self) -> ProviderResult<Felt> {
-        self.provider.classes_root()
+        match &self.tries {
+            Some(tries) => tries.classes_root(self.db.as_ref(), self.provider.block()),
+            None => self.provider.classes_root(),
+        }
     }
 
     fn contracts_root(&self) -> ProviderResult<Felt> {
-        self.provider.contracts_root()
+        match &self.tries {
+            Some(tries) => tries.contracts_root(self.db.as_ref(), self.provider.block()),
+            None => self.provider.contracts_root(),
+        }
     }
 
     fn storage_root(&self, contract: ContractAddress) -> ProviderResult<Option<Felt>> {
-        self.provider.storage_root(contract)
+        match &self.tries {
+            Some(tries) => {
+                let block = self.provider.block();
+                Ok(Some(tries.storage_root(self.db.as_ref(), block, contract)?))
+            }
+            None => self.provider.storage_root(contract),
+        }
     }
 }
 
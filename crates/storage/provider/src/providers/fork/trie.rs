@@ -1,22 +1,49 @@
-use std::collections::BTreeMap;
+//! Tries of a forked chain.
+//!
+//! The tries of the forked network are not available locally. Instead, the tries of the forked
+//! chain are computed as an overlay on top of them: the nodes of the forked network's tries are
+//! fetched through its `starknet_getStorageProof` method as they're needed, and the state updates
+//! of the local blocks are applied on top of them.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use katana_db::abstraction::Database;
+use katana_fork::BackendClient;
 use katana_primitives::block::BlockNumber;
 use katana_primitives::class::{ClassHash, CompiledClassHash};
+use katana_primitives::contract::{Nonce, StorageKey};
 use katana_primitives::state::StateUpdates;
-use katana_primitives::Felt;
+use katana_primitives::{ContractAddress, Felt};
+use katana_rpc_types::trie::ContractStorageKeys;
+use katana_trie::{
+    compute_classes_trie_value, compute_contract_state_hash, MultiProof, PartialTrie,
+    PartialTrieError, ProofNode,
+};
+use parking_lot::Mutex;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
 
 use super::ForkedProvider;
+use crate::error::ProviderError;
+use crate::traits::state_update::StateUpdateProvider;
 use crate::traits::trie::TrieWriter;
 use crate::ProviderResult;
 
+/// The maximum number of keys to request in a single storage proof request to the forked network.
+const MAX_PROOF_KEYS: usize = 100;
+
 impl<Db: Database> TrieWriter for ForkedProvider<Db> {
     fn trie_insert_contract_updates(
         &self,
         block_number: BlockNumber,
         state_updates: &StateUpdates,
     ) -> ProviderResult<Felt> {
-        self.provider.trie_insert_contract_updates(block_number, state_updates)
+        match &self.tries {
+            Some(tries) => {
+                tries.insert_contract_updates(self.provider.as_ref(), block_number, state_updates)
+            }
+            None => self.provider.trie_insert_contract_updates(block_number, state_updates),
+        }
     }
 
     fn trie_insert_declared_classes(
@@ -24,6 +51,660 @@ impl<Db: Database> TrieWriter for ForkedProvider<Db> {
         block_number: BlockNumber,
         updates: &BTreeMap<ClassHash, CompiledClassHash>,
     ) -> ProviderResult<Felt> {
-        self.provider.trie_insert_declared_classes(block_number, updates)
+        match &self.tries {
+            Some(tries) => {
+                tries.insert_declared_classes(self.provider.as_ref(), block_number, updates)
+            }
+            None => self.provider.trie_insert_declared_classes(block_number, updates),
+        }
+    }
+}
+
+/// The tries of the forked chain.
+///
+/// The tries of a block are computed by applying the state updates of the local blocks, from the
+/// forked block up to that block, on top of the tries of the forked network. Only the tries of the
+/// latest block are kept in memory, so that new blocks only need to apply their own state updates.
+/// Computing the roots or proofs of an older block re-applies the state updates of every local
+/// block up to it, which gets slower as the forked chain grows.
+#[derive(Debug)]
+pub(super) struct ForkedTries {
+    /// The block number that the chain is forked from.
+    fork_block: BlockNumber,
+    /// The tries of the forked network.
+    remote: RemoteTries,
+    /// The classes trie of the latest block, along with the number of the next block.
+    classes: Mutex<Option<(BlockNumber, PartialTrie<Poseidon>)>>,
+    /// The contracts trie of the latest block, along with the number of the next block.
+    contracts: Mutex<Option<(BlockNumber, ContractsOverlay)>>,
+}
+
+impl ForkedTries {
+    pub(super) fn new(fork_block: BlockNumber, backend: BackendClient) -> Self {
+        Self {
+            fork_block,
+            remote: RemoteTries::new(backend),
+            classes: Mutex::new(None),
+            contracts: Mutex::new(None),
+        }
+    }
+
+    fn insert_declared_classes(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        updates: &BTreeMap<ClassHash, CompiledClassHash>,
+    ) -> ProviderResult<Felt> {
+        let mut trie = self.classes_trie(provider, block)?;
+        self.remote.insert_classes(&mut trie, updates)?;
+
+        let root = trie.root();
+        *self.classes.lock() = Some((block + 1, trie));
+        Ok(root)
+    }
+
+    fn insert_contract_updates(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        state_updates: &StateUpdates,
+    ) -> ProviderResult<Felt> {
+        let mut contracts = self.contracts_trie(provider, block)?;
+        self.remote.insert_contract_updates(&mut contracts, state_updates)?;
+
+        let root = contracts.trie.root();
+        *self.contracts.lock() = Some((block + 1, contracts));
+        Ok(root)
+    }
+
+    /// Returns the root of the classes trie of the state at the given block.
+    pub(super) fn classes_root(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+    ) -> ProviderResult<Felt> {
+        Ok(self.classes_trie(provider, block + 1)?.root())
+    }
+
+    pub(super) fn contracts_root(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+    ) -> ProviderResult<Felt> {
+        Ok(self.contracts_trie(provider, block + 1)?.trie.root())
+    }
+
+    pub(super) fn storage_root(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        address: ContractAddress,
+    ) -> ProviderResult<Felt> {
+        let contracts = self.contracts_trie(provider, block + 1)?;
+        match contracts.leaves.get(&address) {
+            Some(contract) => Ok(contract.storage.root()),
+            None => self.remote.storage_root(address),
+        }
+    }
+
+    pub(super) fn class_multiproof(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        classes: Vec<ClassHash>,
+    ) -> ProviderResult<MultiProof> {
+        let mut trie = self.classes_trie(provider, block + 1)?;
+
+        self.remote.fetch(ProofRequest { classes: classes.clone(), ..Default::default() })?;
+        self.remote.reveal(&mut trie, RemoteTrie::Classes)?;
+
+        self.remote.retry(&mut trie, RemoteTrie::Classes, |trie| trie.multiproof(classes.clone()))
+    }
+
+    pub(super) fn contract_multiproof(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        addresses: Vec<ContractAddress>,
+    ) -> ProviderResult<MultiProof> {
+        let mut trie = self.contracts_trie(provider, block + 1)?.trie;
+
+        self.remote.fetch(ProofRequest { contracts: addresses.clone(), ..Default::default() })?;
+        self.remote.reveal(&mut trie, RemoteTrie::Contracts)?;
+
+        let keys = addresses.into_iter().map(Felt::from).collect::<Vec<_>>();
+        self.remote.retry(&mut trie, RemoteTrie::Contracts, |trie| trie.multiproof(keys.clone()))
+    }
+
+    pub(super) fn storage_multiproof(
+        &self,
+        provider: &impl StateUpdateProvider,
+        block: BlockNumber,
+        address: ContractAddress,
+        keys: Vec<StorageKey>,
+    ) -> ProviderResult<MultiProof> {
+        let contracts = self.contracts_trie(provider, block + 1)?;
+
+        let storages = vec![ContractStorageKeys { address, keys: keys.clone() }];
+        self.remote.fetch(ProofRequest { storages, ..Default::default() })?;
+
+        let mut trie = match contracts.leaves.get(&address) {
+            Some(contract) => contract.storage.clone(),
+            None => self.remote.storage_trie(address)?,
+        };
+
+        let remote = RemoteTrie::Storage(address);
+        self.remote.reveal(&mut trie, remote)?;
+        self.remote.retry(&mut trie, remote, |trie| trie.multiproof(keys.clone()))
+    }
+
+    /// Returns the classes trie with the state updates of the blocks before `until` applied.
+    fn classes_trie(
+        &self,
+        provider: &impl StateUpdateProvider,
+        until: BlockNumber,
+    ) -> ProviderResult<PartialTrie<Poseidon>> {
+        let cached = match self.classes.lock().as_ref() {
+            Some((next, trie)) if *next <= until => Some((*next, trie.clone())),
+            _ => None,
+        };
+
+        let (from, mut trie) = match cached {
+            Some(cached) => cached,
+            None => (self.fork_block, self.remote.classes_trie()?),
+        };
+
+        for block in from..until {
+            let classes = provider
+                .declared_classes(block.into())?
+                .ok_or(ProviderError::MissingBlockHeader(block))?;
+            self.remote.insert_classes(&mut trie, &classes)?;
+        }
+
+        Ok(trie)
+    }
+
+    /// Returns the contracts trie with the state updates of the blocks before `until` applied.
+    fn contracts_trie(
+        &self,
+        provider: &impl StateUpdateProvider,
+        until: BlockNumber,
+    ) -> ProviderResult<ContractsOverlay> {
+        let cached = match self.contracts.lock().as_ref() {
+            Some((next, contracts)) if *next <= until => Some((*next, contracts.clone())),
+            _ => None,
+        };
+
+        let (from, mut contracts) = match cached {
+            Some(cached) => cached,
+            None => {
+                let trie = self.remote.contracts_trie()?;
+                (self.fork_block, ContractsOverlay { trie, leaves: HashMap::new() })
+            }
+        };
+
+        for block in from..until {
+            let state_updates = provider
+                .state_update(block.into())?
+                .ok_or(ProviderError::MissingBlockHeader(block))?;
+            self.remote.insert_contract_updates(&mut contracts, &state_updates)?;
+        }
+
+        Ok(contracts)
+    }
+}
+
+/// The contracts trie of the forked chain.
+#[derive(Debug, Clone)]
+struct ContractsOverlay {
+    trie: PartialTrie<Pedersen>,
+    /// The contracts that have been updated since the forked block.
+    leaves: HashMap<ContractAddress, UpdatedContract>,
+}
+
+#[derive(Debug, Clone)]
+struct UpdatedContract {
+    class_hash: ClassHash,
+    nonce: Nonce,
+    storage: PartialTrie<Pedersen>,
+}
+
+/// Identifies one of the tries of the forked network.
+#[derive(Debug, Clone, Copy)]
+enum RemoteTrie {
+    Classes,
+    Contracts,
+    Storage(ContractAddress),
+}
+
+/// The keys to request the proofs of from the forked network.
+#[derive(Debug, Default)]
+struct ProofRequest {
+    classes: Vec<ClassHash>,
+    contracts: Vec<ContractAddress>,
+    storages: Vec<ContractStorageKeys>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ProofKey {
+    Class(ClassHash),
+    Contract(ContractAddress),
+    Storage(ContractAddress, StorageKey),
+}
+
+impl ProofRequest {
+    /// Splits the request into requests of at most [`MAX_PROOF_KEYS`] keys each.
+    fn split(self) -> Vec<ProofRequest> {
+        let keys = self
+            .classes
+            .into_iter()
+            .map(ProofKey::Class)
+            .chain(self.contracts.into_iter().map(ProofKey::Contract))
+            .chain(self.storages.into_iter().flat_map(|ContractStorageKeys { address, keys }| {
+                keys.into_iter().map(move |key| ProofKey::Storage(address, key))
+            }))
+            .collect::<Vec<_>>();
+
+        keys.chunks(MAX_PROOF_KEYS)
+            .map(|chunk| {
+                let mut request = ProofRequest::default();
+
+                for &key in chunk {
+                    match key {
+                        ProofKey::Class(class_hash) => request.classes.push(class_hash),
+                        ProofKey::Contract(address) => request.contracts.push(address),
+                        ProofKey::Storage(address, key) => match request.storages.last_mut() {
+                            Some(storage) if storage.address == address => storage.keys.push(key),
+                            _ => {
+                                let keys = vec![key];
+                                request.storages.push(ContractStorageKeys { address, keys })
+                            }
+                        },
+                    }
+                }
+
+                request
+            })
+            .collect()
+    }
+}
+
+/// The tries of the forked network at the forked block, of which only the nodes that have been
+/// fetched are known.
+///
+/// The lock on the fetched nodes is never held while waiting for the forked network.
+#[derive(Debug)]
+struct RemoteTries {
+    backend: BackendClient,
+    fetched: Mutex<FetchedTries>,
+}
+
+#[derive(Debug)]
+struct FetchedTries {
+    /// The roots of the classes and contracts tries.
+    roots: Option<(Felt, Felt)>,
+    classes: FetchedNodes,
+    contracts: FetchedNodes,
+    /// The class hash and nonce of the contracts whose proofs have been fetched.
+    leaves: HashMap<ContractAddress, (ClassHash, Nonce)>,
+    storages: HashMap<ContractAddress, RemoteStorage>,
+}
+
+impl FetchedTries {
+    /// Returns the fetched nodes of the storage trie of a contract.
+    fn storage(&self, address: ContractAddress) -> ProviderResult<&RemoteStorage> {
+        self.storages.get(&address).ok_or(ProviderError::MissingForkedStorageProof(address))
+    }
+}
+
+#[derive(Debug)]
+struct FetchedNodes {
+    nodes: MultiProof,
+    /// The keys whose paths have been fetched.
+    keys: HashSet<Felt>,
+}
+
+impl FetchedNodes {
+    fn new() -> Self {
+        Self { nodes: MultiProof(Default::default()), keys: HashSet::new() }
+    }
+
+    fn insert(&mut self, keys: impl IntoIterator<Item = Felt>, proof: MultiProof) {
+        self.keys.extend(keys);
+        self.nodes.0.extend(proof.0);
+    }
+}
+
+#[derive(Debug)]
+struct RemoteStorage {
+    root: Felt,
+    fetched: FetchedNodes,
+}
+
+impl RemoteTries {
+    fn new(backend: BackendClient) -> Self {
+        let fetched = FetchedTries {
+            roots: None,
+            classes: FetchedNodes::new(),
+            contracts: FetchedNodes::new(),
+            leaves: HashMap::new(),
+            storages: HashMap::new(),
+        };
+        Self { backend, fetched: Mutex::new(fetched) }
+    }
+
+    fn classes_trie(&self) -> ProviderResult<PartialTrie<Poseidon>> {
+        let (root, _) = self.roots()?;
+        let mut trie = PartialTrie::new(root);
+        self.reveal(&mut trie, RemoteTrie::Classes)?;
+        Ok(trie)
+    }
+
+    fn contracts_trie(&self) -> ProviderResult<PartialTrie<Pedersen>> {
+        let (_, root) = self.roots()?;
+        let mut trie = PartialTrie::new(root);
+        self.reveal(&mut trie, RemoteTrie::Contracts)?;
+        Ok(trie)
+    }
+
+    fn storage_trie(&self, address: ContractAddress) -> ProviderResult<PartialTrie<Pedersen>> {
+        let mut trie = PartialTrie::new(self.storage_root(address)?);
+        self.reveal(&mut trie, RemoteTrie::Storage(address))?;
+        Ok(trie)
+    }
+
+    fn storage_root(&self, address: ContractAddress) -> ProviderResult<Felt> {
+        // the root of the storage trie is only known once a proof of the trie has been fetched
+        let fetched = self.fetched.lock().storages.contains_key(&address);
+        if !fetched {
+            let storages = vec![ContractStorageKeys { address, keys: vec![Felt::ZERO] }];
+            self.request(ProofRequest { storages, ..Default::default() })?;
+        }
+        Ok(self.fetched.lock().storage(address)?.root)
+    }
+
+    fn roots(&self) -> ProviderResult<(Felt, Felt)> {
+        let roots = self.fetched.lock().roots;
+        match roots {
+            Some(roots) => Ok(roots),
+            None => self.request(ProofRequest::default()),
+        }
+    }
+
+    /// Reveals the nodes of a trie of the forked network that have been fetched so far.
+    fn reveal<H: StarkHash>(
+        &self,
+        trie: &mut PartialTrie<H>,
+        remote: RemoteTrie,
+    ) -> ProviderResult<()> {
+        let fetched = self.fetched.lock();
+        let nodes = match remote {
+            RemoteTrie::Classes => &fetched.classes.nodes,
+            RemoteTrie::Contracts => &fetched.contracts.nodes,
+            RemoteTrie::Storage(address) => &fetched.storage(address)?.fetched.nodes,
+        };
+        trie.reveal(nodes);
+        Ok(())
+    }
+
+    fn insert_classes(
+        &self,
+        trie: &mut PartialTrie<Poseidon>,
+        classes: &BTreeMap<ClassHash, CompiledClassHash>,
+    ) -> ProviderResult<()> {
+        self.fetch(ProofRequest {
+            classes: classes.keys().copied().collect(),
+            ..Default::default()
+        })?;
+        self.reveal(trie, RemoteTrie::Classes)?;
+
+        for (class_hash, compiled_hash) in classes {
+            let value = compute_classes_trie_value(*compiled_hash);
+            self.retry(trie, RemoteTrie::Classes, |trie| trie.insert(*class_hash, value))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_contract_updates(
+        &self,
+        contracts: &mut ContractsOverlay,
+        state_updates: &StateUpdates,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeSet::new();
+        addresses.extend(state_updates.nonce_updates.keys());
+        addresses.extend(state_updates.storage_updates.keys());
+        addresses.extend(state_updates.deployed_contracts.keys());
+        addresses.extend(state_updates.replaced_classes.keys());
+
+        // fetch the paths of all the updated leaves at once
+        let storages = addresses
+            .iter()
+            .map(|address| {
+                let keys = state_updates.storage_updates.get(address);
+                let keys =
+                    keys.map(|entries| entries.keys().copied().collect()).unwrap_or_default();
+                ContractStorageKeys { address: *address, keys }
+            })
+            .collect();
+
+        let contracts_keys = addresses.iter().copied().collect();
+        self.fetch(ProofRequest { classes: Vec::new(), contracts: contracts_keys, storages })?;
+        self.reveal(&mut contracts.trie, RemoteTrie::Contracts)?;
+
+        for address in addresses {
+            let contract = match contracts.leaves.entry(address) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let leaf = self.fetched.lock().leaves.get(&address).copied();
+                    let (class_hash, nonce) = leaf.unwrap_or_default();
+                    let storage = self.storage_trie(address)?;
+                    entry.insert(UpdatedContract { class_hash, nonce, storage })
+                }
+            };
+
+            if let Some(nonce) = state_updates.nonce_updates.get(&address) {
+                contract.nonce = *nonce;
+            }
+
+            if let Some(class_hash) = state_updates.deployed_contracts.get(&address) {
+                contract.class_hash = *class_hash;
+            }
+
+            if let Some(class_hash) = state_updates.replaced_classes.get(&address) {
+                contract.class_hash = *class_hash;
+            }
+
+            if let Some(entries) = state_updates.storage_updates.get(&address) {
+                let storage = RemoteTrie::Storage(address);
+                self.reveal(&mut contract.storage, storage)?;
+
+                for (key, value) in entries {
+                    self.retry(&mut contract.storage, storage, |trie| trie.insert(*key, *value))?;
+                }
+            }
+
+            let storage_root = contract.storage.root();
+            let leaf =
+                compute_contract_state_hash(&contract.class_hash, &storage_root, &contract.nonce);
+
+            let key = Felt::from(address);
+            self.retry(&mut contracts.trie, RemoteTrie::Contracts, |trie| trie.insert(key, leaf))?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs an operation on a trie, fetching the nodes that the operation needs from the forked
+    /// network until it succeeds.
+    fn retry<H: StarkHash, T>(
+        &self,
+        trie: &mut PartialTrie<H>,
+        remote: RemoteTrie,
+        mut op: impl FnMut(&mut PartialTrie<H>) -> Result<T, PartialTrieError>,
+    ) -> ProviderResult<T> {
+        let mut last_key = None;
+
+        loop {
+            match op(trie) {
+                Ok(value) => return Ok(value),
+
+                // if the node is still missing after fetching its path, the forked network
+                // didn't include it in the proof
+                Err(PartialTrieError::UnrevealedNode(key)) if last_key != Some(key) => {
+                    last_key = Some(key);
+
+                    let request = match remote {
+                        RemoteTrie::Classes => {
+                            ProofRequest { classes: vec![key], ..Default::default() }
+                        }
+                        RemoteTrie::Contracts => {
+                            ProofRequest { contracts: vec![key.into()], ..Default::default() }
+                        }
+                        RemoteTrie::Storage(address) => {
+                            let storages = vec![ContractStorageKeys { address, keys: vec![key] }];
+                            ProofRequest { storages, ..Default::default() }
+                        }
+                    };
+
+                    self.request(request)?;
+                    self.reveal(trie, remote)?;
+                }
+
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    /// Fetches the paths of the keys that haven't been fetched yet, in as few requests as
+    /// possible.
+    fn fetch(&self, request: ProofRequest) -> ProviderResult<()> {
+        let request = {
+            let fetched = self.fetched.lock();
+
+            let classes = request.classes.into_iter();
+            let classes = classes.filter(|key| !fetched.classes.keys.contains(key)).collect();
+
+            let contracts = request.contracts.into_iter();
+            let contracts = contracts
+                .filter(|address| !fetched.contracts.keys.contains(&Felt::from(*address)))
+                .collect();
+
+            let mut storages = Vec::new();
+            for ContractStorageKeys { address, keys } in request.storages {
+                let mut keys = match fetched.storages.get(&address) {
+                    Some(storage) => {
+                        keys.into_iter().filter(|k| !storage.fetched.keys.contains(k)).collect()
+                    }
+                    None => keys,
+                };
+
+                // the storage root is needed even if none of the keys are updated
+                if keys.is_empty() && !fetched.storages.contains_key(&address) {
+                    keys.push(Felt::ZERO);
+                }
+
+                if !keys.is_empty() {
+                    storages.push(ContractStorageKeys { address, keys });
+                }
+            }
+
+            ProofRequest { classes, contracts, storages }
+        };
+
+        for request in request.split() {
+            self.request(request)?;
+        }
+
+        Ok(())
+    }
+
+    /// Requests the proofs of the given keys from the forked network, and returns the roots of its
+    /// classes and contracts tries.
+    fn request(&self, request: ProofRequest) -> ProviderResult<(Felt, Felt)> {
+        let ProofRequest { classes, contracts, storages } = request;
+        let response =
+            self.backend.get_storage_proof(classes.clone(), contracts.clone(), storages.clone())?;
+
+        let roots = response.global_roots;
+        let roots = (roots.classes_tree_root, roots.contracts_tree_root);
+
+        let mut fetched = self.fetched.lock();
+        fetched.roots.get_or_insert(roots);
+
+        fetched.classes.insert(classes, response.classes_proof.nodes.into());
+
+        let leaves = response.contracts_proof.contract_leaves_data;
+        for (address, leaf) in contracts.iter().zip(leaves) {
+            fetched.leaves.insert(*address, (leaf.class_hash, leaf.nonce));
+        }
+
+        let keys = contracts.into_iter().map(Felt::from);
+        fetched.contracts.insert(keys, response.contracts_proof.nodes.into());
+
+        let proofs = response.contracts_storage_proofs.nodes;
+        for (ContractStorageKeys { address, keys }, nodes) in storages.into_iter().zip(proofs) {
+            let proof = MultiProof::from(nodes);
+            let storage = fetched.storages.entry(address).or_insert_with(|| RemoteStorage {
+                root: proof_root(&proof),
+                fetched: FetchedNodes::new(),
+            });
+            storage.fetched.insert(keys, proof);
+        }
+
+        Ok(roots)
+    }
+}
+
+/// Returns the root of the trie that the proof was generated from, ie the only node of the proof
+/// that isn't a child of another node, or zero if the trie is empty.
+fn proof_root(proof: &MultiProof) -> Felt {
+    let children = proof
+        .0
+        .values()
+        .flat_map(|node| match node {
+            ProofNode::Binary { left, right } => vec![*left, *right],
+            ProofNode::Edge { child, .. } => vec![*child],
+        })
+        .collect::<HashSet<_>>();
+
+    proof.0.keys().find(|hash| !children.contains(*hash)).copied().unwrap_or(Felt::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::{address, felt, Felt};
+    use katana_rpc_types::trie::ContractStorageKeys;
+
+    use super::{ProofRequest, MAX_PROOF_KEYS};
+
+    #[test]
+    fn split_proof_request() {
+        let storage_keys = (0..MAX_PROOF_KEYS as u64).map(Felt::from).collect::<Vec<_>>();
+        let request = ProofRequest {
+            classes: vec![felt!("0x1")],
+            contracts: vec![address!("0x2")],
+            storages: vec![
+                ContractStorageKeys { address: address!("0x2"), keys: storage_keys.clone() },
+                ContractStorageKeys { address: address!("0x3"), keys: vec![felt!("0x4")] },
+            ],
+        };
+
+        let requests = request.split();
+        assert_eq!(requests.len(), 2);
+
+        // the keys of a single contract can be split across requests
+        assert_eq!(requests[0].classes, vec![felt!("0x1")]);
+        assert_eq!(requests[0].contracts, vec![address!("0x2")]);
+        assert_eq!(requests[0].storages.len(), 1);
+        assert_eq!(requests[0].storages[0].keys, storage_keys[..MAX_PROOF_KEYS - 2]);
+
+        assert!(requests[1].classes.is_empty());
+        assert!(requests[1].contracts.is_empty());
+        assert_eq!(requests[1].storages.len(), 2);
+        assert_eq!(requests[1].storages[0].address, address!("0x2"));
+        assert_eq!(requests[1].storages[0].keys, storage_keys[MAX_PROOF_KEYS - 2..]);
+        assert_eq!(requests[1].storages[1].address, address!("0x3"));
+        assert_eq!(requests[1].storages[1].keys, vec![felt!("0x4")]);
     }
 }
@@ -0,0 +1,13 @@
+use katana_db::models::account::DevAccount;
+use katana_primitives::ContractAddress;
+
+use crate::ProviderResult;
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait DevAccountProvider: Send + Sync {
+    /// Returns all the development accounts created at runtime, sorted by address.
+    fn dev_accounts(&self) -> ProviderResult<Vec<(ContractAddress, DevAccount)>>;
+
+    /// Records a development account, replacing any previous record for the same address.
+    fn set_dev_account(&self, address: ContractAddress, account: DevAccount) -> ProviderResult<()>;
+}
@@ -150,4 +150,8 @@ pub trait BlockWriter: Send + Sync {
         receipts: Vec<Receipt>,
         executions: Vec<TypedTransactionExecutionInfo>,
     ) -> ProviderResult<()>;
+
+    /// Removes every block after `block`, along with their transactions and state changes, so
+    /// that `block` becomes the latest block.
+    fn unwind_to(&self, block: BlockNumber) -> ProviderResult<()>;
 }
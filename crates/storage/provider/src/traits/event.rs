@@ -0,0 +1,35 @@
+use katana_db::models::event::EventPosition;
+use katana_primitives::block::BlockNumber;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::Felt;
+
+use crate::ProviderResult;
+
+/// Provides access to the event index, which locates the events of the chain by the contract that
+/// emitted them and by their first key.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait EventIndexProvider: Send + Sync {
+    /// Returns the number of the last block whose events have been indexed, or `None` if the
+    /// index hasn't been built. All the blocks before the returned block are indexed as well.
+    fn event_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns, in ascending order, the positions of at most `limit` events emitted by `address`
+    /// starting from `from` (inclusive) up to the end of block `to_block`.
+    fn events_by_address(
+        &self,
+        address: ContractAddress,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>>;
+
+    /// Returns, in ascending order, the positions of at most `limit` events whose first key is
+    /// `key` starting from `from` (inclusive) up to the end of block `to_block`.
+    fn events_by_key(
+        &self,
+        key: Felt,
+        from: EventPosition,
+        to_block: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<EventPosition>>;
+}
@@ -0,0 +1,12 @@
+use katana_db::models::fork::ForkInfo;
+
+use crate::ProviderResult;
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait ForkInfoProvider: Send + Sync {
+    /// Returns the chain that the database was forked from, if any.
+    fn fork_info(&self) -> ProviderResult<Option<ForkInfo>>;
+
+    /// Records the chain that the database was forked from, replacing any previous record.
+    fn set_fork_info(&self, info: ForkInfo) -> ProviderResult<()>;
+}
@@ -2,9 +2,12 @@
 //! a way to restrict certain operations/functions from being accessed at certain parts of the
 //! program.
 
+pub mod account;
 pub mod block;
 pub mod contract;
 pub mod env;
+pub mod event;
+pub mod fork;
 pub mod stage;
 pub mod state;
 pub mod state_update;
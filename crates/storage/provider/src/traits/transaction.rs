@@ -74,6 +74,17 @@ pub trait TransactionTraceProvider: Send + Sync {
     ) -> ProviderResult<Vec<TypedTransactionExecutionInfo>>;
 }
 
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait TransactionTraceWriter: Send + Sync {
+    /// Stores the execution traces of the transactions of an existing block, in the same order as
+    /// the transactions. The existing traces of the block are overwritten.
+    fn insert_transaction_executions(
+        &self,
+        block: BlockNumber,
+        executions: Vec<TypedTransactionExecutionInfo>,
+    ) -> ProviderResult<()>;
+}
+
 #[auto_impl::auto_impl(&, Box, Arc)]
 pub trait ReceiptProvider: Send + Sync {
     /// Returns the transaction receipt given a transaction hash.
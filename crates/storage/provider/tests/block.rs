@@ -14,6 +14,7 @@ use katana_provider::traits::state::{StateFactoryProvider, StateRootProvider};
 use katana_provider::traits::state_update::StateUpdateProvider;
 use katana_provider::traits::transaction::{
     ReceiptProvider, TransactionProvider, TransactionStatusProvider, TransactionTraceProvider,
+    TransactionTraceWriter,
 };
 use katana_provider::BlockchainProvider;
 use rstest_reuse::{self, *};
@@ -93,6 +94,7 @@ where
             l1_gas_prices: expected_block.header.l1_gas_prices.clone(),
             l1_data_gas_prices: expected_block.header.l1_data_gas_prices.clone(),
             sequencer_address: expected_block.header.sequencer_address,
+            l1_da_mode: expected_block.header.l1_da_mode,
         };
 
         let actual_block_hash = provider.block_hash_by_num(expected_block_num)?;
@@ -201,6 +203,7 @@ where
             l1_gas_prices: expected_block.header.l1_gas_prices.clone(),
             l1_data_gas_prices: expected_block.header.l1_data_gas_prices.clone(),
             sequencer_address: expected_block.header.sequencer_address,
+            l1_da_mode: expected_block.header.l1_da_mode,
         };
 
         let actual_block_hash = provider.block_hash_by_num(expected_block_num)?;
@@ -256,6 +259,29 @@ where
     Ok(())
 }
 
+#[test]
+fn insert_transaction_executions_with_db_provider() -> Result<()> {
+    let provider = db_provider();
+    let blocks = utils::generate_dummy_blocks_and_receipts(5);
+
+    // the blocks are stored without their traces, which are only added afterwards
+    for (block, receipts, _) in &blocks {
+        let states = Default::default();
+        let receipts = receipts.clone();
+        provider.insert_block_with_states_and_receipts(block.clone(), states, receipts, vec![])?;
+    }
+
+    for (block, _, executions) in blocks {
+        let number = block.block.header.number;
+        assert_eq!(provider.transaction_executions_by_block(number.into())?, Some(vec![]));
+
+        provider.insert_transaction_executions(number, executions.clone())?;
+        assert_eq!(provider.transaction_executions_by_block(number.into())?, Some(executions));
+    }
+
+    Ok(())
+}
+
 #[apply(test_read_state_update)]
 fn test_read_state_update_with_db_provider(
     #[with(db_provider())] provider: BlockchainProvider<DbProvider>,
@@ -49,8 +49,7 @@ pub mod fork {
         #[default(0)] block_num: u64,
     ) -> BlockchainProvider<ForkedProvider> {
         let provider = JsonRpcClient::new(HttpTransport::new(Url::parse(rpc).unwrap()));
-        let provider =
-            ForkedProvider::new_ephemeral(BlockHashOrNumber::Num(block_num), Arc::new(provider));
+        let provider = ForkedProvider::new_ephemeral(block_num, Arc::new(provider));
         BlockchainProvider::new(provider)
     }
 
@@ -58,10 +57,7 @@ pub mod fork {
     pub fn fork_provider_with_spawned_fork_network(
         #[default(0)] block_num: u64,
     ) -> BlockchainProvider<ForkedProvider> {
-        let provider = ForkedProvider::new_ephemeral(
-            BlockHashOrNumber::Num(block_num),
-            FORKED_PROVIDER.1.clone(),
-        );
+        let provider = ForkedProvider::new_ephemeral(block_num, FORKED_PROVIDER.1.clone());
         BlockchainProvider::new(provider)
     }
 }
@@ -79,6 +79,11 @@ impl<P> Pipeline<P> {
     pub fn handle(&self) -> PipelineHandle {
         PipelineHandle { tx: self.tip_watcher.1.clone() }
     }
+
+    /// Returns the ids of the stages in the pipeline, in the order they are executed.
+    pub fn stage_ids(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.id()).collect()
+    }
 }
 
 impl<P: StageCheckpointProvider> Pipeline<P> {
@@ -122,11 +127,11 @@ impl<P: StageCheckpointProvider> Pipeline<P> {
         for (i, stage) in self.stages.iter_mut().enumerate() {
             let id = stage.id();
 
-            // Get the checkpoint for the stage, otherwise default to block number 0
-            let checkpoint = self.provider.checkpoint(id)?.unwrap_or_default();
+            // Get the checkpoint for the stage, the stage hasn't been executed yet if there is none
+            let checkpoint = self.provider.checkpoint(id)?;
 
             // Skip the stage if the checkpoint is greater than or equal to the target block number
-            if checkpoint >= to {
+            if let Some(checkpoint) = checkpoint.filter(|checkpoint| *checkpoint >= to) {
                 info!(target: "pipeline", %id, "Skipping stage.");
 
                 if i == last_stage_idx {
@@ -136,14 +141,17 @@ impl<P: StageCheckpointProvider> Pipeline<P> {
                 continue;
             }
 
-            info!(target: "pipeline", %id, from = %checkpoint, %to, "Executing stage.");
+            // plus 1 because the checkpoint is inclusive, or start from the genesis block if the
+            // stage has never been executed
+            let from = checkpoint.map_or(0, |checkpoint| checkpoint + 1);
+
+            info!(target: "pipeline", %id, %from, %to, "Executing stage.");
 
-            // plus 1 because the checkpoint is inclusive
-            let input = StageExecutionInput { from: checkpoint + 1, to };
+            let input = StageExecutionInput { from, to };
             stage.execute(&input).await?;
             self.provider.set_checkpoint(id, to)?;
 
-            info!(target: "pipeline", %id, from = %checkpoint, %to, "Stage execution completed.");
+            info!(target: "pipeline", %id, %from, %to, "Stage execution completed.");
         }
 
         Ok(to)
@@ -230,4 +238,34 @@ mod tests {
         let actual_checkpoint = provider.checkpoint("Mock").unwrap();
         assert_eq!(actual_checkpoint, Some(10));
     }
+
+    /// A stage that records the range of blocks of every execution.
+    #[derive(Clone, Default)]
+    struct RecordingStage(std::sync::Arc<std::sync::Mutex<Vec<(u64, u64)>>>);
+
+    #[async_trait::async_trait]
+    impl Stage for RecordingStage {
+        fn id(&self) -> &'static str {
+            "Recording"
+        }
+
+        async fn execute(&mut self, input: &StageExecutionInput) -> StageResult {
+            self.0.lock().unwrap().push((input.from, input.to));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn first_execution_starts_from_genesis() {
+        let provider = test_provider();
+        let stage = RecordingStage::default();
+
+        let (mut pipeline, _handle) = Pipeline::new(&provider, 10);
+        pipeline.add_stage(stage.clone());
+
+        pipeline.run_once_until(5).await.expect("failed to run the pipeline once");
+        pipeline.run_once_until(10).await.expect("failed to run the pipeline once");
+
+        assert_eq!(*stage.0.lock().unwrap(), vec![(0, 5), (6, 10)]);
+    }
 }
@@ -5,7 +5,7 @@ use anyhow::Result;
 use backon::{ExponentialBuilder, Retryable};
 use katana_feeder_gateway::client;
 use katana_feeder_gateway::client::SequencerGateway;
-use katana_feeder_gateway::types::StateUpdateWithBlock;
+use katana_feeder_gateway::types::{BlockStatus, StateUpdateWithBlock};
 use katana_primitives::block::{
     BlockIdOrTag, BlockNumber, FinalityStatus, GasPrice, Header, SealedBlock, SealedBlockWithStatus,
 };
@@ -19,7 +19,6 @@ use katana_primitives::Felt;
 use katana_provider::traits::block::BlockWriter;
 use num_traits::ToPrimitive;
 use starknet::core::types::ResourcePrice;
-use starknet::providers::sequencer::models::BlockStatus;
 use tracing::{debug, error, warn};
 
 use super::{Stage, StageExecutionInput, StageResult};
@@ -60,6 +59,8 @@ impl<P: BlockWriter> Stage for Blocks<P> {
             for block in blocks {
                 let (block, receipts, state_updates) = extract_block_data(block)?;
 
+                // The execution traces aren't provided by the feeder gateway. They are only stored
+                // once the blocks are re-executed by the `Verification` stage.
                 self.provider.insert_block_with_states_and_receipts(
                     block,
                     state_updates,
@@ -158,7 +159,7 @@ fn extract_block_data(
     data: StateUpdateWithBlock,
 ) -> Result<(SealedBlockWithStatus, Vec<Receipt>, StateUpdatesWithClasses)> {
     fn to_gas_prices(prices: ResourcePrice) -> GasPrice {
-        let eth = prices.price_in_fri.to_u128().expect("valid u128");
+        let eth = prices.price_in_wei.to_u128().expect("valid u128");
         let strk = prices.price_in_fri.to_u128().expect("valid u128");
         unsafe { GasPrice::new_unchecked(eth, strk) }
     }
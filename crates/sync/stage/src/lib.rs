@@ -6,10 +6,12 @@ use katana_provider::error::ProviderError;
 mod blocks;
 mod classes;
 mod sequencing;
+mod verification;
 
 pub use blocks::Blocks;
 pub use classes::Classes;
 pub use sequencing::Sequencing;
+pub use verification::Verification;
 
 /// The result type of a stage execution. See [Stage::execute].
 pub type StageResult = Result<(), Error>;
@@ -38,6 +40,11 @@ pub enum Error {
     #[error(transparent)]
     Classes(#[from] classes::Error),
 
+    /// Errors that could happen during the execution of the
+    /// [`Verification`](verification::Verification) stage.
+    #[error(transparent)]
+    Verification(#[from] verification::Error),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
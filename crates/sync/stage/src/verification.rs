@@ -0,0 +1,683 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use katana_executor::{ExecutionResult, ExecutorError, ExecutorFactory};
+use katana_primitives::block::BlockNumber;
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::execution::TypedTransactionExecutionInfo;
+use katana_primitives::hash::{self, StarkHash};
+use katana_primitives::receipt::{Event, MessageToL1, Receipt};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::transaction::{
+    DeclareTxWithClass, ExecutableTx, ExecutableTxWithHash, Tx, TxHash, TxWithHash,
+};
+use katana_primitives::{address, Felt};
+use katana_provider::error::ProviderError;
+use katana_provider::traits::block::{BlockHashProvider, HeaderProvider};
+use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::state::{StateFactoryProvider, StateProvider};
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::{
+    ReceiptProvider, TransactionProvider, TransactionTraceWriter,
+};
+use katana_provider::traits::trie::TrieWriter;
+use starknet::macros::short_string;
+use tracing::debug;
+
+use super::{Stage, StageExecutionInput, StageResult};
+
+/// The number of blocks between a block and the block whose hash it stores in the block hash
+/// registry contract (ie address `0x1`).
+const STORED_BLOCK_HASH_BUFFER: u64 = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("missing {data} for block {block}")]
+    MissingBlockData {
+        /// The block whose data is missing.
+        block: BlockNumber,
+        /// The data that is missing.
+        data: &'static str,
+    },
+
+    #[error("missing class {class_hash:#x} declared in transaction {tx_hash:#x}")]
+    MissingClass { tx_hash: TxHash, class_hash: ClassHash },
+
+    #[error("unsupported transaction type in transaction {0:#x}")]
+    UnsupportedTransaction(TxHash),
+
+    #[error("block {block} has {receipts} receipts but {transactions} executed transactions")]
+    ReceiptCountMismatch {
+        /// The block whose receipts don't match its transactions.
+        block: BlockNumber,
+        /// The number of executed transactions.
+        transactions: usize,
+        /// The number of stored receipts.
+        receipts: usize,
+    },
+
+    /// The locally computed data of a block doesn't match the downloaded one.
+    #[error(transparent)]
+    Divergence(Box<DivergenceReport>),
+
+    #[error(transparent)]
+    Executor(#[from] ExecutorError),
+
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// A difference between the downloaded data of a block and the data computed locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// The computed global state root is different from the one in the block header.
+    StateRoot { expected: Felt, computed: Felt },
+
+    /// The transaction was rejected (ie couldn't be included in the block) when re-executed.
+    Rejected { tx_hash: TxHash, error: String },
+
+    /// The transaction reverted in only one of the two executions.
+    RevertStatus { tx_hash: TxHash, expected: bool, computed: bool },
+
+    /// The transaction was charged a different fee.
+    ActualFee { tx_hash: TxHash, expected: u128, computed: u128 },
+
+    /// The transaction emitted different events.
+    Events { tx_hash: TxHash, expected: Vec<Event>, computed: Vec<Event> },
+
+    /// The transaction sent different L2 to L1 messages.
+    Messages { tx_hash: TxHash, expected: Vec<MessageToL1>, computed: Vec<MessageToL1> },
+
+    /// The state diff of the block is different.
+    StateDiff(StateDiffMismatch),
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StateRoot { expected, computed } => {
+                write!(f, "state root: expected {expected:#x}, computed {computed:#x}")
+            }
+            Self::Rejected { tx_hash, error } => {
+                write!(f, "transaction {tx_hash:#x} rejected: {error}")
+            }
+            Self::RevertStatus { tx_hash, expected, computed } => write!(
+                f,
+                "transaction {tx_hash:#x} reverted: expected {expected}, computed {computed}"
+            ),
+            Self::ActualFee { tx_hash, expected, computed } => write!(
+                f,
+                "transaction {tx_hash:#x} actual fee: expected {expected}, computed {computed}"
+            ),
+            Self::Events { tx_hash, expected, computed } => write!(
+                f,
+                "transaction {tx_hash:#x} events: expected {} events, computed {} events",
+                expected.len(),
+                computed.len()
+            ),
+            Self::Messages { tx_hash, expected, computed } => write!(
+                f,
+                "transaction {tx_hash:#x} messages: expected {} messages, computed {} messages",
+                expected.len(),
+                computed.len()
+            ),
+            Self::StateDiff(mismatch) => write!(f, "state diff: {mismatch}"),
+        }
+    }
+}
+
+/// The entries of a state diff that are different between the downloaded and the computed state
+/// diffs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiffMismatch {
+    /// Contracts whose nonce updates differ.
+    pub nonces: Vec<ContractAddress>,
+    /// Contracts whose storage updates differ.
+    pub storage: Vec<ContractAddress>,
+    /// Contracts whose deployment differ.
+    pub deployed_contracts: Vec<ContractAddress>,
+    /// Contracts whose class replacement differ.
+    pub replaced_classes: Vec<ContractAddress>,
+    /// Classes whose declaration differ.
+    pub declared_classes: Vec<ClassHash>,
+    /// Legacy classes whose declaration differ.
+    pub deprecated_declared_classes: Vec<ClassHash>,
+}
+
+impl StateDiffMismatch {
+    /// Returns the entries that differ between the two state diffs, or `None` if they are equal.
+    pub fn new(expected: &StateUpdates, computed: &StateUpdates) -> Option<Self> {
+        let mismatch = Self {
+            nonces: diff_keys(&expected.nonce_updates, &computed.nonce_updates),
+            storage: diff_keys(&expected.storage_updates, &computed.storage_updates),
+            deployed_contracts: diff_keys(
+                &expected.deployed_contracts,
+                &computed.deployed_contracts,
+            ),
+            replaced_classes: diff_keys(&expected.replaced_classes, &computed.replaced_classes),
+            declared_classes: diff_keys(&expected.declared_classes, &computed.declared_classes),
+            deprecated_declared_classes: expected
+                .deprecated_declared_classes
+                .symmetric_difference(&computed.deprecated_declared_classes)
+                .copied()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        };
+
+        if mismatch == Self::default() {
+            None
+        } else {
+            Some(mismatch)
+        }
+    }
+}
+
+impl fmt::Display for StateDiffMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: [(&str, Vec<Felt>); 6] = [
+            ("nonces", self.nonces.iter().map(|a| **a).collect()),
+            ("storage", self.storage.iter().map(|a| **a).collect()),
+            ("deployed contracts", self.deployed_contracts.iter().map(|a| **a).collect()),
+            ("replaced classes", self.replaced_classes.iter().map(|a| **a).collect()),
+            ("declared classes", self.declared_classes.clone()),
+            ("deprecated declared classes", self.deprecated_declared_classes.clone()),
+        ];
+
+        let mut first = true;
+        for (name, keys) in fields.iter().filter(|(_, keys)| !keys.is_empty()) {
+            let keys = keys.iter().map(|k| format!("{k:#x}")).collect::<Vec<_>>().join(", ");
+            write!(f, "{}{name} of [{keys}] differ", if first { "" } else { "; " })?;
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// Report of all the differences found while verifying a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergenceReport {
+    /// The block that diverged.
+    pub block: BlockNumber,
+    /// The differences found.
+    pub divergences: Vec<Divergence>,
+}
+
+impl fmt::Display for DivergenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block {} diverged from the downloaded data:", self.block)?;
+        for divergence in &self.divergences {
+            write!(f, "\n  - {divergence}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DivergenceReport {}
+
+/// Stage for verifying the integrity of the downloaded blocks.
+///
+/// For every block, the global state trie is updated with the block's state diff and the
+/// resulting state root is compared against the one in the block header. Optionally, the
+/// transactions of the block can be re-executed to make sure their receipts and the resulting
+/// state diff match the downloaded ones. The execution traces of the re-executed transactions
+/// are then stored, as they aren't part of the downloaded data.
+///
+/// This stage must run after the [`Blocks`](crate::Blocks) and [`Classes`](crate::Classes)
+/// stages, as it relies on the blocks and classes to already be in storage.
+#[derive(Debug)]
+pub struct Verification<P, EF> {
+    provider: P,
+    executor_factory: Option<Arc<EF>>,
+}
+
+impl<P, EF> Verification<P, EF> {
+    /// Creates a new stage that only verifies the state root of the blocks.
+    pub fn new(provider: P) -> Self {
+        Self { provider, executor_factory: None }
+    }
+
+    /// Re-executes the blocks using executors created by `executor_factory`.
+    ///
+    /// The results are only meaningful if the executor behaves like the one used to produce the
+    /// blocks (ie same protocol version).
+    pub fn with_execution(mut self, executor_factory: Arc<EF>) -> Self {
+        self.executor_factory = Some(executor_factory);
+        self
+    }
+}
+
+impl<P, EF> Verification<P, EF>
+where
+    P: HeaderProvider
+        + BlockHashProvider
+        + TransactionProvider
+        + ReceiptProvider
+        + StateUpdateProvider
+        + StateFactoryProvider
+        + BlockEnvProvider
+        + TransactionTraceWriter
+        + TrieWriter,
+    EF: ExecutorFactory,
+{
+    fn verify_block(&self, block: BlockNumber) -> Result<(), Error> {
+        let missing = |data| Error::MissingBlockData { block, data };
+
+        let header = self.provider.header(block.into())?.ok_or_else(|| missing("header"))?;
+        let state_updates =
+            self.provider.state_update(block.into())?.ok_or_else(|| missing("state update"))?;
+
+        let diverged =
+            |divergences| Error::Divergence(Box::new(DivergenceReport { block, divergences }));
+
+        let mut traces = None;
+
+        // The genesis block has no parent state to execute its transactions on.
+        if let (Some(factory), true) = (&self.executor_factory, block > 0) {
+            let (divergences, execution_traces) =
+                self.execute_block(factory, block, &state_updates)?;
+
+            // Checked before updating the tries, so that the block can be verified again without
+            // inserting its state diff twice.
+            if !divergences.is_empty() {
+                return Err(diverged(divergences));
+            }
+
+            traces = Some(execution_traces);
+        }
+
+        let state_root = self.compute_state_root(block, &state_updates)?;
+        if state_root != header.state_root {
+            let expected = header.state_root;
+            return Err(diverged(vec![Divergence::StateRoot { expected, computed: state_root }]));
+        }
+
+        if let Some(traces) = traces {
+            self.provider.insert_transaction_executions(block, traces)?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-executes the transactions of the block on top of its parent state, and compares the
+    /// results with the stored ones. Returns the differences found, and the execution traces of
+    /// the transactions.
+    fn execute_block(
+        &self,
+        factory: &EF,
+        block: BlockNumber,
+        expected_state_updates: &StateUpdates,
+    ) -> Result<(Vec<Divergence>, Vec<TypedTransactionExecutionInfo>), Error> {
+        let missing = |data| Error::MissingBlockData { block, data };
+
+        let block_env =
+            self.provider.block_env_at(block.into())?.ok_or_else(|| missing("block env"))?;
+        let txs = self
+            .provider
+            .transactions_by_block(block.into())?
+            .ok_or_else(|| missing("transactions"))?;
+        let receipts =
+            self.provider.receipts_by_block(block.into())?.ok_or_else(|| missing("receipts"))?;
+
+        let parent_block = block - 1;
+        let state = self
+            .provider
+            .historical(parent_block.into())?
+            .ok_or(Error::MissingBlockData { block: parent_block, data: "state" })?;
+
+        // The classes declared in this block aren't part of the parent state yet.
+        let latest_state = self.provider.latest()?;
+        let txs = txs
+            .into_iter()
+            .map(|tx| to_executable_tx(tx, latest_state.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut executor = factory.with_state_and_block_env(state, block_env);
+        if let (_, Some(error)) = executor.execute_transactions(txs)? {
+            return Err(error.into());
+        }
+
+        let output = executor.take_execution_output()?;
+
+        if output.transactions.len() != receipts.len() {
+            let (transactions, receipts) = (output.transactions.len(), receipts.len());
+            return Err(Error::ReceiptCountMismatch { block, transactions, receipts });
+        }
+
+        let mut divergences = Vec::new();
+        let mut traces = Vec::with_capacity(output.transactions.len());
+
+        for ((tx, result), expected) in output.transactions.into_iter().zip(receipts.iter()) {
+            match result {
+                ExecutionResult::Success { receipt, trace, state_diff } => {
+                    divergences.extend(compare_receipts(tx.hash, expected, &receipt));
+                    let trace = TypedTransactionExecutionInfo::new(receipt.r#type(), trace);
+                    traces.push(trace.with_state_diff(state_diff));
+                }
+                ExecutionResult::Failed { error } => {
+                    let error = error.to_string();
+                    divergences.push(Divergence::Rejected { tx_hash: tx.hash, error });
+                }
+            }
+        }
+
+        let mut computed_state_updates = output.states.state_updates;
+        self.update_block_hash_registry(block, &mut computed_state_updates)?;
+
+        if let Some(mismatch) =
+            StateDiffMismatch::new(expected_state_updates, &computed_state_updates)
+        {
+            divergences.push(Divergence::StateDiff(mismatch));
+        }
+
+        Ok((divergences, traces))
+    }
+
+    /// Inserts the state diff of the block into the tries, and returns the new global state root.
+    fn compute_state_root(
+        &self,
+        block: BlockNumber,
+        state_updates: &StateUpdates,
+    ) -> Result<Felt, Error> {
+        let state = self
+            .provider
+            .historical(block.into())?
+            .ok_or(Error::MissingBlockData { block, data: "state" })?;
+
+        // The contract leaves are computed using the latest state for the values that are not in
+        // the state diff. But the blocks after this one might have already been stored, so we
+        // explicitly include the values at this block for all the updated contracts.
+        let mut updates = state_updates.clone();
+
+        let contracts = updates
+            .storage_updates
+            .keys()
+            .chain(updates.nonce_updates.keys())
+            .chain(updates.deployed_contracts.keys())
+            .chain(updates.replaced_classes.keys())
+            .copied()
+            .collect::<HashSet<_>>();
+
+        for address in contracts {
+            if !updates.nonce_updates.contains_key(&address) {
+                if let Some(nonce) = state.nonce(address)? {
+                    updates.nonce_updates.insert(address, nonce);
+                }
+            }
+
+            if !updates.deployed_contracts.contains_key(&address)
+                && !updates.replaced_classes.contains_key(&address)
+            {
+                if let Some(class_hash) = state.class_hash_of_contract(address)? {
+                    updates.replaced_classes.insert(address, class_hash);
+                }
+            }
+        }
+
+        let class_trie_root =
+            self.provider.trie_insert_declared_classes(block, &updates.declared_classes)?;
+        let contract_trie_root = self.provider.trie_insert_contract_updates(block, &updates)?;
+
+        // https://docs.starknet.io/architecture-and-concepts/network-architecture/starknet-state/#state_commitment
+        if class_trie_root == Felt::ZERO {
+            Ok(contract_trie_root)
+        } else {
+            Ok(hash::Poseidon::hash_array(&[
+                short_string!("STARKNET_STATE_V0"),
+                contract_trie_root,
+                class_trie_root,
+            ]))
+        }
+    }
+
+    /// Adds the storage update of the block hash registry contract (ie address `0x1`) to the state
+    /// updates, as it's not the result of any transaction.
+    ///
+    /// https://docs.starknet.io/architecture-and-concepts/network-architecture/starknet-state/#address_0x1
+    fn update_block_hash_registry(
+        &self,
+        block: BlockNumber,
+        state_updates: &mut StateUpdates,
+    ) -> Result<(), Error> {
+        if block >= STORED_BLOCK_HASH_BUFFER {
+            let stored_block = block - STORED_BLOCK_HASH_BUFFER;
+            let hash = self
+                .provider
+                .block_hash_by_num(stored_block)?
+                .ok_or(Error::MissingBlockData { block: stored_block, data: "block hash" })?;
+
+            let storage = state_updates.storage_updates.entry(address!("0x1")).or_default();
+            storage.insert(stored_block.into(), hash);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<P, EF> Stage for Verification<P, EF>
+where
+    P: HeaderProvider
+        + BlockHashProvider
+        + TransactionProvider
+        + ReceiptProvider
+        + StateUpdateProvider
+        + StateFactoryProvider
+        + BlockEnvProvider
+        + TransactionTraceWriter
+        + TrieWriter,
+    EF: ExecutorFactory,
+{
+    fn id(&self) -> &'static str {
+        "Verification"
+    }
+
+    async fn execute(&mut self, input: &StageExecutionInput) -> StageResult {
+        debug!(target: "stage", id = %self.id(), from = %input.from, to = %input.to, "Verifying blocks.");
+
+        for block in input.from..=input.to {
+            self.verify_block(block)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a stored transaction into one that can be executed.
+fn to_executable_tx(
+    tx: TxWithHash,
+    state: &dyn StateProvider,
+) -> Result<ExecutableTxWithHash, Error> {
+    let transaction = match tx.transaction {
+        Tx::Invoke(tx) => ExecutableTx::Invoke(tx),
+        Tx::L1Handler(tx) => ExecutableTx::L1Handler(tx),
+        Tx::DeployAccount(tx) => ExecutableTx::DeployAccount(tx),
+        Tx::Declare(declare) => {
+            let class_hash = declare.class_hash();
+            let class = state
+                .class(class_hash)?
+                .ok_or(Error::MissingClass { tx_hash: tx.hash, class_hash })?;
+            ExecutableTx::Declare(DeclareTxWithClass::new(declare, class))
+        }
+        Tx::Deploy(_) => return Err(Error::UnsupportedTransaction(tx.hash)),
+    };
+
+    Ok(ExecutableTxWithHash { hash: tx.hash, transaction })
+}
+
+fn compare_receipts(tx_hash: TxHash, expected: &Receipt, computed: &Receipt) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+
+    if expected.is_reverted() != computed.is_reverted() {
+        let (expected, computed) = (expected.is_reverted(), computed.is_reverted());
+        divergences.push(Divergence::RevertStatus { tx_hash, expected, computed });
+    }
+
+    if expected.fee().overall_fee != computed.fee().overall_fee {
+        let (expected, computed) = (expected.fee().overall_fee, computed.fee().overall_fee);
+        divergences.push(Divergence::ActualFee { tx_hash, expected, computed });
+    }
+
+    if expected.events() != computed.events() {
+        let (expected, computed) = (expected.events().to_vec(), computed.events().to_vec());
+        divergences.push(Divergence::Events { tx_hash, expected, computed });
+    }
+
+    if expected.messages_sent() != computed.messages_sent() {
+        let expected = expected.messages_sent().to_vec();
+        let computed = computed.messages_sent().to_vec();
+        divergences.push(Divergence::Messages { tx_hash, expected, computed });
+    }
+
+    divergences
+}
+
+/// Returns the keys whose values are different in the two maps, in ascending order.
+fn diff_keys<K, V>(
+    expected: &std::collections::BTreeMap<K, V>,
+    computed: &std::collections::BTreeMap<K, V>,
+) -> Vec<K>
+where
+    K: Ord + Copy,
+    V: PartialEq,
+{
+    let keys = expected.keys().chain(computed.keys()).copied().collect::<BTreeSet<_>>();
+    keys.into_iter().filter(|k| expected.get(k) != computed.get(k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use katana_executor::implementation::noop::NoopExecutorFactory;
+    use katana_primitives::block::{Block, BlockNumber, FinalityStatus, Header};
+    use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
+    use katana_primitives::{address, felt, Felt};
+    use katana_provider::providers::db::DbProvider;
+    use katana_provider::test_utils::test_provider;
+    use katana_provider::traits::block::BlockWriter;
+    use katana_provider::traits::state::{StateFactoryProvider, StateRootProvider};
+
+    use super::{Divergence, Error, StateDiffMismatch, Verification};
+    use crate::{Stage, StageExecutionInput};
+
+    /// Creates a provider with a genesis block deploying a contract, followed by a block updating
+    /// its storage. The headers of the blocks have the given state roots.
+    fn provider_with_blocks(state_roots: [Felt; 2]) -> DbProvider {
+        let provider = DbProvider::new_ephemeral();
+
+        for (number, state_root) in state_roots.into_iter().enumerate() {
+            let number = number as BlockNumber;
+
+            let mut state_updates = StateUpdates::default();
+            if number == 0 {
+                state_updates.deployed_contracts.insert(address!("0x1337"), felt!("0x1"));
+            }
+            state_updates
+                .storage_updates
+                .entry(address!("0x1337"))
+                .or_default()
+                .insert(felt!("0x1"), Felt::from(number + 1));
+
+            let header = Header { number, state_root, ..Default::default() };
+            let block = Block { header, body: Vec::new() }
+                .seal_with_hash_and_status(Felt::from(number), FinalityStatus::AcceptedOnL2);
+            let states = StateUpdatesWithClasses { state_updates, ..Default::default() };
+
+            provider
+                .insert_block_with_states_and_receipts(block, states, Vec::new(), Vec::new())
+                .unwrap();
+        }
+
+        provider
+    }
+
+    /// Returns the state roots of the blocks of [`provider_with_blocks`], as computed by the stage.
+    async fn computed_state_roots() -> [Felt; 2] {
+        let provider = provider_with_blocks([Felt::ZERO; 2]);
+        let mut stage = Verification::<_, NoopExecutorFactory>::new(&provider);
+
+        let mut state_roots = [Felt::ZERO; 2];
+        for (block, state_root) in state_roots.iter_mut().enumerate() {
+            let input = StageExecutionInput { from: block as u64, to: block as u64 };
+            let err = stage.execute(&input).await.unwrap_err();
+
+            let crate::Error::Verification(Error::Divergence(report)) = err else {
+                panic!("expected a divergence error")
+            };
+            let [Divergence::StateRoot { computed, .. }] = report.divergences.as_slice() else {
+                panic!("expected a state root divergence")
+            };
+
+            *state_root = *computed;
+        }
+
+        state_roots
+    }
+
+    #[tokio::test]
+    async fn state_root_divergence() {
+        let provider = test_provider();
+        let mut stage = Verification::<_, NoopExecutorFactory>::new(&provider);
+
+        // the genesis block of the test provider has a zero state root
+        let input = StageExecutionInput { from: 0, to: 0 };
+        let err = stage.execute(&input).await.unwrap_err();
+
+        let crate::Error::Verification(Error::Divergence(report)) = err else {
+            panic!("expected a divergence error")
+        };
+
+        let computed = provider.latest().unwrap().state_root().unwrap();
+        assert_ne!(computed, Felt::ZERO);
+        assert_eq!(report.block, 0);
+        assert_eq!(
+            report.divergences,
+            vec![Divergence::StateRoot { expected: Felt::ZERO, computed }]
+        );
+    }
+
+    #[tokio::test]
+    async fn state_root_verified() {
+        let provider = provider_with_blocks(computed_state_roots().await);
+        let mut stage = Verification::<_, NoopExecutorFactory>::new(&provider);
+
+        let input = StageExecutionInput { from: 0, to: 1 };
+        stage.execute(&input).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn execution_divergence() {
+        let provider = provider_with_blocks(computed_state_roots().await);
+        let factory = Arc::new(NoopExecutorFactory::new());
+        let mut stage = Verification::new(&provider).with_execution(factory);
+
+        // the genesis block isn't executed
+        let input = StageExecutionInput { from: 0, to: 0 };
+        stage.execute(&input).await.unwrap();
+
+        // the noop executor doesn't produce any state changes, so the storage update of the block
+        // isn't reproduced, while its state root still matches
+        let input = StageExecutionInput { from: 1, to: 1 };
+        let err = stage.execute(&input).await.unwrap_err();
+
+        let crate::Error::Verification(Error::Divergence(report)) = err else {
+            panic!("expected a divergence error")
+        };
+
+        let mismatch =
+            StateDiffMismatch { storage: vec![address!("0x1337")], ..Default::default() };
+        assert_eq!(report.block, 1);
+        assert_eq!(report.divergences, vec![Divergence::StateDiff(mismatch.clone())]);
+
+        // the tries weren't updated, so the block can be verified again
+        let err = stage.execute(&input).await.unwrap_err();
+        let crate::Error::Verification(Error::Divergence(report)) = err else {
+            panic!("expected a divergence error")
+        };
+        assert_eq!(report.divergences, vec![Divergence::StateDiff(mismatch)]);
+    }
+}
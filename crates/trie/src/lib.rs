@@ -10,11 +10,13 @@ pub use {bitvec, bonsai_trie as bonsai};
 mod classes;
 mod contracts;
 mod id;
+mod partial;
 mod storages;
 
 pub use classes::*;
 pub use contracts::*;
 pub use id::CommitId;
+pub use partial::{PartialTrie, PartialTrieError};
 pub use storages::StoragesTrie;
 
 /// A lightweight shim for [`BonsaiStorage`].
@@ -0,0 +1,412 @@
+//! A Merkle-Patricia trie of which only some of the nodes are known.
+//!
+//! The trie starts from the root hash of another trie, and its nodes are revealed from the proofs
+//! of that trie. The nodes that haven't been revealed are only known by their hashes. Leaves can
+//! be inserted as long as the nodes along their paths are known, which allows computing the root
+//! and proofs of a trie with some changes applied on top of it, without having the whole trie.
+
+use std::marker::PhantomData;
+
+use bitvec::order::Msb0;
+use bitvec::slice::BitSlice;
+use bitvec::view::{AsBits, BitView};
+use katana_primitives::Felt;
+use starknet_types_core::hash::StarkHash;
+
+use crate::{BitVec, MultiProof, Path, ProofNode};
+
+/// The height of the Starknet tries.
+const HEIGHT: usize = 251;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PartialTrieError {
+    /// A node that hasn't been revealed is needed. The proof of the given key reveals it.
+    #[error("trie node along the path of key {0:#x} is not revealed")]
+    UnrevealedNode(Felt),
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    /// A node that is only known by its hash.
+    Unrevealed(Felt),
+    Leaf(Felt),
+    Binary {
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+    Edge {
+        path: BitVec,
+        child: Box<Node>,
+    },
+}
+
+/// A trie of which only the nodes revealed by proofs are known.
+#[derive(Debug, Clone)]
+pub struct PartialTrie<H> {
+    root: Node,
+    _hash: PhantomData<H>,
+}
+
+impl<H: StarkHash> PartialTrie<H> {
+    /// Creates a trie with the given root, of which none of the nodes are revealed yet.
+    pub fn new(root: Felt) -> Self {
+        let root = if root == Felt::ZERO { Node::Empty } else { Node::Unrevealed(root) };
+        Self { root, _hash: PhantomData }
+    }
+
+    /// Reveals the nodes of the trie that are included in the proof.
+    pub fn reveal(&mut self, proof: &MultiProof) {
+        reveal(&mut self.root, proof, 0);
+    }
+
+    pub fn root(&self) -> Felt {
+        hash::<H>(&self.root)
+    }
+
+    /// Inserts a leaf into the trie, or removes it if the value is zero.
+    ///
+    /// The trie is left unchanged if a node that is needed to insert the leaf hasn't been
+    /// revealed.
+    pub fn insert(&mut self, key: Felt, value: Felt) -> Result<(), PartialTrieError> {
+        let key = key_to_path(key);
+        match insert(&self.root, &key, 0, value) {
+            Ok(root) => {
+                self.root = root;
+                Ok(())
+            }
+            Err(prefix) => Err(PartialTrieError::UnrevealedNode(prefix_to_key(&prefix))),
+        }
+    }
+
+    /// Returns the proof of the given keys.
+    pub fn multiproof(&self, keys: Vec<Felt>) -> Result<MultiProof, PartialTrieError> {
+        let mut nodes = Vec::new();
+
+        for key in keys {
+            let path = key_to_path(key);
+            let mut node = &self.root;
+            let mut depth = 0;
+
+            loop {
+                match node {
+                    Node::Empty | Node::Leaf(..) => break,
+
+                    Node::Unrevealed(..) if depth == HEIGHT => break,
+                    Node::Unrevealed(..) => return Err(PartialTrieError::UnrevealedNode(key)),
+
+                    Node::Binary { left, right } => {
+                        let proof =
+                            ProofNode::Binary { left: hash::<H>(left), right: hash::<H>(right) };
+                        nodes.push((hash::<H>(node), proof));
+
+                        node = if path[depth] { right } else { left };
+                        depth += 1;
+                    }
+
+                    Node::Edge { path: edge, child } => {
+                        let proof =
+                            ProofNode::Edge { child: hash::<H>(child), path: Path(edge.clone()) };
+                        nodes.push((hash::<H>(node), proof));
+
+                        // the key is not in the trie if it diverges from the edge
+                        if path[depth..depth + edge.len()] != edge[..] {
+                            break;
+                        }
+
+                        node = child;
+                        depth += edge.len();
+                    }
+                }
+            }
+        }
+
+        Ok(MultiProof(nodes.into_iter().collect()))
+    }
+}
+
+fn reveal(node: &mut Node, proof: &MultiProof, depth: usize) {
+    match node {
+        // the hash of a leaf is its value
+        Node::Unrevealed(hash) if depth == HEIGHT => *node = Node::Leaf(*hash),
+
+        Node::Unrevealed(hash) => {
+            let Some(proof_node) = proof.0.get(hash) else { return };
+
+            *node = match proof_node {
+                ProofNode::Binary { left, right } => Node::Binary {
+                    left: Box::new(Node::Unrevealed(*left)),
+                    right: Box::new(Node::Unrevealed(*right)),
+                },
+                ProofNode::Edge { child, path } => {
+                    Node::Edge { path: path.0.clone(), child: Box::new(Node::Unrevealed(*child)) }
+                }
+            };
+
+            reveal(node, proof, depth);
+        }
+
+        Node::Binary { left, right } => {
+            reveal(left, proof, depth + 1);
+            reveal(right, proof, depth + 1);
+        }
+
+        Node::Edge { path, child } => reveal(child, proof, depth + path.len()),
+
+        Node::Empty | Node::Leaf(..) => {}
+    }
+}
+
+/// Returns the node with the leaf inserted, or the path of the node that needs to be revealed.
+fn insert(
+    node: &Node,
+    key: &BitSlice<u8, Msb0>,
+    depth: usize,
+    value: Felt,
+) -> Result<Node, BitVec> {
+    if depth == HEIGHT {
+        return Ok(if value == Felt::ZERO { Node::Empty } else { Node::Leaf(value) });
+    }
+
+    match node {
+        Node::Empty if value == Felt::ZERO => Ok(Node::Empty),
+        Node::Empty => Ok(edge(key[depth..].to_bitvec(), Node::Leaf(value))),
+
+        Node::Unrevealed(..) | Node::Leaf(..) => Err(key[..depth].to_bitvec()),
+
+        Node::Binary { left, right } => {
+            let (left, right) = if key[depth] {
+                (left.as_ref().clone(), insert(right, key, depth + 1, value)?)
+            } else {
+                (insert(left, key, depth + 1, value)?, right.as_ref().clone())
+            };
+
+            match (left, right) {
+                (Node::Empty, Node::Empty) => Ok(Node::Empty),
+                (Node::Empty, child) => collapse(&key[..depth], true, child),
+                (child, Node::Empty) => collapse(&key[..depth], false, child),
+                (left, right) => Ok(Node::Binary { left: Box::new(left), right: Box::new(right) }),
+            }
+        }
+
+        Node::Edge { path, child } => {
+            let rest = &key[depth..];
+            let common = path.iter().zip(rest.iter()).take_while(|(a, b)| a == b).count();
+
+            if common == path.len() {
+                let child = insert(child, key, depth + path.len(), value)?;
+                return Ok(edge(path.clone(), child));
+            }
+
+            // the key is not in the trie, so there is nothing to remove
+            if value == Felt::ZERO {
+                return Ok(node.clone());
+            }
+
+            // split the edge where the key diverges from it
+            let existing = edge(path[common + 1..].to_bitvec(), child.as_ref().clone());
+            let new = edge(rest[common + 1..].to_bitvec(), Node::Leaf(value));
+
+            let (left, right) = if path[common] { (new, existing) } else { (existing, new) };
+            let binary = Node::Binary { left: Box::new(left), right: Box::new(right) };
+
+            Ok(edge(path[..common].to_bitvec(), binary))
+        }
+    }
+}
+
+/// Replaces a binary node that is left with a single child by an edge to that child.
+fn collapse(prefix: &BitSlice<u8, Msb0>, bit: bool, child: Node) -> Result<Node, BitVec> {
+    // the child has to be known to be merged with the edge
+    if let Node::Unrevealed(..) = child {
+        let mut path = prefix.to_bitvec();
+        path.push(bit);
+        return Err(path);
+    }
+
+    let mut path = BitVec::new();
+    path.push(bit);
+    Ok(edge(path, child))
+}
+
+/// Creates an edge node, merging it with its child if the child is an edge as well.
+fn edge(mut path: BitVec, child: Node) -> Node {
+    match child {
+        Node::Empty => Node::Empty,
+        Node::Edge { path: child_path, child } => {
+            path.extend_from_bitslice(&child_path);
+            Node::Edge { path, child }
+        }
+        child if path.is_empty() => child,
+        child => Node::Edge { path, child: Box::new(child) },
+    }
+}
+
+fn hash<H: StarkHash>(node: &Node) -> Felt {
+    match node {
+        Node::Empty => Felt::ZERO,
+        Node::Unrevealed(hash) | Node::Leaf(hash) => *hash,
+        Node::Binary { left, right } => H::hash(&hash::<H>(left), &hash::<H>(right)),
+        Node::Edge { path, child } => {
+            H::hash(&hash::<H>(child), &path_to_felt(path)) + Felt::from(path.len())
+        }
+    }
+}
+
+fn key_to_path(key: Felt) -> BitVec {
+    key.to_bytes_be().as_bits::<Msb0>()[5..].to_bitvec()
+}
+
+/// Returns the first key whose path starts with the given prefix.
+fn prefix_to_key(prefix: &BitSlice<u8, Msb0>) -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes.view_bits_mut::<Msb0>()[5..5 + prefix.len()].copy_from_bitslice(prefix);
+    Felt::from_bytes_be(&bytes)
+}
+
+fn path_to_felt(path: &BitSlice<u8, Msb0>) -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes.view_bits_mut::<Msb0>()[256 - path.len()..].copy_from_bitslice(path);
+    Felt::from_bytes_be(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use bonsai_trie::databases::HashMapDb;
+    use katana_primitives::felt;
+    use starknet_types_core::hash::Pedersen;
+
+    use super::*;
+    use crate::{BonsaiTrie, CommitId};
+
+    const IDENTIFIER: &[u8] = b"1";
+
+    struct FullTrie {
+        trie: BonsaiTrie<HashMapDb<CommitId>, Pedersen>,
+        commits: u64,
+    }
+
+    impl FullTrie {
+        fn new(leaves: &[(Felt, Felt)]) -> Self {
+            let mut trie = Self { trie: BonsaiTrie::new(HashMapDb::default()), commits: 0 };
+            trie.insert(leaves);
+            trie
+        }
+
+        fn insert(&mut self, leaves: &[(Felt, Felt)]) {
+            for (key, value) in leaves {
+                self.trie.insert(IDENTIFIER, *key, *value);
+            }
+            self.trie.commit(CommitId::new(self.commits));
+            self.commits += 1;
+        }
+
+        fn root(&self) -> Felt {
+            self.trie.root(IDENTIFIER)
+        }
+
+        fn multiproof(&mut self, keys: &[Felt]) -> MultiProof {
+            self.trie.multiproof(IDENTIFIER, keys.to_vec())
+        }
+    }
+
+    fn leaves() -> Vec<(Felt, Felt)> {
+        (1..=20u64).map(|i| (Felt::from(i * 7), Felt::from(i * 100))).collect()
+    }
+
+    /// Inserts the leaves into the partial trie, revealing the missing nodes from the full trie.
+    fn insert(partial: &mut PartialTrie<Pedersen>, full: &mut FullTrie, leaves: &[(Felt, Felt)]) {
+        for (key, value) in leaves {
+            while let Err(PartialTrieError::UnrevealedNode(missing)) = partial.insert(*key, *value)
+            {
+                partial.reveal(&full.multiproof(&[missing]));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_trie() {
+        let mut partial = PartialTrie::<Pedersen>::new(Felt::ZERO);
+        let mut full = FullTrie::new(&[]);
+        assert_eq!(partial.root(), Felt::ZERO);
+
+        let leaves = leaves();
+        insert(&mut partial, &mut full, &leaves);
+        full.insert(&leaves);
+
+        assert_eq!(partial.root(), full.root());
+    }
+
+    #[test]
+    fn insert_revealed_leaves() {
+        let mut full = FullTrie::new(&leaves());
+        let updates = vec![
+            // existing leaf
+            (Felt::from(14u8), felt!("0x1337")),
+            // new leaves
+            (Felt::from(15u8), felt!("0x420")),
+            (felt!("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"), Felt::ONE),
+        ];
+
+        let keys = updates.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let mut partial = PartialTrie::<Pedersen>::new(full.root());
+        partial.reveal(&full.multiproof(&keys));
+
+        for (key, value) in &updates {
+            partial.insert(*key, *value).unwrap();
+        }
+
+        full.insert(&updates);
+        assert_eq!(partial.root(), full.root());
+    }
+
+    #[test]
+    fn remove_leaves() {
+        let mut full = FullTrie::new(&leaves());
+        let updates = vec![(Felt::from(7u8), Felt::ZERO), (Felt::from(70u8), Felt::ZERO)];
+
+        let mut partial = PartialTrie::<Pedersen>::new(full.root());
+        insert(&mut partial, &mut full, &updates);
+
+        full.insert(&updates);
+        assert_eq!(partial.root(), full.root());
+
+        // removing a leaf that doesn't exist doesn't change the trie
+        let root = partial.root();
+        insert(&mut partial, &mut full, &[(Felt::from(8u8), Felt::ZERO)]);
+        assert_eq!(partial.root(), root);
+    }
+
+    #[test]
+    fn unrevealed_node() {
+        let full = FullTrie::new(&leaves());
+        let mut partial = PartialTrie::<Pedersen>::new(full.root());
+
+        let err = partial.insert(Felt::from(7u8), Felt::ONE).unwrap_err();
+        assert_eq!(err, PartialTrieError::UnrevealedNode(Felt::ZERO));
+        assert_eq!(partial.root(), full.root());
+    }
+
+    #[test]
+    fn multiproof() {
+        let mut full = FullTrie::new(&leaves());
+        let updates = vec![(Felt::from(21u8), felt!("0x1337")), (Felt::from(22u8), Felt::ONE)];
+        let keys = vec![Felt::from(7u8), Felt::from(21u8), Felt::from(22u8), Felt::from(23u8)];
+
+        let mut partial = PartialTrie::<Pedersen>::new(full.root());
+        partial.reveal(&full.multiproof(&keys));
+        insert(&mut partial, &mut full, &updates);
+
+        let proof = partial.multiproof(keys.clone()).unwrap();
+        let values = crate::verify_proof::<Pedersen>(&proof, partial.root(), keys.clone());
+        assert_eq!(values, vec![Felt::from(100u8), felt!("0x1337"), Felt::ONE, Felt::ZERO]);
+
+        full.insert(&updates);
+        assert_eq!(partial.root(), full.root());
+
+        // the proof of a key whose path hasn't been revealed can't be computed
+        let key = Felt::from(140u8);
+        let result = partial.multiproof(vec![key]);
+        assert_eq!(result.unwrap_err(), PartialTrieError::UnrevealedNode(key));
+    }
+}
@@ -13,7 +13,7 @@ use katana_node::{LaunchedNode, Node};
 use katana_primitives::chain::ChainId;
 use katana_primitives::{address, ContractAddress};
 use katana_provider::BlockchainProvider;
-use katana_rpc::HttpClient;
+use katana_rpc::{HttpClient, WsClient};
 use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
 use starknet::core::types::BlockTag;
 pub use starknet::core::types::StarknetError;
@@ -96,11 +96,21 @@ impl TestNode {
     pub fn rpc_http_client(&self) -> HttpClient {
         self.handle().rpc().http_client().expect("failed to get http client for the rpc server")
     }
+
+    /// Returns a WebSocket client to the JSON-RPC server.
+    pub async fn rpc_ws_client(&self) -> WsClient {
+        self.handle().rpc().ws_client().await.expect("failed to get ws client for the rpc server")
+    }
 }
 
 pub fn test_config() -> Config {
     let sequencing = SequencingConfig::default();
-    let dev = DevConfig { fee: false, account_validation: true, fixed_gas_prices: None };
+    let dev = DevConfig {
+        fee: false,
+        account_validation: true,
+        fixed_gas_prices: None,
+        l2_gas_prices: None,
+    };
 
     let mut chain = dev::ChainSpec { id: ChainId::SEPOLIA, ..Default::default() };
     chain.genesis.sequencer_address = address!("0x1");